@@ -3,13 +3,23 @@
 //! Handles downloading attachments from the msgvault server.
 
 use crate::error::AppError;
-use reqwest::Client;
-use std::path::PathBuf;
+use reqwest::header::RANGE;
+use reqwest::{Client, StatusCode};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::io::AsyncWriteExt;
 
 /// Download an attachment from the server
 ///
+/// Data is streamed into a `.part` file next to the final destination. If a
+/// previous attempt left a partial file behind, the download resumes from it
+/// with an HTTP Range request; servers that ignore the range get a full
+/// re-download. The partial file is renamed to its final name on success.
+///
+/// `on_progress` receives the fraction downloaded, counting resumed bytes,
+/// whenever another percent arrives; it is not called if the server doesn't
+/// send a length.
+///
 /// # Arguments
 /// * `client` - HTTP client for making requests
 /// * `base_url` - Base URL of the msgvault server
@@ -17,6 +27,7 @@ use tokio::io::AsyncWriteExt;
 /// * `message_id` - ID of the message containing the attachment
 /// * `attachment_idx` - Index of the attachment in the message
 /// * `filename` - Filename to save the attachment as
+/// * `on_progress` - Called with the fraction downloaded (0.0 to 1.0)
 ///
 /// # Returns
 /// The path where the attachment was saved
@@ -27,26 +38,117 @@ pub async fn download_attachment(
     message_id: i64,
     attachment_idx: usize,
     filename: &str,
+    on_progress: impl FnMut(f32),
+) -> Result<PathBuf, AppError> {
+    let dir = downloads_dir();
+    let safe_filename = sanitize_filename(filename);
+
+    download_to_path(
+        client,
+        &attachment_url(base_url, message_id, attachment_idx),
+        api_key,
+        &partial_path(&dir, message_id, attachment_idx, &safe_filename),
+        &dir.join(&safe_filename),
+        on_progress,
+    )
+    .await
+}
+
+/// Download an attachment to a path picked in a save dialog
+///
+/// Unlike [`download_attachment`], a file already at `dest` is replaced,
+/// since the dialog has asked about it. It is only replaced once the
+/// download has finished.
+pub async fn save_attachment(
+    client: &Client,
+    base_url: &str,
+    api_key: Option<&str>,
+    message_id: i64,
+    attachment_idx: usize,
+    dest: &Path,
+    on_progress: impl FnMut(f32),
 ) -> Result<PathBuf, AppError> {
-    // Build the download URL
-    let url = format!(
+    let dir = dest.parent().unwrap_or_else(|| Path::new("."));
+    let file_name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "attachment".to_string());
+
+    let saved = download_to_path(
+        client,
+        &attachment_url(base_url, message_id, attachment_idx),
+        api_key,
+        &partial_path(dir, message_id, attachment_idx, &file_name),
+        dest,
+        on_progress,
+    )
+    .await?;
+
+    if saved != dest {
+        tokio::fs::rename(&saved, dest)
+            .await
+            .map_err(|e| AppError::ConfigError(format!("Failed to save attachment: {}", e)))?;
+    }
+    Ok(dest.to_path_buf())
+}
+
+/// Number of bytes already downloaded for an attachment (0 if none)
+///
+/// Lets a failed download report how much a retry will resume from.
+pub fn partial_bytes(message_id: i64, attachment_idx: usize, filename: &str) -> u64 {
+    let path = partial_path(
+        &downloads_dir(),
+        message_id,
+        attachment_idx,
+        &sanitize_filename(filename),
+    );
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Delete the partial file of an abandoned download, if there is one
+pub fn remove_partial(message_id: i64, attachment_idx: usize, filename: &str) {
+    let path = partial_path(
+        &downloads_dir(),
+        message_id,
+        attachment_idx,
+        &sanitize_filename(filename),
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+/// Build the download URL for an attachment
+fn attachment_url(base_url: &str, message_id: i64, attachment_idx: usize) -> String {
+    format!(
         "{}/api/v1/messages/{}/attachments/{}",
         base_url.trim_end_matches('/'),
         message_id,
         attachment_idx
-    );
+    )
+}
 
-    // Build the request with optional auth
-    let mut request = client
-        .get(&url)
-        .timeout(Duration::from_secs(300)); // 5 minute timeout for large files
+/// Download `url` through the partial file `part_path` into `dest`
+///
+/// `dest` gets a numbered suffix if a file with that name already exists.
+async fn download_to_path(
+    client: &Client,
+    url: &str,
+    api_key: Option<&str>,
+    part_path: &Path,
+    dest: &Path,
+    mut on_progress: impl FnMut(f32),
+) -> Result<PathBuf, AppError> {
+    // Resume from a partial file left by an earlier attempt
+    let existing = tokio::fs::metadata(part_path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
 
-    if let Some(key) = api_key {
-        request = request.header("X-API-Key", key);
-    }
+    let mut response = send_request(client, url, api_key, existing).await?;
 
-    // Send the request
-    let response = request.send().await?;
+    // The partial file no longer fits the attachment; start over
+    if existing > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        response = send_request(client, url, api_key, 0).await?;
+    }
 
     if !response.status().is_success() {
         return Err(AppError::ApiError {
@@ -55,36 +157,96 @@ pub async fn download_attachment(
         });
     }
 
-    // Get the Downloads directory
-    let downloads_dir = dirs::download_dir()
-        .unwrap_or_else(|| dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")));
+    // Only a 206 continues the partial file; a 200 carries the whole body
+    let resuming = existing > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
 
-    // Sanitize the filename
-    let safe_filename = sanitize_filename(filename);
-    let mut download_path = downloads_dir.join(&safe_filename);
+    let mut file = if resuming {
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(part_path)
+            .await
+    } else {
+        tokio::fs::File::create(part_path).await
+    }
+    .map_err(|e| AppError::ConfigError(format!("Failed to create file: {}", e)))?;
 
-    // Handle duplicate filenames
-    download_path = unique_path(download_path);
+    // A 206 only sends the remainder, so count what is already on disk
+    let mut received = if resuming { existing } else { 0 };
+    let total = response.content_length().map(|len| len + received);
+    let mut reported = 0.0;
 
-    // Download the file
-    let bytes = response.bytes().await?;
+    // Stream to disk so a failure keeps what was received
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| AppError::ConfigError(format!("Failed to write file: {}", e)))?;
 
-    // Write to file
-    let mut file = tokio::fs::File::create(&download_path).await.map_err(|e| {
-        AppError::ConfigError(format!("Failed to create file: {}", e))
-    })?;
-
-    file.write_all(&bytes).await.map_err(|e| {
-        AppError::ConfigError(format!("Failed to write file: {}", e))
-    })?;
+        received += chunk.len() as u64;
+        if let Some(total) = total.filter(|&t| t > 0) {
+            let fraction = (received as f64 / total as f64).min(1.0) as f32;
+            if fraction > reported && (fraction - reported >= 0.01 || fraction >= 1.0) {
+                reported = fraction;
+                on_progress(fraction);
+            }
+        }
+    }
 
     file.flush().await.map_err(|e| {
         AppError::ConfigError(format!("Failed to flush file: {}", e))
     })?;
+    drop(file);
+
+    // Handle duplicate filenames
+    let download_path = unique_path(dest.to_path_buf());
+
+    tokio::fs::rename(part_path, &download_path)
+        .await
+        .map_err(|e| AppError::ConfigError(format!("Failed to finish download: {}", e)))?;
 
     Ok(download_path)
 }
 
+/// Send the download request, asking for the bytes after `offset` if non-zero
+async fn send_request(
+    client: &Client,
+    url: &str,
+    api_key: Option<&str>,
+    offset: u64,
+) -> Result<reqwest::Response, AppError> {
+    // Build the request with optional auth
+    let mut request = client.get(url).timeout(Duration::from_secs(300)); // 5 minute timeout for large files
+
+    if let Some(key) = api_key {
+        request = request.header("X-API-Key", key);
+    }
+
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={}-", offset));
+    }
+
+    Ok(request.send().await?)
+}
+
+/// Get the Downloads directory
+fn downloads_dir() -> PathBuf {
+    dirs::download_dir().unwrap_or_else(|| dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")))
+}
+
+/// Path of the in-progress file for an attachment
+///
+/// Keyed by message and attachment so same-named files don't share a partial.
+fn partial_path(
+    dir: &Path,
+    message_id: i64,
+    attachment_idx: usize,
+    safe_filename: &str,
+) -> PathBuf {
+    dir.join(format!(
+        "{}.{}-{}.part",
+        safe_filename, message_id, attachment_idx
+    ))
+}
+
 /// Sanitize a filename to remove potentially dangerous characters
 fn sanitize_filename(filename: &str) -> String {
     // Remove path separators and other dangerous characters
@@ -158,4 +320,103 @@ mod tests {
         let path = PathBuf::from("/tmp/nonexistent_test_file_12345.pdf");
         assert_eq!(unique_path(path.clone()), path);
     }
+
+    /// Fresh scratch directory for download tests
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("msgvault-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_partial_file() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/messages/7/attachments/0")
+            .match_header("range", "bytes=5-")
+            .with_status(206)
+            .with_body(" world")
+            .create_async()
+            .await;
+
+        let dir = scratch_dir("resume");
+        std::fs::write(partial_path(&dir, 7, 0, "hello.txt"), "hello").unwrap();
+
+        let client = Client::new();
+        let mut reported = Vec::new();
+        let path = download_to_path(
+            &client,
+            &attachment_url(&server.url(), 7, 0),
+            None,
+            &partial_path(&dir, 7, 0, "hello.txt"),
+            &dir.join("hello.txt"),
+            |fraction| reported.push(fraction),
+        )
+        .await
+        .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "hello world");
+        // The resumed bytes count toward the total
+        assert_eq!(reported, vec![1.0]);
+        assert!(!partial_path(&dir, 7, 0, "hello.txt").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_save_attachment_replaces_existing_file() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/messages/7/attachments/0")
+            .with_status(200)
+            .with_body("new contents")
+            .create_async()
+            .await;
+
+        let dir = scratch_dir("save-as");
+        let dest = dir.join("report.txt");
+        std::fs::write(&dest, "old contents").unwrap();
+
+        let client = Client::new();
+        let path = save_attachment(&client, &server.url(), None, 7, 0, &dest, |_| {})
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(path, dest);
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "new contents");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_download_restarts_when_range_ignored() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/v1/messages/7/attachments/1")
+            .with_status(200)
+            .with_body("full body")
+            .create_async()
+            .await;
+
+        let dir = scratch_dir("restart");
+        std::fs::write(partial_path(&dir, 7, 1, "a.txt"), "stale").unwrap();
+
+        let client = Client::new();
+        let path = download_to_path(
+            &client,
+            &attachment_url(&server.url(), 7, 1),
+            None,
+            &partial_path(&dir, 7, 1, "a.txt"),
+            &dir.join("a.txt"),
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "full body");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -2,27 +2,50 @@
 //!
 //! Handles all HTTP communication with the msgvault server.
 
+use crate::api::attachments;
+use crate::api::query::SearchQuery;
 use crate::api::types::{
-    AggregateResponse, DeviceFlowStatus, HealthResponse, MessageDetail, MessageListResponse,
-    OAuthInitResponse, RemoveAccountResponse, SchedulerStatus, SearchResponse, SortDirection,
-    SortField, StatsResponse, SyncTriggerResponse, ThreadResponse, ViewType,
+    AggregateResponse, DateRange, DeviceFlowStatus, Fetched, HealthResponse, MarkReadRequest,
+    MarkReadResponse, MessageDetail, MessageListResponse, NewMessagesResponse, OAuthInitResponse,
+    RemoveAccountResponse, SchedulerStatus, SearchResponse, SetLabelsRequest, SetLabelsResponse,
+    SortDirection, SortField, StageDeletionRequest, StageDeletionResponse, StatsResponse,
+    SyncTriggerResponse, ThreadResponse, ViewType,
 };
 use crate::error::AppError;
+use chrono::{DateTime, Utc};
+use reqwest::tls::TlsInfo;
 use reqwest::Client;
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+/// Bytes of an undecodable response body kept for diagnosis
+const PAYLOAD_EXCERPT_BYTES: usize = 256;
+
 /// API client for msgvault server
+///
+/// Cloning is cheap and clones share one connection pool, so a single
+/// client can be kept and handed to each request's task; kept-alive
+/// connections then skip the TCP and TLS handshakes on rapid navigation.
 #[derive(Debug, Clone)]
 pub struct ApiClient {
     client: Client,
     base_url: String,
     api_key: Option<String>,
+    accept_invalid_certs: bool,
 }
 
 impl ApiClient {
     /// Create a new API client
-    pub fn new(base_url: impl Into<String>, api_key: Option<String>) -> Self {
-        let client = Client::builder()
+    ///
+    /// `accept_invalid_certs` turns off TLS certificate validation; only set
+    /// it when the user has explicitly allowed insecure connections.
+    pub fn new(
+        base_url: impl Into<String>,
+        api_key: Option<String>,
+        accept_invalid_certs: bool,
+    ) -> Self {
+        let client = client_builder(accept_invalid_certs)
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client");
@@ -31,9 +54,22 @@ impl ApiClient {
             client,
             base_url: base_url.into().trim_end_matches('/').to_string(),
             api_key,
+            accept_invalid_certs,
         }
     }
 
+    /// Whether this client was built for these connection settings
+    pub fn is_for(
+        &self,
+        base_url: &str,
+        api_key: Option<&str>,
+        accept_invalid_certs: bool,
+    ) -> bool {
+        self.base_url == base_url.trim_end_matches('/')
+            && self.api_key.as_deref() == api_key
+            && self.accept_invalid_certs == accept_invalid_certs
+    }
+
     /// Build a request with authentication headers
     fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
         let url = format!("{}{}", self.base_url, path);
@@ -46,60 +82,118 @@ impl ApiClient {
         req.header("Accept", "application/json")
     }
 
-    /// Check server health
+    /// Send a request and fail on a non-success status
     ///
-    /// This is the first request made on startup to verify connectivity.
-    pub async fn health(&self) -> Result<HealthResponse, AppError> {
-        let response = self.request(reqwest::Method::GET, "/health").send().await?;
-
-        if !response.status().is_success() {
+    /// Reads are retried once when the connection fails, which covers a
+    /// pooled connection the server has since closed. Writes aren't, since
+    /// the server may have acted on them.
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, AppError> {
+        let request = request.build()?;
+        let retry = if request.method() == reqwest::Method::GET {
+            request.try_clone()
+        } else {
+            None
+        };
+
+        let response = match (self.client.execute(request).await, retry) {
+            (Err(e), Some(retry)) if e.is_connect() => self.client.execute(retry).await?,
+            (result, _) => result?,
+        };
+
+        // 304 only comes back to conditional requests, which expect it
+        let status = response.status();
+        if !status.is_success() && status != reqwest::StatusCode::NOT_MODIFIED {
             return Err(AppError::ApiError {
-                status: response.status().as_u16(),
+                status: status.as_u16(),
                 message: response.text().await.unwrap_or_default(),
             });
         }
 
-        let health: HealthResponse = response.json().await.map_err(|e| AppError::ApiError {
-            status: 0,
-            message: format!("Invalid response: {}", e),
-        })?;
+        Ok(response)
+    }
 
-        Ok(health)
+    /// Send a request conditional on `etag` and decode a changed response
+    ///
+    /// The response's own ETag is returned for the next request.
+    async fn send_json_if_changed<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+        etag: Option<&str>,
+        what: &str,
+    ) -> Result<Fetched<T>, AppError> {
+        let request = match etag {
+            Some(etag) => request.header(reqwest::header::IF_NONE_MATCH, etag),
+            None => request,
+        };
+        let response = self.send(request).await?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(Fetched::NotModified);
+        }
+
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let value = decode_json(response, what).await?;
+        Ok(Fetched::Modified { value, etag })
     }
 
-    /// Fetch archive statistics
+    /// Send a request and decode its JSON response, named `what` in errors
+    async fn send_json<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+        what: &str,
+    ) -> Result<T, AppError> {
+        let response = self.send(request).await?;
+        decode_json(response, what).await
+    }
+
+    /// Check server health
     ///
-    /// Returns total messages, threads, accounts, labels, attachments, and database size.
-    pub async fn stats(&self) -> Result<StatsResponse, AppError> {
+    /// This is the first request made on startup to verify connectivity.
+    /// Over HTTPS the response carries the server certificate's fingerprint
+    /// for pinning.
+    pub async fn health(&self) -> Result<HealthResponse, AppError> {
         let response = self
-            .request(reqwest::Method::GET, "/api/v1/stats")
-            .send()
+            .send(self.request(reqwest::Method::GET, "/health"))
             .await?;
 
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
+        let certificate_sha256 = response
+            .extensions()
+            .get::<TlsInfo>()
+            .and_then(|info| info.peer_certificate())
+            .map(certificate_fingerprint);
 
-        let stats: StatsResponse = response.json().await.map_err(|e| AppError::ApiError {
-            status: 0,
-            message: format!("Invalid stats response: {}", e),
-        })?;
+        let mut health: HealthResponse = decode_json(response, "health").await?;
+        health.certificate_sha256 = certificate_sha256;
+
+        Ok(health)
+    }
 
-        Ok(stats)
+    /// Fetch archive statistics
+    ///
+    /// Returns total messages, threads, accounts, labels, attachments, and database size.
+    ///
+    /// With the ETag of the last response, an unchanged archive comes back
+    /// as `Fetched::NotModified` without a body.
+    pub async fn stats(&self, etag: Option<&str>) -> Result<Fetched<StatsResponse>, AppError> {
+        let request = self.request(reqwest::Method::GET, "/api/v1/stats");
+        self.send_json_if_changed(request, etag, "stats").await
     }
 
     /// Fetch aggregate data for a given view type
     ///
     /// Returns aggregate rows grouped by the specified view type (senders, domains, etc.)
+    ///
+    /// Like [`Self::stats`], `etag` makes the request conditional.
     pub async fn aggregates(
         &self,
         view_type: ViewType,
         sort_field: SortField,
         sort_dir: SortDirection,
-    ) -> Result<AggregateResponse, AppError> {
+        etag: Option<&str>,
+    ) -> Result<Fetched<AggregateResponse>, AppError> {
         let path = format!(
             "/api/v1/aggregates?view_type={}&sort={}&order={}",
             view_type.as_str(),
@@ -107,59 +201,40 @@ impl ApiClient {
             sort_dir.as_str()
         );
 
-        let response = self.request(reqwest::Method::GET, &path).send().await?;
-
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let aggregates: AggregateResponse =
-            response.json().await.map_err(|e| AppError::ApiError {
-                status: 0,
-                message: format!("Invalid aggregates response: {}", e),
-            })?;
-
-        Ok(aggregates)
+        let request = self.request(reqwest::Method::GET, &path);
+        self.send_json_if_changed(request, etag, "aggregates").await
     }
 
     /// Fetch filtered messages
     ///
     /// Returns paginated messages matching the specified filter criteria.
+    /// When a date range is given it replaces the type/value filter with
+    /// `after`/`before` params.
     pub async fn messages_filter(
         &self,
         filter_type: &str,
         filter_value: &str,
+        date_range: Option<DateRange>,
         offset: i64,
         limit: i64,
     ) -> Result<MessageListResponse, AppError> {
-        let response = self
+        let mut query = match date_range {
+            Some(range) => vec![
+                ("after", range.after.format("%Y-%m-%d").to_string()),
+                ("before", range.before.format("%Y-%m-%d").to_string()),
+            ],
+            None => vec![
+                ("type", filter_type.to_string()),
+                ("value", filter_value.to_string()),
+            ],
+        };
+        query.push(("offset", offset.to_string()));
+        query.push(("limit", limit.to_string()));
+
+        let request = self
             .request(reqwest::Method::GET, "/api/v1/messages/filter")
-            .query(&[
-                ("type", filter_type),
-                ("value", filter_value),
-                ("offset", &offset.to_string()),
-                ("limit", &limit.to_string()),
-            ])
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let messages: MessageListResponse =
-            response.json().await.map_err(|e| AppError::ApiError {
-                status: 0,
-                message: format!("Invalid messages response: {}", e),
-            })?;
-
-        Ok(messages)
+            .query(&query);
+        self.send_json(request, "messages").await
     }
 
     /// Fetch message detail
@@ -168,21 +243,8 @@ impl ApiClient {
     pub async fn message_detail(&self, message_id: i64) -> Result<MessageDetail, AppError> {
         let path = format!("/api/v1/messages/{}", message_id);
 
-        let response = self.request(reqwest::Method::GET, &path).send().await?;
-
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let detail: MessageDetail = response.json().await.map_err(|e| AppError::ApiError {
-            status: 0,
-            message: format!("Invalid message detail response: {}", e),
-        })?;
-
-        Ok(detail)
+        let request = self.request(reqwest::Method::GET, &path);
+        self.send_json(request, "message detail").await
     }
 
     /// Fast search (subject/from only)
@@ -193,83 +255,35 @@ impl ApiClient {
         query: &str,
         limit: i64,
     ) -> Result<SearchResponse, AppError> {
-        let response = self
+        let request = self
             .request(reqwest::Method::GET, "/api/v1/search/fast")
-            .query(&[("q", query), ("limit", &limit.to_string())])
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let search: SearchResponse = response.json().await.map_err(|e| AppError::ApiError {
-            status: 0,
-            message: format!("Invalid search response: {}", e),
-        })?;
-
-        Ok(search)
+            .query(&[("q", query), ("limit", &limit.to_string())]);
+        self.send_json(request, "search").await
     }
 
     /// Deep search (full text)
     ///
-    /// Performs full-text search across all message content.
+    /// Performs full-text search across all message content, narrowed by
+    /// any structured filters parsed from the query.
     pub async fn search_deep(
         &self,
-        query: &str,
+        query: &SearchQuery,
         offset: i64,
         limit: i64,
     ) -> Result<SearchResponse, AppError> {
-        let response = self
+        let request = self
             .request(reqwest::Method::GET, "/api/v1/search/deep")
-            .query(&[
-                ("q", query),
-                ("offset", &offset.to_string()),
-                ("limit", &limit.to_string()),
-            ])
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let search: SearchResponse = response.json().await.map_err(|e| AppError::ApiError {
-            status: 0,
-            message: format!("Invalid search response: {}", e),
-        })?;
-
-        Ok(search)
+            .query(&query.params())
+            .query(&[("offset", offset), ("limit", limit)]);
+        self.send_json(request, "search").await
     }
 
     /// Fetch scheduler status for all accounts
     ///
     /// Returns sync status for all configured email accounts.
     pub async fn scheduler_status(&self) -> Result<SchedulerStatus, AppError> {
-        let response = self
-            .request(reqwest::Method::GET, "/api/v1/scheduler/status")
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let status: SchedulerStatus = response.json().await.map_err(|e| AppError::ApiError {
-            status: 0,
-            message: format!("Invalid scheduler status response: {}", e),
-        })?;
-
-        Ok(status)
+        let request = self.request(reqwest::Method::GET, "/api/v1/scheduler/status");
+        self.send_json(request, "scheduler status").await
     }
 
     /// Trigger sync for a specific account
@@ -278,25 +292,21 @@ impl ApiClient {
     pub async fn trigger_sync(&self, email: &str) -> Result<SyncTriggerResponse, AppError> {
         let path = format!("/api/v1/sync/{}", urlencoding::encode(email));
 
-        let response = self
-            .request(reqwest::Method::POST, &path)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let result: SyncTriggerResponse =
-            response.json().await.map_err(|e| AppError::ApiError {
-                status: 0,
-                message: format!("Invalid sync trigger response: {}", e),
-            })?;
+        let request = self.request(reqwest::Method::POST, &path);
+        self.send_json(request, "sync trigger").await
+    }
 
-        Ok(result)
+    /// Pause or resume scheduled sync for an account
+    pub async fn set_sync_paused(
+        &self,
+        email: &str,
+        paused: bool,
+    ) -> Result<SyncTriggerResponse, AppError> {
+        let action = if paused { "pause" } else { "resume" };
+        let path = format!("/api/v1/sync/{}/{}", urlencoding::encode(email), action);
+
+        let request = self.request(reqwest::Method::POST, &path);
+        self.send_json(request, &format!("sync {}", action)).await
     }
 
     /// Initiate OAuth flow for adding an account
@@ -305,25 +315,8 @@ impl ApiClient {
     pub async fn initiate_oauth(&self, email: &str) -> Result<OAuthInitResponse, AppError> {
         let path = format!("/api/v1/auth/initiate/{}", urlencoding::encode(email));
 
-        let response = self
-            .request(reqwest::Method::POST, &path)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let result: OAuthInitResponse =
-            response.json().await.map_err(|e| AppError::ApiError {
-                status: 0,
-                message: format!("Invalid OAuth init response: {}", e),
-            })?;
-
-        Ok(result)
+        let request = self.request(reqwest::Method::POST, &path);
+        self.send_json(request, "OAuth init").await
     }
 
     /// Check device flow status
@@ -335,22 +328,8 @@ impl ApiClient {
             urlencoding::encode(email)
         );
 
-        let response = self.request(reqwest::Method::GET, &path).send().await?;
-
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
-
-        let result: DeviceFlowStatus =
-            response.json().await.map_err(|e| AppError::ApiError {
-                status: 0,
-                message: format!("Invalid device flow status response: {}", e),
-            })?;
-
-        Ok(result)
+        let request = self.request(reqwest::Method::GET, &path);
+        self.send_json(request, "device flow status").await
     }
 
     /// Remove an account
@@ -359,25 +338,77 @@ impl ApiClient {
     pub async fn remove_account(&self, email: &str) -> Result<RemoveAccountResponse, AppError> {
         let path = format!("/api/v1/accounts/{}", urlencoding::encode(email));
 
-        let response = self
-            .request(reqwest::Method::DELETE, &path)
-            .send()
-            .await?;
+        let request = self.request(reqwest::Method::DELETE, &path);
+        self.send_json(request, "remove account").await
+    }
 
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
+    /// Mark messages as read
+    ///
+    /// Returns how many messages changed state on the server.
+    pub async fn mark_read(&self, message_ids: &[i64]) -> Result<MarkReadResponse, AppError> {
+        self.update_read_state("/api/v1/messages/read", message_ids)
+            .await
+    }
+
+    /// Mark messages as unread
+    ///
+    /// Returns how many messages changed state on the server.
+    pub async fn mark_unread(&self, message_ids: &[i64]) -> Result<MarkReadResponse, AppError> {
+        self.update_read_state("/api/v1/messages/unread", message_ids)
+            .await
+    }
+
+    /// Post a read-state change for a set of messages
+    async fn update_read_state(
+        &self,
+        path: &str,
+        message_ids: &[i64],
+    ) -> Result<MarkReadResponse, AppError> {
+        let body = MarkReadRequest {
+            message_ids: message_ids.to_vec(),
+        };
+
+        let request = self.request(reqwest::Method::POST, path).json(&body);
+        self.send_json(request, "read state").await
+    }
 
-        let result: RemoveAccountResponse =
-            response.json().await.map_err(|e| AppError::ApiError {
-                status: 0,
-                message: format!("Invalid remove account response: {}", e),
-            })?;
+    /// Add and remove labels on a set of messages
+    ///
+    /// Returns the resulting labels of each updated message.
+    pub async fn set_labels(
+        &self,
+        message_ids: &[i64],
+        add: &[String],
+        remove: &[String],
+    ) -> Result<SetLabelsResponse, AppError> {
+        let body = SetLabelsRequest {
+            message_ids: message_ids.to_vec(),
+            add: add.to_vec(),
+            remove: remove.to_vec(),
+        };
+
+        let request = self
+            .request(reqwest::Method::POST, "/api/v1/messages/labels")
+            .json(&body);
+        self.send_json(request, "labels").await
+    }
 
-        Ok(result)
+    /// Stage messages for deletion
+    ///
+    /// Staged messages are queued on the server; nothing is removed until
+    /// the deletion is executed there.
+    pub async fn stage_deletion(
+        &self,
+        message_ids: &[i64],
+    ) -> Result<StageDeletionResponse, AppError> {
+        let body = StageDeletionRequest {
+            message_ids: message_ids.to_vec(),
+        };
+
+        let request = self
+            .request(reqwest::Method::POST, "/api/v1/deletion/stage")
+            .json(&body);
+        self.send_json(request, "deletion").await
     }
 
     /// Fetch all messages in a thread
@@ -386,38 +417,310 @@ impl ApiClient {
     pub async fn thread_messages(&self, thread_id: &str) -> Result<Vec<MessageDetail>, AppError> {
         let path = format!("/api/v1/threads/{}", urlencoding::encode(thread_id));
 
-        let response = self.request(reqwest::Method::GET, &path).send().await?;
+        let request = self.request(reqwest::Method::GET, &path);
+        let thread_response: ThreadResponse = self.send_json(request, "thread").await?;
 
-        if !response.status().is_success() {
-            return Err(AppError::ApiError {
-                status: response.status().as_u16(),
-                message: response.text().await.unwrap_or_default(),
-            });
-        }
+        Ok(thread_response.messages)
+    }
 
-        let thread_response: ThreadResponse =
-            response.json().await.map_err(|e| AppError::ApiError {
-                status: 0,
-                message: format!("Invalid thread response: {}", e),
-            })?;
+    /// Count messages synced since a timestamp
+    ///
+    /// Polled in the background to announce new mail.
+    pub async fn messages_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<NewMessagesResponse, AppError> {
+        let request = self
+            .request(reqwest::Method::GET, "/api/v1/messages/since")
+            .query(&[("ts", since.to_rfc3339())]);
+        self.send_json(request, "new messages").await
+    }
 
-        Ok(thread_response.messages)
+    /// Download an attachment into the Downloads folder
+    ///
+    /// Shares this client's connection pool; see
+    /// [`attachments::download_attachment`].
+    pub async fn download_attachment(
+        &self,
+        message_id: i64,
+        attachment_idx: usize,
+        filename: &str,
+        on_progress: impl FnMut(f32),
+    ) -> Result<PathBuf, AppError> {
+        attachments::download_attachment(
+            &self.client,
+            &self.base_url,
+            self.api_key.as_deref(),
+            message_id,
+            attachment_idx,
+            filename,
+            on_progress,
+        )
+        .await
+    }
+
+    /// Download an attachment to a path picked in a save dialog
+    ///
+    /// See [`attachments::save_attachment`].
+    pub async fn save_attachment(
+        &self,
+        message_id: i64,
+        attachment_idx: usize,
+        dest: &Path,
+        on_progress: impl FnMut(f32),
+    ) -> Result<PathBuf, AppError> {
+        attachments::save_attachment(
+            &self.client,
+            &self.base_url,
+            self.api_key.as_deref(),
+            message_id,
+            attachment_idx,
+            dest,
+            on_progress,
+        )
+        .await
+    }
+}
+
+/// Decode a JSON response body, named `what` in errors
+///
+/// When the body doesn't have the expected shape, its start is kept in the
+/// error and printed to stderr, so schema mismatches between client and
+/// server can be diagnosed.
+async fn decode_json<T: DeserializeOwned>(
+    response: reqwest::Response,
+    what: &str,
+) -> Result<T, AppError> {
+    let body = response.bytes().await?;
+    serde_json::from_slice(&body).map_err(|e| {
+        let excerpt = payload_excerpt(&body);
+        eprintln!("Invalid {} response: {}\n{}", what, e, excerpt);
+        AppError::ApiError {
+            status: 0,
+            message: format!("Invalid {} response: {} (received: {})", what, e, excerpt),
+        }
+    })
+}
+
+/// The start of a response body as text, marked when cut short
+fn payload_excerpt(body: &[u8]) -> String {
+    let end = body.len().min(PAYLOAD_EXCERPT_BYTES);
+    let mut excerpt = String::from_utf8_lossy(&body[..end]).into_owned();
+    if body.len() > end {
+        excerpt.push('\u{2026}');
     }
+    excerpt
+}
+
+/// HTTP client builder with the app's TLS policy
+///
+/// Certificate validation stays on unless `accept_invalid_certs` is set.
+/// TLS details are kept on responses so the certificate can be pinned.
+/// Compressed responses are asked for and decoded transparently, which
+/// matters for multi-megabyte aggregate and message lists on slow links.
+fn client_builder(accept_invalid_certs: bool) -> reqwest::ClientBuilder {
+    Client::builder()
+        .gzip(true)
+        .brotli(true)
+        .deflate(true)
+        .tls_info(true)
+        .danger_accept_invalid_certs(accept_invalid_certs)
+}
+
+/// SHA-256 fingerprint of a DER certificate, as colon-separated hex
+fn certificate_fingerprint(der: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA256, der)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Whether a server URL uses plaintext HTTP to a host other than this machine
+///
+/// Loopback addresses are exempt; traffic to them never leaves the machine.
+pub fn is_insecure_url(url: &str) -> bool {
+    let url = url.trim().to_ascii_lowercase();
+    let Some(rest) = url.strip_prefix("http://") else {
+        return false;
+    };
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host_port = authority.rsplit('@').next().unwrap_or("");
+    let host = if let Some(ipv6) = host_port.strip_prefix('[') {
+        ipv6.split(']').next().unwrap_or("")
+    } else {
+        host_port.split(':').next().unwrap_or("")
+    };
+
+    let is_loopback = host == "localhost"
+        || host.ends_with(".localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback());
+    !is_loopback
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_certificate_fingerprint() {
+        assert_eq!(
+            certificate_fingerprint(b"abc"),
+            "BA:78:16:BF:8F:01:CF:EA:41:41:40:DE:5D:AE:22:23:\
+             B0:03:61:A3:96:17:7A:9C:B4:10:FF:61:F2:00:15:AD"
+        );
+    }
+
+    #[test]
+    fn test_is_insecure_url() {
+        assert!(is_insecure_url("http://nas.local:8080"));
+        assert!(is_insecure_url("HTTP://192.168.1.5/"));
+        assert!(!is_insecure_url("https://vault.example.com"));
+        assert!(!is_insecure_url("http://localhost:8080"));
+        assert!(!is_insecure_url("http://127.0.0.1:8080/api"));
+        assert!(!is_insecure_url("http://[::1]:8080"));
+    }
+
+    #[test]
+    fn test_payload_excerpt() {
+        assert_eq!(payload_excerpt(b"{\"ok\": 1}"), "{\"ok\": 1}");
+        let long = vec![b'x'; PAYLOAD_EXCERPT_BYTES + 10];
+        let excerpt = payload_excerpt(&long);
+        assert_eq!(excerpt.chars().count(), PAYLOAD_EXCERPT_BYTES + 1);
+        assert!(excerpt.ends_with('\u{2026}'));
+    }
+
+    #[tokio::test]
+    async fn test_decode_error_includes_payload() {
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/api/v1/stats")
+            .with_status(200)
+            .with_body(r#"{"total": "lots"}"#)
+            .create_async()
+            .await;
+
+        let client = ApiClient::new(server.url(), None, false);
+        let error = client.stats(None).await.unwrap_err().to_string();
+        assert!(error.contains("Invalid stats response"), "{}", error);
+        assert!(error.contains(r#"{"total": "lots"}"#), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_error_status_keeps_server_message() {
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/api/v1/sync/a%40example.com")
+            .with_status(409)
+            .with_body("sync already running")
+            .create_async()
+            .await;
+
+        let client = ApiClient::new(server.url(), None, false);
+        match client.trigger_sync("a@example.com").await {
+            Err(AppError::ApiError { status, message }) => {
+                assert_eq!(status, 409);
+                assert_eq!(message, "sync already running");
+            }
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stats_revalidated_with_etag() {
+        let mut server = mockito::Server::new_async().await;
+        let _changed = server
+            .mock("GET", "/api/v1/stats")
+            .match_header("if-none-match", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("etag", "\"v1\"")
+            .with_body(
+                r#"{"total_messages": 1, "total_threads": 1, "total_accounts": 1,
+                    "total_labels": 0, "total_attachments": 0, "database_size_bytes": 10}"#,
+            )
+            .create_async()
+            .await;
+        let _unchanged = server
+            .mock("GET", "/api/v1/stats")
+            .match_header("if-none-match", "\"v1\"")
+            .with_status(304)
+            .create_async()
+            .await;
+
+        let client = ApiClient::new(server.url(), None, false);
+        match client.stats(None).await {
+            Ok(Fetched::Modified { value, etag }) => {
+                assert_eq!(value.total_messages, 1);
+                assert_eq!(etag.as_deref(), Some("\"v1\""));
+            }
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+        assert!(matches!(
+            client.stats(Some("\"v1\"")).await,
+            Ok(Fetched::NotModified)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_compressed_response_is_decoded() {
+        use std::io::Write;
+
+        let body = r#"{"view_type": "senders", "rows": [{"key": "a@example.com", "count": 2,
+            "total_size": 10, "attachment_size": 0, "attachment_count": 0, "total_unique": 1}]}"#;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(body.as_bytes()).unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/api/v1/aggregates")
+            .match_query(mockito::Matcher::Any)
+            .match_header(
+                "accept-encoding",
+                mockito::Matcher::Regex("gzip".to_string()),
+            )
+            .with_status(200)
+            .with_header("content-encoding", "gzip")
+            .with_body(encoder.finish().unwrap())
+            .create_async()
+            .await;
+
+        let client = ApiClient::new(server.url(), None, false);
+        match client
+            .aggregates(
+                ViewType::Senders,
+                SortField::Count,
+                SortDirection::Desc,
+                None,
+            )
+            .await
+        {
+            Ok(Fetched::Modified { value, .. }) => assert_eq!(value.rows[0].key, "a@example.com"),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
     #[test]
     fn test_client_creation() {
-        let client = ApiClient::new("http://localhost:8080", None);
+        let client = ApiClient::new("http://localhost:8080", None, false);
         assert_eq!(client.base_url, "http://localhost:8080");
     }
 
     #[test]
     fn test_client_trims_trailing_slash() {
-        let client = ApiClient::new("http://localhost:8080/", None);
+        let client = ApiClient::new("http://localhost:8080/", None, false);
         assert_eq!(client.base_url, "http://localhost:8080");
     }
+
+    #[test]
+    fn test_client_is_for_its_settings() {
+        let client = ApiClient::new("http://localhost:8080/", Some("key".to_string()), false);
+        assert!(client.is_for("http://localhost:8080", Some("key"), false));
+        assert!(!client.is_for("http://localhost:9090", Some("key"), false));
+        assert!(!client.is_for("http://localhost:8080", None, false));
+        assert!(!client.is_for("http://localhost:8080", Some("key"), true));
+    }
 }
@@ -0,0 +1,63 @@
+//! Gravatar avatar lookup
+//!
+//! Fetches profile images for sender addresses. Only used when the user has
+//! opted in to loading remote avatars, since each lookup tells Gravatar which
+//! addresses are being viewed.
+
+use crate::error::AppError;
+use reqwest::{Client, StatusCode};
+use std::time::Duration;
+
+/// Image size requested from Gravatar (pixels, square)
+const AVATAR_SIZE: u32 = 96;
+
+/// Gravatar image URL for an email address
+///
+/// `d=404` makes Gravatar answer 404 instead of a placeholder image when the
+/// address has no avatar, so the caller can fall back to initials.
+pub fn gravatar_url(email: &str) -> String {
+    let hash = md5::compute(email.trim().to_lowercase().as_bytes());
+    format!(
+        "https://www.gravatar.com/avatar/{:x}?d=404&s={}",
+        hash, AVATAR_SIZE
+    )
+}
+
+/// Fetch the avatar image for an email address
+///
+/// Returns `Ok(None)` if the address has no Gravatar.
+pub async fn fetch_avatar(client: &Client, email: &str) -> Result<Option<Vec<u8>>, AppError> {
+    let response = client
+        .get(gravatar_url(email))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    if !response.status().is_success() {
+        return Err(AppError::ApiError {
+            status: response.status().as_u16(),
+            message: format!("Failed to fetch avatar: {}", response.status()),
+        });
+    }
+
+    Ok(Some(response.bytes().await?.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gravatar_url_normalizes_email() {
+        // Example hash from the Gravatar documentation
+        let url = gravatar_url(" MyEmailAddress@example.com ");
+        assert_eq!(
+            url,
+            "https://www.gravatar.com/avatar/0bc83cb571cd1c50ba6f3e8a78ef1346?d=404&s=96"
+        );
+    }
+}
@@ -4,7 +4,10 @@
 
 pub mod attachments;
 pub mod client;
+pub mod gravatar;
+pub mod query;
 pub mod types;
 
-pub use attachments::download_attachment;
-pub use client::ApiClient;
+pub use client::{is_insecure_url, ApiClient};
+pub use gravatar::fetch_avatar;
+pub use query::SearchQuery;
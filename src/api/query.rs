@@ -0,0 +1,238 @@
+//! Search query parsing
+//!
+//! Parses the small filter language accepted in the search box, e.g.
+//! `from:alice@x.com subject:"invoice" has:attachment after:2023-01-01`,
+//! into structured parameters for deep search. Anything that is not a
+//! recognised filter is kept as free text.
+
+use chrono::NaiveDate;
+
+/// Date format accepted by `after:` and `before:`
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Search filters parsed from a query string
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Free-text terms left after removing filters
+    pub text: String,
+    /// Sender address or name (`from:`)
+    pub from: Option<String>,
+    /// Recipient address or name (`to:`)
+    pub to: Option<String>,
+    /// Subject text (`subject:`)
+    pub subject: Option<String>,
+    /// Only messages with attachments (`has:attachment`)
+    pub has_attachment: bool,
+    /// Only messages sent on or after this date (`after:`)
+    pub after: Option<NaiveDate>,
+    /// Only messages sent before this date (`before:`)
+    pub before: Option<NaiveDate>,
+}
+
+impl SearchQuery {
+    /// Parse a query string
+    ///
+    /// Unknown keys, empty values and invalid dates are not errors; those
+    /// tokens are searched as free text instead. A repeated filter keeps
+    /// its last value.
+    pub fn parse(input: &str) -> Self {
+        let mut query = SearchQuery::default();
+        let mut text: Vec<&str> = Vec::new();
+
+        for token in tokenize(input) {
+            if !query.apply_filter(token) {
+                text.push(token);
+            }
+        }
+
+        query.text = text.join(" ");
+        query
+    }
+
+    /// Apply a `key:value` token, returning false if it is not a valid filter
+    fn apply_filter(&mut self, token: &str) -> bool {
+        let Some((key, value)) = token.split_once(':') else {
+            return false;
+        };
+
+        let value = value.trim_matches('"');
+        if value.is_empty() {
+            return false;
+        }
+
+        match key.to_lowercase().as_str() {
+            "from" => self.from = Some(value.to_string()),
+            "to" => self.to = Some(value.to_string()),
+            "subject" => self.subject = Some(value.to_string()),
+            "has" if matches!(value.to_lowercase().as_str(), "attachment" | "attachments") => {
+                self.has_attachment = true
+            }
+            "after" => match NaiveDate::parse_from_str(value, DATE_FORMAT) {
+                Ok(date) => self.after = Some(date),
+                Err(_) => return false,
+            },
+            "before" => match NaiveDate::parse_from_str(value, DATE_FORMAT) {
+                Ok(date) => self.before = Some(date),
+                Err(_) => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    /// Query-string parameters for the search endpoint
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("q", self.text.clone())];
+
+        if let Some(from) = &self.from {
+            params.push(("from", from.clone()));
+        }
+        if let Some(to) = &self.to {
+            params.push(("to", to.clone()));
+        }
+        if let Some(subject) = &self.subject {
+            params.push(("subject", subject.clone()));
+        }
+        if self.has_attachment {
+            params.push(("has_attachment", "true".to_string()));
+        }
+        if let Some(after) = self.after {
+            params.push(("after", after.format(DATE_FORMAT).to_string()));
+        }
+        if let Some(before) = self.before {
+            params.push(("before", before.format(DATE_FORMAT).to_string()));
+        }
+
+        params
+    }
+}
+
+/// Split a query on whitespace, keeping double-quoted sections together
+///
+/// Quotes stay in the returned tokens. An unterminated quote runs to the
+/// end of the input.
+fn tokenize(input: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut in_quotes = false;
+
+    for (i, c) in input.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+
+        if c.is_whitespace() && !in_quotes {
+            if let Some(s) = start.take() {
+                tokens.push(&input[s..i]);
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+
+    if let Some(s) = start {
+        tokens.push(&input[s..]);
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_free_text_only() {
+        let query = SearchQuery::parse("quarterly report");
+        assert_eq!(
+            query,
+            SearchQuery {
+                text: "quarterly report".to_string(),
+                ..SearchQuery::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_multiple_filters() {
+        let query = SearchQuery::parse(
+            "from:alice@x.com subject:\"invoice\" has:attachment after:2023-01-01 budget",
+        );
+        assert_eq!(query.from.as_deref(), Some("alice@x.com"));
+        assert_eq!(query.subject.as_deref(), Some("invoice"));
+        assert!(query.has_attachment);
+        assert_eq!(query.after, Some(date(2023, 1, 1)));
+        assert_eq!(query.before, None);
+        assert_eq!(query.text, "budget");
+    }
+
+    #[test]
+    fn test_quoted_values_keep_spaces() {
+        let query = SearchQuery::parse("subject:\"march invoice\" to:\"Bob Smith\"");
+        assert_eq!(query.subject.as_deref(), Some("march invoice"));
+        assert_eq!(query.to.as_deref(), Some("Bob Smith"));
+        assert_eq!(query.text, "");
+    }
+
+    #[test]
+    fn test_quoted_free_text_phrase_preserved() {
+        let query = SearchQuery::parse("\"exact phrase\" from:bob");
+        assert_eq!(query.text, "\"exact phrase\"");
+        assert_eq!(query.from.as_deref(), Some("bob"));
+    }
+
+    #[test]
+    fn test_unterminated_quote_runs_to_end() {
+        let query = SearchQuery::parse("from:carol subject:\"late payment");
+        assert_eq!(query.from.as_deref(), Some("carol"));
+        assert_eq!(query.subject.as_deref(), Some("late payment"));
+    }
+
+    #[test]
+    fn test_keys_are_case_insensitive() {
+        let query = SearchQuery::parse("FROM:dave Has:Attachments");
+        assert_eq!(query.from.as_deref(), Some("dave"));
+        assert!(query.has_attachment);
+    }
+
+    #[test]
+    fn test_malformed_filters_fall_back_to_text() {
+        let input = "from: after:yesterday has:pdf cc:erin re:meeting";
+        let query = SearchQuery::parse(input);
+        assert_eq!(
+            query,
+            SearchQuery {
+                text: input.to_string(),
+                ..SearchQuery::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_repeated_filter_keeps_last() {
+        let query = SearchQuery::parse("from:a@x.com from:b@x.com");
+        assert_eq!(query.from.as_deref(), Some("b@x.com"));
+    }
+
+    #[test]
+    fn test_params() {
+        let query = SearchQuery::parse("from:alice before:2024-02-29 lunch");
+        assert_eq!(
+            query.params(),
+            vec![
+                ("q", "lunch".to_string()),
+                ("from", "alice".to_string()),
+                ("before", "2024-02-29".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(SearchQuery::parse("   "), SearchQuery::default());
+    }
+}
@@ -3,13 +3,50 @@
 //! These types mirror the Go server's JSON responses.
 //! See msgvault/internal/query/models.go for the source definitions.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Days, Months, NaiveDate, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 
+/// Oldest server version that has every endpoint this app uses
+pub const MIN_SERVER_VERSION: &str = "0.4.0";
+
 /// Health check response
 #[derive(Debug, Clone, Deserialize)]
 pub struct HealthResponse {
     pub status: String,
+    /// Server version, e.g. "0.4.1" (older servers don't report one)
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Whether the server serves a web view of each message at `/messages/{id}`
+    #[serde(default)]
+    pub web_ui: bool,
+    /// SHA-256 fingerprint of the server's TLS certificate (HTTPS only)
+    #[serde(skip)]
+    pub certificate_sha256: Option<String>,
+}
+
+impl HealthResponse {
+    /// Whether the server reports a version older than `MIN_SERVER_VERSION`
+    ///
+    /// Servers that don't report a version aren't flagged, since there is
+    /// nothing to compare.
+    pub fn is_outdated(&self) -> bool {
+        self.version
+            .as_deref()
+            .is_some_and(|v| version_parts(v) < version_parts(MIN_SERVER_VERSION))
+    }
+}
+
+/// Numeric parts of a dotted version, ignoring a leading "v" and any
+/// pre-release or build suffix ("v0.4.1-rc1" is [0, 4, 1])
+fn version_parts(version: &str) -> Vec<u32> {
+    let version = version.trim().trim_start_matches('v');
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let mut parts: Vec<u32> = core.split('.').map(|p| p.parse().unwrap_or(0)).collect();
+    // "0.4" and "0.4.0" are the same version
+    while parts.last() == Some(&0) {
+        parts.pop();
+    }
+    parts
 }
 
 /// Archive statistics response from /api/v1/stats
@@ -59,6 +96,16 @@ pub struct MessageSummary {
     /// Thread ID for conversation threading
     #[serde(default)]
     pub thread_id: Option<String>,
+    /// Number of messages in the thread (1 if the server doesn't say)
+    #[serde(default = "default_thread_count")]
+    pub thread_count: i64,
+    /// Whether the message is unread (false if the server has no read state)
+    #[serde(default)]
+    pub is_unread: bool,
+}
+
+fn default_thread_count() -> i64 {
+    1
 }
 
 /// Email address with optional name
@@ -78,6 +125,28 @@ pub struct Attachment {
     pub filename: String,
     pub mime_type: String,
     pub size_bytes: i64,
+    /// Content-Disposition type, "attachment" or "inline" (older servers omit it)
+    #[serde(default)]
+    pub disposition: Option<String>,
+    /// Content-ID that `cid:` URLs in the HTML body refer to
+    #[serde(default)]
+    pub content_id: Option<String>,
+}
+
+impl Attachment {
+    /// Whether this is an image shown in the body rather than a file the
+    /// sender attached
+    ///
+    /// Only images count: mail clients also mark PDFs and other documents
+    /// inline, and users still expect those in the attachment list.
+    pub fn is_inline(&self) -> bool {
+        let inline = self
+            .disposition
+            .as_deref()
+            .is_some_and(|d| d.eq_ignore_ascii_case("inline"));
+        self.mime_type.to_ascii_lowercase().starts_with("image/")
+            && (inline || self.content_id.is_some())
+    }
 }
 
 /// Full message detail from /api/v1/messages/{id}
@@ -95,6 +164,9 @@ pub struct MessageDetail {
     pub sent_at: DateTime<Utc>,
     #[serde(default)]
     pub body: String,
+    /// HTML body, if the message has one
+    #[serde(default)]
+    pub body_html: Option<String>,
     #[serde(default)]
     pub labels: Vec<String>,
     #[serde(default)]
@@ -102,6 +174,16 @@ pub struct MessageDetail {
     /// Thread ID for conversation threading
     #[serde(default)]
     pub thread_id: Option<String>,
+    /// Raw headers, in message order (empty if the server doesn't send them)
+    #[serde(default)]
+    pub headers: Vec<MessageHeader>,
+}
+
+/// A raw message header
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageHeader {
+    pub name: String,
+    pub value: String,
 }
 
 /// Paginated message list response
@@ -156,6 +238,81 @@ pub struct SchedulerStatus {
     pub accounts: Vec<AccountSyncStatus>,
 }
 
+/// Messages synced since a timestamp, counted per account
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewMessagesResponse {
+    #[serde(default)]
+    pub accounts: Vec<NewMessageCount>,
+    /// When the newest of them was synced; the next check starts here
+    #[serde(default)]
+    pub latest: Option<DateTime<Utc>>,
+    /// The new messages themselves, from servers that list them
+    #[serde(default)]
+    pub messages: Vec<NewMessageRef>,
+}
+
+/// A newly synced message, enough to tell whether it is muted
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewMessageRef {
+    /// Account it was synced for
+    pub account: String,
+    #[serde(rename = "from")]
+    pub from_addr: String,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+}
+
+/// New message count for one account
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewMessageCount {
+    pub email: String,
+    pub count: i64,
+}
+
+impl NewMessagesResponse {
+    /// Take muted messages out of the per-account counts
+    ///
+    /// Servers that don't list the new messages can't be filtered, so their
+    /// counts are announced unchanged.
+    pub fn without_muted(mut self, is_muted: impl Fn(&NewMessageRef) -> bool) -> Self {
+        for message in self.messages.iter().filter(|m| is_muted(m)) {
+            if let Some(account) = self
+                .accounts
+                .iter_mut()
+                .find(|a| a.email == message.account)
+            {
+                account.count = (account.count - 1).max(0);
+            }
+        }
+        self.messages.retain(|m| !is_muted(m));
+        self
+    }
+
+    /// Toast text, e.g. "12 new messages synced for alice@example.com"
+    ///
+    /// None when nothing new arrived.
+    pub fn summary(&self) -> Option<String> {
+        let accounts: Vec<&NewMessageCount> =
+            self.accounts.iter().filter(|a| a.count > 0).collect();
+        let total: i64 = accounts.iter().map(|a| a.count).sum();
+        let noun = if total == 1 { "message" } else { "messages" };
+
+        match accounts.as_slice() {
+            [] => None,
+            [account] => Some(format!(
+                "{} new {} synced for {}",
+                total, noun, account.email
+            )),
+            _ => Some(format!(
+                "{} new {} synced across {} accounts",
+                total,
+                noun,
+                accounts.len()
+            )),
+        }
+    }
+}
+
 /// Sync status for a single account
 #[derive(Debug, Clone, Deserialize)]
 pub struct AccountSyncStatus {
@@ -173,6 +330,19 @@ pub struct AccountSyncStatus {
     pub error: Option<String>,
 }
 
+impl AccountSyncStatus {
+    /// Whether the email or display name contains `query`, ignoring case
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.email.to_lowercase().contains(&query)
+            || self
+                .display_name
+                .as_ref()
+                .is_some_and(|n| n.to_lowercase().contains(&query))
+    }
+}
+
 /// Possible sync states
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -243,8 +413,66 @@ pub struct RemoveAccountResponse {
     pub message: String,
 }
 
+/// Request body for marking messages as read or unread
+#[derive(Debug, Clone, Serialize)]
+pub struct MarkReadRequest {
+    pub message_ids: Vec<i64>,
+}
+
+/// Response from marking messages as read or unread
+#[derive(Debug, Clone, Deserialize)]
+pub struct MarkReadResponse {
+    /// Number of messages whose read state changed
+    pub updated: i64,
+}
+
+/// Request body for adding and removing labels on messages
+#[derive(Debug, Clone, Serialize)]
+pub struct SetLabelsRequest {
+    pub message_ids: Vec<i64>,
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+/// Labels of a single message after a label change
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageLabels {
+    pub id: i64,
+    pub labels: Vec<String>,
+}
+
+/// Response from changing labels on messages
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetLabelsResponse {
+    /// Resulting labels of each updated message
+    #[serde(default)]
+    pub messages: Vec<MessageLabels>,
+}
+
+/// Request body for staging messages for deletion
+#[derive(Debug, Clone, Serialize)]
+pub struct StageDeletionRequest {
+    pub message_ids: Vec<i64>,
+}
+
+/// Response from staging messages for deletion
+#[derive(Debug, Clone, Deserialize)]
+pub struct StageDeletionResponse {
+    /// Number of messages added to the deletion queue
+    pub staged: i64,
+}
+
+/// Response to a request sent with the ETag of an earlier one
+#[derive(Debug, Clone)]
+pub enum Fetched<T> {
+    /// The resource changed, or there was no ETag to compare
+    Modified { value: T, etag: Option<String> },
+    /// 304: the earlier response still applies
+    NotModified,
+}
+
 /// View types for aggregation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ViewType {
     Senders,
@@ -324,7 +552,7 @@ impl ViewType {
 }
 
 /// Sort fields for aggregates
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum SortField {
     #[default]
     Count,
@@ -345,7 +573,7 @@ impl SortField {
 }
 
 /// Sort direction
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum SortDirection {
     #[default]
     Desc,
@@ -360,3 +588,252 @@ impl SortDirection {
         }
     }
 }
+
+/// Date range derived from a Time aggregate bucket key
+///
+/// `after` is the first day in the bucket and `before` is the first day after
+/// it, matching the server's inclusive/exclusive `after`/`before` filter params.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub after: NaiveDate,
+    pub before: NaiveDate,
+}
+
+impl DateRange {
+    /// Parse a Time bucket key into a date range
+    ///
+    /// Supports years ("2023"), months ("2023-06"), ISO weeks ("2023-W23")
+    /// and days ("2023-06-15"). Returns None for anything else.
+    pub fn from_time_bucket(key: &str) -> Option<Self> {
+        let key = key.trim();
+
+        if let Ok(day) = NaiveDate::parse_from_str(key, "%Y-%m-%d") {
+            return Some(Self {
+                after: day,
+                before: day.succ_opt()?,
+            });
+        }
+
+        if let Some((year, week)) = key.split_once("-W") {
+            let year: i32 = year.parse().ok()?;
+            let week: u32 = week.parse().ok()?;
+            let start = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+            return Some(Self {
+                after: start,
+                before: start.checked_add_days(Days::new(7))?,
+            });
+        }
+
+        if let Some((year, month)) = key.split_once('-') {
+            let year: i32 = year.parse().ok()?;
+            let month: u32 = month.parse().ok()?;
+            let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+            return Some(Self {
+                after: start,
+                before: start.checked_add_months(Months::new(1))?,
+            });
+        }
+
+        if key.len() == 4 {
+            let year: i32 = key.parse().ok()?;
+            return Some(Self {
+                after: NaiveDate::from_ymd_opt(year, 1, 1)?,
+                before: NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+            });
+        }
+
+        None
+    }
+
+    /// Human-readable range for breadcrumbs (inclusive end date)
+    pub fn describe(&self) -> String {
+        let last = self.before.pred_opt().unwrap_or(self.after);
+        if last == self.after {
+            self.after.format("%Y-%m-%d").to_string()
+        } else {
+            format!(
+                "{} \u{2013} {}",
+                self.after.format("%Y-%m-%d"),
+                last.format("%Y-%m-%d")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_new_messages_summary() {
+        let count = |email: &str, count| NewMessageCount {
+            email: email.to_string(),
+            count,
+        };
+        let response = |accounts| NewMessagesResponse {
+            accounts,
+            latest: None,
+            messages: Vec::new(),
+        };
+
+        assert_eq!(response(vec![count("a@x.com", 0)]).summary(), None);
+        assert_eq!(
+            response(vec![count("alice@x.com", 12), count("bob@x.com", 0)]).summary(),
+            Some("12 new messages synced for alice@x.com".to_string())
+        );
+        assert_eq!(
+            response(vec![count("a@x.com", 1)]).summary(),
+            Some("1 new message synced for a@x.com".to_string())
+        );
+        assert_eq!(
+            response(vec![count("a@x.com", 2), count("b@x.com", 3)]).summary(),
+            Some("5 new messages synced across 2 accounts".to_string())
+        );
+    }
+
+    #[test]
+    fn test_muted_messages_are_not_counted() {
+        let json = r#"{
+            "accounts": [{"email": "a@x.com", "count": 3}, {"email": "b@x.com", "count": 1}],
+            "messages": [
+                {"account": "a@x.com", "from": "News <news@shop.com>"},
+                {"account": "a@x.com", "from": "bob@x.com", "thread_id": "t1"},
+                {"account": "a@x.com", "from": "carol@x.com"},
+                {"account": "b@x.com", "from": "news@shop.com"}
+            ]
+        }"#;
+        let response: NewMessagesResponse = serde_json::from_str(json).unwrap();
+        let response = response.without_muted(|m| {
+            m.from_addr.contains("news@shop.com") || m.thread_id.as_deref() == Some("t1")
+        });
+
+        assert_eq!(response.messages.len(), 1);
+        assert_eq!(
+            response.summary(),
+            Some("1 new message synced for a@x.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_server_version_check() {
+        let health = |version: Option<&str>| HealthResponse {
+            status: "ok".to_string(),
+            version: version.map(str::to_string),
+            web_ui: false,
+            certificate_sha256: None,
+        };
+
+        assert!(health(Some("0.3.9")).is_outdated());
+        assert!(health(Some("v0.3.12-rc1")).is_outdated());
+        assert!(!health(Some("0.4")).is_outdated());
+        assert!(!health(Some("0.4.0")).is_outdated());
+        assert!(!health(Some("0.10.0")).is_outdated());
+        assert!(!health(Some("1.0.0+build5")).is_outdated());
+        assert!(!health(None).is_outdated());
+    }
+
+    #[test]
+    fn test_inline_images_are_not_attachments() {
+        let attachment = |mime: &str, extra: &str| -> Attachment {
+            serde_json::from_str(&format!(
+                r#"{{"filename": "f", "mime_type": "{}", "size_bytes": 1{}}}"#,
+                mime, extra
+            ))
+            .unwrap()
+        };
+
+        assert!(!attachment("image/png", "").is_inline());
+        assert!(attachment("image/png", r#", "disposition": "INLINE""#).is_inline());
+        assert!(attachment("image/gif", r#", "content_id": "logo@x""#).is_inline());
+        assert!(!attachment("image/png", r#", "disposition": "attachment""#).is_inline());
+        assert!(!attachment("application/pdf", r#", "disposition": "inline""#).is_inline());
+    }
+
+    #[test]
+    fn test_thread_count_defaults_to_one() {
+        let json = r#"{
+            "id": 1,
+            "subject": "Hi",
+            "snippet": "",
+            "from": "a@example.com",
+            "sent_at": "2024-06-01T10:00:00Z",
+            "size_bytes": 10,
+            "has_attachments": false
+        }"#;
+        let msg: MessageSummary = serde_json::from_str(json).unwrap();
+        assert_eq!(msg.thread_count, 1);
+
+        let json = json.replacen("\"id\": 1,", "\"id\": 1, \"thread_count\": 3,", 1);
+        let msg: MessageSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(msg.thread_count, 3);
+    }
+
+    #[test]
+    fn test_account_matches_email_or_name() {
+        let account = AccountSyncStatus {
+            email: "alice@example.com".to_string(),
+            display_name: Some("Alice Work".to_string()),
+            status: SyncState::Idle,
+            last_sync_at: None,
+            next_sync_at: None,
+            messages_synced: None,
+            error: None,
+        };
+
+        assert!(account.matches(""));
+        assert!(account.matches("EXAMPLE"));
+        assert!(account.matches(" work "));
+        assert!(!account.matches("bob"));
+    }
+
+    #[test]
+    fn test_date_range_month_bucket() {
+        let range = DateRange::from_time_bucket("2023-06").unwrap();
+        assert_eq!(range.after, date(2023, 6, 1));
+        assert_eq!(range.before, date(2023, 7, 1));
+
+        // December rolls over into the next year
+        let range = DateRange::from_time_bucket("2023-12").unwrap();
+        assert_eq!(range.after, date(2023, 12, 1));
+        assert_eq!(range.before, date(2024, 1, 1));
+    }
+
+    #[test]
+    fn test_date_range_week_bucket() {
+        let range = DateRange::from_time_bucket("2023-W23").unwrap();
+        assert_eq!(range.after, date(2023, 6, 5));
+        assert_eq!(range.before, date(2023, 6, 12));
+
+        // ISO week 1 can start in the previous calendar year
+        let range = DateRange::from_time_bucket("2025-W01").unwrap();
+        assert_eq!(range.after, date(2024, 12, 30));
+    }
+
+    #[test]
+    fn test_date_range_day_bucket() {
+        let range = DateRange::from_time_bucket("2024-02-29").unwrap();
+        assert_eq!(range.after, date(2024, 2, 29));
+        assert_eq!(range.before, date(2024, 3, 1));
+        assert_eq!(range.describe(), "2024-02-29");
+    }
+
+    #[test]
+    fn test_date_range_year_bucket() {
+        let range = DateRange::from_time_bucket("2022").unwrap();
+        assert_eq!(range.after, date(2022, 1, 1));
+        assert_eq!(range.before, date(2023, 1, 1));
+        assert_eq!(range.describe(), "2022-01-01 \u{2013} 2022-12-31");
+    }
+
+    #[test]
+    fn test_date_range_invalid_bucket() {
+        assert_eq!(DateRange::from_time_bucket(""), None);
+        assert_eq!(DateRange::from_time_bucket("2023-13"), None);
+        assert_eq!(DateRange::from_time_bucket("2023-W54"), None);
+        assert_eq!(DateRange::from_time_bucket("June"), None);
+    }
+}
@@ -2,29 +2,50 @@
 //!
 //! Implements the Iced Application pattern with MVU architecture.
 
-use crate::config::Settings;
+use crate::config::{ConfigRecovery, SavedSearch, Session, Settings};
 use crate::message::Message;
 use crate::model::AppState;
 use crate::update;
 use crate::view;
-use iced::event::Event;
-use iced::keyboard;
+use iced::event::{self, Event};
 use iced::time;
+use iced::{keyboard, mouse, window};
 use iced::{Element, Subscription, Task};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Main application state container
 pub struct MsgVaultApp {
     state: AppState,
     settings: Settings,
+    /// Last session written to disk (to skip redundant writes)
+    saved_session: Option<Session>,
 }
 
 impl MsgVaultApp {
     /// Create new application with settings
-    pub fn new(settings: Settings) -> (Self, Task<Message>) {
-        let state = AppState::new(&settings);
+    pub fn new(
+        settings: Settings,
+        config_recovery: Option<ConfigRecovery>,
+    ) -> (Self, Task<Message>) {
+        let mut state = AppState::new(&settings);
+        state.config_recovery = config_recovery;
+        state.saved_searches = SavedSearch::load_all();
+        if settings.restore_session {
+            state.pending_session = Session::load();
+        }
+
+        // Closing the main window is handled in update so it can exit the app
+        let (main_window, open_main) = window::open(window::Settings {
+            size: iced::Size::new(1200.0, 800.0),
+            min_size: Some(iced::Size::new(900.0, 600.0)),
+            position: window::Position::Centered,
+            exit_on_close_request: false,
+            ..Default::default()
+        });
+        state.main_window = Some(main_window);
 
         let app = Self {
+            saved_session: state.pending_session.clone(),
             state,
             settings: settings.clone(),
         };
@@ -38,11 +59,21 @@ impl MsgVaultApp {
             Task::done(Message::CheckHealth)
         };
 
-        (app, initial_task)
+        (app, Task::batch([open_main.discard(), initial_task]))
     }
 
     /// Window title
-    pub fn title(&self) -> String {
+    pub fn title(&self, window: window::Id) -> String {
+        if self.state.compose_window == Some(window) {
+            return format!("{} - msgvault", self.state.compose.mode.display_name());
+        }
+        if let Some((_, message_window)) = self.state.message_windows.by_window(window) {
+            return match &message_window.detail {
+                Some(detail) => format!("{} - msgvault", detail.subject),
+                None => "msgvault".to_string(),
+            };
+        }
+
         match self.state.is_connected() {
             true => format!("msgvault - {}", &self.state.server_url),
             false => "msgvault".to_string(),
@@ -51,28 +82,141 @@ impl MsgVaultApp {
 
     /// Handle messages and update state
     pub fn update(&mut self, message: Message) -> Task<Message> {
-        update::handle(&mut self.state, message)
+        let focused = self.state.focused_message_id();
+        let task = update::handle(&mut self.state, message);
+        self.persist_session();
+
+        // Prefetch the focused row's message once focus rests on it
+        let now_focused = self.state.focused_message_id();
+        if now_focused != focused {
+            self.state.message_cache.focus(now_focused, Instant::now());
+        }
+
+        // Sending, discarding or docking compose closes its window
+        if !self.state.compose.is_open {
+            if let Some(id) = self.state.compose_window.take() {
+                return Task::batch([task, window::close(id)]);
+            }
+        }
+        task
     }
 
-    /// Render the view
-    pub fn view(&self) -> Element<'_, Message> {
-        view::render(&self.state)
+    /// Write the current view to disk when it changed (if session restore is on)
+    fn persist_session(&mut self) {
+        if !self.state.restore_session || !self.state.is_connected() {
+            return;
+        }
+
+        if let Some(session) = self.state.session() {
+            if self.saved_session.as_ref() != Some(&session) {
+                let _ = session.save();
+                self.saved_session = Some(session);
+            }
+        }
+    }
+
+    /// Render a window
+    pub fn view(&self, window: window::Id) -> Element<'_, Message> {
+        view::render_window(&self.state, window)
     }
 
     /// Subscribe to events (keyboard, sync polling, etc.)
     pub fn subscription(&self) -> Subscription<Message> {
-        let keyboard = iced::event::listen().map(|event| match event {
-            Event::Keyboard(keyboard::Event::KeyPressed {
-                key, modifiers, ..
-            }) => Message::KeyPressed(key, modifiers),
-            _ => Message::None,
+        let keyboard = iced::event::listen_with(|event, status, window| match event {
+            // Key presses a focused text input consumed are still forwarded,
+            // flagged so only Escape and chords act on them
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+                Some(Message::KeyPressed {
+                    window,
+                    key,
+                    modifiers,
+                    captured: status == event::Status::Captured,
+                })
+            }
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                Some(Message::ModifiersChanged(modifiers))
+            }
+            // Files dragged from the OS attach to compose
+            Event::Window(window::Event::FileHovered(_)) => Some(Message::FilesHovered(window)),
+            Event::Window(window::Event::FilesHoveredLeft) => {
+                Some(Message::FilesHoveredLeft(window))
+            }
+            Event::Window(window::Event::FileDropped(path)) => {
+                Some(Message::FileDropped(window, path))
+            }
+            _ if status == event::Status::Captured => None,
+            // Cursor and window size position the context menu
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                Some(Message::CursorMoved(window, position))
+            }
+            Event::Window(window::Event::Resized(size))
+            | Event::Window(window::Event::Opened { size, .. }) => {
+                Some(Message::WindowResized(window, size))
+            }
+            _ => None,
         });
 
+        let windows = Subscription::batch([
+            window::close_requests().map(Message::WindowCloseRequested),
+            window::close_events().map(Message::WindowClosed),
+        ]);
+
+        // Finish a divider drag wherever the button is released
+        let divider = if self.state.dragging_divider.is_some() {
+            iced::event::listen_with(|event, _status, _window| match event {
+                Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                    Some(Message::DividerReleased)
+                }
+                _ => None,
+            })
+        } else {
+            Subscription::none()
+        };
+
         if self.state.is_connected() {
             let tick = time::every(Duration::from_secs(30)).map(|_| Message::SyncTick);
-            Subscription::batch([keyboard, tick])
+
+            // Notice a vanished server while idle; backs off while it fails
+            let health =
+                time::every(self.state.health_check_interval()).map(|_| Message::HealthTick);
+
+            // Poll for the type-ahead idle timeout only while it is active
+            let typeahead = if self.state.typeahead_buffer.is_some() {
+                time::every(Duration::from_millis(250)).map(|_| Message::TypeaheadTick)
+            } else {
+                Subscription::none()
+            };
+
+            // Poll for the prefetch dwell only while a row is waiting
+            let prefetch = if self.state.message_cache.is_waiting() {
+                time::every(Duration::from_millis(100)).map(|_| Message::PrefetchTick)
+            } else {
+                Subscription::none()
+            };
+
+            // Animate the activity spinner only while something is running
+            let activity = if self.state.is_busy() {
+                time::every(Duration::from_millis(100)).map(|_| Message::ActivityTick)
+            } else {
+                Subscription::none()
+            };
+
+            // Expire the confirmation toast
+            let toast = if self.state.toast.is_some() {
+                time::every(Duration::from_millis(500)).map(|_| Message::ToastTick)
+            } else {
+                Subscription::none()
+            };
+
+            Subscription::batch([
+                keyboard, windows, divider, tick, health, typeahead, prefetch, activity, toast,
+            ])
+        } else if self.state.reconnect_in.is_some() {
+            // Count down to the connection screen's next retry
+            let reconnect = time::every(Duration::from_secs(1)).map(|_| Message::ReconnectTick);
+            Subscription::batch([keyboard, windows, reconnect])
         } else {
-            keyboard
+            Subscription::batch([keyboard, windows])
         }
     }
 }
@@ -3,10 +3,11 @@
 //! Implements zero-configuration server discovery chain:
 //! 1. MSGVAULT_HOME environment variable
 //! 2. Default config file locations
-//! 3. Localhost port probing
+//! 3. Localhost port probing (MSGVAULT_PORT, configured ports, defaults)
 //! 4. Fall back to wizard
 
-use std::path::PathBuf;
+use iced::futures::future::join_all;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// Result of server discovery
@@ -62,152 +63,185 @@ impl DiscoveryResult {
     }
 }
 
+/// Ports probed on localhost after any configured ones
+const PROBE_PORTS: [u16; 4] = [8080, 8081, 3000, 9000];
+
 /// Run the full discovery chain
-pub async fn discover_server() -> DiscoveryResult {
-    let mut steps = Vec::new();
+///
+/// `extra_ports` are probed on localhost before the default ports. Every
+/// source is checked at once, so a run takes about one probe timeout; the
+/// earliest source in the chain that found a server wins.
+pub async fn discover_server(extra_ports: Vec<u16>) -> DiscoveryResult {
+    let checks = sources(&extra_ports).into_iter().map(|source| async move {
+        let name = source.name();
+        (name, source.check().await)
+    });
+    let outcomes = join_all(checks).await;
 
-    // Step 1: Check MSGVAULT_HOME environment variable
-    if let Some(result) = check_env_var(&mut steps).await {
-        return result;
+    let mut steps = Vec::new();
+    let mut found: Option<FoundServer> = None;
+    for (name, outcome) in outcomes {
+        match outcome {
+            Ok(server) => {
+                steps.push(DiscoveryStep {
+                    name,
+                    status: DiscoveryStepStatus::Found(server.url.clone()),
+                });
+                found.get_or_insert(server);
+            }
+            Err(status) => steps.push(DiscoveryStep { name, status }),
+        }
     }
 
-    // Step 2: Check default config locations
-    if let Some(result) = check_config_files(&mut steps).await {
-        return result;
-    }
+    match found {
+        Some(server) => DiscoveryResult {
+            server_url: Some(server.url),
+            api_key: server.api_key,
+            source: server.source,
+            steps,
+        },
+        None => {
+            steps.push(DiscoveryStep {
+                name: "No server found".to_string(),
+                status: DiscoveryStepStatus::NotFound,
+            });
 
-    // Step 3: Probe localhost ports
-    if let Some(result) = probe_localhost(&mut steps).await {
-        return result;
+            DiscoveryResult {
+                server_url: None,
+                api_key: None,
+                source: DiscoverySource::NeedsWizard,
+                steps,
+            }
+        }
     }
+}
 
-    // Step 4: No server found
-    steps.push(DiscoveryStep {
-        name: "No server found".to_string(),
-        status: DiscoveryStepStatus::NotFound,
-    });
+/// Check every discovery source and report what each found or why it
+/// failed, for diagnosing a server that isn't being found
+pub async fn diagnose_discovery(extra_ports: Vec<u16>) -> Vec<DiscoveryStep> {
+    discover_server(extra_ports).await.steps
+}
 
-    DiscoveryResult {
-        server_url: None,
-        api_key: None,
-        source: DiscoverySource::NeedsWizard,
-        steps,
-    }
+/// A responding server and where it was found
+struct FoundServer {
+    url: String,
+    api_key: Option<String>,
+    source: DiscoverySource,
 }
 
-/// Check MSGVAULT_HOME environment variable
-async fn check_env_var(steps: &mut Vec<DiscoveryStep>) -> Option<DiscoveryResult> {
-    let step_name = "MSGVAULT_HOME".to_string();
-
-    if let Ok(home) = std::env::var("MSGVAULT_HOME") {
-        let home_path = PathBuf::from(&home);
-        let config_path = home_path.join("config.toml");
-
-        if config_path.exists() {
-            if let Ok(contents) = std::fs::read_to_string(&config_path) {
-                if let Ok(config) = toml::from_str::<MsgvaultConfig>(&contents) {
-                    if let Some(server_url) = config.get_server_url() {
-                        // Verify the server is reachable
-                        if ping_server(&server_url).await {
-                            steps.push(DiscoveryStep {
-                                name: step_name,
-                                status: DiscoveryStepStatus::Found(server_url.clone()),
-                            });
-
-                            return Some(DiscoveryResult {
-                                server_url: Some(server_url),
-                                api_key: config.get_api_key(),
-                                source: DiscoverySource::EnvVar,
-                                steps: steps.clone(),
-                            });
-                        }
-                    }
-                }
-            }
-        }
+/// A place a server might be found
+enum Source {
+    /// Config file under MSGVAULT_HOME
+    EnvVar,
+    /// Config file at a default location
+    ConfigFile(PathBuf),
+    /// Server listening on a localhost port
+    Port(u16),
+}
 
-        steps.push(DiscoveryStep {
-            name: step_name,
-            status: DiscoveryStepStatus::NotFound,
-        });
-    } else {
-        steps.push(DiscoveryStep {
-            name: step_name,
-            status: DiscoveryStepStatus::NotFound,
-        });
-    }
+/// Every source, in the order discovery tries them
+fn sources(extra_ports: &[u16]) -> Vec<Source> {
+    let mut sources = vec![Source::EnvVar];
+    sources.extend(get_config_paths().into_iter().map(Source::ConfigFile));
+    sources.extend(probe_ports(extra_ports).into_iter().map(Source::Port));
+    sources
+}
 
-    None
+/// Localhost ports to probe: MSGVAULT_PORT, then the extra ports, then the
+/// defaults, without repeats
+fn probe_ports(extra_ports: &[u16]) -> Vec<u16> {
+    let env_port = std::env::var("MSGVAULT_PORT")
+        .ok()
+        .and_then(|p| p.trim().parse::<u16>().ok());
+
+    let mut ports: Vec<u16> = Vec::new();
+    for port in env_port
+        .into_iter()
+        .chain(extra_ports.iter().copied())
+        .chain(PROBE_PORTS)
+    {
+        if port != 0 && !ports.contains(&port) {
+            ports.push(port);
+        }
+    }
+    ports
 }
 
-/// Check default config file locations
-async fn check_config_files(steps: &mut Vec<DiscoveryStep>) -> Option<DiscoveryResult> {
-    let config_paths = get_config_paths();
-
-    for path in config_paths {
-        let step_name = format!("Config: {}", path.display());
-
-        if path.exists() {
-            if let Ok(contents) = std::fs::read_to_string(&path) {
-                if let Ok(config) = toml::from_str::<MsgvaultConfig>(&contents) {
-                    if let Some(server_url) = config.get_server_url() {
-                        // Verify the server is reachable
-                        if ping_server(&server_url).await {
-                            steps.push(DiscoveryStep {
-                                name: step_name,
-                                status: DiscoveryStepStatus::Found(server_url.clone()),
-                            });
-
-                            return Some(DiscoveryResult {
-                                server_url: Some(server_url),
-                                api_key: config.get_api_key(),
-                                source: DiscoverySource::ConfigFile(path),
-                                steps: steps.clone(),
-                            });
-                        }
-                    }
+impl Source {
+    /// Step name shown in the wizard
+    fn name(&self) -> String {
+        match self {
+            Source::EnvVar => "MSGVAULT_HOME".to_string(),
+            Source::ConfigFile(path) => format!("Config: {}", path.display()),
+            Source::Port(port) => format!("Probe: localhost:{}", port),
+        }
+    }
+
+    /// Look for a responding server, or say why there isn't one
+    async fn check(self) -> Result<FoundServer, DiscoveryStepStatus> {
+        match self {
+            Source::EnvVar => {
+                let home =
+                    std::env::var("MSGVAULT_HOME").map_err(|_| DiscoveryStepStatus::NotFound)?;
+                let config_path = PathBuf::from(&home).join("config.toml");
+                if !config_path.exists() {
+                    return Err(DiscoveryStepStatus::Failed(format!(
+                        "No config.toml in {}",
+                        home
+                    )));
+                }
+                let (url, api_key) = read_config(&config_path).await?;
+                Ok(FoundServer {
+                    url,
+                    api_key,
+                    source: DiscoverySource::EnvVar,
+                })
+            }
+            Source::ConfigFile(path) => {
+                let (url, api_key) = read_config(&path).await?;
+                Ok(FoundServer {
+                    url,
+                    api_key,
+                    source: DiscoverySource::ConfigFile(path),
+                })
+            }
+            Source::Port(port) => {
+                let url = format!("http://localhost:{}", port);
+                match ping_server(&url).await {
+                    Ok(()) => Ok(FoundServer {
+                        url,
+                        api_key: None,
+                        source: DiscoverySource::LocalhostProbe(port),
+                    }),
+                    // Nothing listening is the usual case for most ports
+                    Err(PingError::Unreachable(_)) => Err(DiscoveryStepStatus::NotFound),
+                    Err(e) => Err(DiscoveryStepStatus::Failed(e.reason())),
                 }
             }
         }
-
-        steps.push(DiscoveryStep {
-            name: step_name,
-            status: DiscoveryStepStatus::NotFound,
-        });
     }
-
-    None
 }
 
-/// Probe localhost ports for running server
-async fn probe_localhost(steps: &mut Vec<DiscoveryStep>) -> Option<DiscoveryResult> {
-    let ports = [8080, 8081, 3000, 9000];
-
-    for port in ports {
-        let url = format!("http://localhost:{}", port);
-        let step_name = format!("Probe: localhost:{}", port);
-
-        if ping_server(&url).await {
-            steps.push(DiscoveryStep {
-                name: step_name,
-                status: DiscoveryStepStatus::Found(url.clone()),
-            });
+/// Server URL and API key from a msgvault config file, once the server
+/// it names has answered
+async fn read_config(path: &Path) -> Result<(String, Option<String>), DiscoveryStepStatus> {
+    if !path.exists() {
+        return Err(DiscoveryStepStatus::NotFound);
+    }
 
-            return Some(DiscoveryResult {
-                server_url: Some(url),
-                api_key: None,
-                source: DiscoverySource::LocalhostProbe(port),
-                steps: steps.clone(),
-            });
-        }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| DiscoveryStepStatus::Failed(format!("Can't read file: {}", e)))?;
+    let config: MsgvaultConfig = toml::from_str(&contents)
+        .map_err(|e| DiscoveryStepStatus::Failed(format!("Invalid config: {}", e.message())))?;
+    let server_url = config
+        .get_server_url()
+        .ok_or_else(|| DiscoveryStepStatus::Failed("No server configured".to_string()))?;
 
-        steps.push(DiscoveryStep {
-            name: step_name,
-            status: DiscoveryStepStatus::NotFound,
-        });
-    }
+    ping_server(&server_url)
+        .await
+        .map_err(|e| DiscoveryStepStatus::Failed(format!("{}: {}", server_url, e.reason())))?;
 
-    None
+    Ok((server_url, config.get_api_key()))
 }
 
 /// Get list of default config paths to check
@@ -235,24 +269,42 @@ fn get_config_paths() -> Vec<PathBuf> {
     paths
 }
 
+/// Why a server didn't pass its health check
+enum PingError {
+    /// Nothing answered at the address
+    Unreachable(String),
+    /// Something answered, but not a healthy msgvault server
+    Unhealthy(String),
+}
+
+impl PingError {
+    /// Reason shown in the discovery steps
+    fn reason(self) -> String {
+        match self {
+            PingError::Unreachable(reason) | PingError::Unhealthy(reason) => reason,
+        }
+    }
+}
+
 /// Ping a server to check if it's reachable
-async fn ping_server(url: &str) -> bool {
+async fn ping_server(url: &str) -> Result<(), PingError> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(2))
         .build()
-        .ok();
-
-    let client = match client {
-        Some(c) => c,
-        None => return false,
-    };
+        .map_err(|e| PingError::Unreachable(e.to_string()))?;
 
     // Try /health endpoint (actual msgvault endpoint)
     let health_url = format!("{}/health", url.trim_end_matches('/'));
 
     match client.get(&health_url).send().await {
-        Ok(response) => response.status().is_success(),
-        Err(_) => false,
+        Ok(response) if response.status().is_success() => Ok(()),
+        Ok(response) => Err(PingError::Unhealthy(format!(
+            "/health returned HTTP {}",
+            response.status().as_u16()
+        ))),
+        Err(e) if e.is_timeout() => Err(PingError::Unreachable("Timed out".to_string())),
+        Err(e) if e.is_connect() => Err(PingError::Unreachable("Connection refused".to_string())),
+        Err(e) => Err(PingError::Unreachable(e.to_string())),
     }
 }
 
@@ -3,13 +3,23 @@
 //! Handles loading and saving application settings.
 
 pub mod discovery;
+pub mod saved_searches;
+pub mod session;
 
-pub use discovery::{discover_server, DiscoveryResult, DiscoverySource, DiscoveryStep, DiscoveryStepStatus};
+pub use discovery::{
+    diagnose_discovery, discover_server, DiscoveryResult, DiscoverySource, DiscoveryStep,
+    DiscoveryStepStatus,
+};
+pub use saved_searches::SavedSearch;
+pub use session::{Session, SessionView};
 
+use crate::api::types::ViewType;
+use chrono::{DateTime, Utc};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 /// Application settings persisted to disk
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,9 +32,352 @@ pub struct Settings {
     #[serde(default)]
     pub api_key: String,
 
-    /// Allow insecure (HTTP) connections
+    /// Allow insecure connections: plaintext HTTP to remote servers and
+    /// TLS certificates that fail validation
     #[serde(default)]
     pub allow_insecure: bool,
+
+    /// Remember the server's certificate and refuse to connect if it changes
+    #[serde(default)]
+    pub pin_certificate: bool,
+
+    /// SHA-256 fingerprint of the pinned server certificate
+    #[serde(default)]
+    pub pinned_certificate: Option<String>,
+
+    /// View to open after connecting
+    #[serde(default)]
+    pub default_view: DefaultView,
+
+    /// Restore the last-open view instead of the default view
+    #[serde(default)]
+    pub restore_session: bool,
+
+    /// Show inline keyboard hints under each view
+    #[serde(default = "default_true")]
+    pub show_key_hints: bool,
+
+    /// Show a preview line under each message in the list
+    #[serde(default = "default_true")]
+    pub show_snippets: bool,
+
+    /// Characters of preview shown per message
+    #[serde(default = "default_snippet_length")]
+    pub snippet_length: usize,
+
+    /// Maximum number of attachment downloads running at once
+    #[serde(default = "default_max_concurrent_downloads")]
+    pub max_concurrent_downloads: usize,
+
+    /// Open downloaded attachments as soon as they finish
+    #[serde(default)]
+    pub auto_open_downloads: bool,
+
+    /// Extensions (lowercase, no dot) that auto-open applies to
+    #[serde(default = "default_auto_open_extensions")]
+    pub auto_open_extensions: Vec<String>,
+
+    /// Fetch sender avatars from Gravatar
+    #[serde(default)]
+    pub load_remote_avatars: bool,
+
+    /// Show OS notifications when a manual sync finishes or an account fails
+    #[serde(default)]
+    pub desktop_notifications: bool,
+
+    /// Color palette
+    #[serde(default)]
+    pub theme: ThemeChoice,
+
+    /// Units for file and database sizes
+    #[serde(default)]
+    pub byte_units: ByteUnits,
+
+    /// Where an opened message is shown relative to the message list
+    #[serde(default)]
+    pub reading_pane: ReadingPaneMode,
+
+    /// Sidebar width in pixels
+    #[serde(default = "default_sidebar_width")]
+    pub sidebar_width: f32,
+
+    /// Message list width beside the detail pane, in pixels
+    #[serde(default = "default_message_list_width")]
+    pub message_list_width: f32,
+
+    /// Show the sidebar as an icon-only rail
+    #[serde(default)]
+    pub sidebar_collapsed: bool,
+
+    /// Senders whose remote images load without asking
+    #[serde(default)]
+    pub remote_content_senders: Vec<String>,
+
+    /// Senders whose messages are hidden or marked in lists (client-side only)
+    #[serde(default)]
+    pub blocked_senders: Vec<String>,
+
+    /// Whether blocked senders are hidden or marked
+    #[serde(default)]
+    pub blocked_sender_display: BlockedSenderDisplay,
+
+    /// Senders whose new messages aren't announced
+    #[serde(default)]
+    pub muted_senders: Vec<String>,
+
+    /// Thread IDs whose new messages aren't announced
+    #[serde(default)]
+    pub muted_threads: Vec<String>,
+
+    /// When new messages were last checked for; only newer ones are announced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_messages_checked_at: Option<DateTime<Utc>>,
+
+    /// Extra localhost ports for discovery to probe, before the defaults
+    #[serde(default)]
+    pub discovery_ports: Vec<u16>,
+
+    /// Senders and domains pinned to the sidebar, in display order
+    #[serde(default)]
+    pub pinned_filters: Vec<PinnedFilter>,
+}
+
+/// Serde default for settings that are on unless turned off
+fn default_true() -> bool {
+    true
+}
+
+/// Serde default for the message list preview length
+fn default_snippet_length() -> usize {
+    80
+}
+
+/// Serde default for the download concurrency limit
+fn default_max_concurrent_downloads() -> usize {
+    3
+}
+
+/// Serde default for the auto-open allowlist: documents and images
+fn default_auto_open_extensions() -> Vec<String> {
+    ["pdf", "png", "jpg", "jpeg", "gif", "txt"]
+        .iter()
+        .map(|ext| ext.to_string())
+        .collect()
+}
+
+/// Serde default for the sidebar width
+fn default_sidebar_width() -> f32 {
+    crate::theme::spacing::SIDEBAR_WIDTH
+}
+
+/// Serde default for the message list width
+fn default_message_list_width() -> f32 {
+    crate::theme::spacing::MESSAGE_LIST_WIDTH
+}
+
+/// Choices offered for the download concurrency limit
+pub const DOWNLOAD_LIMIT_OPTIONS: &[usize] = &[1, 2, 3, 5, 8];
+
+/// Choices offered for the message list preview length
+pub const SNIPPET_LENGTH_OPTIONS: &[usize] = &[40, 80, 120, 200];
+
+/// Config file that failed to parse and was set aside
+#[derive(Debug, Clone)]
+pub struct ConfigRecovery {
+    /// Where the unreadable file was moved
+    pub backup_path: PathBuf,
+    /// Why it couldn't be parsed
+    pub error: String,
+}
+
+/// Landing view shown after connecting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultView {
+    #[default]
+    Dashboard,
+    Senders,
+    Search,
+    Sync,
+}
+
+impl DefaultView {
+    /// Human-readable display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            DefaultView::Dashboard => "Dashboard",
+            DefaultView::Senders => "Senders",
+            DefaultView::Search => "Search",
+            DefaultView::Sync => "Sync",
+        }
+    }
+
+    /// All landing views in display order
+    pub fn all() -> &'static [DefaultView] {
+        &[
+            DefaultView::Dashboard,
+            DefaultView::Senders,
+            DefaultView::Search,
+            DefaultView::Sync,
+        ]
+    }
+}
+
+/// Color palette choice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeChoice {
+    #[default]
+    FoundryDark,
+    HighContrast,
+}
+
+impl ThemeChoice {
+    /// Human-readable display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ThemeChoice::FoundryDark => "Foundry Dark",
+            ThemeChoice::HighContrast => "High Contrast",
+        }
+    }
+
+    /// All palettes in display order
+    pub fn all() -> &'static [ThemeChoice] {
+        &[ThemeChoice::FoundryDark, ThemeChoice::HighContrast]
+    }
+
+    /// Make this the active palette for drawing
+    pub fn apply(self) {
+        crate::theme::colors::set_high_contrast(self == ThemeChoice::HighContrast);
+    }
+}
+
+/// Unit system for displayed sizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteUnits {
+    /// KiB, MiB, GiB (powers of 1024)
+    #[default]
+    Binary,
+    /// KB, MB, GB (powers of 1000)
+    Decimal,
+}
+
+impl ByteUnits {
+    /// Human-readable display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ByteUnits::Binary => "KiB (1024)",
+            ByteUnits::Decimal => "KB (1000)",
+        }
+    }
+
+    /// All unit systems in display order
+    pub fn all() -> &'static [ByteUnits] {
+        &[ByteUnits::Binary, ByteUnits::Decimal]
+    }
+
+    /// Make this the unit system for displayed sizes
+    pub fn apply(self) {
+        crate::view::widgets::stats_card::set_decimal_byte_units(self == ByteUnits::Decimal);
+    }
+}
+
+/// Where an opened message is shown relative to the message list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingPaneMode {
+    /// Beside the list while a message is open
+    #[default]
+    WhenReading,
+    /// Beside the list at all times, showing the focused message
+    Always,
+    /// Never; an opened message replaces the full-width list
+    Off,
+}
+
+impl ReadingPaneMode {
+    /// Human-readable display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ReadingPaneMode::WhenReading => "When Reading",
+            ReadingPaneMode::Always => "Always",
+            ReadingPaneMode::Off => "Off",
+        }
+    }
+
+    /// All modes in display order
+    pub fn all() -> &'static [ReadingPaneMode] {
+        &[
+            ReadingPaneMode::WhenReading,
+            ReadingPaneMode::Always,
+            ReadingPaneMode::Off,
+        ]
+    }
+
+    /// The mode after this one, for cycling with a shortcut
+    pub fn next(self) -> Self {
+        match self {
+            ReadingPaneMode::WhenReading => ReadingPaneMode::Always,
+            ReadingPaneMode::Always => ReadingPaneMode::Off,
+            ReadingPaneMode::Off => ReadingPaneMode::WhenReading,
+        }
+    }
+}
+
+/// How messages from blocked senders appear in lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockedSenderDisplay {
+    /// Left out of message lists, search results and the Senders view
+    #[default]
+    Hide,
+    /// Shown with a "Blocked" badge
+    Mark,
+}
+
+impl BlockedSenderDisplay {
+    /// Human-readable display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BlockedSenderDisplay::Hide => "Hide",
+            BlockedSenderDisplay::Mark => "Mark",
+        }
+    }
+
+    /// All display modes in display order
+    pub fn all() -> &'static [BlockedSenderDisplay] {
+        &[BlockedSenderDisplay::Hide, BlockedSenderDisplay::Mark]
+    }
+}
+
+/// A sender or domain pinned to the sidebar for one-click access
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedFilter {
+    /// Aggregate the value belongs to (Senders or Domains)
+    pub view_type: ViewType,
+    /// Sender address or domain
+    pub value: String,
+}
+
+impl PinnedFilter {
+    /// Pin for a sender address
+    pub fn sender(email: &str) -> Self {
+        Self {
+            view_type: ViewType::Senders,
+            value: email.to_string(),
+        }
+    }
+
+    /// Whether this pins the same sender or domain (case-insensitive)
+    pub fn same_as(&self, other: &PinnedFilter) -> bool {
+        self.view_type == other.view_type && self.value.eq_ignore_ascii_case(&other.value)
+    }
+
+    /// Filter description for the message list breadcrumb
+    pub fn description(&self) -> String {
+        format!("{}: {}", self.view_type.display_name(), self.value)
+    }
 }
 
 impl Default for Settings {
@@ -33,37 +386,125 @@ impl Default for Settings {
             server_url: String::new(),
             api_key: String::new(),
             allow_insecure: false,
+            pin_certificate: false,
+            pinned_certificate: None,
+            default_view: DefaultView::default(),
+            restore_session: false,
+            show_key_hints: true,
+            show_snippets: true,
+            snippet_length: default_snippet_length(),
+            max_concurrent_downloads: default_max_concurrent_downloads(),
+            auto_open_downloads: false,
+            auto_open_extensions: default_auto_open_extensions(),
+            load_remote_avatars: false,
+            desktop_notifications: false,
+            theme: ThemeChoice::default(),
+            byte_units: ByteUnits::default(),
+            reading_pane: ReadingPaneMode::default(),
+            sidebar_width: default_sidebar_width(),
+            message_list_width: default_message_list_width(),
+            sidebar_collapsed: false,
+            remote_content_senders: Vec::new(),
+            blocked_senders: Vec::new(),
+            muted_senders: Vec::new(),
+            muted_threads: Vec::new(),
+            blocked_sender_display: BlockedSenderDisplay::default(),
+            new_messages_checked_at: None,
+            discovery_ports: Vec::new(),
+            pinned_filters: Vec::new(),
         }
     }
 }
 
 impl Settings {
     /// Get the configuration directory path
-    fn config_dir() -> Option<PathBuf> {
+    pub fn config_dir() -> Option<PathBuf> {
         ProjectDirs::from("com", "msgvault", "msgvault-desktop")
             .map(|dirs| dirs.config_dir().to_path_buf())
     }
 
     /// Get the configuration file path
-    fn config_path() -> Option<PathBuf> {
+    pub fn config_path() -> Option<PathBuf> {
         Self::config_dir().map(|dir| dir.join("config.toml"))
     }
 
     /// Load settings from disk, or return defaults if not found
-    pub fn load() -> Result<Self, String> {
+    ///
+    /// A file that can't be parsed is moved to `config.toml.bak`, so the
+    /// next save doesn't overwrite it, and defaults are returned along with
+    /// a `ConfigRecovery` describing what happened.
+    pub fn load() -> Result<(Self, Option<ConfigRecovery>), String> {
         let path = match Self::config_path() {
             Some(p) => p,
-            None => return Ok(Self::default()),
+            None => return Ok((Self::default(), None)),
         };
 
         if !path.exists() {
-            return Ok(Self::default());
+            return Ok((Self::default(), None));
         }
 
         let contents =
             fs::read_to_string(&path).map_err(|e| format!("Failed to read config: {}", e))?;
 
-        toml::from_str(&contents).map_err(|e| format!("Failed to parse config: {}", e))
+        match toml::from_str(&contents) {
+            Ok(settings) => Ok((settings, None)),
+            Err(e) => {
+                let backup_path = path.with_extension("toml.bak");
+                fs::rename(&path, &backup_path).map_err(|rename_err| {
+                    format!(
+                        "Failed to parse config ({}) and to back it up: {}",
+                        e, rename_err
+                    )
+                })?;
+                Ok((
+                    Self::default(),
+                    Some(ConfigRecovery {
+                        backup_path,
+                        error: e.to_string(),
+                    }),
+                ))
+            }
+        }
+    }
+
+    /// Serialize settings for export to another machine
+    ///
+    /// The API key is blanked unless `include_api_key` is set, so an
+    /// exported file can be shared without granting access to the server.
+    pub fn to_export(&self, include_api_key: bool) -> Result<String, String> {
+        let mut settings = self.clone();
+        if !include_api_key {
+            settings.api_key.clear();
+        }
+        // Specific to this machine's view of the archive
+        settings.new_messages_checked_at = None;
+        toml::to_string_pretty(&settings)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))
+    }
+
+    /// Parse and validate settings from an exported file
+    pub fn from_export(contents: &str) -> Result<Self, String> {
+        let settings: Settings =
+            toml::from_str(contents).map_err(|e| format!("Not a settings file: {}", e))?;
+
+        let url = settings.server_url.trim();
+        if !url.is_empty() && !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(format!("Invalid server URL: {}", url));
+        }
+        if !DOWNLOAD_LIMIT_OPTIONS.contains(&settings.max_concurrent_downloads) {
+            return Err(format!(
+                "Invalid download limit: {}",
+                settings.max_concurrent_downloads
+            ));
+        }
+        if !SNIPPET_LENGTH_OPTIONS.contains(&settings.snippet_length) {
+            return Err(format!(
+                "Invalid snippet length: {}",
+                settings.snippet_length
+            ));
+        }
+
+        Ok(settings)
     }
 
     /// Save settings to disk
@@ -80,6 +521,28 @@ impl Settings {
         let contents = toml::to_string_pretty(self)
             .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-        fs::write(&path, contents).map_err(|e| format!("Failed to write config: {}", e))
+        write_atomic(&path, &contents).map_err(|e| format!("Failed to write config: {}", e))
+    }
+}
+
+/// Replace a file's contents without ever leaving it half-written
+///
+/// Writes to a temporary file next to `path`, flushes it to disk and renames
+/// it over the original, so a crash mid-save leaves the old file intact.
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = fs::File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp_path, path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
     }
+    result
 }
@@ -0,0 +1,64 @@
+//! Saved searches
+//!
+//! Named search queries shown in the sidebar as smart folders, persisted
+//! next to the settings file.
+
+use crate::config::{write_atomic, Settings};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// A search the user saved for quick access
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    /// Name shown in the sidebar
+    pub name: String,
+    /// Query text, including any filters
+    pub query: String,
+    /// Whether the search runs in deep (full-text) mode
+    #[serde(default)]
+    pub deep: bool,
+}
+
+/// On-disk layout (TOML needs a table at the top level)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedSearchFile {
+    #[serde(default)]
+    searches: Vec<SavedSearch>,
+}
+
+impl SavedSearch {
+    /// Get the saved searches file path
+    fn saved_searches_path() -> Option<PathBuf> {
+        Settings::config_dir().map(|dir| dir.join("saved_searches.toml"))
+    }
+
+    /// Load saved searches, or none if the file is missing or unreadable
+    pub fn load_all() -> Vec<Self> {
+        Self::saved_searches_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| toml::from_str::<SavedSearchFile>(&contents).ok())
+            .map(|file| file.searches)
+            .unwrap_or_default()
+    }
+
+    /// Save the full list of saved searches to disk
+    pub fn save_all(searches: &[SavedSearch]) -> Result<(), String> {
+        let dir = match Settings::config_dir() {
+            Some(d) => d,
+            None => return Err("Could not determine config directory".to_string()),
+        };
+
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+
+        let file = SavedSearchFile {
+            searches: searches.to_vec(),
+        };
+        let contents = toml::to_string_pretty(&file)
+            .map_err(|e| format!("Failed to serialize saved searches: {}", e))?;
+
+        write_atomic(&dir.join("saved_searches.toml"), &contents)
+            .map_err(|e| format!("Failed to write saved searches: {}", e))
+    }
+}
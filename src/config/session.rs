@@ -0,0 +1,72 @@
+//! Session persistence
+//!
+//! Remembers the view (and filter) the user was last in so it can be
+//! restored after the next connect.
+
+use crate::api::types::ViewType;
+use crate::config::{write_atomic, Settings};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Lightweight snapshot of the last-open view
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    /// Which view was open
+    pub view: SessionView,
+    /// Message filter type (for message list/detail views)
+    #[serde(default)]
+    pub filter_type: String,
+    /// Message filter value
+    #[serde(default)]
+    pub filter_value: String,
+    /// Breadcrumb description of the message filter
+    #[serde(default)]
+    pub filter_description: String,
+    /// Pagination offset into the message list
+    #[serde(default)]
+    pub messages_offset: i64,
+}
+
+/// Restorable view kinds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SessionView {
+    Dashboard,
+    Aggregates { view_type: ViewType },
+    Messages,
+    MessageDetail { message_id: i64 },
+    Search { query: String },
+    Sync,
+    Accounts,
+}
+
+impl Session {
+    /// Get the session file path
+    fn session_path() -> Option<PathBuf> {
+        Settings::config_dir().map(|dir| dir.join("session.toml"))
+    }
+
+    /// Load the last saved session, if any
+    pub fn load() -> Option<Self> {
+        let contents = fs::read_to_string(Self::session_path()?).ok()?;
+        toml::from_str(&contents).ok()
+    }
+
+    /// Save the session to disk
+    pub fn save(&self) -> Result<(), String> {
+        let dir = match Settings::config_dir() {
+            Some(d) => d,
+            None => return Err("Could not determine config directory".to_string()),
+        };
+
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+
+        let contents = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize session: {}", e))?;
+
+        write_atomic(&dir.join("session.toml"), &contents)
+            .map_err(|e| format!("Failed to write session: {}", e))
+    }
+}
@@ -16,6 +16,12 @@ pub enum AppError {
 
     #[error("Request failed: {0}")]
     RequestFailed(String),
+
+    #[error("Plain HTTP blocked; use https://")]
+    InsecureBlocked,
+
+    #[error("Server certificate changed since pinning")]
+    CertificateChanged,
 }
 
 impl From<reqwest::Error> for AppError {
@@ -0,0 +1,69 @@
+//! CSV export of archive statistics
+//!
+//! Produces a small metric/value table for pasting into a spreadsheet.
+
+use crate::api::types::StatsResponse;
+use crate::view::widgets::format_bytes;
+
+/// Format dashboard statistics as a two-column CSV table
+///
+/// Counts are written raw so spreadsheets read them as numbers; the
+/// database size is given both in bytes and human-readable.
+pub fn stats_csv(stats: &StatsResponse) -> String {
+    let rows = [
+        ("messages", stats.total_messages.to_string()),
+        ("threads", stats.total_threads.to_string()),
+        ("accounts", stats.total_accounts.to_string()),
+        ("labels", stats.total_labels.to_string()),
+        ("attachments", stats.total_attachments.to_string()),
+        ("database_size_bytes", stats.database_size_bytes.to_string()),
+        ("database_size", format_bytes(stats.database_size_bytes)),
+    ];
+
+    let mut csv = String::from("metric,value\n");
+    for (metric, value) in rows {
+        csv.push_str(&format!("{},{}\n", metric, csv_field(&value)));
+    }
+    csv
+}
+
+/// Quote a field if it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_csv() {
+        let stats = StatsResponse {
+            total_messages: 12_345,
+            total_threads: 6_789,
+            total_accounts: 2,
+            total_labels: 40,
+            total_attachments: 512,
+            database_size_bytes: 1_048_576,
+        };
+        let csv = stats_csv(&stats);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "metric,value");
+        assert_eq!(lines[1], "messages,12345");
+        assert_eq!(lines[6], "database_size_bytes,1048576");
+        assert!(lines[7].starts_with("database_size,"));
+        assert_eq!(lines.len(), 8);
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("1.0 MiB"), "1.0 MiB");
+        assert_eq!(csv_field("1,0 MiB"), "\"1,0 MiB\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,9 @@
+//! Export of archive data to files
+//!
+//! Formats messages and statistics for use outside the app.
+
+pub mod csv;
+pub mod pdf;
+
+pub use csv::stats_csv;
+pub use pdf::{message_pdf, pdf_file_name};
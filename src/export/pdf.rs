@@ -0,0 +1,348 @@
+//! PDF export of a single message
+//!
+//! Lays out the header, body and attachment list as plain text on A4
+//! pages using the standard Helvetica fonts, so no font files are needed.
+
+use crate::api::types::MessageDetail;
+use chrono::{DateTime, Local, Utc};
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str};
+
+/// A4 page size in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+
+/// Page margin in points
+const MARGIN: f32 = 56.0;
+
+/// Body font size and line height in points
+const FONT_SIZE: f32 = 10.0;
+const LINE_HEIGHT: f32 = 14.0;
+
+/// Subject font size in points
+const TITLE_SIZE: f32 = 14.0;
+
+/// Characters per line; Helvetica averages about half its size in width
+const WRAP_COLUMNS: usize = ((PAGE_WIDTH - 2.0 * MARGIN) / (FONT_SIZE * 0.5)) as usize;
+
+/// One laid-out line of the document
+#[derive(Debug, Clone, PartialEq)]
+struct Line {
+    text: String,
+    bold: bool,
+    size: f32,
+}
+
+impl Line {
+    fn plain(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            bold: false,
+            size: FONT_SIZE,
+        }
+    }
+
+    fn bold(text: impl Into<String>) -> Self {
+        Self {
+            bold: true,
+            ..Self::plain(text)
+        }
+    }
+}
+
+/// Render a message as a PDF document
+pub fn message_pdf(message: &MessageDetail) -> Vec<u8> {
+    let lines = layout(message);
+    let lines_per_page = ((PAGE_HEIGHT - 2.0 * MARGIN) / LINE_HEIGHT) as usize;
+    let pages: Vec<&[Line]> = lines.chunks(lines_per_page.max(1)).collect();
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let bold_font_id = Ref::new(4);
+    let page_ids: Vec<Ref> = (0..pages.len())
+        .map(|i| Ref::new(5 + 2 * i as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(pages.len() as i32);
+    pdf.type1_font(font_id)
+        .base_font(Name(b"Helvetica"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.type1_font(bold_font_id)
+        .base_font(Name(b"Helvetica-Bold"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+    for (page_lines, page_id) in pages.iter().zip(&page_ids) {
+        let content_id = Ref::new(page_id.get() + 1);
+
+        {
+            let mut page = pdf.page(*page_id);
+            page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+            page.parent(page_tree_id);
+            page.contents(content_id);
+            page.resources()
+                .fonts()
+                .pair(Name(b"F1"), font_id)
+                .pair(Name(b"F2"), bold_font_id);
+        }
+
+        let mut content = Content::new();
+        let mut y = PAGE_HEIGHT - MARGIN;
+        for line in page_lines.iter() {
+            y -= LINE_HEIGHT;
+            if line.text.is_empty() {
+                continue;
+            }
+            let font = if line.bold { Name(b"F2") } else { Name(b"F1") };
+            let encoded = win_ansi(&line.text);
+            content.begin_text();
+            content.set_font(font, line.size);
+            content.next_line(MARGIN, y);
+            content.show(Str(&encoded));
+            content.end_text();
+        }
+        pdf.stream(content_id, &content.finish());
+    }
+
+    pdf.finish()
+}
+
+/// Suggested file name for a message's PDF, based on its subject
+pub fn pdf_file_name(message: &MessageDetail) -> String {
+    let stem: String = message
+        .subject
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+        .take(60)
+        .collect();
+    let stem = stem.trim();
+
+    if stem.is_empty() {
+        format!("message-{}.pdf", message.id)
+    } else {
+        format!("{}.pdf", stem)
+    }
+}
+
+/// Lay out the header, body and attachment list as wrapped lines
+fn layout(message: &MessageDetail) -> Vec<Line> {
+    let mut lines = Vec::new();
+
+    let subject = if message.subject.is_empty() {
+        "(No subject)"
+    } else {
+        &message.subject
+    };
+    for part in wrap(
+        subject,
+        (WRAP_COLUMNS as f32 * FONT_SIZE / TITLE_SIZE) as usize,
+    ) {
+        lines.push(Line {
+            size: TITLE_SIZE,
+            ..Line::bold(part)
+        });
+    }
+    lines.push(Line::plain(""));
+
+    let local: DateTime<Local> = message.sent_at.with_timezone(&Local);
+    let mut header = vec![
+        ("From", message.from_addr.clone()),
+        ("To", message.to.join(", ")),
+    ];
+    if !message.cc.is_empty() {
+        header.push(("Cc", message.cc.join(", ")));
+    }
+    header.push(("Date", format_date(&message.sent_at, &local)));
+    for (label, value) in header {
+        for (i, part) in wrap(&value, WRAP_COLUMNS - 8).into_iter().enumerate() {
+            if i == 0 {
+                lines.push(Line::bold(format!("{}: {}", label, part)));
+            } else {
+                lines.push(Line::plain(format!("    {}", part)));
+            }
+        }
+    }
+    lines.push(Line::plain(""));
+
+    if message.body.is_empty() {
+        lines.push(Line::plain("(No message body)"));
+    } else {
+        for body_line in message.body.lines() {
+            lines.extend(wrap(body_line, WRAP_COLUMNS).into_iter().map(Line::plain));
+        }
+    }
+
+    // Inline images are part of the body, not files worth listing
+    let attachments: Vec<_> = message
+        .attachments
+        .iter()
+        .filter(|a| !a.is_inline())
+        .collect();
+    if !attachments.is_empty() {
+        lines.push(Line::plain(""));
+        lines.push(Line::bold(format!("Attachments ({})", attachments.len())));
+        for attachment in attachments {
+            let entry = format!(
+                "- {} ({} bytes)",
+                attachment.filename, attachment.size_bytes
+            );
+            lines.extend(wrap(&entry, WRAP_COLUMNS).into_iter().map(Line::plain));
+        }
+    }
+
+    lines
+}
+
+/// Format the sent date with the local time and the UTC original
+fn format_date(utc: &DateTime<Utc>, local: &DateTime<Local>) -> String {
+    format!(
+        "{} ({} UTC)",
+        local.format("%a, %b %d, %Y at %I:%M %p"),
+        utc.format("%Y-%m-%d %H:%M")
+    )
+}
+
+/// Word-wrap a line to at most `columns` characters
+///
+/// Words longer than a line are split. Returns one empty line for empty input.
+fn wrap(text: &str, columns: usize) -> Vec<String> {
+    let columns = columns.max(1);
+    let text = text.replace('\t', "    ");
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split(' ') {
+        let mut word = word;
+        loop {
+            let needed = if current.is_empty() { 0 } else { 1 } + word.chars().count();
+            if current.chars().count() + needed <= columns {
+                if !current.is_empty() {
+                    current.push(' ');
+                }
+                current.push_str(word);
+                break;
+            }
+            if current.is_empty() {
+                // Word alone is too long; split it
+                let split = word
+                    .char_indices()
+                    .nth(columns)
+                    .map(|(i, _)| i)
+                    .unwrap_or(word.len());
+                lines.push(word[..split].to_string());
+                word = &word[split..];
+                if word.is_empty() {
+                    break;
+                }
+            } else {
+                lines.push(std::mem::take(&mut current));
+            }
+        }
+    }
+
+    lines.push(current);
+    lines
+}
+
+/// Encode text for the standard fonts' WinAnsi encoding
+///
+/// Latin-1 maps directly and common typographic punctuation is translated;
+/// anything else becomes `?`.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '\u{20}'..='\u{7e}' | '\u{a0}'..='\u{ff}' => c as u8,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201c}' => 0x93,
+            '\u{201d}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            '\u{2026}' => 0x85,
+            '\u{20ac}' => 0x80,
+            _ => b'?',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::Attachment;
+
+    fn sample_message(body: &str) -> MessageDetail {
+        MessageDetail {
+            id: 7,
+            subject: "Quarterly report".to_string(),
+            from_addr: "Alice <alice@example.com>".to_string(),
+            to: vec!["bob@example.com".to_string()],
+            cc: Vec::new(),
+            bcc: Vec::new(),
+            sent_at: DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            body: body.to_string(),
+            body_html: None,
+            labels: Vec::new(),
+            attachments: vec![Attachment {
+                id: None,
+                filename: "report.pdf".to_string(),
+                mime_type: "application/pdf".to_string(),
+                size_bytes: 2048,
+                disposition: None,
+                content_id: None,
+            }],
+            thread_id: None,
+            headers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("one two three", 7), vec!["one two", "three"]);
+        assert_eq!(wrap("abcdefgh", 3), vec!["abc", "def", "gh"]);
+        assert_eq!(wrap("", 10), vec![""]);
+    }
+
+    #[test]
+    fn test_win_ansi() {
+        assert_eq!(win_ansi("café – “ok”"), b"caf\xe9 \x96 \x93ok\x94".to_vec());
+        assert_eq!(win_ansi("日本"), b"??".to_vec());
+    }
+
+    #[test]
+    fn test_layout_includes_header_body_and_attachments() {
+        let lines = layout(&sample_message("Numbers attached.\n\nThanks"));
+        let texts: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+
+        assert_eq!(texts[0], "Quarterly report");
+        assert!(texts.contains(&"From: Alice <alice@example.com>"));
+        assert!(texts.contains(&"To: bob@example.com"));
+        assert!(texts.contains(&"Numbers attached."));
+        assert!(texts.contains(&"- report.pdf (2048 bytes)"));
+    }
+
+    #[test]
+    fn test_pdf_file_name() {
+        let mut message = sample_message("");
+        message.subject = "Re: Q1/Q2 numbers?".to_string();
+        assert_eq!(pdf_file_name(&message), "Re Q1Q2 numbers.pdf");
+
+        message.subject = "???".to_string();
+        assert_eq!(pdf_file_name(&message), "message-7.pdf");
+    }
+
+    #[test]
+    fn test_long_body_spans_pages() {
+        let body = "line\n".repeat(200);
+        let pdf = message_pdf(&sample_message(&body));
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF-"));
+        assert!(text.contains("/Count 5"));
+    }
+}
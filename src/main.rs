@@ -7,6 +7,7 @@ mod api;
 mod app;
 mod config;
 mod error;
+mod export;
 mod message;
 mod model;
 pub mod theme;
@@ -18,41 +19,44 @@ use config::Settings;
 use iced::theme::{Custom, Palette};
 use iced::Theme;
 
-/// Create the msgvault Foundry Dark theme
+/// Create the msgvault theme from the active palette
 fn msgvault_theme() -> Theme {
+    let name = if theme::colors::is_high_contrast() {
+        "High Contrast"
+    } else {
+        "Foundry Dark"
+    };
     Theme::Custom(std::sync::Arc::new(Custom::new(
-        "Foundry Dark".to_string(),
+        name.to_string(),
         Palette {
-            background: theme::colors::BG_BASE,
-            text: theme::colors::TEXT_PRIMARY,
-            primary: theme::colors::ACCENT_PRIMARY,
-            success: theme::colors::ACCENT_SUCCESS,
-            danger: theme::colors::ACCENT_ERROR,
+            background: theme::colors::bg_base(),
+            text: theme::colors::text_primary(),
+            primary: theme::colors::accent_primary(),
+            success: theme::colors::accent_success(),
+            danger: theme::colors::accent_error(),
         },
     )))
 }
 
 fn main() -> iced::Result {
     // Load configuration
-    let settings = Settings::load().unwrap_or_else(|e| {
+    let (settings, config_recovery) = Settings::load().unwrap_or_else(|e| {
         eprintln!("Warning: Could not load config: {}", e);
-        Settings::default()
+        (Settings::default(), None)
     });
+    settings.theme.apply();
+    settings.byte_units.apply();
 
     // Run the Iced application with Foundry Dark theme and IBM Plex fonts
-    iced::application(MsgVaultApp::title, MsgVaultApp::update, MsgVaultApp::view)
+    // A daemon so compose can open in its own window; the main window is
+    // opened by MsgVaultApp::new
+    iced::daemon(MsgVaultApp::title, MsgVaultApp::update, MsgVaultApp::view)
         .subscription(MsgVaultApp::subscription)
-        .window(iced::window::Settings {
-            size: iced::Size::new(1200.0, 800.0),
-            min_size: Some(iced::Size::new(900.0, 600.0)),
-            position: iced::window::Position::Centered,
-            ..Default::default()
-        })
         .font(include_bytes!("../assets/fonts/IBMPlexSans-Regular.ttf").as_slice())
         .font(include_bytes!("../assets/fonts/IBMPlexSans-Medium.ttf").as_slice())
         .font(include_bytes!("../assets/fonts/IBMPlexSans-SemiBold.ttf").as_slice())
         .font(include_bytes!("../assets/fonts/IBMPlexMono-Regular.ttf").as_slice())
         .default_font(theme::typography::FONT_PRIMARY)
-        .theme(|_| msgvault_theme())
-        .run_with(|| MsgVaultApp::new(settings))
+        .theme(|_, _| msgvault_theme())
+        .run_with(|| MsgVaultApp::new(settings, config_recovery))
 }
@@ -4,13 +4,19 @@
 //! The update function processes these to modify application state.
 
 use crate::api::types::{
-    AggregateResponse, DeviceFlowStatus, HealthResponse, MessageDetail, MessageListResponse,
-    OAuthInitResponse, RemoveAccountResponse, SchedulerStatus, SearchResponse, StatsResponse,
+    AggregateResponse, DeviceFlowStatus, Fetched, HealthResponse, MarkReadResponse, MessageDetail,
+    MessageListResponse, NewMessagesResponse, OAuthInitResponse, RemoveAccountResponse,
+    SchedulerStatus, SearchResponse, SetLabelsResponse, StageDeletionResponse, StatsResponse,
     SyncTriggerResponse, ViewType,
 };
-use crate::config::DiscoveryResult;
+use crate::config::{
+    BlockedSenderDisplay, ByteUnits, DefaultView, DiscoveryResult, DiscoveryStep, PinnedFilter,
+    ReadingPaneMode, Session, Settings, ThemeChoice,
+};
 use crate::error::AppError;
-use crate::model::{SettingsTab, ViewLevel};
+use crate::model::{
+    AggregateQuery, ComposeMode, ContextTarget, Divider, MenuBarMenu, SettingsTab, ViewLevel,
+};
 
 /// All possible messages in the application
 #[derive(Debug, Clone)]
@@ -24,6 +30,14 @@ pub enum Message {
     ConfirmDiscoveredServer,
     /// User chose manual entry
     ChooseManualEntry,
+    /// Check every discovery source and show all the results
+    RunDiscoveryDiagnostics,
+    /// Every discovery source was checked
+    DiscoveryDiagnosticsComplete(Vec<DiscoveryStep>),
+    /// Wizard port input changed
+    WizardPortChanged(String),
+    /// Add the wizard's port to discovery and search again
+    DiscoverOnPort,
     /// Wizard server URL changed
     WizardServerUrlChanged(String),
     /// Wizard API key changed
@@ -36,26 +50,62 @@ pub enum Message {
     CheckHealth,
     /// Health check completed
     HealthChecked(Result<HealthResponse, AppError>),
+    /// Hide the banner about an outdated server
+    DismissServerVersionNotice,
+    /// Periodic background health check while connected
+    HealthTick,
+    /// Background health check completed
+    HealthPinged(Result<HealthResponse, AppError>),
+    /// One second of the connection screen's retry countdown passed
+    ReconnectTick,
+    /// Stop the retry countdown and focus the server URL field
+    EditConnectionSettings,
 
     // === Stats ===
     /// Fetch archive statistics
     FetchStats,
     /// Stats loaded
-    StatsLoaded(Result<StatsResponse, AppError>),
+    StatsLoaded(Result<Fetched<StatsResponse>, AppError>),
 
     // === Aggregates ===
     /// Fetch aggregates for current view type
     FetchAggregates(ViewType),
     /// Aggregates loaded
-    AggregatesLoaded(Result<AggregateResponse, AppError>),
+    AggregatesLoaded(AggregateQuery, Result<Fetched<AggregateResponse>, AppError>),
+    /// Aggregate list filter text changed
+    AggregateFilterChanged(String),
+    /// Aggregate list scrolled
+    AggregatesScrolled(iced::widget::scrollable::Viewport),
     /// Select an aggregate row by index
     SelectAggregate(usize),
     /// Move selection up
     SelectPrevious,
     /// Move selection down
     SelectNext,
+    /// Jump to the first row of the current list (Home)
+    SelectFirst,
+    /// Jump to the last row of the current list (End)
+    SelectLast,
+    /// Move selection up by a page (PageUp)
+    PageSelectUp,
+    /// Move selection down by a page (PageDown)
+    PageSelectDown,
+    /// Start type-ahead jump in the current list (g)
+    StartTypeahead,
+    /// Append typed text to the type-ahead buffer
+    TypeaheadInput(String),
+    /// Remove the last character from the type-ahead buffer
+    TypeaheadBackspace,
+    /// Dismiss type-ahead
+    CancelTypeahead,
+    /// Periodic check for type-ahead idle timeout
+    TypeaheadTick,
     /// Drill down into selected aggregate
     DrillDown,
+    /// Select an aggregate row by index and drill into it (heatmap click)
+    DrillDownRow(usize),
+    /// Toggle the Time aggregate between heatmap and list layouts
+    ToggleTimeLayout,
     /// Toggle sort field (name -> count -> size)
     ToggleSortField,
     /// Toggle sort direction
@@ -69,12 +119,36 @@ pub enum Message {
     },
     /// Messages loaded
     MessagesLoaded(Result<MessageListResponse, AppError>),
+    /// Message detail fetched while restoring a saved session
+    SessionMessageLoaded(Session, Result<MessageDetail, AppError>),
     /// Select a message in the list
     SelectMessage(usize),
     /// Open the selected message (view detail)
     OpenMessage,
     /// Message detail loaded
     MessageDetailLoaded(Result<MessageDetail, AppError>),
+    /// Open a message by ID, e.g. from "More from this sender"
+    OpenMessageById(i64),
+    /// Check whether focus has rested long enough to prefetch its message
+    PrefetchTick,
+    /// Background fetch of the focused message completed
+    DetailPrefetched(i64, Result<MessageDetail, AppError>),
+    /// Recent messages from a sender loaded
+    SenderMessagesLoaded(String, Result<MessageListResponse, AppError>),
+    /// Show the current message's body past the preview cap
+    ShowFullBody,
+    /// Expand or collapse a message's quoted text
+    ToggleQuotedText(i64),
+    /// Load a message's blocked remote content this once
+    LoadRemoteContent(i64),
+    /// Always load remote content from a sender
+    AllowRemoteContent(String),
+    /// Stop loading remote content from a sender
+    DisallowRemoteContent(String),
+    /// Save a message as a PDF chosen in a save dialog
+    ExportMessagePdf(i64),
+    /// PDF export finished (None if the dialog was cancelled)
+    PdfExported(Result<Option<std::path::PathBuf>, String>),
     /// Go to next page of messages
     NextPage,
     /// Go to previous page of messages
@@ -83,6 +157,20 @@ pub enum Message {
     PreviousMessage,
     /// Navigate to next message in list
     NextMessage,
+    /// Mark every loaded message in the current list as read
+    MarkAllRead,
+    /// Mark a single message as read or unread
+    SetReadState { message_id: i64, read: bool },
+    /// Read-state request finished (IDs that were optimistically updated)
+    ReadStateUpdated {
+        message_ids: Vec<i64>,
+        read: bool,
+        result: Result<MarkReadResponse, AppError>,
+    },
+    /// Select only this message and ask to confirm its deletion
+    DeleteOne(i64),
+    /// Avatar fetch finished for a sender address (None if it has no avatar)
+    AvatarLoaded(String, Result<Option<Vec<u8>>, AppError>),
 
     // === Threading ===
     /// View full thread for current message
@@ -111,10 +199,22 @@ pub enum Message {
     SearchLoaded(Result<SearchResponse, AppError>),
     /// Toggle between fast/deep search mode
     ToggleSearchMode,
+    /// Show or hide the search filter tips
+    ToggleSearchTips,
     /// Select a search result
     SelectSearchResult(usize),
     /// Open selected search result
     OpenSearchResult,
+    /// Go to next page of deep search results
+    NextSearchPage,
+    /// Go to previous page of deep search results
+    PreviousSearchPage,
+    /// Save the current query and mode as a sidebar smart folder
+    SaveCurrentSearch,
+    /// Open a saved search and run it
+    OpenSavedSearch(usize),
+    /// Remove a saved search
+    DeleteSavedSearch(usize),
 
     // === Sync ===
     /// Open sync status view
@@ -131,10 +231,25 @@ pub enum Message {
     RefreshSyncStatus,
     /// Periodic sync tick (fires every 30s when connected)
     SyncTick,
+    /// Pause or resume scheduled sync for an account
+    SetSyncPaused { email: String, paused: bool },
+    /// Pause/resume response
+    SyncPausedSet(Result<SyncTriggerResponse, AppError>),
+
+    /// A sync desktop notification was clicked
+    SyncNotificationClicked,
+    /// Check for messages synced since the last check
+    CheckNewMessages,
+    /// New message counts received
+    NewMessagesChecked(Result<NewMessagesResponse, AppError>),
 
     // === Account Management ===
     /// Open accounts view
     OpenAccounts,
+    /// Go to the add-account form and focus its email field
+    FocusAddAccount,
+    /// Filter text for the Accounts and Sync lists changed
+    AccountFilterChanged(String),
     /// Email input changed for add account
     AddAccountEmailChanged(String),
     /// Start add account flow (initiate OAuth)
@@ -143,12 +258,24 @@ pub enum Message {
     OAuthInitiated(Result<OAuthInitResponse, AppError>),
     /// Open browser for OAuth
     OpenOAuthBrowser(String),
+    /// Open a message in the server's web view
+    OpenMessageInBrowser(i64),
     /// Poll device flow status
     PollDeviceFlow,
     /// Device flow status received
     DeviceFlowStatusReceived(Result<DeviceFlowStatus, AppError>),
     /// Cancel add account flow
     CancelAddAccount,
+    /// Restart the OAuth flow for an existing account
+    ReauthenticateAccount(String),
+    /// Pick a file of email addresses to add as accounts
+    ImportAccounts,
+    /// Account list file read (None if the picker was cancelled)
+    AccountListLoaded(Result<Option<String>, String>),
+    /// Move on from an account signing in through the browser
+    NextImportedAccount,
+    /// Stop an account import, or dismiss a finished one
+    CancelAccountImport,
     /// Show remove account confirmation
     ShowRemoveAccountModal(String),
     /// Hide remove account modal
@@ -188,9 +315,39 @@ pub enum Message {
         message_id: i64,
         attachment_idx: usize,
         error: String,
+        /// Bytes kept on disk for a resumed retry
+        partial_bytes: u64,
+    },
+    /// A download task stopped, finished or aborted, freeing its slot
+    DownloadStopped {
+        message_id: i64,
+        attachment_idx: usize,
     },
     /// Open a downloaded file
     OpenFile(std::path::PathBuf),
+    /// Show a file in the system file manager
+    RevealFile(std::path::PathBuf),
+    /// Pick where to save an attachment, then download it there
+    SaveAttachmentAs {
+        message_id: i64,
+        attachment_idx: usize,
+        filename: String,
+    },
+    /// Save As finished (None if the dialog was cancelled)
+    AttachmentSaved(Result<Option<std::path::PathBuf>, String>),
+    /// Cancel a queued or running download
+    CancelDownload {
+        message_id: i64,
+        attachment_idx: usize,
+    },
+    /// Show or hide the downloads panel
+    ToggleDownloadsPanel,
+    /// Drop completed and failed downloads from the panel
+    ClearFinishedDownloads,
+    /// Fetch a message's attachment list and download every attachment
+    DownloadAllAttachments(i64),
+    /// Attachment list fetched for downloading
+    AttachmentsListed(Result<MessageDetail, AppError>),
 
     // === Compose ===
     /// Open compose for new email
@@ -201,6 +358,8 @@ pub enum Message {
     OpenReplyAll(i64),
     /// Open compose as forward of message
     OpenForward(i64),
+    /// Message being replied to or forwarded, fetched because it wasn't loaded
+    ComposeSourceLoaded(ComposeMode, Result<MessageDetail, AppError>),
     /// To field input changed
     ComposeToChanged(String),
     /// Add recipient to To field
@@ -245,6 +404,10 @@ pub enum Message {
     ComposeDiscard,
     /// Close compose (with confirmation if dirty)
     ComposeClose,
+    /// Move compose out of the modal into its own window
+    PopOutCompose,
+    /// Close the compose window and continue in the modal
+    DockCompose,
 
     // === Settings ===
     /// Open settings view
@@ -255,6 +418,52 @@ pub enum Message {
     SettingsServerUrlChanged(String),
     /// Settings API key changed
     SettingsApiKeyChanged(String),
+    /// Settings insecure connections toggled
+    SettingsAllowInsecureToggled(bool),
+    /// Settings certificate pinning toggled
+    SettingsPinCertificateToggled(bool),
+    /// Include the API key in exported settings toggled
+    SettingsExportApiKeyToggled(bool),
+    /// Save the settings to a file chosen in a save dialog
+    ExportSettings,
+    /// Show the folder holding the config file in the file manager
+    OpenConfigFolder,
+    /// Settings export finished (None if the dialog was cancelled)
+    SettingsExported(Result<Option<std::path::PathBuf>, String>),
+    /// Save the keyboard shortcut cheatsheet to a text file
+    ExportShortcuts,
+    /// Cheatsheet written (None if the save dialog was cancelled)
+    ShortcutsExported(Result<Option<std::path::PathBuf>, String>),
+    /// Load settings from a file chosen in an open dialog
+    ImportSettings,
+    /// Settings file read and validated (None if the dialog was cancelled)
+    SettingsImported(Result<Option<Settings>, String>),
+    /// Settings default landing view changed
+    SettingsDefaultViewChanged(DefaultView),
+    /// Settings restore-last-view toggled
+    SettingsRestoreSessionToggled(bool),
+    /// Settings inline keyboard hints toggled
+    SettingsShowKeyHintsToggled(bool),
+    /// Settings message list previews toggled
+    SettingsShowSnippetsToggled(bool),
+    /// Settings message list preview length changed
+    SettingsSnippetLengthChanged(usize),
+    /// Settings color palette changed
+    SettingsThemeChanged(ThemeChoice),
+    /// Settings size units changed
+    SettingsByteUnitsChanged(ByteUnits),
+    /// Settings reading pane mode changed
+    SettingsReadingPaneChanged(ReadingPaneMode),
+    /// Settings download concurrency limit changed
+    SettingsMaxDownloadsChanged(usize),
+    /// Settings auto-open downloads toggled
+    SettingsAutoOpenDownloadsToggled(bool),
+    /// Settings auto-open extensions edited
+    SettingsAutoOpenExtensionsChanged(String),
+    /// Settings remote avatars toggled
+    SettingsLoadRemoteAvatarsToggled(bool),
+    /// Settings desktop notifications toggled
+    SettingsDesktopNotificationsToggled(bool),
     /// Test connection
     TestConnection,
     /// Connection test result
@@ -263,8 +472,20 @@ pub enum Message {
     SaveSettings,
     /// Settings saved
     SettingsSaved(Result<(), String>),
+    /// Show reset-to-defaults confirmation modal
+    ShowResetSettingsModal,
+    /// Hide reset-to-defaults confirmation modal
+    HideResetSettingsModal,
+    /// Reset all settings to defaults and return to the setup wizard
+    ConfirmResetSettings,
 
     // === Selection ===
+    /// A message row was clicked (shift-click selects the range from the anchor)
+    RowClicked(usize),
+    /// The pointer entered a message row
+    RowHovered(i64),
+    /// The pointer left a message row
+    RowUnhovered(i64),
     /// Toggle selection of current message (Space key)
     ToggleSelection,
     /// Select all visible messages (Shift+A key)
@@ -275,10 +496,64 @@ pub enum Message {
     ShowDeleteModal,
     /// Hide delete confirmation modal
     HideDeleteModal,
+    /// Count typed into the large-deletion confirmation changed
+    DeleteConfirmInputChanged(String),
     /// Confirm deletion of selected messages
     ConfirmDelete,
     /// Stage selected messages for deletion
     StageForDeletion,
+    /// One chunk of a deletion was staged (or failed)
+    DeletionChunkStaged {
+        message_ids: Vec<i64>,
+        result: Result<StageDeletionResponse, AppError>,
+    },
+
+    // === Blocked Senders ===
+    /// Add a sender to the blocklist
+    BlockSender(String),
+    /// Remove a sender from the blocklist
+    UnblockSender(String),
+    /// Blocklist add field changed
+    BlockSenderInputChanged(String),
+    /// Choose whether blocked senders are hidden or marked
+    BlockedSenderDisplayChanged(BlockedSenderDisplay),
+
+    // === Muting ===
+    /// Stop announcing new messages from a sender
+    MuteSender(String),
+    /// Announce new messages from a sender again
+    UnmuteSender(String),
+    /// Stop announcing new messages in a thread
+    MuteThread(String),
+    /// Announce new messages in a thread again
+    UnmuteThread(String),
+
+    // === Pinned ===
+    /// Pin a sender or domain to the sidebar
+    PinFilter(PinnedFilter),
+    /// Remove a sender or domain from the sidebar
+    UnpinFilter(PinnedFilter),
+    /// Move a pinned entry up or down the sidebar
+    MovePinnedFilter { index: usize, up: bool },
+    /// Open the messages for a pinned entry
+    OpenPinnedFilter(usize),
+
+    // === Labels ===
+    /// Open the label picker for the selection or focused message (l key)
+    OpenLabelPicker,
+    /// Close the label picker
+    CloseLabelPicker,
+    /// Available labels loaded for the picker
+    LabelListLoaded(Result<Fetched<AggregateResponse>, AppError>),
+    /// Label picker filter text changed
+    LabelFilterChanged(String),
+    /// Add or remove a label on the picker's messages
+    ApplyLabel { label: String, add: bool },
+    /// Label change finished (labels before the optimistic update, for revert)
+    LabelsUpdated {
+        previous: Vec<(i64, Vec<String>)>,
+        result: Result<SetLabelsResponse, AppError>,
+    },
 
     // === Navigation ===
     /// Navigate to a specific view
@@ -297,12 +572,93 @@ pub enum Message {
     ServerUrlChanged(String),
     /// API key changed in settings
     ApiKeyChanged(String),
+    /// Insecure HTTP permitted from the connection screen
+    AllowInsecureConnection,
+    /// Accept the server's changed certificate as the new pin
+    TrustNewCertificate,
     /// Retry connection button pressed
     RetryConnection,
 
+    // === Notifications ===
+    /// Dismiss the current toast
+    DismissToast,
+    /// Dismiss the notice that the config file was reset
+    DismissConfigRecovery,
+    /// Check whether the current toast has expired
+    ToastTick,
+
+    // === Context Menu ===
+    /// Cursor moved (window coordinates)
+    CursorMoved(iced::window::Id, iced::Point),
+    /// A window was opened or resized
+    WindowResized(iced::window::Id, iced::Size),
+    /// Start dragging a panel divider
+    DividerPressed(Divider),
+    /// Mouse released while dragging a panel divider
+    DividerReleased,
+    /// Collapse the sidebar to an icon rail, or expand it again
+    ToggleSidebar,
+    /// Switch to the next reading pane mode (Cmd/Ctrl+])
+    CycleReadingPane,
+    /// Open the context menu for an item at the cursor
+    ShowContextMenu(ContextTarget),
+    /// Close the context menu
+    CloseContextMenu,
+    /// Close the context menu and dispatch the chosen item's message
+    ContextMenuSelect(Box<Message>),
+    /// Copy text to the system clipboard
+    CopyToClipboard(String),
+
+    // === Menu Bar ===
+    /// Open a menu bar menu, or close it if already open
+    ToggleMenu(MenuBarMenu),
+    /// Pointer moved onto a menu title (switches menus while one is open)
+    HoverMenu(MenuBarMenu),
+    /// Close the open menu bar menu
+    CloseMenu,
+    /// Close the menu bar menu and dispatch the chosen item's message
+    MenuSelect(Box<Message>),
+    /// Close a window (File menu or Cmd+W in that window)
+    CloseWindow(iced::window::Id),
+    /// The user asked to close a window (title bar button)
+    WindowCloseRequested(iced::window::Id),
+    /// A window was closed
+    WindowClosed(iced::window::Id),
+    /// Files are being dragged over a window
+    FilesHovered(iced::window::Id),
+    /// Dragged files left a window without being dropped
+    FilesHoveredLeft(iced::window::Id),
+    /// A file was dropped onto a window (one message per file)
+    FileDropped(iced::window::Id, std::path::PathBuf),
+    /// Open a message in its own window
+    OpenMessageWindow(i64),
+    /// A message window's message loaded
+    WindowMessageLoaded(i64, Result<MessageDetail, AppError>),
+    /// Quit the application, asking first if work would be lost
+    Quit,
+    /// Quit despite the unsent message or unfinished downloads
+    ConfirmQuit,
+    /// Quit once the remaining downloads have finished
+    QuitWhenDone,
+    /// Keep the application open
+    CancelQuit,
+
+    // === Activity ===
+    /// A tracked background operation finished
+    OperationFinished,
+    /// Advance the activity spinner animation
+    ActivityTick,
+
     // === Keyboard ===
-    /// A key was pressed
-    KeyPressed(iced::keyboard::Key, iced::keyboard::Modifiers),
+    /// A key was pressed in a window (`captured` if a focused text input consumed it)
+    KeyPressed {
+        window: iced::window::Id,
+        key: iced::keyboard::Key,
+        modifiers: iced::keyboard::Modifiers,
+        captured: bool,
+    },
+    /// Held modifier keys changed
+    ModifiersChanged(iced::keyboard::Modifiers),
 
     // === No-op ===
     /// Message that does nothing (used for unhandled events)
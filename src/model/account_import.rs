@@ -0,0 +1,209 @@
+//! Bulk account import
+//!
+//! Accounts read from a file are added one at a time through the normal
+//! OAuth flow, since each may need the user to sign in or enter a device
+//! code before the next can start.
+
+/// Progress of one imported account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportStatus {
+    /// Waiting for its turn
+    Queued,
+    /// OAuth started, waiting for the server or the device code
+    Authorizing,
+    /// Sign-in page opened in the browser
+    InBrowser,
+    /// Account added
+    Added,
+    /// Skipped by the user
+    Skipped,
+    /// OAuth could not be started or did not complete
+    Failed(String),
+}
+
+impl ImportStatus {
+    /// Short label for the status column
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Queued => "Queued",
+            Self::Authorizing => "Authorizing\u{2026}",
+            Self::InBrowser => "Sign in via browser",
+            Self::Added => "Added",
+            Self::Skipped => "Skipped",
+            Self::Failed(e) => e,
+        }
+    }
+}
+
+/// One account in an import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportEntry {
+    pub email: String,
+    pub status: ImportStatus,
+}
+
+/// Accounts being imported, in file order
+#[derive(Debug, Clone, Default)]
+pub struct AccountImport {
+    pub entries: Vec<ImportEntry>,
+    current: Option<usize>,
+}
+
+impl AccountImport {
+    /// Queue every address for import
+    pub fn new(emails: Vec<String>) -> Self {
+        Self {
+            entries: emails
+                .into_iter()
+                .map(|email| ImportEntry {
+                    email,
+                    status: ImportStatus::Queued,
+                })
+                .collect(),
+            current: None,
+        }
+    }
+
+    /// Whether an account is currently being added
+    pub fn is_active(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// Status of the account currently being added
+    pub fn current_status(&self) -> Option<&ImportStatus> {
+        self.current.map(|i| &self.entries[i].status)
+    }
+
+    /// Update the account currently being added
+    pub fn set_status(&mut self, status: ImportStatus) {
+        if let Some(i) = self.current {
+            self.entries[i].status = status;
+        }
+    }
+
+    /// Mark the current account finished and start the next queued one,
+    /// returning its address
+    pub fn advance(&mut self, status: ImportStatus) -> Option<String> {
+        self.set_status(status);
+        self.start()
+    }
+
+    /// Start the first queued account, returning its address
+    pub fn start(&mut self) -> Option<String> {
+        self.current = self
+            .entries
+            .iter()
+            .position(|e| e.status == ImportStatus::Queued);
+        let i = self.current?;
+        self.entries[i].status = ImportStatus::Authorizing;
+        Some(self.entries[i].email.clone())
+    }
+
+    /// Stop importing, leaving the remaining accounts skipped
+    pub fn cancel(&mut self) {
+        self.current = None;
+        for entry in &mut self.entries {
+            if matches!(
+                entry.status,
+                ImportStatus::Queued | ImportStatus::Authorizing
+            ) {
+                entry.status = ImportStatus::Skipped;
+            }
+        }
+    }
+
+    /// Number of accounts added so far
+    pub fn added(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.status == ImportStatus::Added)
+            .count()
+    }
+}
+
+/// Email addresses in a newline-separated or CSV list
+///
+/// Takes the first field containing `@` on each line, so header rows and
+/// extra columns such as names are skipped. Duplicates are dropped.
+pub fn parse_account_list(contents: &str) -> Vec<String> {
+    let mut emails: Vec<String> = Vec::new();
+    for line in contents.lines() {
+        let Some(email) = line
+            .split([',', ';', '\t'])
+            .map(|field| field.trim().trim_matches(['"', '\'', '<', '>']).trim())
+            .find(|field| field.contains('@') && !field.contains(char::is_whitespace))
+        else {
+            continue;
+        };
+        if !emails.iter().any(|e| e.eq_ignore_ascii_case(email)) {
+            emails.push(email.to_string());
+        }
+    }
+    emails
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_and_csv_lists() {
+        let plain = "alice@example.com\n\n  bob@example.com  \nALICE@example.com\n";
+        assert_eq!(
+            parse_account_list(plain),
+            vec!["alice@example.com", "bob@example.com"]
+        );
+
+        let csv = "name,email\n\"Alice\",\"alice@example.com\"\nCarol;carol@example.com\nno address here\n";
+        assert_eq!(
+            parse_account_list(csv),
+            vec!["alice@example.com", "carol@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_accounts_are_added_one_at_a_time() {
+        let mut import = AccountImport::new(vec![
+            "a@example.com".to_string(),
+            "b@example.com".to_string(),
+            "c@example.com".to_string(),
+        ]);
+        assert!(!import.is_active());
+
+        assert_eq!(import.start().as_deref(), Some("a@example.com"));
+        assert_eq!(import.current_status(), Some(&ImportStatus::Authorizing));
+
+        assert_eq!(
+            import.advance(ImportStatus::Added).as_deref(),
+            Some("b@example.com")
+        );
+        import.set_status(ImportStatus::InBrowser);
+        assert_eq!(
+            import.advance(ImportStatus::Added).as_deref(),
+            Some("c@example.com")
+        );
+        assert_eq!(
+            import.advance(ImportStatus::Failed("Expired".to_string())),
+            None
+        );
+
+        assert!(!import.is_active());
+        assert_eq!(import.added(), 2);
+    }
+
+    #[test]
+    fn test_cancel_skips_remaining_accounts() {
+        let mut import = AccountImport::new(vec![
+            "a@example.com".to_string(),
+            "b@example.com".to_string(),
+        ]);
+        import.start();
+        import.cancel();
+
+        assert!(!import.is_active());
+        assert!(import
+            .entries
+            .iter()
+            .all(|e| e.status == ImportStatus::Skipped));
+    }
+}
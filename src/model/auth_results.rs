@@ -0,0 +1,194 @@
+//! Sender authentication results
+//!
+//! Reads the SPF, DKIM and DMARC verdicts a receiving server recorded in a
+//! message's `Authentication-Results` headers, to show how far the sender
+//! can be trusted.
+
+use crate::api::types::MessageHeader;
+
+/// Verdict of one authentication mechanism
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthVerdict {
+    /// The check passed
+    Pass,
+    /// The check ran and did not pass (fail, softfail, neutral, errors)
+    Fail,
+    /// No result was recorded, or the server reported `none`
+    Missing,
+}
+
+impl AuthVerdict {
+    /// Parse a result keyword such as `pass` or `softfail`
+    fn parse(keyword: &str) -> Self {
+        match keyword.to_ascii_lowercase().as_str() {
+            "pass" => Self::Pass,
+            "none" | "" => Self::Missing,
+            _ => Self::Fail,
+        }
+    }
+
+    /// Combine results for the same mechanism; one pass is enough
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Pass, _) | (_, Self::Pass) => Self::Pass,
+            (Self::Fail, _) | (_, Self::Fail) => Self::Fail,
+            _ => Self::Missing,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail => "fail",
+            Self::Missing => "none",
+        }
+    }
+}
+
+/// Overall trust shown as the header badge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trust {
+    /// SPF, DKIM and DMARC all passed
+    Verified,
+    /// Some checks passed and none failed
+    Partial,
+    /// A check failed, or none passed
+    Unverified,
+}
+
+impl Trust {
+    /// Badge text
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Verified => "Verified",
+            Self::Partial => "Partially verified",
+            Self::Unverified => "Unverified",
+        }
+    }
+}
+
+/// SPF, DKIM and DMARC verdicts for a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthResults {
+    pub spf: AuthVerdict,
+    pub dkim: AuthVerdict,
+    pub dmarc: AuthVerdict,
+}
+
+impl AuthResults {
+    /// Read the verdicts from a message's headers
+    ///
+    /// None when there is no `Authentication-Results` header, so messages
+    /// from servers that don't record one aren't flagged.
+    pub fn from_headers(headers: &[MessageHeader]) -> Option<Self> {
+        let mut results = Self {
+            spf: AuthVerdict::Missing,
+            dkim: AuthVerdict::Missing,
+            dmarc: AuthVerdict::Missing,
+        };
+        let mut found = false;
+
+        for header in headers
+            .iter()
+            .filter(|h| h.name.eq_ignore_ascii_case("Authentication-Results"))
+        {
+            found = true;
+            // The first part names the server that ran the checks
+            for part in header.value.split(';').skip(1) {
+                let Some((method, rest)) = part.trim().split_once('=') else {
+                    continue;
+                };
+                let verdict = AuthVerdict::parse(rest.split_whitespace().next().unwrap_or(""));
+                let slot = match method.trim().to_ascii_lowercase().as_str() {
+                    "spf" => &mut results.spf,
+                    "dkim" => &mut results.dkim,
+                    "dmarc" => &mut results.dmarc,
+                    _ => continue,
+                };
+                *slot = slot.merge(verdict);
+            }
+        }
+
+        found.then_some(results)
+    }
+
+    /// Overall trust: all passed, some passed, or failed
+    pub fn trust(&self) -> Trust {
+        let verdicts = [self.spf, self.dkim, self.dmarc];
+        if verdicts.iter().all(|v| *v == AuthVerdict::Pass) {
+            Trust::Verified
+        } else if verdicts.contains(&AuthVerdict::Fail) || !verdicts.contains(&AuthVerdict::Pass) {
+            Trust::Unverified
+        } else {
+            Trust::Partial
+        }
+    }
+
+    /// One-line summary for the badge tooltip, e.g. "SPF pass · DKIM none"
+    pub fn summary(&self) -> String {
+        format!(
+            "SPF {} \u{00B7} DKIM {} \u{00B7} DMARC {}",
+            self.spf.label(),
+            self.dkim.label(),
+            self.dmarc.label()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth_header(value: &str) -> MessageHeader {
+        MessageHeader {
+            name: "Authentication-Results".to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_all_pass_is_verified() {
+        let results = AuthResults::from_headers(&[auth_header(
+            "mx.google.com; dkim=pass header.i=@example.com; spf=pass smtp.mailfrom=example.com; dmarc=pass (p=NONE) header.from=example.com",
+        )])
+        .unwrap();
+
+        assert_eq!(results.spf, AuthVerdict::Pass);
+        assert_eq!(results.trust(), Trust::Verified);
+        assert_eq!(
+            results.summary(),
+            "SPF pass \u{00B7} DKIM pass \u{00B7} DMARC pass"
+        );
+    }
+
+    #[test]
+    fn test_partial_and_failed_results() {
+        let partial =
+            AuthResults::from_headers(&[auth_header("mx.example.net; spf=pass; dkim=none")])
+                .unwrap();
+        assert_eq!(partial.trust(), Trust::Partial);
+
+        let failed = AuthResults::from_headers(&[auth_header(
+            "mx.example.net; spf=softfail; dkim=pass; dmarc=fail",
+        )])
+        .unwrap();
+        assert_eq!(failed.spf, AuthVerdict::Fail);
+        assert_eq!(failed.trust(), Trust::Unverified);
+    }
+
+    #[test]
+    fn test_repeated_headers_and_missing_header() {
+        let results = AuthResults::from_headers(&[
+            auth_header("a.example; dkim=fail"),
+            auth_header("b.example; dkim=pass"),
+        ])
+        .unwrap();
+        assert_eq!(results.dkim, AuthVerdict::Pass);
+
+        let other = MessageHeader {
+            name: "Received".to_string(),
+            value: "from mx.example".to_string(),
+        };
+        assert_eq!(AuthResults::from_headers(&[other]), None);
+    }
+}
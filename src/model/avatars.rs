@@ -0,0 +1,134 @@
+//! Avatar image cache
+//!
+//! Remote avatars fetched for sender addresses, keyed by normalized email.
+//! Addresses without an avatar are remembered so they are not re-fetched.
+
+use iced::widget::image::Handle;
+use std::collections::HashMap;
+
+/// Fetch state of a single avatar
+#[derive(Debug, Clone)]
+pub enum AvatarEntry {
+    /// Request in flight
+    Loading,
+    /// Image available
+    Loaded(Handle),
+    /// No avatar, or the fetch failed; initials are shown
+    Missing,
+}
+
+/// Cache of avatar images by email address
+#[derive(Debug, Clone, Default)]
+pub struct AvatarCache {
+    entries: HashMap<String, AvatarEntry>,
+}
+
+impl AvatarCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Normalize an address for use as a cache key
+    fn key(email: &str) -> String {
+        email.trim().to_lowercase()
+    }
+
+    /// Image for an address, if one has been loaded
+    pub fn get(&self, email: &str) -> Option<&Handle> {
+        match self.entries.get(&Self::key(email)) {
+            Some(AvatarEntry::Loaded(handle)) => Some(handle),
+            _ => None,
+        }
+    }
+
+    /// Mark an address as loading if it has not been seen yet
+    ///
+    /// Returns true if the caller should start a fetch.
+    pub fn begin_fetch(&mut self, email: &str) -> bool {
+        let key = Self::key(email);
+        if key.is_empty() || self.entries.contains_key(&key) {
+            return false;
+        }
+        self.entries.insert(key, AvatarEntry::Loading);
+        true
+    }
+
+    /// Store a fetched image, or record that none exists
+    pub fn set(&mut self, email: &str, bytes: Option<Vec<u8>>) {
+        let entry = match bytes {
+            Some(bytes) => AvatarEntry::Loaded(Handle::from_bytes(bytes)),
+            None => AvatarEntry::Missing,
+        };
+        self.entries.insert(Self::key(email), entry);
+    }
+
+    /// Forget all cached avatars
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Extract the bare address from a "Name <email>" sender string
+pub fn sender_address(from: &str) -> &str {
+    match (from.find('<'), from.rfind('>')) {
+        (Some(start), Some(end)) if start < end => from[start + 1..end].trim(),
+        _ => from.trim(),
+    }
+}
+
+/// Whether a list of addresses contains `address`, ignoring case
+pub fn contains_address(list: &[String], address: &str) -> bool {
+    list.iter().any(|a| a.eq_ignore_ascii_case(address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_begin_fetch_once_per_address() {
+        let mut cache = AvatarCache::new();
+        assert!(cache.begin_fetch("Alice@Example.com"));
+        assert!(!cache.begin_fetch(" alice@example.com"));
+        assert!(!cache.begin_fetch(""));
+    }
+
+    #[test]
+    fn test_missing_avatar_not_refetched() {
+        let mut cache = AvatarCache::new();
+        assert!(cache.begin_fetch("bob@example.com"));
+        cache.set("bob@example.com", None);
+        assert!(cache.get("bob@example.com").is_none());
+        assert!(!cache.begin_fetch("bob@example.com"));
+    }
+
+    #[test]
+    fn test_sender_address() {
+        assert_eq!(
+            sender_address("Alice <alice@example.com>"),
+            "alice@example.com"
+        );
+        assert_eq!(sender_address("bob@example.com"), "bob@example.com");
+        assert_eq!(
+            sender_address("\"Carol\" < carol@example.com >"),
+            "carol@example.com"
+        );
+    }
+
+    #[test]
+    fn test_loaded_avatar_lookup() {
+        let mut cache = AvatarCache::new();
+        cache.set("Carol@example.com", Some(vec![0x89, 0x50, 0x4e, 0x47]));
+        assert!(cache.get("carol@example.com").is_some());
+
+        cache.clear();
+        assert!(cache.get("carol@example.com").is_none());
+    }
+
+    #[test]
+    fn test_contains_address() {
+        let list = vec!["news@example.com".to_string()];
+        assert!(contains_address(&list, "News@Example.com"));
+        assert!(!contains_address(&list, "other@example.com"));
+    }
+}
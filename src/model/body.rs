@@ -0,0 +1,124 @@
+//! Message body display helpers
+//!
+//! Keeps pathological bodies (multi-megabyte newsletters) from stalling
+//! the detail view, and finds quoted reply history so it can be collapsed.
+
+/// Characters of a body shown before the "Show full message" expander
+pub const BODY_PREVIEW_CHARS: usize = 50_000;
+
+/// Cut a body to at most `max_chars` characters, or None if it already fits
+pub fn truncate_body(body: &str, max_chars: usize) -> Option<&str> {
+    body.char_indices()
+        .nth(max_chars)
+        .map(|(end, _)| &body[..end])
+}
+
+/// A run of body lines that are either all quoted or all new text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodySegment<'a> {
+    /// The lines, including their line breaks
+    pub text: &'a str,
+    /// Whether the lines are quoted history
+    pub quoted: bool,
+}
+
+/// Split a body into new text and quoted history
+///
+/// Quoted lines start with `>`. An `On ... wrote:` attribution line (as
+/// written by `format_quoted_body`) and blank lines directly before or
+/// inside a quote belong to it.
+pub fn split_quoted(body: &str) -> Vec<BodySegment<'_>> {
+    let lines: Vec<&str> = body.split_inclusive('\n').collect();
+    let is_quote = |line: &str| line.trim_start().starts_with('>');
+
+    // Whether the next non-blank line after `i` is a quote
+    let quote_follows = |i: usize| {
+        lines[i + 1..]
+            .iter()
+            .find(|line| !line.trim().is_empty())
+            .is_some_and(|line| is_quote(line))
+    };
+
+    let mut quoted = vec![false; lines.len()];
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        quoted[i] = is_quote(line)
+            || (trimmed.starts_with("On ") && trimmed.ends_with("wrote:") && quote_follows(i))
+            || (trimmed.is_empty() && i > 0 && quoted[i - 1] && quote_follows(i));
+    }
+
+    let mut segments: Vec<BodySegment<'_>> = Vec::new();
+    let mut start = 0;
+    for (line, is_quoted) in lines.iter().zip(quoted) {
+        let end = start + line.len();
+        match segments.last_mut() {
+            Some(last) if last.quoted == is_quoted => {
+                let begin = end - last.text.len() - line.len();
+                last.text = &body[begin..end];
+            }
+            _ => segments.push(BodySegment {
+                text: &body[start..end],
+                quoted: is_quoted,
+            }),
+        }
+        start = end;
+    }
+
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_body_untouched() {
+        assert_eq!(truncate_body("Hello", 5), None);
+        assert_eq!(truncate_body("", 0), None);
+    }
+
+    #[test]
+    fn test_long_body_truncated_on_char_boundary() {
+        assert_eq!(truncate_body("Hello world", 5), Some("Hello"));
+        assert_eq!(truncate_body("héllo wörld", 4), Some("héll"));
+    }
+
+    #[test]
+    fn test_split_quoted_reply() {
+        let body = "Sounds good.\n\nOn Mon, Jan 1, alice@x.com wrote:\n> Lunch?\n>\n> A\n";
+        assert_eq!(
+            split_quoted(body),
+            vec![
+                BodySegment {
+                    text: "Sounds good.\n\n",
+                    quoted: false
+                },
+                BodySegment {
+                    text: "On Mon, Jan 1, alice@x.com wrote:\n> Lunch?\n>\n> A\n",
+                    quoted: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_quoted_interleaved() {
+        let body = "> question one\n\nanswer one\n> question two\nanswer two";
+        let quoted: Vec<bool> = split_quoted(body).iter().map(|s| s.quoted).collect();
+        assert_eq!(quoted, vec![true, false, true, false]);
+        assert_eq!(split_quoted(body)[1].text, "\nanswer one\n");
+    }
+
+    #[test]
+    fn test_split_quoted_plain_text() {
+        let body = "On Monday I wrote:\nthe report";
+        assert_eq!(
+            split_quoted(body),
+            vec![BodySegment {
+                text: body,
+                quoted: false
+            }]
+        );
+        assert!(split_quoted("").is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! State management for email composition, replies, and forwards.
 
+use crate::api::types::MessageDetail;
 use std::path::PathBuf;
 
 /// Mode of email composition
@@ -74,6 +75,8 @@ pub struct ComposeState {
     pub is_dirty: bool,
     /// Show CC/BCC fields
     pub show_cc_bcc: bool,
+    /// Files are being dragged over compose
+    pub is_drop_target: bool,
     /// Current input field (for CC/BCC toggle)
     pub to_input: String,
     pub cc_input: String,
@@ -192,6 +195,7 @@ impl ComposeState {
         self.send_error = None;
         self.is_dirty = false;
         self.show_cc_bcc = false;
+        self.is_drop_target = false;
         self.to_input.clear();
         self.cc_input.clear();
         self.bcc_input.clear();
@@ -263,6 +267,16 @@ impl ComposeState {
     }
 }
 
+/// Quote a whole message, attributed to its sender, for replies or for
+/// pasting elsewhere
+pub fn quote_message(msg: &MessageDetail) -> String {
+    format_quoted_body(
+        &msg.from_addr,
+        &msg.sent_at.format("%b %d, %Y at %H:%M").to_string(),
+        &msg.body,
+    )
+}
+
 /// Format a quoted body for replies
 pub fn format_quoted_body(from: &str, date: &str, body: &str) -> String {
     let mut quoted = format!("On {}, {} wrote:\n", date, from);
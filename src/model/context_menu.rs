@@ -0,0 +1,115 @@
+//! Context menu state
+//!
+//! Iced has no native context menus, so right-clicks open a popup overlay
+//! positioned at the cursor. This tracks what was clicked and where.
+
+use iced::{window, Point, Size};
+
+/// Width of a context menu in pixels
+pub const MENU_WIDTH: f32 = 200.0;
+
+/// Height of a single menu item in pixels
+pub const MENU_ITEM_HEIGHT: f32 = 28.0;
+
+/// Vertical padding around the menu items in pixels
+pub const MENU_PADDING: f32 = 8.0;
+
+/// What a context menu was opened on
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextTarget {
+    /// A message row in the message list or search results
+    Message { message_id: i64, index: usize },
+    /// An account in the sidebar
+    Account { email: String },
+    /// A saved search in the sidebar
+    SavedSearch { index: usize },
+    /// A pinned sender or domain in the sidebar
+    PinnedFilter { index: usize },
+    /// A row in the Domains aggregate view
+    Domain { domain: String },
+    /// A row in the Senders aggregate view
+    Sender { email: String },
+    /// An attachment in the message detail view
+    Attachment {
+        message_id: i64,
+        attachment_idx: usize,
+        filename: String,
+    },
+}
+
+/// An open context menu
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextMenu {
+    /// Window position of the click
+    pub position: Point,
+    /// Item the menu acts on
+    pub target: ContextTarget,
+    /// Window the menu was opened in (None for the main window)
+    pub window: Option<window::Id>,
+}
+
+impl ContextMenu {
+    /// Open a menu at the cursor position
+    pub fn new(position: Point, target: ContextTarget) -> Self {
+        Self {
+            position,
+            target,
+            window: None,
+        }
+    }
+
+    /// Open the menu in another window than the main one
+    pub fn in_window(mut self, window: Option<window::Id>) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Top-left corner of the popup, kept inside the window
+    ///
+    /// Menus that would overflow the right or bottom edge open to the
+    /// left of or above the cursor instead.
+    pub fn origin(&self, item_count: usize, window: Size) -> Point {
+        let height = item_count as f32 * MENU_ITEM_HEIGHT + MENU_PADDING * 2.0;
+
+        let x = if self.position.x + MENU_WIDTH > window.width {
+            self.position.x - MENU_WIDTH
+        } else {
+            self.position.x
+        };
+        let y = if self.position.y + height > window.height {
+            self.position.y - height
+        } else {
+            self.position.y
+        };
+
+        Point::new(x.max(0.0), y.max(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn menu_at(x: f32, y: f32) -> ContextMenu {
+        ContextMenu::new(
+            Point::new(x, y),
+            ContextTarget::Message {
+                message_id: 1,
+                index: 0,
+            },
+        )
+    }
+
+    #[test]
+    fn test_origin_opens_at_cursor() {
+        let origin = menu_at(100.0, 100.0).origin(4, Size::new(1200.0, 800.0));
+        assert_eq!(origin, Point::new(100.0, 100.0));
+    }
+
+    #[test]
+    fn test_origin_flips_near_edges() {
+        let origin = menu_at(1150.0, 780.0).origin(4, Size::new(1200.0, 800.0));
+        assert_eq!(origin.x, 1150.0 - MENU_WIDTH);
+        assert!(origin.y < 780.0);
+    }
+}
@@ -0,0 +1,148 @@
+//! Bulk deletion progress
+//!
+//! Large selections are staged for deletion in chunks, one request at a
+//! time, so progress can be shown and a failed chunk doesn't sink the rest.
+
+use std::collections::VecDeque;
+
+/// Messages staged per request
+pub const DELETION_CHUNK_SIZE: usize = 100;
+
+/// Selections at least this large need the count typed to confirm
+pub const LARGE_DELETION: usize = 1_000;
+
+/// Whether a deletion of `count` messages may go ahead given what was
+/// typed into the confirmation box
+///
+/// Small deletions need no typing. Large ones need the count, with or
+/// without thousands separators.
+pub fn deletion_confirmed(count: usize, typed: &str) -> bool {
+    if count < LARGE_DELETION {
+        return true;
+    }
+    let digits: String = typed
+        .chars()
+        .filter(|c| !matches!(c, ',' | '.' | '_' | ' '))
+        .collect();
+    digits == count.to_string()
+}
+
+/// A selection being staged for deletion
+#[derive(Debug, Clone, Default)]
+pub struct DeletionProgress {
+    /// Chunks not yet sent
+    pending: VecDeque<Vec<i64>>,
+    /// Messages in the whole selection
+    pub total: usize,
+    /// Messages sent so far, whatever the outcome
+    pub sent: usize,
+    /// Messages the server reports staged so far
+    pub staged: usize,
+    /// Messages whose chunk failed
+    pub failed: Vec<i64>,
+    /// Error from the most recent failed chunk
+    pub last_error: Option<String>,
+}
+
+impl DeletionProgress {
+    /// Split a selection into chunks
+    pub fn new(message_ids: Vec<i64>) -> Self {
+        Self {
+            total: message_ids.len(),
+            pending: message_ids
+                .chunks(DELETION_CHUNK_SIZE)
+                .map(<[i64]>::to_vec)
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Take the next chunk to send
+    pub fn next_chunk(&mut self) -> Option<Vec<i64>> {
+        self.pending.pop_front()
+    }
+
+    /// Record the outcome of a chunk: how many the server staged, or why
+    /// it failed
+    pub fn record(&mut self, chunk: Vec<i64>, result: Result<usize, String>) {
+        self.sent += chunk.len();
+        match result {
+            Ok(staged) => self.staged += staged,
+            Err(e) => {
+                self.failed.extend(chunk);
+                self.last_error = Some(e);
+            }
+        }
+    }
+
+    /// Share of the selection handled so far, for a progress bar
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.sent as f32 / self.total as f32
+        }
+    }
+
+    /// Outcome once every chunk is done, e.g. "480 of 500 staged, 20 failed"
+    pub fn summary(&self) -> String {
+        if self.failed.is_empty() {
+            format!(
+                "Staged {} message{} for deletion",
+                self.staged,
+                if self.staged == 1 { "" } else { "s" }
+            )
+        } else {
+            format!(
+                "{} of {} staged, {} failed",
+                self.staged,
+                self.total,
+                self.failed.len()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_deletions_need_the_count_typed() {
+        assert!(deletion_confirmed(999, ""));
+        assert!(!deletion_confirmed(50_000, ""));
+        assert!(!deletion_confirmed(50_000, "5000"));
+        assert!(deletion_confirmed(50_000, "50000"));
+        assert!(deletion_confirmed(50_000, " 50,000 "));
+    }
+
+    #[test]
+    fn test_chunks_cover_selection() {
+        let mut progress = DeletionProgress::new((0..250).collect());
+        assert_eq!(progress.total, 250);
+
+        let sizes: Vec<usize> = std::iter::from_fn(|| progress.next_chunk())
+            .map(|chunk| chunk.len())
+            .collect();
+        assert_eq!(sizes, vec![100, 100, 50]);
+    }
+
+    #[test]
+    fn test_partial_failure_is_reported() {
+        let mut progress = DeletionProgress::new((0..500).collect());
+        for i in 0..5 {
+            let chunk = progress.next_chunk().unwrap();
+            let result = if i == 2 {
+                Err("timed out".to_string())
+            } else {
+                Ok(chunk.len())
+            };
+            progress.record(chunk, result);
+        }
+
+        assert!(progress.next_chunk().is_none());
+        assert_eq!(progress.fraction(), 1.0);
+        assert_eq!(progress.failed, (200..300).collect::<Vec<_>>());
+        assert_eq!(progress.summary(), "400 of 500 staged, 100 failed");
+    }
+}
@@ -2,20 +2,26 @@
 //!
 //! Tracks download progress and status for message attachments.
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use crate::theme::icons::FileKind;
+use iced::task;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 
 /// Download state for a single attachment
 #[derive(Debug, Clone)]
 pub enum DownloadState {
     /// Not yet started
     NotStarted,
+    /// Waiting for a free download slot
+    Queued,
     /// Currently downloading
     Downloading { progress: f32 }, // 0.0 to 1.0
     /// Download complete
     Complete { path: PathBuf },
     /// Download failed
     Failed { error: String },
+    /// Download cancelled by the user
+    Cancelled,
 }
 
 impl Default for DownloadState {
@@ -30,6 +36,11 @@ impl DownloadState {
         matches!(self, Self::Downloading { .. })
     }
 
+    /// Check if download is waiting for a slot
+    pub fn is_queued(&self) -> bool {
+        matches!(self, Self::Queued)
+    }
+
     /// Check if download is complete
     pub fn is_complete(&self) -> bool {
         matches!(self, Self::Complete { .. })
@@ -40,6 +51,11 @@ impl DownloadState {
         matches!(self, Self::Failed { .. })
     }
 
+    /// Check if download was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled)
+    }
+
     /// Get the download path if complete
     pub fn path(&self) -> Option<&PathBuf> {
         match self {
@@ -70,6 +86,17 @@ impl DownloadState {
 pub struct DownloadTracker {
     /// Map of (message_id, attachment_index) -> download state
     pub downloads: HashMap<(i64, usize), DownloadState>,
+    /// Bytes kept on disk from interrupted downloads (resumed on retry)
+    pub partial: HashMap<(i64, usize), u64>,
+    /// Requested downloads in request order, with their filenames
+    pub entries: Vec<((i64, usize), String)>,
+    /// Downloads waiting for a free slot, oldest first
+    pub queue: VecDeque<(i64, usize)>,
+    /// Abort handles of download tasks that haven't stopped yet
+    ///
+    /// A cancelled download keeps its slot until its task has stopped, so a
+    /// restart can't share the partial file with it.
+    pub running: HashMap<(i64, usize), task::Handle>,
 }
 
 impl DownloadTracker {
@@ -86,6 +113,80 @@ impl DownloadTracker {
             .unwrap_or(&NOT_STARTED)
     }
 
+    /// Queue an attachment for download
+    ///
+    /// Re-queueing a failed download keeps its place in the panel list.
+    pub fn enqueue(&mut self, message_id: i64, attachment_idx: usize, filename: String) {
+        let key = (message_id, attachment_idx);
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = filename,
+            None => self.entries.push((key, filename)),
+        }
+        if !self.queue.contains(&key) {
+            self.queue.push_back(key);
+        }
+        self.downloads.insert(key, DownloadState::Queued);
+    }
+
+    /// Take the next queued download if fewer than `limit` are running
+    ///
+    /// A download whose previous task is still stopping waits its turn.
+    /// Returns the message ID, attachment index, and filename to start.
+    pub fn next_queued(&mut self, limit: usize) -> Option<(i64, usize, String)> {
+        if self.active_count() >= limit.max(1) {
+            return None;
+        }
+        let pos = self
+            .queue
+            .iter()
+            .position(|key| !self.running.contains_key(key))?;
+        let key = self.queue.remove(pos)?;
+        let filename = self.filename(key.0, key.1).unwrap_or_default().to_string();
+        Some((key.0, key.1, filename))
+    }
+
+    /// Remove a download from the queue before it starts
+    ///
+    /// Returns false if the download was not waiting in the queue.
+    pub fn dequeue(&mut self, message_id: i64, attachment_idx: usize) -> bool {
+        let key = (message_id, attachment_idx);
+        let Some(pos) = self.queue.iter().position(|k| *k == key) else {
+            return false;
+        };
+        self.queue.remove(pos);
+        self.entries.retain(|(k, _)| *k != key);
+        self.downloads.remove(&key);
+        true
+    }
+
+    /// Number of download slots in use, counting cancelled tasks still stopping
+    pub fn active_count(&self) -> usize {
+        let untracked = self
+            .downloads
+            .iter()
+            .filter(|(key, s)| s.is_downloading() && !self.running.contains_key(key))
+            .count();
+        untracked + self.running.len()
+    }
+
+    /// Number of downloads running or waiting for a slot
+    pub fn pending_count(&self) -> usize {
+        let downloading = self
+            .downloads
+            .values()
+            .filter(|s| s.is_downloading())
+            .count();
+        downloading + self.queue.len()
+    }
+
+    /// Filename recorded when a download was requested
+    pub fn filename(&self, message_id: i64, attachment_idx: usize) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| *k == (message_id, attachment_idx))
+            .map(|(_, name)| name.as_str())
+    }
+
     /// Set an attachment as downloading with progress
     pub fn set_downloading(&mut self, message_id: i64, attachment_idx: usize, progress: f32) {
         self.downloads.insert(
@@ -94,8 +195,71 @@ impl DownloadTracker {
         );
     }
 
+    /// Record the abort handle of a download task that is starting
+    pub fn task_started(&mut self, message_id: i64, attachment_idx: usize, handle: task::Handle) {
+        self.running.insert((message_id, attachment_idx), handle);
+    }
+
+    /// Forget a download task that has stopped, freeing its slot
+    ///
+    /// Returns true if the task was aborted, leaving a partial file behind.
+    pub fn task_stopped(&mut self, message_id: i64, attachment_idx: usize) -> bool {
+        self.running
+            .remove(&(message_id, attachment_idx))
+            .is_some_and(|handle| handle.is_aborted())
+    }
+
+    /// Cancel a running download
+    ///
+    /// Aborts the download task; its slot stays taken until the task has
+    /// stopped. Returns false if the download was not running.
+    pub fn cancel(&mut self, message_id: i64, attachment_idx: usize) -> bool {
+        let key = (message_id, attachment_idx);
+        if !self.get(message_id, attachment_idx).is_downloading() {
+            return false;
+        }
+        if let Some(handle) = self.running.get(&key) {
+            handle.abort();
+        }
+        self.set_cancelled(message_id, attachment_idx);
+        true
+    }
+
+    /// Cancel every running and queued download, as on quit
+    ///
+    /// Returns the message ID, attachment index, and filename of each so
+    /// their partial files can be removed.
+    pub fn cancel_unfinished(&mut self) -> Vec<(i64, usize, String)> {
+        let unfinished: Vec<(i64, usize, String)> = self
+            .entries
+            .iter()
+            .filter(|(key, _)| {
+                let state = self.get(key.0, key.1);
+                state.is_downloading() || state.is_queued()
+            })
+            .map(|(key, filename)| (key.0, key.1, filename.clone()))
+            .collect();
+
+        self.queue.clear();
+        for (message_id, attachment_idx, _) in &unfinished {
+            if let Some(handle) = self.running.get(&(*message_id, *attachment_idx)) {
+                handle.abort();
+            }
+            self.set_cancelled(*message_id, *attachment_idx);
+        }
+        unfinished
+    }
+
+    /// Set an attachment download as cancelled
+    pub fn set_cancelled(&mut self, message_id: i64, attachment_idx: usize) {
+        let key = (message_id, attachment_idx);
+        self.partial.remove(&key);
+        self.downloads.insert(key, DownloadState::Cancelled);
+    }
+
     /// Set an attachment download as complete
     pub fn set_complete(&mut self, message_id: i64, attachment_idx: usize, path: PathBuf) {
+        self.partial.remove(&(message_id, attachment_idx));
         self.downloads.insert(
             (message_id, attachment_idx),
             DownloadState::Complete { path },
@@ -110,11 +274,42 @@ impl DownloadTracker {
         );
     }
 
+    /// Record how many bytes an interrupted download left on disk
+    pub fn set_partial(&mut self, message_id: i64, attachment_idx: usize, bytes: u64) {
+        if bytes > 0 {
+            self.partial.insert((message_id, attachment_idx), bytes);
+        } else {
+            self.partial.remove(&(message_id, attachment_idx));
+        }
+    }
+
+    /// Get the bytes a retry will resume from (0 if it starts over)
+    pub fn partial_bytes(&self, message_id: i64, attachment_idx: usize) -> u64 {
+        self.partial
+            .get(&(message_id, attachment_idx))
+            .copied()
+            .unwrap_or(0)
+    }
+
     /// Clear the download state for an attachment
     pub fn clear(&mut self, message_id: i64, attachment_idx: usize) {
         self.downloads.remove(&(message_id, attachment_idx));
     }
 
+    /// Drop finished (complete, failed, or cancelled) downloads from the panel list
+    pub fn clear_finished(&mut self) {
+        let downloads = &mut self.downloads;
+        self.entries.retain(|(key, _)| {
+            let finished = downloads
+                .get(key)
+                .is_none_or(|s| s.is_complete() || s.is_failed() || s.is_cancelled());
+            if finished {
+                downloads.remove(key);
+            }
+            !finished
+        });
+    }
+
     /// Clear all downloads for a message
     pub fn clear_message(&mut self, message_id: i64) {
         self.downloads
@@ -127,6 +322,77 @@ impl DownloadTracker {
     }
 }
 
+/// Name to save an attachment under so it opens in the right application
+///
+/// Files are opened by extension, so a name with no extension, or one at
+/// odds with the MIME type (a PDF sent as "scan.dat"), gets the type's
+/// usual extension added.
+pub fn local_filename(filename: &str, mime_type: &str) -> String {
+    let Some(kind) = FileKind::from_mime_type(mime_type) else {
+        return filename.to_string();
+    };
+    match mime_extension(mime_type) {
+        Some(ext) if FileKind::from_filename(filename) != kind => format!("{}.{}", filename, ext),
+        _ => filename.to_string(),
+    }
+}
+
+/// Extensions that never open by themselves, whatever the allowlist says:
+/// programs, scripts, installers and archives
+const NEVER_AUTO_OPEN: &[&str] = &[
+    "exe", "msi", "bat", "cmd", "com", "scr", "ps1", "vbs", "js", "jar", "sh", "command", "app",
+    "dmg", "pkg", "deb", "rpm", "appimage", "lnk", "iso", "zip", "7z", "rar", "tar", "gz", "tgz",
+];
+
+/// Whether a finished download should open without being asked
+///
+/// Only files whose extension is on the allowlist open, and never anything
+/// that could run code.
+pub fn should_auto_open(path: &Path, allowed: &[String]) -> bool {
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    let ext = ext.to_lowercase();
+    !NEVER_AUTO_OPEN.contains(&ext.as_str()) && allowed.contains(&ext)
+}
+
+/// Extensions typed as a list like "pdf, .PNG jpg", lowercased without dots
+pub fn parse_extensions(input: &str) -> Vec<String> {
+    let mut extensions: Vec<String> = Vec::new();
+    for ext in input.split([',', ' ', ';']) {
+        let ext = ext.trim().trim_start_matches('.').to_lowercase();
+        if !ext.is_empty() && !extensions.contains(&ext) {
+            extensions.push(ext);
+        }
+    }
+    extensions
+}
+
+/// Usual extension for common MIME types
+fn mime_extension(mime_type: &str) -> Option<&'static str> {
+    let mime = mime_type.split(';').next()?.trim().to_lowercase();
+    Some(match mime.as_str() {
+        "application/pdf" => "pdf",
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "text/plain" => "txt",
+        "text/html" => "html",
+        "text/csv" => "csv",
+        "text/calendar" => "ics",
+        "message/rfc822" => "eml",
+        "application/zip" => "zip",
+        "application/msword" => "doc",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => "docx",
+        "application/vnd.ms-excel" => "xls",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => "xlsx",
+        "application/vnd.ms-powerpoint" => "ppt",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation" => "pptx",
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,4 +452,145 @@ mod tests {
         assert!(matches!(tracker.get(1, 1), DownloadState::NotStarted));
         assert!(tracker.get(2, 0).is_downloading());
     }
+
+    #[test]
+    fn test_download_tracker_partial() {
+        let mut tracker = DownloadTracker::new();
+
+        tracker.set_failed(1, 0, "Connection reset".to_string());
+        tracker.set_partial(1, 0, 4096);
+        assert_eq!(tracker.partial_bytes(1, 0), 4096);
+
+        // Completing the download drops the partial record
+        tracker.set_complete(1, 0, PathBuf::from("/tmp/test.pdf"));
+        assert_eq!(tracker.partial_bytes(1, 0), 0);
+    }
+
+    #[test]
+    fn test_download_tracker_queue_respects_limit() {
+        let mut tracker = DownloadTracker::new();
+
+        tracker.enqueue(1, 0, "a.pdf".to_string());
+        tracker.enqueue(1, 1, "b.pdf".to_string());
+        tracker.enqueue(2, 0, "c.pdf".to_string());
+        assert!(tracker.get(1, 0).is_queued());
+
+        // Two slots: the first two start, the third waits
+        let first = tracker.next_queued(2).unwrap();
+        assert_eq!(first, (1, 0, "a.pdf".to_string()));
+        tracker.set_downloading(1, 0, 0.0);
+        let second = tracker.next_queued(2).unwrap();
+        tracker.set_downloading(second.0, second.1, 0.0);
+        assert!(tracker.next_queued(2).is_none());
+        assert_eq!(tracker.pending_count(), 3);
+
+        // A finished download frees a slot
+        tracker.set_complete(1, 0, PathBuf::from("/tmp/a.pdf"));
+        assert_eq!(tracker.next_queued(2).unwrap().2, "c.pdf");
+    }
+
+    #[test]
+    fn test_download_tracker_dequeue_and_clear_finished() {
+        let mut tracker = DownloadTracker::new();
+
+        tracker.enqueue(1, 0, "a.pdf".to_string());
+        tracker.enqueue(1, 1, "b.pdf".to_string());
+        assert!(tracker.dequeue(1, 1));
+        assert!(!tracker.dequeue(1, 1));
+        assert_eq!(tracker.entries.len(), 1);
+
+        tracker.next_queued(1);
+        tracker.set_failed(1, 0, "Timeout".to_string());
+        tracker.clear_finished();
+        assert!(tracker.entries.is_empty());
+        assert!(matches!(tracker.get(1, 0), DownloadState::NotStarted));
+    }
+
+    /// Abort handle of a task that was never run
+    fn handle() -> task::Handle {
+        iced::Task::done(()).abortable().1
+    }
+
+    #[test]
+    fn test_download_tracker_cancel_aborts_task() {
+        let mut tracker = DownloadTracker::new();
+
+        tracker.enqueue(1, 0, "a.pdf".to_string());
+        tracker.next_queued(1);
+        tracker.set_downloading(1, 0, 0.2);
+        let task = handle();
+        tracker.task_started(1, 0, task.clone());
+        tracker.set_partial(1, 0, 1024);
+
+        assert!(tracker.cancel(1, 0));
+        assert!(task.is_aborted());
+        assert!(tracker.get(1, 0).is_cancelled());
+        assert_eq!(tracker.partial_bytes(1, 0), 0);
+        assert_eq!(tracker.pending_count(), 0);
+
+        // Only running downloads can be cancelled
+        assert!(!tracker.cancel(1, 0));
+
+        // A restart waits until the cancelled task has stopped
+        tracker.enqueue(1, 0, "a.pdf".to_string());
+        assert_eq!(tracker.active_count(), 1);
+        assert!(tracker.next_queued(2).is_none());
+        assert!(tracker.task_stopped(1, 0));
+        assert_eq!(tracker.next_queued(2).unwrap().2, "a.pdf");
+    }
+
+    #[test]
+    fn test_cancel_unfinished() {
+        let mut tracker = DownloadTracker::new();
+        tracker.enqueue(1, 0, "running.pdf".to_string());
+        tracker.enqueue(1, 1, "queued.pdf".to_string());
+        tracker.enqueue(2, 0, "done.pdf".to_string());
+        tracker.next_queued(3);
+        let task = handle();
+        tracker.task_started(1, 0, task.clone());
+        tracker.set_downloading(1, 0, 0.2);
+        tracker.set_complete(2, 0, PathBuf::from("/tmp/done.pdf"));
+
+        let cancelled = tracker.cancel_unfinished();
+        assert_eq!(
+            cancelled,
+            vec![
+                (1, 0, "running.pdf".to_string()),
+                (1, 1, "queued.pdf".to_string()),
+            ]
+        );
+        assert!(task.is_aborted());
+        assert_eq!(tracker.pending_count(), 0);
+        assert!(tracker.get(2, 0).is_complete());
+    }
+
+    #[test]
+    fn test_local_filename_matches_mime_type() {
+        assert_eq!(local_filename("scan", "application/pdf"), "scan.pdf");
+        assert_eq!(
+            local_filename("scan.dat", "application/pdf"),
+            "scan.dat.pdf"
+        );
+        assert_eq!(
+            local_filename("Report.PDF", "application/pdf"),
+            "Report.PDF"
+        );
+        assert_eq!(local_filename("photo.jpeg", "image/jpeg"), "photo.jpeg");
+        assert_eq!(
+            local_filename("setup.exe", "application/octet-stream"),
+            "setup.exe"
+        );
+    }
+
+    #[test]
+    fn test_auto_open_respects_allowlist() {
+        let allowed = parse_extensions("pdf, .PNG  jpg;exe");
+        assert_eq!(allowed, vec!["pdf", "png", "jpg", "exe"]);
+
+        assert!(should_auto_open(Path::new("/tmp/Report.PDF"), &allowed));
+        assert!(!should_auto_open(Path::new("/tmp/notes.txt"), &allowed));
+        assert!(!should_auto_open(Path::new("/tmp/README"), &allowed));
+        // Never, even when listed
+        assert!(!should_auto_open(Path::new("/tmp/setup.exe"), &allowed));
+    }
 }
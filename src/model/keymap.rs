@@ -0,0 +1,287 @@
+//! Keyboard bindings
+//!
+//! Single source of truth for shortcut keys. The key handler matches
+//! character keys through the keymap and the inline hints, help modal and
+//! cheatsheet read their labels from it, so they cannot drift apart.
+
+use crate::model::menu_bar::chord_label;
+use iced::keyboard::Modifiers;
+
+/// Actions that can be triggered from the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    // Fixed (named) keys
+    Open,
+    Back,
+    CycleView,
+    ToggleSearchMode,
+    ToggleSelection,
+    PreviousMessage,
+    NextMessage,
+    FirstLast,
+    PageUpDown,
+
+    // Character keys
+    MoveDown,
+    MoveUp,
+    JumpTo,
+    ToggleTimeLayout,
+    Search,
+    NextPage,
+    PreviousPage,
+    ToggleSortField,
+    ToggleSortDirection,
+    Help,
+    SelectAll,
+    ClearSelection,
+    Delete,
+    MarkAllRead,
+    Label,
+    Downloads,
+    CopyKey,
+    Sync,
+    Accounts,
+    Settings,
+    Compose,
+    Reply,
+    ReplyAll,
+    Forward,
+    ViewThread,
+    ExpandAll,
+    CollapseAll,
+}
+
+/// Default bindings, in help-display order
+///
+/// Named keys (Enter, Esc, arrows...) are listed for display only; the key
+/// handler matches them directly.
+const DEFAULT_BINDINGS: &[(Action, &str)] = &[
+    (Action::Open, "Enter"),
+    (Action::Back, "Esc"),
+    (Action::CycleView, "Tab"),
+    (Action::ToggleSearchMode, "Tab"),
+    (Action::ToggleSelection, "Space"),
+    (Action::PreviousMessage, "\u{2190}"),
+    (Action::NextMessage, "\u{2192}"),
+    (Action::FirstLast, "Home/End"),
+    (Action::PageUpDown, "PgUp/PgDn"),
+    (Action::MoveDown, "j"),
+    (Action::MoveUp, "k"),
+    (Action::JumpTo, "g"),
+    (Action::ToggleTimeLayout, "v"),
+    (Action::Search, "/"),
+    (Action::NextPage, "n"),
+    (Action::PreviousPage, "p"),
+    (Action::ToggleSortField, "s"),
+    (Action::ToggleSortDirection, "r"),
+    (Action::Help, "?"),
+    (Action::SelectAll, "A"),
+    (Action::ClearSelection, "x"),
+    (Action::Delete, "d"),
+    (Action::MarkAllRead, "M"),
+    (Action::Label, "l"),
+    (Action::Downloads, "D"),
+    (Action::CopyKey, "Y"),
+    (Action::Sync, "y"),
+    (Action::Accounts, "a"),
+    (Action::Settings, ","),
+    (Action::Compose, "c"),
+    (Action::Reply, "r"),
+    (Action::ReplyAll, "R"),
+    (Action::Forward, "f"),
+    (Action::ViewThread, "t"),
+    (Action::ExpandAll, "e"),
+    (Action::CollapseAll, "E"),
+];
+
+/// Key column of a help entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpKey {
+    /// Whatever key the action is bound to
+    Bound(Action),
+    /// A key or gesture outside the keymap
+    Fixed(&'static str),
+    /// Cmd (macOS) or Ctrl (elsewhere) plus a key
+    Chord(&'static str),
+}
+
+/// Shortcuts listed in the help modal and cheatsheet, by section
+pub const HELP_SECTIONS: &[(&str, &[(HelpKey, &str)])] = &[
+    (
+        "Navigation",
+        &[
+            (HelpKey::Bound(Action::MoveDown), "Move down"),
+            (HelpKey::Bound(Action::MoveUp), "Move up"),
+            (HelpKey::Bound(Action::FirstLast), "First / last row"),
+            (HelpKey::Bound(Action::PageUpDown), "Move by a page"),
+            (
+                HelpKey::Bound(Action::JumpTo),
+                "Jump to row by typed prefix",
+            ),
+            (
+                HelpKey::Bound(Action::ToggleTimeLayout),
+                "Heatmap / list (Time view)",
+            ),
+            (HelpKey::Bound(Action::Open), "Open / Drill down"),
+            (HelpKey::Bound(Action::Back), "Go back"),
+            (HelpKey::Bound(Action::CycleView), "Cycle view types"),
+            (HelpKey::Fixed("F6"), "Focus sidebar / content"),
+        ],
+    ),
+    (
+        "Views",
+        &[
+            (HelpKey::Bound(Action::Search), "Search"),
+            (HelpKey::Bound(Action::Sync), "Sync status"),
+            (HelpKey::Bound(Action::Accounts), "Accounts"),
+            (HelpKey::Bound(Action::Settings), "Settings"),
+        ],
+    ),
+    (
+        "Actions",
+        &[
+            (HelpKey::Bound(Action::ToggleSelection), "Toggle selection"),
+            (HelpKey::Bound(Action::SelectAll), "Select all"),
+            (HelpKey::Fixed("Shift+Click"), "Select range"),
+            (HelpKey::Bound(Action::ClearSelection), "Clear selection"),
+            (HelpKey::Bound(Action::Delete), "Delete selected"),
+            (HelpKey::Bound(Action::MarkAllRead), "Mark all read"),
+            (HelpKey::Bound(Action::Label), "Label selected"),
+            (HelpKey::Bound(Action::Downloads), "Downloads panel"),
+            (HelpKey::Bound(Action::CopyKey), "Copy sender / domain"),
+            (HelpKey::Bound(Action::ToggleSortField), "Toggle sort field"),
+            (HelpKey::Bound(Action::ToggleSortDirection), "Reverse sort"),
+        ],
+    ),
+    (
+        "Messages",
+        &[
+            (HelpKey::Bound(Action::NextPage), "Next page"),
+            (HelpKey::Bound(Action::PreviousPage), "Previous page"),
+            (HelpKey::Bound(Action::PreviousMessage), "Previous message"),
+            (HelpKey::Bound(Action::NextMessage), "Next message"),
+            (HelpKey::Bound(Action::Compose), "New message"),
+            (HelpKey::Bound(Action::Reply), "Reply"),
+            (HelpKey::Bound(Action::ReplyAll), "Reply all"),
+            (HelpKey::Bound(Action::Forward), "Forward"),
+            (HelpKey::Bound(Action::ViewThread), "View conversation"),
+        ],
+    ),
+    (
+        "General",
+        &[
+            (HelpKey::Bound(Action::Help), "Toggle this help"),
+            (HelpKey::Chord("f"), "Find"),
+            (HelpKey::Chord("\\"), "Collapse / expand sidebar"),
+            (HelpKey::Chord("]"), "Switch reading pane layout"),
+            (HelpKey::Chord(","), "Settings"),
+            (HelpKey::Chord("w"), "Close window"),
+            (HelpKey::Chord("q"), "Quit"),
+        ],
+    ),
+];
+
+/// Mapping from actions to keys
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, String)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: DEFAULT_BINDINGS
+                .iter()
+                .map(|(action, key)| (*action, key.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Key label bound to an action (empty if unbound)
+    pub fn key(&self, action: Action) -> &str {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, key)| key.as_str())
+            .unwrap_or("")
+    }
+
+    /// Check whether a pressed character key triggers an action
+    ///
+    /// The binding carries the Shift state: a letter bound in upper case
+    /// needs Shift and one in lower case must not have it, so Caps Lock
+    /// doesn't turn `r` into `R`. Other keys (`?`, `/`) match whatever
+    /// modifiers produced them.
+    pub fn matches(&self, action: Action, key: &str, modifiers: Modifiers) -> bool {
+        self.bindings
+            .iter()
+            .any(|(a, k)| *a == action && k == key && shift_agrees(k, modifiers.shift()))
+    }
+
+    /// Label for a help entry's key, as currently bound
+    pub fn help_key(&self, key: HelpKey) -> String {
+        match key {
+            HelpKey::Bound(action) => self.key(action).to_string(),
+            HelpKey::Fixed(label) => label.to_string(),
+            HelpKey::Chord(key) => chord_label(key),
+        }
+    }
+
+    /// Plain-text shortcut reference for copying or printing
+    pub fn cheatsheet(&self) -> String {
+        let mut out = String::from("msgvault keyboard shortcuts\n");
+        for (section, entries) in HELP_SECTIONS {
+            out.push_str(&format!("\n{}\n", section));
+            for (key, description) in *entries {
+                out.push_str(&format!("  {:<14}{}\n", self.help_key(*key), description));
+            }
+        }
+        out
+    }
+}
+
+/// Whether the Shift state fits a bound key
+fn shift_agrees(key: &str, shift: bool) -> bool {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_alphabetic() => c.is_uppercase() == shift,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_keymap_labels() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.key(Action::MoveDown), "j");
+        assert_eq!(keymap.key(Action::Back), "Esc");
+    }
+
+    #[test]
+    fn test_cheatsheet_lists_bound_keys() {
+        let sheet = Keymap::default().cheatsheet();
+        assert!(sheet.contains("\nNavigation\n"));
+        assert!(sheet.contains("  j             Move down\n"));
+        assert!(sheet.contains("Shift+Click"));
+        assert!(sheet.contains(&format!("  {:<14}Quit", chord_label("q"))));
+    }
+
+    #[test]
+    fn test_keymap_matches_is_per_action() {
+        let keymap = Keymap::default();
+        let none = Modifiers::empty();
+        // "r" is shared by context-dependent actions
+        assert!(keymap.matches(Action::ToggleSortDirection, "r", none));
+        assert!(keymap.matches(Action::Reply, "r", none));
+        assert!(!keymap.matches(Action::ReplyAll, "r", none));
+        assert!(keymap.matches(Action::ReplyAll, "R", Modifiers::SHIFT));
+        // Caps Lock alone isn't Shift
+        assert!(!keymap.matches(Action::ReplyAll, "R", none));
+        assert!(keymap.matches(Action::Help, "?", Modifiers::SHIFT));
+    }
+}
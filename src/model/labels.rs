@@ -0,0 +1,127 @@
+//! Label picker state
+//!
+//! Popup for adding or removing a label on a set of messages at once.
+
+use std::collections::HashMap;
+
+/// How many of the targeted messages carry a label
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelCoverage {
+    None,
+    Some,
+    All,
+}
+
+/// An open label picker
+#[derive(Debug, Clone, Default)]
+pub struct LabelPicker {
+    /// Messages the picker applies to
+    pub message_ids: Vec<i64>,
+    /// Labels known to the server
+    pub available: Vec<String>,
+    /// Whether the label list is still loading
+    pub is_loading: bool,
+    /// Filter text, also used as the name of a new label
+    pub filter: String,
+}
+
+impl LabelPicker {
+    /// Open a picker for a set of messages
+    pub fn new(message_ids: Vec<i64>) -> Self {
+        Self {
+            message_ids,
+            is_loading: true,
+            ..Self::default()
+        }
+    }
+
+    /// Available labels matching the filter (case-insensitive)
+    pub fn filtered(&self) -> Vec<&str> {
+        let filter = self.filter.trim().to_lowercase();
+        self.available
+            .iter()
+            .filter(|label| filter.is_empty() || label.to_lowercase().contains(&filter))
+            .map(|label| label.as_str())
+            .collect()
+    }
+
+    /// Filter text as a new label name, if it does not exist yet
+    pub fn new_label(&self) -> Option<&str> {
+        let name = self.filter.trim();
+        if name.is_empty() || self.available.iter().any(|l| l.eq_ignore_ascii_case(name)) {
+            None
+        } else {
+            Some(name)
+        }
+    }
+}
+
+/// Coverage of a label across the given messages' current labels
+pub fn label_coverage(label: &str, labels: &HashMap<i64, &[String]>) -> LabelCoverage {
+    let count = labels
+        .values()
+        .filter(|l| l.iter().any(|x| x == label))
+        .count();
+
+    if count == 0 {
+        LabelCoverage::None
+    } else if count == labels.len() {
+        LabelCoverage::All
+    } else {
+        LabelCoverage::Some
+    }
+}
+
+/// Apply a label change to one message's labels, keeping them unique
+pub fn apply_label_change(labels: &mut Vec<String>, add: &[String], remove: &[String]) {
+    labels.retain(|l| !remove.contains(l));
+    for label in add {
+        if !labels.contains(label) {
+            labels.push(label.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filtered_and_new_label() {
+        let mut picker = LabelPicker::new(vec![1, 2]);
+        picker.available = vec!["Work".to_string(), "Receipts".to_string()];
+
+        picker.filter = "wo".to_string();
+        assert_eq!(picker.filtered(), vec!["Work"]);
+        assert_eq!(picker.new_label(), Some("wo"));
+
+        picker.filter = "work".to_string();
+        assert_eq!(picker.new_label(), None);
+    }
+
+    #[test]
+    fn test_label_coverage() {
+        let work = vec!["Work".to_string()];
+        let none: Vec<String> = Vec::new();
+        let mut labels: HashMap<i64, &[String]> = HashMap::new();
+        labels.insert(1, &work);
+        labels.insert(2, &none);
+
+        assert_eq!(label_coverage("Work", &labels), LabelCoverage::Some);
+        assert_eq!(label_coverage("Other", &labels), LabelCoverage::None);
+
+        labels.insert(2, &work);
+        assert_eq!(label_coverage("Work", &labels), LabelCoverage::All);
+    }
+
+    #[test]
+    fn test_apply_label_change() {
+        let mut labels = vec!["Inbox".to_string(), "Work".to_string()];
+        apply_label_change(
+            &mut labels,
+            &["Receipts".to_string(), "Inbox".to_string()],
+            &["Work".to_string()],
+        );
+        assert_eq!(labels, vec!["Inbox".to_string(), "Receipts".to_string()]);
+    }
+}
@@ -0,0 +1,89 @@
+//! Windowed list rendering
+//!
+//! Long lists only build the rows inside the visible part of their
+//! scrollable, with spacers standing in for the rest so the scroll height
+//! stays right. Every row must be the same height for this to line up.
+
+use std::ops::Range;
+
+/// Rows built past each edge of the viewport, so fast scrolling doesn't
+/// show blank space before the next frame
+const OVERSCAN_ROWS: usize = 10;
+
+/// Visible part of a scrollable list
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListWindow {
+    /// Scroll offset from the top, in pixels
+    pub offset: f32,
+    /// Viewport height in pixels
+    pub height: f32,
+}
+
+impl Default for ListWindow {
+    /// Before the first scroll event, assume a tall viewport at the top
+    fn default() -> Self {
+        Self {
+            offset: 0.0,
+            height: 1200.0,
+        }
+    }
+}
+
+impl ListWindow {
+    /// Rows to build for a list of `len` rows, each `pitch` pixels tall
+    pub fn visible_rows(&self, len: usize, pitch: f32) -> Range<usize> {
+        let first = (self.offset.max(0.0) / pitch) as usize;
+        let shown = (self.height.max(0.0) / pitch).ceil() as usize + 1;
+        let start = first.saturating_sub(OVERSCAN_ROWS).min(len);
+        let end = (first + shown + OVERSCAN_ROWS).min(len);
+        start..end
+    }
+
+    /// Scroll offset that brings row `index` into view, or `None` if it is
+    /// already fully visible
+    pub fn reveal(&self, index: usize, pitch: f32) -> Option<f32> {
+        let top = index as f32 * pitch;
+        let bottom = top + pitch;
+        if top < self.offset {
+            Some(top)
+        } else if bottom > self.offset + self.height {
+            Some((bottom - self.height).max(0.0))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_rows_cover_viewport_and_overscan() {
+        let window = ListWindow {
+            offset: 4000.0,
+            height: 400.0,
+        };
+        // Rows 100..110 are on screen
+        assert_eq!(window.visible_rows(10_000, 40.0), 90..121);
+        assert_eq!(window.visible_rows(105, 40.0), 90..105);
+
+        let top = ListWindow {
+            offset: 0.0,
+            height: 400.0,
+        };
+        assert_eq!(top.visible_rows(10_000, 40.0), 0..21);
+        assert_eq!(top.visible_rows(0, 40.0), 0..0);
+    }
+
+    #[test]
+    fn test_reveal_scrolls_only_when_needed() {
+        let window = ListWindow {
+            offset: 400.0,
+            height: 400.0,
+        };
+        assert_eq!(window.reveal(12, 40.0), None);
+        assert_eq!(window.reveal(5, 40.0), Some(200.0));
+        assert_eq!(window.reveal(20, 40.0), Some(440.0));
+    }
+}
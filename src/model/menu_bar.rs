@@ -0,0 +1,66 @@
+//! Application menu bar state
+//!
+//! Iced has no native menu bar, so the File/Edit/View/Help menus are drawn
+//! along the top of the window as popups, like the context menu. This
+//! tracks which one is open.
+
+/// Height of the menu bar in pixels
+pub const MENU_BAR_HEIGHT: f32 = 28.0;
+
+/// Width of each menu title in the bar in pixels
+pub const MENU_TITLE_WIDTH: f32 = 56.0;
+
+/// Width of an open menu in pixels
+pub const MENU_WIDTH: f32 = 240.0;
+
+/// A top-level menu in the bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuBarMenu {
+    File,
+    Edit,
+    View,
+    Help,
+}
+
+impl MenuBarMenu {
+    /// All menus, in bar order
+    pub fn all() -> &'static [MenuBarMenu] {
+        &[Self::File, Self::Edit, Self::View, Self::Help]
+    }
+
+    /// Title shown in the bar
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::File => "File",
+            Self::Edit => "Edit",
+            Self::View => "View",
+            Self::Help => "Help",
+        }
+    }
+
+    /// Left edge of the menu's title, where its popup opens
+    pub fn offset(&self) -> f32 {
+        let index = Self::all().iter().position(|m| m == self).unwrap_or(0);
+        index as f32 * MENU_TITLE_WIDTH
+    }
+}
+
+/// Label for a Cmd (macOS) or Ctrl (elsewhere) chord on a key
+pub fn chord_label(key: &str) -> String {
+    if cfg!(target_os = "macos") {
+        format!("\u{2318}{}", key.to_uppercase())
+    } else {
+        format!("Ctrl+{}", key.to_uppercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_menu_offsets_follow_bar_order() {
+        assert_eq!(MenuBarMenu::File.offset(), 0.0);
+        assert_eq!(MenuBarMenu::Help.offset(), 3.0 * MENU_TITLE_WIDTH);
+    }
+}
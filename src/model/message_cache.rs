@@ -0,0 +1,158 @@
+//! Prefetched message details
+//!
+//! The detail for the focused list row is fetched once focus has rested on
+//! it briefly, so opening it with Enter shows the message without waiting
+//! on the server. Recently used details are kept, oldest dropped first.
+
+use crate::api::types::MessageDetail;
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Number of message details kept
+pub const MESSAGE_CACHE_SIZE: usize = 50;
+
+/// How long focus must stay on a row before its detail is prefetched
+pub const PREFETCH_DWELL: Duration = Duration::from_millis(300);
+
+/// Recently fetched message details and the prefetch waiting on focus
+#[derive(Debug, Clone, Default)]
+pub struct MessageCache {
+    /// Least recently used first
+    entries: VecDeque<MessageDetail>,
+    /// Messages being prefetched
+    in_flight: HashSet<i64>,
+    /// Focused message and when it was focused
+    pending: Option<(i64, Instant)>,
+}
+
+impl MessageCache {
+    /// A cached message, marked as the most recently used
+    pub fn get(&mut self, message_id: i64) -> Option<MessageDetail> {
+        let pos = self.entries.iter().position(|m| m.id == message_id)?;
+        let detail = self.entries.remove(pos)?;
+        self.entries.push_back(detail.clone());
+        Some(detail)
+    }
+
+    /// A cached message, without marking it used (for drawing)
+    pub fn peek(&self, message_id: i64) -> Option<&MessageDetail> {
+        self.entries.iter().find(|m| m.id == message_id)
+    }
+
+    /// Store a fetched message, replacing any older copy
+    pub fn insert(&mut self, detail: MessageDetail) {
+        self.entries.retain(|m| m.id != detail.id);
+        self.entries.push_back(detail);
+        while self.entries.len() > MESSAGE_CACHE_SIZE {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Replace a cached message's labels
+    pub fn set_labels(&mut self, message_id: i64, labels: &[String]) {
+        if let Some(detail) = self.entries.iter_mut().find(|m| m.id == message_id) {
+            detail.labels = labels.to_vec();
+        }
+    }
+
+    /// Focus moved to another row (or away from the list)
+    ///
+    /// Starts the dwell for a message not already cached or on its way;
+    /// whatever was waiting before is cancelled.
+    pub fn focus(&mut self, message_id: Option<i64>, now: Instant) {
+        self.pending = message_id
+            .filter(|id| !self.in_flight.contains(id) && !self.entries.iter().any(|m| m.id == *id))
+            .map(|id| (id, now));
+    }
+
+    /// Whether a prefetch is waiting for focus to rest
+    pub fn is_waiting(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// The message to prefetch once focus has rested long enough
+    pub fn due(&mut self, now: Instant) -> Option<i64> {
+        let (message_id, since) = self.pending?;
+        if now.duration_since(since) < PREFETCH_DWELL {
+            return None;
+        }
+        self.pending = None;
+        self.in_flight.insert(message_id);
+        Some(message_id)
+    }
+
+    /// A prefetch finished; a failed one can be retried by focusing again
+    pub fn prefetched(&mut self, message_id: i64, detail: Option<MessageDetail>) {
+        self.in_flight.remove(&message_id);
+        if let Some(detail) = detail {
+            self.insert(detail);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn mock_message(id: i64) -> MessageDetail {
+        MessageDetail {
+            id,
+            subject: format!("Message {}", id),
+            from_addr: "test@example.com".to_string(),
+            to: vec!["recipient@example.com".to_string()],
+            cc: vec![],
+            bcc: vec![],
+            sent_at: Utc::now(),
+            body: String::new(),
+            body_html: None,
+            labels: vec![],
+            attachments: vec![],
+            thread_id: None,
+            headers: vec![],
+        }
+    }
+
+    #[test]
+    fn test_prefetch_waits_for_focus_to_rest() {
+        let mut cache = MessageCache::default();
+        let start = Instant::now();
+
+        cache.focus(Some(1), start);
+        // Moving on cancels the first row
+        cache.focus(Some(2), start + Duration::from_millis(100));
+        assert_eq!(cache.due(start + Duration::from_millis(300)), None);
+        assert_eq!(cache.due(start + Duration::from_millis(400)), Some(2));
+        assert!(!cache.is_waiting());
+
+        // In flight: focusing again doesn't fetch twice
+        cache.focus(Some(2), start);
+        assert!(!cache.is_waiting());
+
+        // A failure can be retried
+        cache.prefetched(2, None);
+        cache.focus(Some(2), start);
+        assert!(cache.is_waiting());
+
+        cache.prefetched(2, Some(mock_message(2)));
+        cache.focus(Some(2), start);
+        assert!(!cache.is_waiting());
+        assert_eq!(cache.peek(2).map(|m| m.id), Some(2));
+        assert_eq!(cache.get(2).map(|m| m.id), Some(2));
+    }
+
+    #[test]
+    fn test_least_recently_used_is_dropped() {
+        let mut cache = MessageCache::default();
+        for id in 0..MESSAGE_CACHE_SIZE as i64 {
+            cache.insert(mock_message(id));
+        }
+        // Using the oldest keeps it
+        assert!(cache.get(0).is_some());
+        cache.insert(mock_message(100));
+
+        assert!(cache.get(0).is_some());
+        assert!(cache.get(1).is_none());
+        assert!(cache.get(100).is_some());
+    }
+}
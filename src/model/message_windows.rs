@@ -0,0 +1,142 @@
+//! Message windows
+//!
+//! Messages popped out into their own OS windows so one can be read while
+//! browsing others. Windows are keyed by message ID, and each loads and
+//! holds its own copy of the message.
+
+use crate::api::types::MessageDetail;
+use iced::window;
+use std::collections::HashMap;
+
+/// A message shown in its own window
+#[derive(Debug, Clone)]
+pub struct MessageWindow {
+    /// The OS window
+    pub window: window::Id,
+    /// The message, once loaded
+    pub detail: Option<MessageDetail>,
+    /// Why loading failed, if it did
+    pub error: Option<String>,
+}
+
+/// Open message windows by message ID
+#[derive(Debug, Clone, Default)]
+pub struct MessageWindows {
+    windows: HashMap<i64, MessageWindow>,
+}
+
+impl MessageWindows {
+    /// Track a newly opened window for a message, not yet loaded
+    pub fn open(&mut self, message_id: i64, window: window::Id) {
+        self.windows.insert(
+            message_id,
+            MessageWindow {
+                window,
+                detail: None,
+                error: None,
+            },
+        );
+    }
+
+    /// The window showing a message, if one is open
+    pub fn window_for(&self, message_id: i64) -> Option<window::Id> {
+        self.windows.get(&message_id).map(|w| w.window)
+    }
+
+    /// The message ID and contents of a window
+    pub fn by_window(&self, window: window::Id) -> Option<(i64, &MessageWindow)> {
+        self.windows
+            .iter()
+            .find(|(_, w)| w.window == window)
+            .map(|(id, w)| (*id, w))
+    }
+
+    /// A message loaded in one of the windows
+    pub fn message(&self, message_id: i64) -> Option<&MessageDetail> {
+        self.windows.get(&message_id)?.detail.as_ref()
+    }
+
+    /// Store a window's load result; ignored if it was closed meanwhile
+    pub fn loaded(&mut self, message_id: i64, result: Result<MessageDetail, String>) {
+        if let Some(w) = self.windows.get_mut(&message_id) {
+            match result {
+                Ok(detail) => {
+                    w.detail = Some(detail);
+                    w.error = None;
+                }
+                Err(e) => w.error = Some(e),
+            }
+        }
+    }
+
+    /// Replace a message's labels if it is open in a window
+    pub fn set_labels(&mut self, message_id: i64, labels: &[String]) {
+        if let Some(detail) = self
+            .windows
+            .get_mut(&message_id)
+            .and_then(|w| w.detail.as_mut())
+        {
+            detail.labels = labels.to_vec();
+        }
+    }
+
+    /// Forget a closed window, returning the message it showed
+    pub fn close(&mut self, window: window::Id) -> Option<i64> {
+        let (message_id, _) = self.by_window(window)?;
+        self.windows.remove(&message_id);
+        Some(message_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn mock_message(id: i64) -> MessageDetail {
+        MessageDetail {
+            id,
+            subject: format!("Message {}", id),
+            from_addr: "test@example.com".to_string(),
+            to: vec!["recipient@example.com".to_string()],
+            cc: vec![],
+            bcc: vec![],
+            sent_at: Utc::now(),
+            body: String::new(),
+            body_html: None,
+            labels: vec![],
+            attachments: vec![],
+            thread_id: None,
+            headers: vec![],
+        }
+    }
+
+    #[test]
+    fn test_open_load_and_close() {
+        let mut windows = MessageWindows::default();
+        let window = window::Id::unique();
+        windows.open(7, window);
+
+        assert_eq!(windows.window_for(7), Some(window));
+        assert!(windows.message(7).is_none());
+
+        windows.loaded(7, Ok(mock_message(7)));
+        assert_eq!(windows.message(7).map(|m| m.id), Some(7));
+        assert_eq!(windows.by_window(window).map(|(id, _)| id), Some(7));
+
+        assert_eq!(windows.close(window), Some(7));
+        assert_eq!(windows.window_for(7), None);
+        assert_eq!(windows.close(window), None);
+    }
+
+    #[test]
+    fn test_load_after_close_is_ignored() {
+        let mut windows = MessageWindows::default();
+        let window = window::Id::unique();
+        windows.open(7, window);
+        windows.close(window);
+
+        windows.loaded(7, Ok(mock_message(7)));
+        assert!(windows.message(7).is_none());
+    }
+}
@@ -1,13 +1,47 @@
 //! Application state modules
 
+pub mod account_import;
+pub mod auth_results;
+pub mod avatars;
+pub mod body;
 pub mod compose;
+pub mod context_menu;
+pub mod deletion;
 pub mod downloads;
+mod keymap;
+pub mod labels;
+pub mod list_window;
+pub mod menu_bar;
+pub mod message_cache;
+pub mod message_windows;
+pub mod modal;
 mod navigation;
+pub mod panels;
+pub mod remote_content;
+pub mod response_cache;
 mod state;
+pub mod sync_watch;
 pub mod thread;
+pub mod toast;
 
-pub use compose::{format_quoted_body, AttachmentDraft, ComposeMode, ComposeState};
+pub use account_import::AccountImport;
+pub use auth_results::{AuthResults, Trust};
+pub use avatars::{contains_address, sender_address, AvatarCache};
+pub use compose::{quote_message, AttachmentDraft, ComposeMode, ComposeState};
+pub use context_menu::{ContextMenu, ContextTarget};
+pub use deletion::DeletionProgress;
 pub use downloads::{DownloadState, DownloadTracker};
+pub use keymap::{Action, Keymap, HELP_SECTIONS};
+pub use labels::LabelPicker;
+pub use list_window::ListWindow;
+pub use menu_bar::MenuBarMenu;
+pub use message_windows::MessageWindow;
+pub use modal::Modal;
 pub use navigation::{BreadcrumbEntry, ViewLevel};
-pub use state::{AppState, ConnectionStatus, LoadingState, SettingsTab, WizardStep};
+pub use panels::{Divider, PanelWidths};
+pub use state::{
+    AggregateQuery, AppState, ConnectionStatus, Connectivity, FocusArea, LoadingState, SettingsTab,
+    WizardStep, MORE_FROM_SENDER, SEARCH_PAGE_SIZE,
+};
 pub use thread::ThreadState;
+pub use toast::Toast;
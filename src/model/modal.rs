@@ -0,0 +1,115 @@
+//! Modal stack
+//!
+//! Open dialogs and panels in the order they were opened. The view draws
+//! every entry, last on top, and Escape closes the topmost one.
+
+/// A dialog or panel drawn over the main view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modal {
+    /// Keyboard shortcuts reference
+    Help,
+    /// Confirm staging the selection for deletion
+    ConfirmDelete,
+    /// Confirm removing the account in `AppState::removing_account`
+    RemoveAccount,
+    /// Attachment downloads panel
+    Downloads,
+    /// Confirm resetting all settings to their defaults
+    ResetSettings,
+    /// Confirm quitting with an unsent message or unfinished downloads
+    ConfirmQuit,
+}
+
+/// Open modals, bottom first
+#[derive(Debug, Clone, Default)]
+pub struct ModalStack {
+    modals: Vec<Modal>,
+}
+
+impl ModalStack {
+    /// Open a modal on top; one already open moves to the top
+    pub fn push(&mut self, modal: Modal) {
+        self.close(modal);
+        self.modals.push(modal);
+    }
+
+    /// Close the topmost modal
+    pub fn pop(&mut self) -> Option<Modal> {
+        self.modals.pop()
+    }
+
+    /// Close a modal wherever it is in the stack
+    pub fn close(&mut self, modal: Modal) {
+        self.modals.retain(|m| *m != modal);
+    }
+
+    /// Open a modal if closed, close it if open
+    pub fn toggle(&mut self, modal: Modal) {
+        if self.is_open(modal) {
+            self.close(modal);
+        } else {
+            self.push(modal);
+        }
+    }
+
+    /// Whether a modal is open
+    pub fn is_open(&self, modal: Modal) -> bool {
+        self.modals.contains(&modal)
+    }
+
+    /// The topmost modal
+    pub fn top(&self) -> Option<Modal> {
+        self.modals.last().copied()
+    }
+
+    /// Open modals, bottom first
+    pub fn iter(&self) -> impl Iterator<Item = Modal> + '_ {
+        self.modals.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_pop() {
+        let mut stack = ModalStack::default();
+        stack.push(Modal::Downloads);
+        stack.push(Modal::Help);
+
+        assert_eq!(stack.top(), Some(Modal::Help));
+        assert_eq!(stack.pop(), Some(Modal::Help));
+        assert_eq!(stack.top(), Some(Modal::Downloads));
+        assert_eq!(stack.pop(), Some(Modal::Downloads));
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn test_push_existing_moves_to_top() {
+        let mut stack = ModalStack::default();
+        stack.push(Modal::Help);
+        stack.push(Modal::Downloads);
+        stack.push(Modal::Help);
+
+        assert_eq!(
+            stack.iter().collect::<Vec<_>>(),
+            vec![Modal::Downloads, Modal::Help]
+        );
+    }
+
+    #[test]
+    fn test_close_and_toggle() {
+        let mut stack = ModalStack::default();
+        stack.toggle(Modal::Downloads);
+        stack.push(Modal::ConfirmDelete);
+        assert!(stack.is_open(Modal::Downloads));
+
+        stack.toggle(Modal::Downloads);
+        assert!(!stack.is_open(Modal::Downloads));
+        assert_eq!(stack.top(), Some(Modal::ConfirmDelete));
+
+        stack.close(Modal::ConfirmDelete);
+        assert_eq!(stack.top(), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! Resizable panel widths
+//!
+//! The sidebar and message list are resized by dragging the dividers to
+//! their right; the detail pane takes whatever width is left.
+
+use crate::theme::spacing;
+
+/// Narrowest and widest the sidebar can be dragged
+pub const SIDEBAR_MIN_WIDTH: f32 = 180.0;
+pub const SIDEBAR_MAX_WIDTH: f32 = 400.0;
+
+/// Narrowest the message list can be dragged
+pub const MESSAGE_LIST_MIN_WIDTH: f32 = 280.0;
+
+/// Width always left for the detail pane
+pub const DETAIL_MIN_WIDTH: f32 = 320.0;
+
+/// Width of a draggable divider
+pub const DIVIDER_WIDTH: f32 = 4.0;
+
+/// Width of the collapsed, icon-only sidebar
+pub const SIDEBAR_RAIL_WIDTH: f32 = 52.0;
+
+/// A draggable divider between panels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Divider {
+    /// Right edge of the sidebar
+    Sidebar,
+    /// Right edge of the message list, beside the detail pane
+    MessageList,
+}
+
+/// Widths of the sidebar and message list in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PanelWidths {
+    pub sidebar: f32,
+    pub message_list: f32,
+}
+
+impl Default for PanelWidths {
+    fn default() -> Self {
+        Self {
+            sidebar: spacing::SIDEBAR_WIDTH,
+            message_list: spacing::MESSAGE_LIST_WIDTH,
+        }
+    }
+}
+
+impl PanelWidths {
+    /// Width the sidebar takes on screen, including its divider
+    ///
+    /// The collapsed rail has no divider; the expanded width is kept for
+    /// when it's expanded again.
+    pub fn visible_sidebar(&self, collapsed: bool) -> f32 {
+        if collapsed {
+            SIDEBAR_RAIL_WIDTH
+        } else {
+            self.sidebar.clamp(SIDEBAR_MIN_WIDTH, SIDEBAR_MAX_WIDTH) + DIVIDER_WIDTH
+        }
+    }
+
+    /// Widths kept within the minimums for a window of `window_width`
+    ///
+    /// The detail pane keeps `DETAIL_MIN_WIDTH` unless the window is too
+    /// narrow for the list minimum, in which case the list wins.
+    pub fn clamped(self, window_width: f32, sidebar_collapsed: bool) -> Self {
+        let list_max = (window_width
+            - self.visible_sidebar(sidebar_collapsed)
+            - DETAIL_MIN_WIDTH
+            - DIVIDER_WIDTH)
+            .max(MESSAGE_LIST_MIN_WIDTH);
+
+        Self {
+            sidebar: self.sidebar.clamp(SIDEBAR_MIN_WIDTH, SIDEBAR_MAX_WIDTH),
+            message_list: self.message_list.clamp(MESSAGE_LIST_MIN_WIDTH, list_max),
+        }
+    }
+
+    /// Move a divider to the window x-coordinate `x`
+    pub fn drag(&mut self, divider: Divider, x: f32, window_width: f32, sidebar_collapsed: bool) {
+        match divider {
+            Divider::Sidebar => self.sidebar = x,
+            Divider::MessageList => {
+                self.message_list = x - self.visible_sidebar(sidebar_collapsed);
+            }
+        }
+        *self = self.clamped(window_width, sidebar_collapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamped_keeps_minimums() {
+        let widths = PanelWidths {
+            sidebar: 20.0,
+            message_list: 5000.0,
+        }
+        .clamped(1200.0, false);
+
+        assert_eq!(widths.sidebar, SIDEBAR_MIN_WIDTH);
+        assert_eq!(
+            widths.message_list,
+            1200.0 - SIDEBAR_MIN_WIDTH - DETAIL_MIN_WIDTH - 2.0 * DIVIDER_WIDTH
+        );
+        assert_eq!(
+            PanelWidths::default().clamped(400.0, false).message_list,
+            MESSAGE_LIST_MIN_WIDTH
+        );
+    }
+
+    #[test]
+    fn test_drag_moves_divider_to_cursor() {
+        let mut widths = PanelWidths::default();
+        widths.drag(Divider::Sidebar, 300.0, 1600.0, false);
+        assert_eq!(widths.sidebar, 300.0);
+
+        widths.drag(Divider::MessageList, 800.0, 1600.0, false);
+        assert_eq!(widths.message_list, 800.0 - 300.0 - DIVIDER_WIDTH);
+
+        // The collapsed rail keeps the expanded width for later
+        widths.drag(Divider::MessageList, 800.0, 1600.0, true);
+        assert_eq!(widths.message_list, 800.0 - SIDEBAR_RAIL_WIDTH);
+        assert_eq!(widths.sidebar, 300.0);
+    }
+}
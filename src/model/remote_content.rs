@@ -0,0 +1,72 @@
+//! Remote content blocking
+//!
+//! Images that an HTML message loads from other servers double as read
+//! receipts, so they stay blocked until the user loads them for one
+//! message or allows the sender for good.
+
+/// Number of `<img>` tags in `html` that load from another server
+///
+/// Inline (`cid:`) and `data:` images are part of the message and don't
+/// count.
+pub fn remote_image_count(html: &str) -> usize {
+    let lower = html.to_ascii_lowercase();
+    lower
+        .match_indices("<img")
+        .filter(|(start, _)| {
+            let tag = &lower[*start..];
+            let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+            img_src(tag).is_some_and(is_remote_url)
+        })
+        .count()
+}
+
+/// Value of the `src` attribute in an `<img ...` tag
+fn img_src(tag: &str) -> Option<&str> {
+    let mut rest = tag;
+    while let Some(pos) = rest.find("src") {
+        // Skip attributes that merely end in "src", like data-src
+        let preceded_by_space = rest[..pos].chars().last().is_some_and(char::is_whitespace);
+        let after = rest[pos + 3..].trim_start();
+        if preceded_by_space {
+            if let Some(value) = after.strip_prefix('=') {
+                let value = value.trim_start();
+                let value = match value.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let value = &value[1..];
+                        &value[..value.find(quote).unwrap_or(value.len())]
+                    }
+                    _ => &value[..value.find(char::is_whitespace).unwrap_or(value.len())],
+                };
+                return Some(value.trim());
+            }
+        }
+        rest = &rest[pos + 3..];
+    }
+    None
+}
+
+/// Whether a URL points at another server
+fn is_remote_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://") || url.starts_with("//")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_only_remote_images() {
+        let html = r#"
+            <p>Hi</p>
+            <img src="https://tracker.example.com/open.gif" width=1>
+            <IMG SRC='http://cdn.example.com/logo.png'>
+            <img src=//cdn.example.com/banner.png alt=x>
+            <img src="cid:part1@example.com">
+            <img src="data:image/png;base64,AAAA">
+            <img data-src="https://lazy.example.com/a.png">
+        "#;
+
+        assert_eq!(remote_image_count(html), 3);
+        assert_eq!(remote_image_count("<p>No images</p>"), 0);
+    }
+}
@@ -0,0 +1,99 @@
+//! Responses kept with their ETags
+//!
+//! Slowly changing endpoints (stats, aggregates) are requested with the
+//! ETag of the last response. A 304 reply reuses the decoded copy kept
+//! here instead of downloading and decoding the payload again.
+
+use crate::api::types::Fetched;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Decoded responses and their ETags, by request
+#[derive(Debug, Clone)]
+pub struct ResponseCache<K, T> {
+    entries: HashMap<K, (String, T)>,
+}
+
+impl<K, T> Default for ResponseCache<K, T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, T: Clone> ResponseCache<K, T> {
+    /// ETag to send with the next request for `key`
+    pub fn etag(&self, key: &K) -> Option<String> {
+        self.entries.get(key).map(|(etag, _)| etag.clone())
+    }
+
+    /// The response to use for a fetch, remembering changed ones
+    ///
+    /// None when the server says nothing changed but the copy is gone
+    /// (e.g. the cache was cleared meanwhile); fetch again to recover.
+    pub fn resolve(&mut self, key: K, fetched: Fetched<T>) -> Option<T> {
+        match fetched {
+            Fetched::Modified {
+                value,
+                etag: Some(etag),
+            } => {
+                self.entries.insert(key, (etag, value.clone()));
+                Some(value)
+            }
+            Fetched::Modified { value, etag: None } => {
+                self.entries.remove(&key);
+                Some(value)
+            }
+            Fetched::NotModified => self.entries.get(&key).map(|(_, value)| value.clone()),
+        }
+    }
+
+    /// Forget every response, e.g. after switching servers
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modified(value: &str, etag: Option<&str>) -> Fetched<String> {
+        Fetched::Modified {
+            value: value.to_string(),
+            etag: etag.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_not_modified_reuses_cached_value() {
+        let mut cache = ResponseCache::default();
+        assert_eq!(cache.etag(&"senders"), None);
+
+        assert_eq!(
+            cache
+                .resolve("senders", modified("v1", Some("\"a\"")))
+                .as_deref(),
+            Some("v1")
+        );
+        assert_eq!(cache.etag(&"senders").as_deref(), Some("\"a\""));
+        assert_eq!(
+            cache.resolve("senders", Fetched::NotModified).as_deref(),
+            Some("v1")
+        );
+
+        // A response without an ETag can't be revalidated later
+        cache.resolve("senders", modified("v2", None));
+        assert_eq!(cache.etag(&"senders"), None);
+        assert_eq!(cache.resolve("senders", Fetched::NotModified), None);
+    }
+
+    #[test]
+    fn test_clear_forgets_etags() {
+        let mut cache = ResponseCache::default();
+        cache.resolve((), modified("stats", Some("\"1\"")));
+        cache.clear();
+        assert_eq!(cache.etag(&()), None);
+    }
+}
@@ -4,21 +4,48 @@
 //! Contains all application state that determines what to render.
 
 use crate::api::types::{
-    AccountSyncStatus, AggregateRow, MessageDetail, MessageSummary, OAuthInitResponse,
-    SortDirection, SortField, StatsResponse,
+    AccountSyncStatus, AggregateResponse, AggregateRow, MessageDetail, MessageSummary,
+    OAuthInitResponse, SortDirection, SortField, StatsResponse, ViewType,
 };
-use crate::config::{DiscoveryResult, DiscoveryStep, Settings};
+use crate::api::ApiClient;
+use crate::config::{
+    BlockedSenderDisplay, ByteUnits, ConfigRecovery, DefaultView, DiscoveryResult, DiscoveryStep,
+    PinnedFilter, ReadingPaneMode, SavedSearch, Session, SessionView, Settings, ThemeChoice,
+};
+use crate::model::account_import::AccountImport;
+use crate::model::avatars::{contains_address, sender_address, AvatarCache};
 use crate::model::compose::ComposeState;
+use crate::model::context_menu::ContextMenu;
+use crate::model::deletion::DeletionProgress;
 use crate::model::downloads::DownloadTracker;
-use crate::model::navigation::NavigationStack;
+use crate::model::keymap::Keymap;
+use crate::model::labels::LabelPicker;
+use crate::model::list_window::ListWindow;
+use crate::model::menu_bar::MenuBarMenu;
+use crate::model::message_cache::MessageCache;
+use crate::model::message_windows::MessageWindows;
+use crate::model::modal::ModalStack;
+use crate::model::navigation::{NavigationStack, ViewLevel};
+use crate::model::panels::{Divider, PanelWidths};
+use crate::model::response_cache::ResponseCache;
+use crate::model::sync_watch::SyncWatch;
 use crate::model::thread::ThreadState;
-use std::collections::HashSet;
+use crate::model::toast::Toast;
+use chrono::{DateTime, Utc};
+use iced::keyboard::Modifiers;
+use iced::window;
+use iced::{Point, Size};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
 /// Connection status with the msgvault server
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConnectionStatus {
     /// Haven't tried connecting yet
     Unknown,
+    /// First health check after launch with saved settings; shows a splash
+    /// rather than the connection form
+    Starting,
     /// Currently attempting to connect
     Connecting,
     /// Successfully connected to server
@@ -27,6 +54,51 @@ pub enum ConnectionStatus {
     Failed(String),
 }
 
+/// Number of search results fetched per page
+pub const SEARCH_PAGE_SIZE: i64 = 50;
+
+/// Messages listed under "More from this sender"
+pub const MORE_FROM_SENDER: usize = 5;
+
+/// What an aggregate request asked for, keying its cached response
+pub type AggregateQuery = (ViewType, SortField, SortDirection);
+
+/// Background health check interval while the server answers
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Longest background health check interval after repeated failures
+const HEALTH_CHECK_MAX_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Wait before the connection screen first retries an unreachable server
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Longest wait between connection screen retries
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Server reachability once the app has connected
+///
+/// Distinct from `ConnectionStatus`, which gates the initial connection
+/// screen. Losing the server afterwards keeps the loaded data on screen
+/// read-only instead of returning to that screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Server reachable, all actions available
+    #[default]
+    Online,
+    /// Server unreachable, showing previously loaded data read-only
+    Offline,
+}
+
+/// Which part of the window keyboard navigation acts on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FocusArea {
+    /// The main list or detail view
+    #[default]
+    Content,
+    /// The navigation sidebar
+    Sidebar,
+}
+
 /// Loading state for async operations
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum LoadingState {
@@ -48,12 +120,40 @@ pub struct AppState {
     // === Connection ===
     /// Current connection status
     pub connection_status: ConnectionStatus,
+    /// Server reachability after connecting
+    pub connectivity: Connectivity,
+    /// Background health checks failed in a row, for backoff
+    pub health_failures: u32,
+    /// Seconds until the connection screen retries, while counting down
+    pub reconnect_in: Option<u64>,
+    /// Client shared by API requests, rebuilt when the connection settings change
+    pub api_client: Option<ApiClient>,
     /// Server URL (from config or user input)
     pub server_url: String,
     /// API key for authentication
     pub api_key: String,
+    /// Whether plaintext HTTP to non-local servers and unverified TLS
+    /// certificates are permitted
+    pub allow_insecure: bool,
+    /// Whether the server certificate is pinned
+    pub pin_certificate: bool,
+    /// Fingerprint of the pinned server certificate, recorded on first connect
+    pub pinned_certificate: Option<String>,
+    /// Fingerprint the server presented when it didn't match the pin
+    pub certificate_mismatch: Option<String>,
+    /// Version the server reported, when older than `MIN_SERVER_VERSION`
+    pub outdated_server_version: Option<String>,
+    /// Whether the outdated server banner was dismissed this session
+    pub server_version_notice_dismissed: bool,
+    /// Whether the server advertised a web view of messages
+    pub server_web_ui: bool,
+    /// Settings in use before the connection settings changed, kept until
+    /// the new connection passes a health check
+    pub previous_connection: Option<Settings>,
     /// Whether this is the first run (no config exists)
     pub first_run: bool,
+    /// Set when the config file was unreadable and reset to defaults
+    pub config_recovery: Option<ConfigRecovery>,
 
     // === Wizard/Discovery ===
     /// Whether we're in discovery mode (checking for server)
@@ -62,6 +162,12 @@ pub struct AppState {
     pub discovery_steps: Vec<DiscoveryStep>,
     /// Discovery result (after discovery completes)
     pub discovery_result: Option<DiscoveryResult>,
+    /// Results of checking every discovery source, when run from the wizard
+    pub discovery_diagnostics: Option<Vec<DiscoveryStep>>,
+    /// Extra localhost ports discovery probes (saved)
+    pub discovery_ports: Vec<u16>,
+    /// Port typed into the wizard to search for a server on
+    pub wizard_port: String,
     /// Current wizard step (if in wizard mode)
     pub wizard_step: WizardStep,
 
@@ -72,26 +178,55 @@ pub struct AppState {
     // === Data ===
     /// Archive statistics (loaded on connect)
     pub stats: Option<StatsResponse>,
+    /// Last stats response and its ETag
+    pub stats_cache: ResponseCache<(), StatsResponse>,
     /// Loading state for current data fetch
     pub loading: LoadingState,
 
     // === Aggregates ===
     /// Current aggregate data
     pub aggregates: Vec<AggregateRow>,
-    /// Currently selected row index
+    /// Last aggregate response and its ETag for each query
+    pub aggregate_cache: ResponseCache<AggregateQuery, AggregateResponse>,
+    /// Currently selected row index, into the filtered rows
     pub selected_index: usize,
+    /// Text narrowing the loaded aggregate rows by key
+    pub aggregate_filter: String,
+    /// Visible part of the aggregate list, for windowed rendering
+    pub aggregate_window: ListWindow,
     /// Current sort field
     pub sort_field: SortField,
     /// Current sort direction
     pub sort_dir: SortDirection,
+    /// Whether the Time aggregate renders as a calendar heatmap (vs a list)
+    pub time_heatmap: bool,
+
+    // === Type-ahead ===
+    /// Prefix typed after `g` (None when type-ahead is inactive)
+    pub typeahead_buffer: Option<String>,
+    /// When the type-ahead buffer was last changed
+    pub typeahead_last_input: Option<Instant>,
 
     // === Messages ===
     /// Current message list
     pub messages: Vec<MessageSummary>,
     /// Selected message index in list
     pub message_selected_index: usize,
+    /// Message row under the pointer, which shows reply/forward buttons
+    pub hovered_message: Option<i64>,
     /// Current message detail (when viewing single message)
     pub current_message: Option<MessageDetail>,
+    /// Recently fetched and prefetched message details
+    pub message_cache: MessageCache,
+    /// Whether the current message's body is shown past the preview cap
+    pub show_full_body: bool,
+    /// Messages whose quoted text is expanded
+    pub expanded_quotes: HashSet<i64>,
+    /// Messages whose remote content was loaded once
+    pub remote_content_loaded: HashSet<i64>,
+    /// Recent messages per sender address (lowercased), for "More from
+    /// this sender"
+    pub sender_messages: HashMap<String, Vec<MessageSummary>>,
     /// Pagination offset
     pub messages_offset: i64,
     /// Total messages matching filter
@@ -108,20 +243,42 @@ pub struct AppState {
     pub search_query: String,
     /// Whether deep search mode is enabled
     pub search_deep_mode: bool,
+    /// Whether the filter syntax tips are shown
+    pub show_search_tips: bool,
     /// Search results
     pub search_results: Vec<MessageSummary>,
     /// Selected result index
     pub search_selected_index: usize,
     /// Total matching results
     pub search_total: i64,
+    /// Pagination offset into deep search results
+    pub search_offset: i64,
     /// Whether a search is in progress
     pub is_searching: bool,
+    /// Searches saved as sidebar smart folders
+    pub saved_searches: Vec<SavedSearch>,
 
     // === Selection ===
     /// Set of selected message IDs
     pub selected_messages: HashSet<i64>,
-    /// Whether the delete confirmation modal is showing
-    pub show_delete_modal: bool,
+    /// Row index shift-click ranges extend from (last plain click or toggle)
+    pub selection_anchor: Option<usize>,
+    /// Selection being staged for deletion, chunk by chunk
+    pub deletion: Option<DeletionProgress>,
+    /// Count typed to confirm a large deletion
+    pub delete_confirm_input: String,
+    /// Modifier keys currently held (for shift-click)
+    pub modifiers: Modifiers,
+    /// Whether the last key press went to a focused text input
+    pub text_input_focused: bool,
+    /// Area keyboard navigation acts on
+    pub focus_area: FocusArea,
+    /// Focused sidebar entry while the sidebar has focus
+    pub sidebar_focus_index: usize,
+    /// Whether the focus ring is shown (keyboard in use, not the mouse)
+    pub focus_visible: bool,
+    /// Open label picker for the selection
+    pub label_picker: Option<LabelPicker>,
 
     // === Sync ===
     /// Account sync status list
@@ -130,6 +287,16 @@ pub struct AppState {
     pub sync_loading: bool,
     /// Currently syncing account (if manual sync triggered)
     pub syncing_account: Option<String>,
+    /// Manual syncs awaited for a finished notification
+    pub sync_watch: SyncWatch,
+    /// Filter text for the Accounts and Sync lists
+    pub account_filter: String,
+    /// When new messages were last checked for (saved)
+    pub new_messages_checked_at: Option<DateTime<Utc>>,
+    /// False once the server turns out not to support the new message check
+    pub new_messages_supported: bool,
+    /// False once the server turns out not to support pausing sync
+    pub sync_pause_supported: bool,
 
     // === Account Management ===
     /// Email input for adding new account
@@ -142,12 +309,16 @@ pub struct AppState {
     pub polling_device_flow: bool,
     /// Account being removed (for confirmation)
     pub removing_account: Option<String>,
-    /// Whether the account removal modal is showing
-    pub show_remove_modal: bool,
+    /// Accounts imported from a file, added one at a time
+    pub account_import: AccountImport,
+
+    // === Modals ===
+    /// Open dialogs and panels, drawn bottom to top
+    pub modals: ModalStack,
 
     // === Help ===
-    /// Whether the help modal is showing
-    pub show_help_modal: bool,
+    /// Keyboard bindings (drives the key handler and inline hints)
+    pub keymap: Keymap,
 
     // === Settings ===
     /// Current settings tab
@@ -156,10 +327,88 @@ pub struct AppState {
     pub settings_server_url: String,
     /// Settings form: API key (editable copy)
     pub settings_api_key: String,
+    /// Settings form: allow insecure connections (editable copy)
+    pub settings_allow_insecure: bool,
+    /// Settings form: pin the server certificate (editable copy)
+    pub settings_pin_certificate: bool,
+    /// Whether exported settings include the API key
+    pub settings_export_api_key: bool,
     /// Whether testing connection
     pub testing_connection: bool,
     /// Connection test result
     pub connection_test_result: Option<Result<(), String>>,
+    /// View to open after connecting (saved setting)
+    pub default_view: DefaultView,
+    /// Settings form: default view (editable copy)
+    pub settings_default_view: DefaultView,
+    /// Whether to restore the last-open view after connecting (saved setting)
+    pub restore_session: bool,
+    /// Settings form: restore session (editable copy)
+    pub settings_restore_session: bool,
+    /// Session loaded from disk, waiting to be restored on connect
+    pub pending_session: Option<Session>,
+    /// Whether inline keyboard hints are shown (saved setting)
+    pub show_key_hints: bool,
+    /// Settings form: show keyboard hints (editable copy)
+    pub settings_show_key_hints: bool,
+    /// Whether message rows show a preview line (saved setting)
+    pub show_snippets: bool,
+    /// Settings form: show previews (editable copy)
+    pub settings_show_snippets: bool,
+    /// Characters of preview per message row (saved setting)
+    pub snippet_length: usize,
+    /// Settings form: preview length (editable copy)
+    pub settings_snippet_length: usize,
+    /// Maximum concurrent attachment downloads (saved setting)
+    pub max_concurrent_downloads: usize,
+    /// Settings form: download limit (editable copy)
+    pub settings_max_concurrent_downloads: usize,
+    /// Whether finished downloads open automatically (saved setting)
+    pub auto_open_downloads: bool,
+    /// Settings form: auto-open downloads (editable copy)
+    pub settings_auto_open_downloads: bool,
+    /// Extensions auto-open applies to (saved setting)
+    pub auto_open_extensions: Vec<String>,
+    /// Settings form: auto-open extensions as typed, comma-separated
+    pub settings_auto_open_extensions: String,
+    /// Whether sender avatars are fetched from Gravatar (saved setting)
+    pub load_remote_avatars: bool,
+    /// Settings form: load remote avatars (editable copy)
+    pub settings_load_remote_avatars: bool,
+    /// Whether sync results raise OS notifications (saved setting)
+    pub desktop_notifications: bool,
+    /// Settings form: desktop notifications (editable copy)
+    pub settings_desktop_notifications: bool,
+    /// Senders whose remote content always loads (saved setting)
+    pub remote_content_senders: Vec<String>,
+    /// Senders hidden or marked in lists (saved setting)
+    pub blocked_senders: Vec<String>,
+    /// Whether blocked senders are hidden or marked (saved setting)
+    pub blocked_sender_display: BlockedSenderDisplay,
+    /// Senders whose new messages aren't announced (saved setting)
+    pub muted_senders: Vec<String>,
+    /// Threads whose new messages aren't announced (saved setting)
+    pub muted_threads: Vec<String>,
+    /// Address typed into the blocklist's add field
+    pub block_sender_input: String,
+    /// Senders and domains pinned to the sidebar (saved setting)
+    pub pinned_filters: Vec<PinnedFilter>,
+    /// Color palette (saved setting)
+    pub theme: ThemeChoice,
+    /// Settings form: color palette (editable copy)
+    pub settings_theme: ThemeChoice,
+    /// Units for displayed sizes (saved setting)
+    pub byte_units: ByteUnits,
+    /// Settings form: size units (editable copy)
+    pub settings_byte_units: ByteUnits,
+    /// Where opened messages are shown (saved setting)
+    pub reading_pane: ReadingPaneMode,
+    /// Settings form: reading pane mode (editable copy)
+    pub settings_reading_pane: ReadingPaneMode,
+    /// Avatar images fetched for sender addresses
+    pub avatars: AvatarCache,
+    /// HTTP client shared by every Gravatar lookup
+    pub avatar_client: reqwest::Client,
 
     // === Compose ===
     /// Compose email state
@@ -168,10 +417,48 @@ pub struct AppState {
     // === Downloads ===
     /// Download state tracker for attachments
     pub downloads: DownloadTracker,
+    /// Quit once the remaining downloads have finished
+    pub quit_when_done: bool,
 
     // === Threading ===
     /// Thread/conversation view state
     pub thread: ThreadState,
+
+    // === Notifications ===
+    /// Confirmation toast currently shown
+    pub toast: Option<Toast>,
+
+    // === Context Menu ===
+    /// Open right-click menu, if any
+    pub context_menu: Option<ContextMenu>,
+    /// Sidebar and message list widths, as dragged
+    pub panel_widths: PanelWidths,
+    /// Panel divider being dragged, if any
+    pub dragging_divider: Option<Divider>,
+    /// Whether the sidebar is collapsed to an icon-only rail
+    pub sidebar_collapsed: bool,
+    /// Open menu bar menu, if any
+    pub open_menu: Option<MenuBarMenu>,
+    /// Last known cursor position in window coordinates
+    pub cursor_position: Point,
+    /// Window the cursor last moved in
+    pub cursor_window: Option<window::Id>,
+    /// Current window size (initial size until the first resize event)
+    pub window_size: Size,
+
+    // === Windows ===
+    /// Main window, once opened
+    pub main_window: Option<window::Id>,
+    /// Window the compose form was popped out into, if any
+    pub compose_window: Option<window::Id>,
+    /// Messages popped out into their own windows
+    pub message_windows: MessageWindows,
+
+    // === Activity ===
+    /// Number of background operations still running
+    pub active_operations: usize,
+    /// Spinner animation frame
+    pub activity_frame: usize,
 }
 
 /// Settings tabs
@@ -180,6 +467,7 @@ pub enum SettingsTab {
     #[default]
     Server,
     Display,
+    Blocked,
 }
 
 /// Wizard step for first-run setup
@@ -203,15 +491,35 @@ impl AppState {
 
         Self {
             // Connection
-            connection_status: ConnectionStatus::Unknown,
+            connection_status: if first_run {
+                ConnectionStatus::Unknown
+            } else {
+                ConnectionStatus::Starting
+            },
+            connectivity: Connectivity::Online,
+            health_failures: 0,
+            reconnect_in: None,
+            api_client: None,
             server_url: settings.server_url.clone(),
             api_key: settings.api_key.clone(),
+            allow_insecure: settings.allow_insecure,
+            pin_certificate: settings.pin_certificate,
+            pinned_certificate: settings.pinned_certificate.clone(),
+            certificate_mismatch: None,
+            outdated_server_version: None,
+            server_web_ui: false,
+            server_version_notice_dismissed: false,
+            previous_connection: None,
             first_run,
+            config_recovery: None,
 
             // Wizard/Discovery
             discovering: first_run,
             discovery_steps: Vec::new(),
             discovery_result: None,
+            discovery_diagnostics: None,
+            discovery_ports: settings.discovery_ports.clone(),
+            wizard_port: String::new(),
             wizard_step: if first_run { WizardStep::Discovering } else { WizardStep::Complete },
 
             // Navigation
@@ -219,18 +527,33 @@ impl AppState {
 
             // Data
             stats: None,
+            stats_cache: ResponseCache::default(),
             loading: LoadingState::Idle,
 
             // Aggregates
             aggregates: Vec::new(),
+            aggregate_cache: ResponseCache::default(),
             selected_index: 0,
+            aggregate_filter: String::new(),
+            aggregate_window: ListWindow::default(),
             sort_field: SortField::Count,
             sort_dir: SortDirection::Desc,
+            time_heatmap: true,
+
+            // Type-ahead
+            typeahead_buffer: None,
+            typeahead_last_input: None,
 
             // Messages
             messages: Vec::new(),
             message_selected_index: 0,
+            hovered_message: None,
             current_message: None,
+            message_cache: MessageCache::default(),
+            sender_messages: HashMap::new(),
+            show_full_body: false,
+            expanded_quotes: HashSet::new(),
+            remote_content_loaded: HashSet::new(),
             messages_offset: 0,
             messages_total: 0,
             messages_limit: 50,
@@ -240,14 +563,25 @@ impl AppState {
             // Search
             search_query: String::new(),
             search_deep_mode: false,
+            show_search_tips: false,
             search_results: Vec::new(),
             search_selected_index: 0,
             search_total: 0,
+            search_offset: 0,
             is_searching: false,
+            saved_searches: Vec::new(),
 
             // Selection
             selected_messages: HashSet::new(),
-            show_delete_modal: false,
+            selection_anchor: None,
+            deletion: None,
+            delete_confirm_input: String::new(),
+            modifiers: Modifiers::default(),
+            text_input_focused: false,
+            focus_area: FocusArea::Content,
+            sidebar_focus_index: 0,
+            focus_visible: false,
+            label_picker: None,
 
             // Sync
             sync_accounts: Vec::new(),
@@ -255,31 +589,96 @@ impl AppState {
             syncing_account: None,
 
             // Account Management
+            sync_watch: SyncWatch::default(),
+            account_filter: String::new(),
+            new_messages_checked_at: settings.new_messages_checked_at,
+            new_messages_supported: true,
+            sync_pause_supported: true,
             add_account_email: String::new(),
             adding_account: false,
             oauth_response: None,
             polling_device_flow: false,
             removing_account: None,
-            show_remove_modal: false,
+            account_import: AccountImport::default(),
+
+            // Modals
+            modals: ModalStack::default(),
 
             // Help
-            show_help_modal: false,
+            keymap: Keymap::default(),
 
             // Settings
             settings_tab: SettingsTab::default(),
             settings_server_url: settings.server_url.clone(),
             settings_api_key: settings.api_key.clone(),
+            settings_allow_insecure: settings.allow_insecure,
+            settings_pin_certificate: settings.pin_certificate,
+            settings_export_api_key: false,
             testing_connection: false,
             connection_test_result: None,
+            default_view: settings.default_view,
+            settings_default_view: settings.default_view,
+            restore_session: settings.restore_session,
+            settings_restore_session: settings.restore_session,
+            pending_session: None,
+            show_key_hints: settings.show_key_hints,
+            settings_show_key_hints: settings.show_key_hints,
+            show_snippets: settings.show_snippets,
+            settings_show_snippets: settings.show_snippets,
+            snippet_length: settings.snippet_length,
+            settings_snippet_length: settings.snippet_length,
+            max_concurrent_downloads: settings.max_concurrent_downloads,
+            settings_max_concurrent_downloads: settings.max_concurrent_downloads,
+            load_remote_avatars: settings.load_remote_avatars,
+            settings_load_remote_avatars: settings.load_remote_avatars,
+            auto_open_downloads: settings.auto_open_downloads,
+            settings_auto_open_downloads: settings.auto_open_downloads,
+            settings_auto_open_extensions: settings.auto_open_extensions.join(", "),
+            auto_open_extensions: settings.auto_open_extensions.clone(),
+            desktop_notifications: settings.desktop_notifications,
+            settings_desktop_notifications: settings.desktop_notifications,
+            remote_content_senders: settings.remote_content_senders.clone(),
+            blocked_senders: settings.blocked_senders.clone(),
+            pinned_filters: settings.pinned_filters.clone(),
+            blocked_sender_display: settings.blocked_sender_display,
+            muted_senders: settings.muted_senders.clone(),
+            muted_threads: settings.muted_threads.clone(),
+            block_sender_input: String::new(),
+            theme: settings.theme,
+            settings_theme: settings.theme,
+            byte_units: settings.byte_units,
+            settings_byte_units: settings.byte_units,
+            reading_pane: settings.reading_pane,
+            settings_reading_pane: settings.reading_pane,
+            avatars: AvatarCache::new(),
+            avatar_client: reqwest::Client::new(),
 
             // Compose
             compose: ComposeState::new(),
 
             // Downloads
             downloads: DownloadTracker::new(),
+            quit_when_done: false,
 
             // Threading
             thread: ThreadState::new(),
+            toast: None,
+            context_menu: None,
+            panel_widths: PanelWidths {
+                sidebar: settings.sidebar_width,
+                message_list: settings.message_list_width,
+            },
+            dragging_divider: None,
+            sidebar_collapsed: settings.sidebar_collapsed,
+            open_menu: None,
+            cursor_position: Point::ORIGIN,
+            cursor_window: None,
+            window_size: Size::new(1200.0, 800.0),
+            main_window: None,
+            compose_window: None,
+            message_windows: MessageWindows::default(),
+            active_operations: 0,
+            activity_frame: 0,
         }
     }
 
@@ -288,9 +687,274 @@ impl AppState {
         matches!(self.connection_status, ConnectionStatus::Connected)
     }
 
+    /// Current labels of a loaded message
+    pub fn message_labels(&self, message_id: i64) -> Option<&[String]> {
+        self.messages
+            .iter()
+            .chain(self.search_results.iter())
+            .find(|m| m.id == message_id)
+            .map(|m| m.labels.as_slice())
+            .or_else(|| {
+                self.current_message
+                    .as_ref()
+                    .filter(|m| m.id == message_id)
+                    .map(|m| m.labels.as_slice())
+            })
+    }
+
+    /// Replace a message's labels everywhere it is loaded
+    pub fn set_message_labels(&mut self, message_id: i64, labels: &[String]) {
+        for msg in self
+            .messages
+            .iter_mut()
+            .chain(self.search_results.iter_mut())
+            .filter(|m| m.id == message_id)
+        {
+            msg.labels = labels.to_vec();
+        }
+        if let Some(detail) = self.current_message.as_mut().filter(|m| m.id == message_id) {
+            detail.labels = labels.to_vec();
+        }
+        self.message_windows.set_labels(message_id, labels);
+        self.message_cache.set_labels(message_id, labels);
+    }
+
+    /// The message under the list cursor, in the message list or search results
+    pub fn focused_message_id(&self) -> Option<i64> {
+        match self.navigation.current() {
+            ViewLevel::Messages { .. } => self.messages.get(self.message_selected_index),
+            ViewLevel::Search => self.search_results.get(self.search_selected_index),
+            _ => None,
+        }
+        .map(|m| m.id)
+    }
+
+    /// Whether a message's remote content may load, once or for its sender
+    pub fn remote_content_allowed(&self, message: &MessageDetail) -> bool {
+        self.remote_content_loaded.contains(&message.id)
+            || contains_address(
+                &self.remote_content_senders,
+                sender_address(&message.from_addr),
+            )
+    }
+
+    /// Whether a sender ("Name <email>" or a bare address) is blocked
+    pub fn is_blocked(&self, sender: &str) -> bool {
+        contains_address(&self.blocked_senders, sender_address(sender))
+    }
+
+    /// Whether new messages from a sender ("Name <email>" or a bare
+    /// address) are kept quiet
+    pub fn is_sender_muted(&self, sender: &str) -> bool {
+        contains_address(&self.muted_senders, sender_address(sender))
+    }
+
+    /// Whether new messages in a thread are kept quiet
+    pub fn is_thread_muted(&self, thread_id: &str) -> bool {
+        self.muted_threads.iter().any(|t| t == thread_id)
+    }
+
+    /// Whether a sender or domain is pinned to the sidebar
+    pub fn is_pinned(&self, filter: &PinnedFilter) -> bool {
+        self.pinned_filters.iter().any(|p| p.same_as(filter))
+    }
+
+    /// Other recent messages from a message's sender, once fetched
+    pub fn more_from_sender(&self, message: &MessageDetail) -> Vec<&MessageSummary> {
+        self.sender_messages
+            .get(&sender_address(&message.from_addr).to_lowercase())
+            .map(|list| {
+                list.iter()
+                    .filter(|m| m.id != message.id)
+                    .take(MORE_FROM_SENDER)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// What quitting now would throw away, if anything
+    pub fn quit_warning(&self) -> Option<String> {
+        let draft = self.compose.is_open && self.compose.is_dirty && self.compose.has_content();
+        let downloads = match self.downloads.pending_count() {
+            0 => None,
+            1 => Some("1 unfinished download".to_string()),
+            n => Some(format!("{} unfinished downloads", n)),
+        };
+
+        match (draft, downloads) {
+            (false, None) => None,
+            (true, None) => Some("The message being composed will be discarded.".to_string()),
+            (false, Some(downloads)) => Some(format!(
+                "Quitting cancels {} and deletes the partly downloaded files.",
+                downloads
+            )),
+            (true, Some(downloads)) => Some(format!(
+                "The message being composed will be discarded, and {} cancelled \
+                 with the partly downloaded files deleted.",
+                downloads
+            )),
+        }
+    }
+
+    /// Combined size of the selected messages in a loaded list
+    pub fn selection_size(&self, messages: &[MessageSummary]) -> i64 {
+        messages
+            .iter()
+            .filter(|m| self.selected_messages.contains(&m.id))
+            .map(|m| m.size_bytes)
+            .sum()
+    }
+
+    /// Aggregate rows whose key contains the list filter (case-insensitive),
+    /// or every row when there is no filter
+    pub fn filtered_aggregates(&self) -> Vec<&AggregateRow> {
+        let filter = self.aggregate_filter.trim().to_lowercase();
+        self.aggregates
+            .iter()
+            .filter(|agg| filter.is_empty() || agg.key.to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    /// Drop blocked senders from the loaded lists when they are hidden
+    ///
+    /// Display filtering only: the messages stay on the server, and totals
+    /// still count them.
+    pub fn hide_blocked(&mut self) {
+        if self.blocked_sender_display != BlockedSenderDisplay::Hide {
+            return;
+        }
+
+        let blocked = &self.blocked_senders;
+        self.messages
+            .retain(|m| !contains_address(blocked, &m.from_email));
+        self.search_results
+            .retain(|m| !contains_address(blocked, &m.from_email));
+        if matches!(
+            self.navigation.current(),
+            ViewLevel::Aggregates {
+                view_type: ViewType::Senders
+            }
+        ) {
+            self.aggregates
+                .retain(|a| !contains_address(blocked, &a.key));
+        }
+
+        self.message_selected_index = self
+            .message_selected_index
+            .min(self.messages.len().saturating_sub(1));
+        self.search_selected_index = self
+            .search_selected_index
+            .min(self.search_results.len().saturating_sub(1));
+        self.selected_index = self
+            .selected_index
+            .min(self.filtered_aggregates().len().saturating_sub(1));
+    }
+
+    /// A message loaded in the detail pane, one of the message windows or
+    /// the open thread
+    pub fn loaded_message(&self, message_id: i64) -> Option<&MessageDetail> {
+        self.current_message
+            .as_ref()
+            .filter(|m| m.id == message_id)
+            .or_else(|| self.message_windows.message(message_id))
+            .or_else(|| self.thread.messages.iter().find(|m| m.id == message_id))
+    }
+
+    /// Check if the server was lost after connecting
+    pub fn is_offline(&self) -> bool {
+        self.connectivity == Connectivity::Offline
+    }
+
+    /// Time until the next background health check
+    ///
+    /// Doubles with each failure in a row, up to five minutes.
+    pub fn health_check_interval(&self) -> Duration {
+        let factor = 1u32 << self.health_failures.min(4);
+        (HEALTH_CHECK_INTERVAL * factor).min(HEALTH_CHECK_MAX_INTERVAL)
+    }
+
+    /// Time until the connection screen retries a failed connection
+    ///
+    /// Doubles with each failure in a row, up to a minute.
+    pub fn reconnect_delay(&self) -> Duration {
+        let factor = 1u32 << self.health_failures.min(4);
+        (RECONNECT_DELAY * factor).min(RECONNECT_MAX_DELAY)
+    }
+
+    /// Check if any background operation is still running
+    pub fn is_busy(&self) -> bool {
+        self.active_operations > 0
+    }
+
+    /// Whether the focus ring is drawn in the content area
+    pub fn content_focus_visible(&self) -> bool {
+        self.focus_visible && self.focus_area == FocusArea::Content
+    }
+
+    /// Sidebar entry showing the focus ring, if the sidebar has focus
+    pub fn sidebar_focus(&self) -> Option<usize> {
+        (self.focus_visible && self.focus_area == FocusArea::Sidebar)
+            .then_some(self.sidebar_focus_index)
+    }
+
+    /// Snapshot the current view for session persistence
+    ///
+    /// Returns None for transient views (settings, threads, sub-aggregates)
+    /// so the last restorable view is kept.
+    pub fn session(&self) -> Option<Session> {
+        let view = match self.navigation.current() {
+            ViewLevel::Dashboard => SessionView::Dashboard,
+            ViewLevel::Aggregates { view_type } => SessionView::Aggregates {
+                view_type: *view_type,
+            },
+            ViewLevel::Messages { .. } => SessionView::Messages,
+            ViewLevel::MessageDetail { message_id } => SessionView::MessageDetail {
+                message_id: *message_id,
+            },
+            ViewLevel::Search => SessionView::Search {
+                query: self.search_query.clone(),
+            },
+            ViewLevel::Sync => SessionView::Sync,
+            ViewLevel::Accounts => SessionView::Accounts,
+            ViewLevel::SubAggregates { .. } | ViewLevel::Thread { .. } | ViewLevel::Settings => {
+                return None
+            }
+        };
+
+        Some(Session {
+            view,
+            filter_type: self.filter_type.clone(),
+            filter_value: self.filter_value.clone(),
+            filter_description: self
+                .navigation
+                .current_filter_description()
+                .unwrap_or_default(),
+            messages_offset: self.messages_offset,
+        })
+    }
+
+    /// Address of a message in the server's web view, if it has one
+    pub fn message_web_url(&self, message_id: i64) -> Option<String> {
+        self.server_web_ui.then(|| {
+            format!(
+                "{}/messages/{}",
+                self.server_url.trim_end_matches('/'),
+                message_id
+            )
+        })
+    }
+
+    /// Characters of preview to show per message row, or None when hidden
+    pub fn list_snippet_length(&self) -> Option<usize> {
+        self.show_snippets.then_some(self.snippet_length)
+    }
+
     /// Check if we're currently connecting
     pub fn is_connecting(&self) -> bool {
-        matches!(self.connection_status, ConnectionStatus::Connecting)
+        matches!(
+            self.connection_status,
+            ConnectionStatus::Starting | ConnectionStatus::Connecting
+        )
     }
 
     /// Get window title based on current state
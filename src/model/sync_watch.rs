@@ -0,0 +1,171 @@
+//! Sync transitions worth a desktop notification
+//!
+//! Compares successive scheduler status polls to notice when a manually
+//! triggered sync has finished or an account has started failing.
+
+use crate::api::types::{AccountSyncStatus, SyncState};
+use std::collections::HashMap;
+
+/// A change in an account's sync state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncEvent {
+    /// A manually triggered sync ran and went back to idle
+    Finished {
+        email: String,
+        messages_synced: Option<i64>,
+    },
+    /// The account moved into the error state
+    Failed {
+        email: String,
+        error: Option<String>,
+    },
+}
+
+impl SyncEvent {
+    /// Notification title
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Finished { .. } => "Sync finished",
+            Self::Failed { .. } => "Sync failed",
+        }
+    }
+
+    /// Notification body naming the account
+    pub fn body(&self) -> String {
+        match self {
+            Self::Finished {
+                email,
+                messages_synced: Some(count),
+            } => format!("{}: {} messages synced", email, count),
+            Self::Finished { email, .. } => format!("{} is up to date", email),
+            Self::Failed {
+                email,
+                error: Some(error),
+            } => format!("{}: {}", email, error),
+            Self::Failed { email, .. } => format!("{} could not sync", email),
+        }
+    }
+}
+
+/// Manual syncs waiting to finish
+#[derive(Debug, Clone, Default)]
+pub struct SyncWatch {
+    /// Triggered accounts, and whether they have been seen running
+    manual: HashMap<String, bool>,
+}
+
+impl SyncWatch {
+    /// Watch a manually triggered sync until it finishes
+    pub fn watch(&mut self, email: String) {
+        self.manual.insert(email, false);
+    }
+
+    /// Events between two status polls
+    ///
+    /// A manual sync counts as finished once it has been seen running (or
+    /// its last sync time moved) and is idle again, so a poll that lands
+    /// before the sync starts doesn't report it done.
+    pub fn update(
+        &mut self,
+        previous: &[AccountSyncStatus],
+        current: &[AccountSyncStatus],
+    ) -> Vec<SyncEvent> {
+        let mut events = Vec::new();
+
+        for account in current {
+            let before = previous.iter().find(|a| a.email == account.email);
+            let was = before.map(|a| &a.status);
+            // A sync too quick to be caught running still moves last_sync_at
+            let synced = before.is_some_and(|a| a.last_sync_at != account.last_sync_at);
+
+            if account.status == SyncState::Error {
+                self.manual.remove(&account.email);
+                // Accounts already failing when first seen aren't news
+                if was.is_some_and(|s| *s != SyncState::Error) {
+                    events.push(SyncEvent::Failed {
+                        email: account.email.clone(),
+                        error: account.error.clone(),
+                    });
+                }
+                continue;
+            }
+
+            let Some(seen_running) = self.manual.get_mut(&account.email) else {
+                continue;
+            };
+            if account.status == SyncState::Running {
+                *seen_running = true;
+            } else if *seen_running || was == Some(&SyncState::Running) || synced {
+                self.manual.remove(&account.email);
+                events.push(SyncEvent::Finished {
+                    email: account.email.clone(),
+                    messages_synced: account.messages_synced,
+                });
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(email: &str, status: SyncState) -> AccountSyncStatus {
+        AccountSyncStatus {
+            email: email.to_string(),
+            display_name: None,
+            status,
+            last_sync_at: None,
+            next_sync_at: None,
+            messages_synced: Some(3),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_manual_sync_finishes_after_running() {
+        let mut watch = SyncWatch::default();
+        watch.watch("a@example.com".to_string());
+
+        let idle = vec![account("a@example.com", SyncState::Idle)];
+        let running = vec![account("a@example.com", SyncState::Running)];
+
+        // Not started yet
+        assert!(watch.update(&idle, &idle).is_empty());
+        assert!(watch.update(&idle, &running).is_empty());
+
+        let events = watch.update(&running, &idle);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].body(), "a@example.com: 3 messages synced");
+
+        // Reported once
+        assert!(watch.update(&idle, &idle).is_empty());
+
+        // A sync that finished between polls
+        watch.watch("a@example.com".to_string());
+        let mut synced = account("a@example.com", SyncState::Idle);
+        synced.last_sync_at = Some("2024-06-01T10:00:00Z".to_string());
+        assert_eq!(watch.update(&idle, &[synced]).len(), 1);
+    }
+
+    #[test]
+    fn test_error_transition_reported_once() {
+        let mut watch = SyncWatch::default();
+        let idle = vec![account("a@example.com", SyncState::Idle)];
+        let failed = vec![account("a@example.com", SyncState::Error)];
+
+        let events = watch.update(&idle, &failed);
+        assert_eq!(
+            events,
+            vec![SyncEvent::Failed {
+                email: "a@example.com".to_string(),
+                error: None,
+            }]
+        );
+        assert_eq!(events[0].title(), "Sync failed");
+        assert!(watch.update(&failed, &failed).is_empty());
+        assert!(watch.update(&[], &failed).is_empty());
+    }
+}
@@ -109,9 +109,11 @@ mod tests {
             bcc: vec![],
             sent_at: Utc::now(),
             body: format!("Body of message {}", id),
+            body_html: None,
             labels: vec![],
             attachments: vec![],
             thread_id: Some("thread123".to_string()),
+            headers: vec![],
         }
     }
 
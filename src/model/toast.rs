@@ -0,0 +1,62 @@
+//! Toast notifications
+//!
+//! Short-lived confirmation messages shown over the bottom of the window.
+
+use std::time::{Duration, Instant};
+
+/// How long a toast stays visible
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// A transient notification
+#[derive(Debug, Clone)]
+pub struct Toast {
+    /// Text to show
+    pub message: String,
+    /// Whether the toast reports a failure
+    pub is_error: bool,
+    /// When the toast appeared
+    pub shown_at: Instant,
+}
+
+impl Toast {
+    /// Create an informational toast
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            is_error: false,
+            shown_at: Instant::now(),
+        }
+    }
+
+    /// Create an error toast
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            is_error: true,
+            ..Self::info(message)
+        }
+    }
+
+    /// Check whether the toast should be dismissed
+    pub fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.shown_at) >= TOAST_DURATION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toast_expires_after_duration() {
+        let toast = Toast::info("Marked 3 messages as read");
+        assert!(!toast.is_expired(toast.shown_at));
+        assert!(toast.is_expired(toast.shown_at + TOAST_DURATION));
+    }
+
+    #[test]
+    fn test_error_toast() {
+        let toast = Toast::error("Failed");
+        assert!(toast.is_error);
+        assert_eq!(toast.message, "Failed");
+    }
+}
@@ -2,196 +2,471 @@
 //!
 //! "Foundry Dark" palette — warm neutrals with copper accent.
 //! Backgrounds have a slight amber cast; text has a cool cast for contrast.
+//! Every text tone meets WCAG AA (4.5:1) on the base and surface
+//! backgrounds; disabled text meets 3:1.
+//!
+//! "High Contrast" palette — near-black backgrounds, near-white text and
+//! opaque borders for low-vision users; text meets WCAG AAA (7:1).
+//!
+//! Tokens are functions rather than constants so the palette can be
+//! switched at runtime with `set_high_contrast`.
 
 use iced::Color;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// One palette's values for every color token
+struct Scheme {
+    bg_deep: Color,
+    bg_base: Color,
+    bg_surface: Color,
+    bg_elevated: Color,
+    bg_overlay: Color,
+    text_primary: Color,
+    text_secondary: Color,
+    text_muted: Color,
+    text_disabled: Color,
+    accent_primary: Color,
+    accent_hover: Color,
+    accent_pressed: Color,
+    accent_success: Color,
+    accent_warning: Color,
+    accent_error: Color,
+    accent_info: Color,
+    border_subtle: Color,
+    border_visible: Color,
+    border_focus: Color,
+    selection_bg: Color,
+    selection_strong: Color,
+    focus_ring: Color,
+}
+
+/// Default dark palette
+const FOUNDRY_DARK: Scheme = Scheme {
+    // Background Hierarchy
+    bg_deep: Color {
+        r: 0.102,
+        g: 0.094,
+        b: 0.086,
+        a: 1.0,
+    }, // #1a1816
+    bg_base: Color {
+        r: 0.129,
+        g: 0.122,
+        b: 0.110,
+        a: 1.0,
+    }, // #211f1c
+    bg_surface: Color {
+        r: 0.165,
+        g: 0.157,
+        b: 0.145,
+        a: 1.0,
+    }, // #2a2825
+    bg_elevated: Color {
+        r: 0.200,
+        g: 0.188,
+        b: 0.173,
+        a: 1.0,
+    }, // #33302c
+    bg_overlay: Color {
+        r: 0.239,
+        g: 0.224,
+        b: 0.208,
+        a: 1.0,
+    }, // #3d3935
+    // Text Hierarchy
+    text_primary: Color {
+        r: 0.910,
+        g: 0.894,
+        b: 0.875,
+        a: 1.0,
+    }, // #e8e4df
+    text_secondary: Color {
+        r: 0.659,
+        g: 0.635,
+        b: 0.620,
+        a: 1.0,
+    }, // #a8a29e
+    text_muted: Color {
+        r: 0.596,
+        g: 0.573,
+        b: 0.549,
+        a: 1.0,
+    }, // #98928c
+    text_disabled: Color {
+        r: 0.463,
+        g: 0.439,
+        b: 0.416,
+        a: 1.0,
+    }, // #76706a
+    // Accent: Copper
+    accent_primary: Color {
+        r: 0.831,
+        g: 0.584,
+        b: 0.416,
+        a: 1.0,
+    }, // #d4956a
+    accent_hover: Color {
+        r: 0.878,
+        g: 0.659,
+        b: 0.494,
+        a: 1.0,
+    }, // #e0a87e
+    accent_pressed: Color {
+        r: 0.722,
+        g: 0.490,
+        b: 0.333,
+        a: 1.0,
+    }, // #b87d55
+    // Semantic Colors
+    accent_success: Color {
+        r: 0.478,
+        g: 0.722,
+        b: 0.478,
+        a: 1.0,
+    }, // #7ab87a
+    accent_warning: Color {
+        r: 0.831,
+        g: 0.722,
+        b: 0.416,
+        a: 1.0,
+    }, // #d4b86a
+    accent_error: Color {
+        r: 0.780,
+        g: 0.361,
+        b: 0.361,
+        a: 1.0,
+    }, // #c75c5c
+    accent_info: Color {
+        r: 0.416,
+        g: 0.624,
+        b: 0.831,
+        a: 1.0,
+    }, // #6a9fd4
+    // Borders
+    border_subtle: Color {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 0.06,
+    },
+    border_visible: Color {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 0.12,
+    },
+    border_focus: Color {
+        r: 0.831,
+        g: 0.584,
+        b: 0.416,
+        a: 0.6,
+    },
+    // Selection
+    selection_bg: Color {
+        r: 0.831,
+        g: 0.584,
+        b: 0.416,
+        a: 0.12,
+    },
+    selection_strong: Color {
+        r: 0.831,
+        g: 0.584,
+        b: 0.416,
+        a: 0.2,
+    },
+    focus_ring: Color {
+        r: 0.831,
+        g: 0.584,
+        b: 0.416,
+        a: 0.5,
+    },
+};
+
+/// High-contrast palette
+const HIGH_CONTRAST: Scheme = Scheme {
+    // Background Hierarchy
+    bg_deep: Color {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 1.0,
+    }, // #000000
+    bg_base: Color {
+        r: 0.039,
+        g: 0.039,
+        b: 0.039,
+        a: 1.0,
+    }, // #0a0a0a
+    bg_surface: Color {
+        r: 0.078,
+        g: 0.078,
+        b: 0.078,
+        a: 1.0,
+    }, // #141414
+    bg_elevated: Color {
+        r: 0.122,
+        g: 0.122,
+        b: 0.122,
+        a: 1.0,
+    }, // #1f1f1f
+    bg_overlay: Color {
+        r: 0.161,
+        g: 0.161,
+        b: 0.161,
+        a: 1.0,
+    }, // #292929
+    // Text Hierarchy
+    text_primary: Color {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 1.0,
+    }, // #ffffff
+    text_secondary: Color {
+        r: 0.902,
+        g: 0.902,
+        b: 0.902,
+        a: 1.0,
+    }, // #e6e6e6
+    text_muted: Color {
+        r: 0.800,
+        g: 0.800,
+        b: 0.800,
+        a: 1.0,
+    }, // #cccccc
+    text_disabled: Color {
+        r: 0.651,
+        g: 0.651,
+        b: 0.651,
+        a: 1.0,
+    }, // #a6a6a6
+    // Accent: Copper
+    accent_primary: Color {
+        r: 0.941,
+        g: 0.678,
+        b: 0.486,
+        a: 1.0,
+    }, // #f0ad7c
+    accent_hover: Color {
+        r: 0.969,
+        g: 0.769,
+        b: 0.612,
+        a: 1.0,
+    }, // #f7c49c
+    accent_pressed: Color {
+        r: 0.851,
+        g: 0.569,
+        b: 0.369,
+        a: 1.0,
+    }, // #d9915e
+    // Semantic Colors
+    accent_success: Color {
+        r: 0.561,
+        g: 0.851,
+        b: 0.561,
+        a: 1.0,
+    }, // #8fd98f
+    accent_warning: Color {
+        r: 1.0,
+        g: 0.827,
+        b: 0.420,
+        a: 1.0,
+    }, // #ffd36b
+    accent_error: Color {
+        r: 1.0,
+        g: 0.478,
+        b: 0.478,
+        a: 1.0,
+    }, // #ff7a7a
+    accent_info: Color {
+        r: 0.498,
+        g: 0.722,
+        b: 1.0,
+        a: 1.0,
+    }, // #7fb8ff
+    // Borders
+    border_subtle: Color {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 0.3,
+    },
+    border_visible: Color {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 0.6,
+    },
+    border_focus: Color {
+        r: 0.941,
+        g: 0.678,
+        b: 0.486,
+        a: 1.0,
+    },
+    // Selection
+    selection_bg: Color {
+        r: 0.941,
+        g: 0.678,
+        b: 0.486,
+        a: 0.22,
+    },
+    selection_strong: Color {
+        r: 0.941,
+        g: 0.678,
+        b: 0.486,
+        a: 0.35,
+    },
+    focus_ring: Color {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 1.0,
+    },
+};
+
+/// Whether the high-contrast palette is active
+static HIGH_CONTRAST_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Switch between the Foundry Dark and High Contrast palettes
+pub fn set_high_contrast(enabled: bool) {
+    HIGH_CONTRAST_ACTIVE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the high-contrast palette is active
+pub fn is_high_contrast() -> bool {
+    HIGH_CONTRAST_ACTIVE.load(Ordering::Relaxed)
+}
+
+/// The active palette
+fn scheme() -> &'static Scheme {
+    if is_high_contrast() {
+        &HIGH_CONTRAST
+    } else {
+        &FOUNDRY_DARK
+    }
+}
 
-// === Background Hierarchy (warm undertone) ===
+// === Background Hierarchy ===
 
 /// Deep background — app chrome, window frame
-pub const BG_DEEP: Color = Color {
-    r: 0.102,
-    g: 0.094,
-    b: 0.086,
-    a: 1.0,
-}; // #1a1816
+pub fn bg_deep() -> Color {
+    scheme().bg_deep
+}
 
 /// Base background — primary application background
-pub const BG_BASE: Color = Color {
-    r: 0.129,
-    g: 0.122,
-    b: 0.110,
-    a: 1.0,
-}; // #211f1c
+pub fn bg_base() -> Color {
+    scheme().bg_base
+}
 
 /// Surface background — cards, panels, containers
-pub const BG_SURFACE: Color = Color {
-    r: 0.165,
-    g: 0.157,
-    b: 0.145,
-    a: 1.0,
-}; // #2a2825
+pub fn bg_surface() -> Color {
+    scheme().bg_surface
+}
 
 /// Elevated background — hover states, raised elements
-pub const BG_ELEVATED: Color = Color {
-    r: 0.200,
-    g: 0.188,
-    b: 0.173,
-    a: 1.0,
-}; // #33302c
+pub fn bg_elevated() -> Color {
+    scheme().bg_elevated
+}
 
 /// Overlay background — modals, dropdowns, tooltips
-pub const BG_OVERLAY: Color = Color {
-    r: 0.239,
-    g: 0.224,
-    b: 0.208,
-    a: 1.0,
-}; // #3d3935
+pub fn bg_overlay() -> Color {
+    scheme().bg_overlay
+}
 
-// === Text Hierarchy (cool neutral for contrast) ===
+// === Text Hierarchy ===
 
 /// Primary text — headings, important content
-pub const TEXT_PRIMARY: Color = Color {
-    r: 0.910,
-    g: 0.894,
-    b: 0.875,
-    a: 1.0,
-}; // #e8e4df
+pub fn text_primary() -> Color {
+    scheme().text_primary
+}
 
 /// Secondary text — body text, descriptions
-pub const TEXT_SECONDARY: Color = Color {
-    r: 0.659,
-    g: 0.635,
-    b: 0.620,
-    a: 1.0,
-}; // #a8a29e
+pub fn text_secondary() -> Color {
+    scheme().text_secondary
+}
 
 /// Muted text — timestamps, metadata, hints
-pub const TEXT_MUTED: Color = Color {
-    r: 0.420,
-    g: 0.396,
-    b: 0.376,
-    a: 1.0,
-}; // #6b6560
+pub fn text_muted() -> Color {
+    scheme().text_muted
+}
 
 /// Disabled text — inactive elements, placeholders
-pub const TEXT_DISABLED: Color = Color {
-    r: 0.290,
-    g: 0.271,
-    b: 0.255,
-    a: 1.0,
-}; // #4a4541
+pub fn text_disabled() -> Color {
+    scheme().text_disabled
+}
 
 // === Accent: Copper ===
 
 /// Primary accent — links, primary actions, brand color
-pub const ACCENT_PRIMARY: Color = Color {
-    r: 0.831,
-    g: 0.584,
-    b: 0.416,
-    a: 1.0,
-}; // #d4956a
+pub fn accent_primary() -> Color {
+    scheme().accent_primary
+}
 
 /// Accent hover state
-pub const ACCENT_HOVER: Color = Color {
-    r: 0.878,
-    g: 0.659,
-    b: 0.494,
-    a: 1.0,
-}; // #e0a87e
+pub fn accent_hover() -> Color {
+    scheme().accent_hover
+}
 
 /// Accent pressed state
-pub const ACCENT_PRESSED: Color = Color {
-    r: 0.722,
-    g: 0.490,
-    b: 0.333,
-    a: 1.0,
-}; // #b87d55
+pub fn accent_pressed() -> Color {
+    scheme().accent_pressed
+}
 
 // === Semantic Colors ===
 
 /// Success — confirmations, positive
-pub const ACCENT_SUCCESS: Color = Color {
-    r: 0.478,
-    g: 0.722,
-    b: 0.478,
-    a: 1.0,
-}; // #7ab87a
+pub fn accent_success() -> Color {
+    scheme().accent_success
+}
 
 /// Warning — caution, attention
-pub const ACCENT_WARNING: Color = Color {
-    r: 0.831,
-    g: 0.722,
-    b: 0.416,
-    a: 1.0,
-}; // #d4b86a
+pub fn accent_warning() -> Color {
+    scheme().accent_warning
+}
 
 /// Error — errors, destructive actions
-pub const ACCENT_ERROR: Color = Color {
-    r: 0.780,
-    g: 0.361,
-    b: 0.361,
-    a: 1.0,
-}; // #c75c5c
+pub fn accent_error() -> Color {
+    scheme().accent_error
+}
 
 /// Info — informational, threads
-pub const ACCENT_INFO: Color = Color {
-    r: 0.416,
-    g: 0.624,
-    b: 0.831,
-    a: 1.0,
-}; // #6a9fd4
+pub fn accent_info() -> Color {
+    scheme().accent_info
+}
 
 // === Borders ===
 
 /// Subtle border — dividers, separators
-pub const BORDER_SUBTLE: Color = Color {
-    r: 1.0,
-    g: 1.0,
-    b: 1.0,
-    a: 0.06,
-};
+pub fn border_subtle() -> Color {
+    scheme().border_subtle
+}
 
 /// Visible border — input fields, card outlines
-pub const BORDER_VISIBLE: Color = Color {
-    r: 1.0,
-    g: 1.0,
-    b: 1.0,
-    a: 0.12,
-};
+pub fn border_visible() -> Color {
+    scheme().border_visible
+}
 
 /// Focus border — keyboard focus indicator
-pub const BORDER_FOCUS: Color = Color {
-    r: 0.831,
-    g: 0.584,
-    b: 0.416,
-    a: 0.6,
-};
+pub fn border_focus() -> Color {
+    scheme().border_focus
+}
 
 // === Selection ===
 
 /// Selection background — highlighted items
-pub const SELECTION_BG: Color = Color {
-    r: 0.831,
-    g: 0.584,
-    b: 0.416,
-    a: 0.12,
-};
+pub fn selection_bg() -> Color {
+    scheme().selection_bg
+}
 
 /// Strong selection — active selection
-pub const SELECTION_STRONG: Color = Color {
-    r: 0.831,
-    g: 0.584,
-    b: 0.416,
-    a: 0.20,
-};
+pub fn selection_strong() -> Color {
+    scheme().selection_strong
+}
 
 /// Focus ring color
-pub const FOCUS_RING: Color = Color {
-    r: 0.831,
-    g: 0.584,
-    b: 0.416,
-    a: 0.5,
-};
+pub fn focus_ring() -> Color {
+    scheme().focus_ring
+}
 
 // === Transparent ===
 
@@ -229,3 +504,66 @@ pub fn darken(color: Color, factor: f32) -> Color {
 pub fn with_alpha(color: Color, alpha: f32) -> Color {
     Color { a: alpha, ..color }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// WCAG relative luminance of an opaque color
+    fn luminance(color: Color) -> f32 {
+        let channel = |c: f32| {
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b)
+    }
+
+    /// WCAG contrast ratio between two opaque colors
+    fn contrast_ratio(a: Color, b: Color) -> f32 {
+        let (la, lb) = (luminance(a), luminance(b));
+        (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+    }
+
+    /// Check every text tone against the base and surface backgrounds
+    fn assert_text_contrast(scheme: &Scheme, text_min: f32, disabled_min: f32) {
+        for bg in [scheme.bg_base, scheme.bg_surface] {
+            for text in [
+                scheme.text_primary,
+                scheme.text_secondary,
+                scheme.text_muted,
+            ] {
+                assert!(
+                    contrast_ratio(text, bg) >= text_min,
+                    "{:?} on {:?} is {:.2}:1",
+                    text,
+                    bg,
+                    contrast_ratio(text, bg)
+                );
+            }
+            assert!(contrast_ratio(scheme.text_disabled, bg) >= disabled_min);
+        }
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black = Color::BLACK;
+        let white = Color::WHITE;
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_foundry_dark_meets_aa() {
+        assert_text_contrast(&FOUNDRY_DARK, 4.5, 3.0);
+        assert!(contrast_ratio(FOUNDRY_DARK.accent_primary, FOUNDRY_DARK.bg_base) >= 4.5);
+    }
+
+    #[test]
+    fn test_high_contrast_meets_aaa() {
+        assert_text_contrast(&HIGH_CONTRAST, 7.0, 4.5);
+        assert!(contrast_ratio(HIGH_CONTRAST.accent_primary, HIGH_CONTRAST.bg_base) >= 7.0);
+    }
+}
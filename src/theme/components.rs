@@ -14,11 +14,11 @@ use super::spacing;
 /// Card style — elevated surface with subtle border and shadow
 pub fn card_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(colors::BG_SURFACE)),
+        background: Some(Background::Color(colors::bg_surface())),
         border: Border {
             radius: spacing::RADIUS_LG.into(),
             width: 1.0,
-            color: colors::BORDER_SUBTLE,
+            color: colors::border_subtle(),
         },
         shadow: Shadow {
             color: Color::from_rgba(0.0, 0.0, 0.0, 0.2),
@@ -32,11 +32,11 @@ pub fn card_style(_theme: &Theme) -> container::Style {
 /// Panel style — surface container without shadow
 pub fn panel_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(colors::BG_SURFACE)),
+        background: Some(Background::Color(colors::bg_surface())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             width: 1.0,
-            color: colors::BORDER_SUBTLE,
+            color: colors::border_subtle(),
         },
         ..Default::default()
     }
@@ -45,7 +45,7 @@ pub fn panel_style(_theme: &Theme) -> container::Style {
 /// Sidebar style — deep background for navigation
 pub fn sidebar_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(colors::BG_DEEP)),
+        background: Some(Background::Color(colors::bg_deep())),
         border: Border {
             radius: 0.0.into(),
             width: 0.0,
@@ -66,11 +66,11 @@ pub fn modal_backdrop_style(_theme: &Theme) -> container::Style {
 /// Modal dialog — elevated overlay container
 pub fn modal_dialog_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(colors::BG_OVERLAY)),
+        background: Some(Background::Color(colors::bg_overlay())),
         border: Border {
             radius: spacing::RADIUS_XL.into(),
             width: 1.0,
-            color: colors::BORDER_VISIBLE,
+            color: colors::border_visible(),
         },
         shadow: Shadow {
             color: Color::from_rgba(0.0, 0.0, 0.0, 0.4),
@@ -81,10 +81,24 @@ pub fn modal_dialog_style(_theme: &Theme) -> container::Style {
     }
 }
 
+/// Tooltip bubble — small overlay label
+pub fn tooltip_style(_theme: &Theme) -> container::Style {
+    container::Style {
+        background: Some(Background::Color(colors::bg_overlay())),
+        text_color: Some(colors::text_primary()),
+        border: Border {
+            radius: spacing::RADIUS_SM.into(),
+            width: 1.0,
+            color: colors::border_visible(),
+        },
+        ..Default::default()
+    }
+}
+
 /// Selected row background
 pub fn selected_row_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(colors::SELECTION_BG)),
+        background: Some(Background::Color(colors::selection_bg())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             ..Default::default()
@@ -96,7 +110,7 @@ pub fn selected_row_style(_theme: &Theme) -> container::Style {
 /// Hover row background
 pub fn hover_row_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(colors::BG_ELEVATED)),
+        background: Some(Background::Color(colors::bg_elevated())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             ..Default::default()
@@ -105,15 +119,27 @@ pub fn hover_row_style(_theme: &Theme) -> container::Style {
     }
 }
 
+/// Focus ring — copper outline around the keyboard-focused element
+pub fn focus_ring_style(_theme: &Theme) -> container::Style {
+    container::Style {
+        border: Border {
+            radius: spacing::RADIUS_MD.into(),
+            width: 2.0,
+            color: colors::focus_ring(),
+        },
+        ..Default::default()
+    }
+}
+
 // === Button Styles ===
 
 /// Primary button — copper accent, main action
 pub fn button_primary(_theme: &Theme, status: button::Status) -> button::Style {
     let bg_color = match status {
-        button::Status::Hovered => colors::ACCENT_HOVER,
-        button::Status::Pressed => colors::ACCENT_PRESSED,
-        button::Status::Disabled => colors::with_alpha(colors::ACCENT_PRIMARY, 0.5),
-        _ => colors::ACCENT_PRIMARY,
+        button::Status::Hovered => colors::accent_hover(),
+        button::Status::Pressed => colors::accent_pressed(),
+        button::Status::Disabled => colors::with_alpha(colors::accent_primary(), 0.5),
+        _ => colors::accent_primary(),
     };
 
     button::Style {
@@ -130,19 +156,19 @@ pub fn button_primary(_theme: &Theme, status: button::Status) -> button::Style {
 /// Secondary button — less prominent action
 pub fn button_secondary(_theme: &Theme, status: button::Status) -> button::Style {
     let bg_color = match status {
-        button::Status::Hovered => colors::BG_ELEVATED,
-        button::Status::Pressed => colors::BG_OVERLAY,
-        button::Status::Disabled => colors::with_alpha(colors::BG_SURFACE, 0.5),
-        _ => colors::BG_SURFACE,
+        button::Status::Hovered => colors::bg_elevated(),
+        button::Status::Pressed => colors::bg_overlay(),
+        button::Status::Disabled => colors::with_alpha(colors::bg_surface(), 0.5),
+        _ => colors::bg_surface(),
     };
 
     button::Style {
         background: Some(Background::Color(bg_color)),
-        text_color: colors::TEXT_PRIMARY,
+        text_color: colors::text_primary(),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             width: 1.0,
-            color: colors::BORDER_VISIBLE,
+            color: colors::border_visible(),
         },
         ..Default::default()
     }
@@ -151,14 +177,14 @@ pub fn button_secondary(_theme: &Theme, status: button::Status) -> button::Style
 /// Ghost button — minimal visual weight
 pub fn button_ghost(_theme: &Theme, status: button::Status) -> button::Style {
     let bg_color = match status {
-        button::Status::Hovered => colors::BG_ELEVATED,
-        button::Status::Pressed => colors::BG_OVERLAY,
+        button::Status::Hovered => colors::bg_elevated(),
+        button::Status::Pressed => colors::bg_overlay(),
         _ => Color::TRANSPARENT,
     };
 
     button::Style {
         background: Some(Background::Color(bg_color)),
-        text_color: colors::TEXT_SECONDARY,
+        text_color: colors::text_secondary(),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             ..Default::default()
@@ -170,10 +196,10 @@ pub fn button_ghost(_theme: &Theme, status: button::Status) -> button::Style {
 /// Danger button — destructive action
 pub fn button_danger(_theme: &Theme, status: button::Status) -> button::Style {
     let bg_color = match status {
-        button::Status::Hovered => colors::lighten(colors::ACCENT_ERROR, 0.1),
-        button::Status::Pressed => colors::darken(colors::ACCENT_ERROR, 0.1),
-        button::Status::Disabled => colors::with_alpha(colors::ACCENT_ERROR, 0.5),
-        _ => colors::ACCENT_ERROR,
+        button::Status::Hovered => colors::lighten(colors::accent_error(), 0.1),
+        button::Status::Pressed => colors::darken(colors::accent_error(), 0.1),
+        button::Status::Disabled => colors::with_alpha(colors::accent_error(), 0.5),
+        _ => colors::accent_error(),
     };
 
     button::Style {
@@ -190,14 +216,14 @@ pub fn button_danger(_theme: &Theme, status: button::Status) -> button::Style {
 /// Icon button — circular, minimal
 pub fn button_icon(_theme: &Theme, status: button::Status) -> button::Style {
     let bg_color = match status {
-        button::Status::Hovered => colors::BG_ELEVATED,
-        button::Status::Pressed => colors::BG_OVERLAY,
+        button::Status::Hovered => colors::bg_elevated(),
+        button::Status::Pressed => colors::bg_overlay(),
         _ => Color::TRANSPARENT,
     };
 
     button::Style {
         background: Some(Background::Color(bg_color)),
-        text_color: colors::TEXT_SECONDARY,
+        text_color: colors::text_secondary(),
         border: Border {
             radius: spacing::RADIUS_FULL.into(),
             ..Default::default()
@@ -211,23 +237,30 @@ pub fn button_icon(_theme: &Theme, status: button::Status) -> button::Style {
 /// Default text input style
 pub fn text_input_style(_theme: &Theme, status: text_input::Status) -> text_input::Style {
     let (border_color, bg_color) = match status {
-        text_input::Status::Focused => (colors::ACCENT_PRIMARY, colors::BG_ELEVATED),
-        text_input::Status::Hovered => (colors::BORDER_VISIBLE, colors::BG_ELEVATED),
-        text_input::Status::Disabled => (colors::BORDER_SUBTLE, colors::BG_DEEP),
-        _ => (colors::BORDER_VISIBLE, colors::BG_SURFACE),
+        text_input::Status::Focused => (colors::accent_primary(), colors::bg_elevated()),
+        text_input::Status::Hovered => (colors::border_visible(), colors::bg_elevated()),
+        text_input::Status::Disabled => (colors::border_subtle(), colors::bg_deep()),
+        _ => (colors::border_visible(), colors::bg_surface()),
+    };
+
+    // The focused field gets a thicker outline, matching the focus ring
+    let border_width = if matches!(status, text_input::Status::Focused) {
+        2.0
+    } else {
+        1.0
     };
 
     text_input::Style {
         background: Background::Color(bg_color),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
-            width: 1.0,
+            width: border_width,
             color: border_color,
         },
-        icon: colors::TEXT_MUTED,
-        placeholder: colors::TEXT_DISABLED,
-        value: colors::TEXT_PRIMARY,
-        selection: colors::SELECTION_BG,
+        icon: colors::text_muted(),
+        placeholder: colors::text_disabled(),
+        value: colors::text_primary(),
+        selection: colors::selection_bg(),
     }
 }
 
@@ -236,48 +269,55 @@ pub fn text_input_style(_theme: &Theme, status: text_input::Status) -> text_inpu
 /// Primary text style
 pub fn text_primary(_theme: &Theme) -> text::Style {
     text::Style {
-        color: Some(colors::TEXT_PRIMARY),
+        color: Some(colors::text_primary()),
     }
 }
 
 /// Secondary text style
 pub fn text_secondary(_theme: &Theme) -> text::Style {
     text::Style {
-        color: Some(colors::TEXT_SECONDARY),
+        color: Some(colors::text_secondary()),
     }
 }
 
 /// Muted text style
 pub fn text_muted(_theme: &Theme) -> text::Style {
     text::Style {
-        color: Some(colors::TEXT_MUTED),
+        color: Some(colors::text_muted()),
     }
 }
 
 /// Accent text style (copper)
 pub fn text_accent(_theme: &Theme) -> text::Style {
     text::Style {
-        color: Some(colors::ACCENT_PRIMARY),
+        color: Some(colors::accent_primary()),
     }
 }
 
 /// Success text style
 pub fn text_success(_theme: &Theme) -> text::Style {
     text::Style {
-        color: Some(colors::ACCENT_SUCCESS),
+        color: Some(colors::accent_success()),
+    }
+}
+
+/// Warning text style
+pub fn text_warning(_theme: &Theme) -> text::Style {
+    text::Style {
+        color: Some(colors::accent_warning()),
     }
 }
 
 /// Error text style
 pub fn text_error(_theme: &Theme) -> text::Style {
     text::Style {
-        color: Some(colors::ACCENT_ERROR),
+        color: Some(colors::accent_error()),
     }
 }
 
 /// Info text style
 pub fn text_info(_theme: &Theme) -> text::Style {
     text::Style {
-        color: Some(colors::ACCENT_INFO),
+        color: Some(colors::accent_info()),
     }
 }
@@ -18,6 +18,12 @@ pub const FORWARD: &str = "↪";
 pub const DELETE: &str = "✕";
 pub const DOWNLOAD: &str = "↓";
 pub const OPEN: &str = "↗";
+pub const LABEL: &str = "⚑";
+pub const BLOCK: &str = "⊘";
+pub const PIN: &str = "✦";
+pub const QUOTE: &str = "❝";
+pub const COPY: &str = "⎘";
+pub const MUTE: &str = "⊖";
 
 // === State ===
 pub const CHECK: &str = "✓";
@@ -35,6 +41,10 @@ pub const ARROW_DOWN: &str = "↓";
 pub const EXPAND: &str = "▸";
 pub const COLLAPSE: &str = "▾";
 pub const EXPAND_ALL: &str = "▸▸";
+pub const SIDEBAR_COLLAPSE: &str = "«";
+pub const SIDEBAR_EXPAND: &str = "»";
+pub const POP_OUT: &str = "⧉";
+pub const DOCK: &str = "⊡";
 
 // === Indicators ===
 pub const DOT_FILLED: &str = "●";
@@ -43,26 +53,144 @@ pub const DIAMOND: &str = "◆";
 pub const DIAMOND_SM: &str = "◇";
 
 // === File Types ===
-pub const FILE_PDF: &str = "PDF";
-pub const FILE_DOC: &str = "DOC";
-pub const FILE_XLS: &str = "XLS";
-pub const FILE_IMG: &str = "IMG";
-pub const FILE_ZIP: &str = "ZIP";
-pub const FILE_AUDIO: &str = "AUD";
-pub const FILE_VIDEO: &str = "VID";
-pub const FILE_GENERIC: &str = "FILE";
+// Letter codes rather than pictographs: the bundled IBM Plex fonts have no
+// document symbols, and anything outside their coverage renders as tofu.
 
-/// Get file type icon from filename extension
-pub fn file_icon(filename: &str) -> &'static str {
-    let ext = filename.rsplit('.').next().unwrap_or("").to_lowercase();
-    match ext.as_str() {
-        "pdf" => FILE_PDF,
-        "doc" | "docx" | "odt" | "rtf" => FILE_DOC,
-        "xls" | "xlsx" | "csv" | "ods" => FILE_XLS,
-        "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" | "bmp" => FILE_IMG,
-        "zip" | "tar" | "gz" | "rar" | "7z" => FILE_ZIP,
-        "mp3" | "wav" | "m4a" | "flac" | "ogg" => FILE_AUDIO,
-        "mp4" | "mov" | "avi" | "mkv" | "webm" => FILE_VIDEO,
-        _ => FILE_GENERIC,
+/// Broad file type of an attachment, used to pick its icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Pdf,
+    Document,
+    Spreadsheet,
+    Presentation,
+    Image,
+    Archive,
+    Audio,
+    Video,
+    Text,
+    Generic,
+}
+
+impl FileKind {
+    /// Classify a file by its extension
+    pub fn from_filename(filename: &str) -> Self {
+        let ext = match filename.rsplit_once('.') {
+            Some((_, ext)) => ext.to_lowercase(),
+            None => return FileKind::Generic,
+        };
+        match ext.as_str() {
+            "pdf" => FileKind::Pdf,
+            "doc" | "docx" | "odt" | "rtf" | "pages" => FileKind::Document,
+            "xls" | "xlsx" | "csv" | "ods" | "numbers" => FileKind::Spreadsheet,
+            "ppt" | "pptx" | "odp" | "key" => FileKind::Presentation,
+            "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" | "bmp" | "heic" | "tif" | "tiff" => {
+                FileKind::Image
+            }
+            "zip" | "tar" | "gz" | "tgz" | "rar" | "7z" | "bz2" | "xz" => FileKind::Archive,
+            "mp3" | "wav" | "m4a" | "flac" | "ogg" | "aac" => FileKind::Audio,
+            "mp4" | "mov" | "avi" | "mkv" | "webm" => FileKind::Video,
+            "txt" | "md" | "log" | "eml" | "ics" | "vcf" | "json" | "xml" | "html" | "htm" => {
+                FileKind::Text
+            }
+            _ => FileKind::Generic,
+        }
+    }
+
+    /// Classify a file by its MIME type, if the type says anything specific
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        let mime = mime_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        let (top, sub) = mime.split_once('/')?;
+        let kind = match (top, sub) {
+            ("application", "pdf") => FileKind::Pdf,
+            ("image", _) => FileKind::Image,
+            ("audio", _) => FileKind::Audio,
+            ("video", _) => FileKind::Video,
+            ("text", "csv") | ("application", "vnd.ms-excel") => FileKind::Spreadsheet,
+            ("application", s) if s.contains("spreadsheet") => FileKind::Spreadsheet,
+            ("application", "vnd.ms-powerpoint") => FileKind::Presentation,
+            ("application", s) if s.contains("presentation") => FileKind::Presentation,
+            ("application", "msword" | "rtf") => FileKind::Document,
+            ("application", s)
+                if s.contains("wordprocessing") || s.ends_with("opendocument.text") =>
+            {
+                FileKind::Document
+            }
+            (
+                "application",
+                "zip" | "gzip" | "x-tar" | "x-gzip" | "x-7z-compressed" | "x-rar-compressed"
+                | "vnd.rar" | "x-bzip2" | "x-xz",
+            ) => FileKind::Archive,
+            ("text", _) | ("message", "rfc822") | ("application", "json" | "xml") => FileKind::Text,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Classify an attachment by its MIME type, falling back to the
+    /// extension when the type is missing or generic
+    pub fn detect(filename: &str, mime_type: Option<&str>) -> Self {
+        mime_type
+            .and_then(Self::from_mime_type)
+            .unwrap_or_else(|| Self::from_filename(filename))
+    }
+
+    /// Short code shown inside the file icon
+    pub fn code(self) -> &'static str {
+        match self {
+            FileKind::Pdf => "PDF",
+            FileKind::Document => "DOC",
+            FileKind::Spreadsheet => "XLS",
+            FileKind::Presentation => "PPT",
+            FileKind::Image => "IMG",
+            FileKind::Archive => "ZIP",
+            FileKind::Audio => "AUD",
+            FileKind::Video => "VID",
+            FileKind::Text => "TXT",
+            FileKind::Generic => "FILE",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_kind_from_filename() {
+        assert_eq!(FileKind::from_filename("Report.PDF"), FileKind::Pdf);
+        assert_eq!(FileKind::from_filename("photo.jpeg"), FileKind::Image);
+        assert_eq!(FileKind::from_filename("backup.tar.gz"), FileKind::Archive);
+        assert_eq!(FileKind::from_filename("README"), FileKind::Generic);
+        assert_eq!(FileKind::from_filename("invite.ics").code(), "TXT");
+    }
+
+    #[test]
+    fn test_file_kind_prefers_mime_type() {
+        assert_eq!(
+            FileKind::detect("scan", Some("application/pdf")),
+            FileKind::Pdf
+        );
+        assert_eq!(
+            FileKind::detect(
+                "budget.bin",
+                Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+            ),
+            FileKind::Spreadsheet
+        );
+        assert_eq!(
+            FileKind::detect("notes.txt", Some("TEXT/PLAIN; charset=utf-8")),
+            FileKind::Text
+        );
+        // Generic types say nothing, so the extension decides
+        assert_eq!(
+            FileKind::detect("photo.png", Some("application/octet-stream")),
+            FileKind::Image
+        );
+        assert_eq!(FileKind::detect("photo.png", None), FileKind::Image);
     }
 }
@@ -9,7 +9,8 @@ pub mod icons;
 pub mod spacing;
 pub mod typography;
 
-pub use colors::*;
+// Color tokens stay under `colors::`; their names overlap the text style
+// functions in `components`.
 pub use components::*;
 pub use icons::*;
 pub use spacing::*;
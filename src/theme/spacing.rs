@@ -56,9 +56,12 @@ pub const RADIUS_FULL: f32 = 999.0;
 
 // === Layout Dimensions ===
 
-/// Sidebar width
+/// Default sidebar width
 pub const SIDEBAR_WIDTH: f32 = 240.0;
 
+/// Default message list width beside the detail pane
+pub const MESSAGE_LIST_WIDTH: f32 = 420.0;
+
 /// Message list panel width (as fill portion)
 pub const MESSAGE_LIST_PORTION: u16 = 2;
 
@@ -3,26 +3,69 @@
 //! The Update in the MVU pattern.
 //! Processes Messages and returns Commands for async operations.
 
-use crate::api::types::{DeviceFlowState, SortDirection, SortField};
-use crate::api::ApiClient;
-use crate::config::{discover_server, Settings};
+use crate::api::types::{
+    DateRange, DeviceFlowState, MessageDetail, SortDirection, SortField, ViewType,
+};
+use crate::api::{is_insecure_url, ApiClient, SearchQuery};
+use crate::config::{
+    diagnose_discovery, discover_server, DefaultView, SavedSearch, Session, SessionView, Settings,
+};
+use crate::error::AppError;
 use crate::message::Message;
-use crate::model::{AppState, ConnectionStatus, LoadingState, SettingsTab, ViewLevel, WizardStep};
+use crate::model::account_import::{parse_account_list, ImportStatus};
+use crate::model::deletion::deletion_confirmed;
+use crate::model::downloads::{local_filename, parse_extensions, should_auto_open};
+use crate::model::sync_watch::SyncEvent;
+use crate::model::{
+    contains_address, labels::apply_label_change, quote_message, sender_address, AccountImport,
+    Action, AggregateQuery, AppState, ComposeMode, ConnectionStatus, Connectivity, ContextMenu,
+    ContextTarget, DeletionProgress, FocusArea, LabelPicker, ListWindow, LoadingState, Modal,
+    SettingsTab, Toast, ViewLevel, WizardStep, MORE_FROM_SENDER, SEARCH_PAGE_SIZE,
+};
+use crate::view::aggregates::{AGGREGATES_SCROLL, AGGREGATE_ROW_PITCH};
+use iced::futures::SinkExt;
 use iced::keyboard::{Key, Modifiers};
+use iced::widget::scrollable;
+use iced::window;
 use iced::Task;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Number of rows PageUp/PageDown move the selection by (roughly one viewport)
+const PAGE_JUMP_ROWS: usize = 10;
+
+/// How long type-ahead waits for the next keystroke before clearing
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Aggregate query listing the labels offered by the label picker
+const LABEL_LIST_QUERY: AggregateQuery = (ViewType::Labels, SortField::Name, SortDirection::Asc);
 
 /// Process a message and update state
 ///
 /// Returns a Task that may spawn async work (like API calls).
 pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
+    // Offline mode is read-only
+    if state.is_offline() && is_write_action(&message) {
+        state.toast = Some(Toast::error("Unavailable while offline"));
+        return Task::none();
+    }
+
     match message {
         // === Discovery ===
         Message::StartDiscovery => {
             state.discovering = true;
             state.wizard_step = WizardStep::Discovering;
-
-            Task::perform(
-                async { discover_server().await },
+            // Scanning again starts from a clean step list, and from the
+            // connection screen it brings the wizard back to show progress
+            state.first_run = true;
+            state.discovery_steps.clear();
+            state.discovery_result = None;
+            state.discovery_diagnostics = None;
+
+            let ports = state.discovery_ports.clone();
+            perform_tracked(
+                state,
+                async move { discover_server(ports).await },
                 Message::DiscoveryComplete,
             )
         }
@@ -54,12 +97,7 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             state.first_run = false;
 
             // Save settings and connect
-            let settings = Settings {
-                server_url: state.server_url.clone(),
-                api_key: state.api_key.clone(),
-                allow_insecure: true,
-            };
-            let _ = settings.save();
+            let _ = current_settings(state).save();
 
             // Now connect to the server
             Task::done(Message::CheckHealth)
@@ -70,6 +108,46 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             Task::none()
         }
 
+        Message::RunDiscoveryDiagnostics => {
+            state.discovering = true;
+            state.discovery_diagnostics = None;
+
+            let ports = state.discovery_ports.clone();
+            perform_tracked(
+                state,
+                async move { diagnose_discovery(ports).await },
+                Message::DiscoveryDiagnosticsComplete,
+            )
+        }
+
+        Message::WizardPortChanged(port) => {
+            state.wizard_port = port;
+            Task::none()
+        }
+
+        Message::DiscoverOnPort => {
+            let port = match state.wizard_port.trim().parse::<u16>() {
+                Ok(port) if port != 0 => port,
+                _ => {
+                    state.toast = Some(Toast::error("Enter a port number between 1 and 65535"));
+                    return Task::none();
+                }
+            };
+
+            // Remembered so later discovery runs probe it too
+            state.discovery_ports.retain(|p| *p != port);
+            state.discovery_ports.insert(0, port);
+            let _ = current_settings(state).save();
+            state.wizard_port.clear();
+            handle(state, Message::StartDiscovery)
+        }
+
+        Message::DiscoveryDiagnosticsComplete(steps) => {
+            state.discovering = false;
+            state.discovery_diagnostics = Some(steps);
+            Task::none()
+        }
+
         Message::WizardServerUrlChanged(url) => {
             state.server_url = url;
             Task::none()
@@ -89,12 +167,7 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             state.first_run = false;
 
             // Save settings
-            let settings = Settings {
-                server_url: state.server_url.clone(),
-                api_key: state.api_key.clone(),
-                allow_insecure: true,
-            };
-            let _ = settings.save();
+            let _ = current_settings(state).save();
 
             // Connect to the server
             Task::done(Message::CheckHealth)
@@ -102,35 +175,183 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
 
         // === Connection ===
         Message::CheckHealth => {
-            state.connection_status = ConnectionStatus::Connecting;
+            // While offline or switching servers this is a reconnect attempt;
+            // keep showing loaded data. The launch check keeps its splash.
+            if !state.is_offline()
+                && state.previous_connection.is_none()
+                && state.connection_status != ConnectionStatus::Starting
+            {
+                state.connection_status = ConnectionStatus::Connecting;
+            }
+            state.reconnect_in = None;
 
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
-            } else {
-                Some(state.api_key.clone())
-            };
+            if !state.allow_insecure && is_insecure_url(&state.server_url) {
+                return Task::done(Message::HealthChecked(Err(AppError::InsecureBlocked)));
+            }
 
-            Task::perform(
+            let client = api_client(state);
+
+            perform_tracked(
+                state,
                 async move {
-                    let client = ApiClient::new(url, api_key);
                     client.health().await
                 },
                 Message::HealthChecked,
             )
         }
 
+        Message::DismissServerVersionNotice => {
+            state.server_version_notice_dismissed = true;
+            Task::none()
+        }
+
         Message::HealthChecked(result) => match result {
-            Ok(_health) => {
+            Ok(health) => {
+                state.outdated_server_version = health
+                    .is_outdated()
+                    .then(|| health.version.clone())
+                    .flatten();
+                state.server_web_ui = health.web_ui;
+                if certificate_changed(state, health.certificate_sha256) {
+                    return handle(
+                        state,
+                        Message::HealthChecked(Err(AppError::CertificateChanged)),
+                    );
+                }
+                state.certificate_mismatch = None;
+                state.health_failures = 0;
+
+                if let Some(previous) = state.previous_connection.take() {
+                    state.toast = Some(Toast::info(format!("Connected to {}", state.server_url)));
+                    if previous.server_url != state.server_url {
+                        // Views loaded from the old server no longer apply
+                        state.navigation.reset();
+                        state.stats_cache.clear();
+                        state.aggregate_cache.clear();
+                    }
+                }
+                if is_insecure_url(&state.server_url) {
+                    state.toast = Some(Toast::error(
+                        "Connected over unencrypted HTTP; your API key and mail are readable on the network",
+                    ));
+                } else if state.allow_insecure && state.server_url.starts_with("https://") {
+                    state.toast = Some(Toast::error(
+                        "Certificate validation is off; this connection could be intercepted",
+                    ));
+                }
+
+                if state.is_offline() {
+                    // Back online: re-enable writes and refresh the sidebar
+                    state.connectivity = Connectivity::Online;
+                    state.toast = Some(Toast::info("Reconnected to server"));
+                    return Task::batch([
+                        Task::done(Message::FetchStats),
+                        Task::done(Message::FetchSyncStatus),
+                    ]);
+                }
+
                 state.connection_status = ConnectionStatus::Connected;
+
+                // Restore the last session or open the configured landing view,
+                // unless the user has already navigated
+                let landing = if state.navigation.can_go_back() {
+                    Task::none()
+                } else if let Some(session) = state.pending_session.take() {
+                    restore_session(state, session)
+                } else {
+                    landing_view(state)
+                };
+
                 // Fetch both stats AND sync status for sidebar accounts
                 Task::batch([
                     Task::done(Message::FetchStats),
                     Task::done(Message::FetchSyncStatus),
+                    landing,
                 ])
             }
             Err(e) => {
-                state.connection_status = ConnectionStatus::Failed(e.to_string());
+                // New connection settings don't work; go back to the previous
+                // server, its TLS policy and pin, on disk too. Other changes
+                // saved with them are kept.
+                if let Some(previous) = state.previous_connection.take() {
+                    state.toast = Some(Toast::error(format!(
+                        "Could not connect to {}: {}. Still using {}",
+                        state.server_url, e, previous.server_url
+                    )));
+                    state.server_url = previous.server_url;
+                    state.api_key = previous.api_key;
+                    state.allow_insecure = previous.allow_insecure;
+                    state.pin_certificate = previous.pin_certificate;
+                    state.pinned_certificate = previous.pinned_certificate;
+                    state.certificate_mismatch = None;
+                    let _ = current_settings(state).save();
+                    return Task::none();
+                }
+
+                if !state.is_offline() {
+                    // Retry an unreachable server on the backoff schedule;
+                    // other failures need the user to change something
+                    if matches!(
+                        e,
+                        AppError::ConnectionFailed(_) | AppError::RequestFailed(_)
+                    ) {
+                        state.reconnect_in = Some(state.reconnect_delay().as_secs());
+                        state.health_failures = state.health_failures.saturating_add(1);
+                    }
+                    state.connection_status = ConnectionStatus::Failed(e.to_string());
+                }
+                Task::none()
+            }
+        },
+
+        Message::ReconnectTick => match state.reconnect_in {
+            Some(seconds) if seconds > 1 => {
+                state.reconnect_in = Some(seconds - 1);
+                Task::none()
+            }
+            Some(_) => handle(state, Message::CheckHealth),
+            None => Task::none(),
+        },
+
+        Message::EditConnectionSettings => {
+            state.reconnect_in = None;
+            iced::widget::text_input::focus(iced::widget::text_input::Id::new(
+                crate::view::SERVER_URL_INPUT,
+            ))
+        }
+
+        Message::HealthTick => {
+            let client = api_client(state);
+            Task::perform(async move { client.health().await }, Message::HealthPinged)
+        }
+
+        Message::HealthPinged(result) => match result {
+            Ok(health) => {
+                // A certificate swapped mid-session is refused like on connect
+                if certificate_changed(state, health.certificate_sha256.clone()) {
+                    return handle(
+                        state,
+                        Message::HealthChecked(Err(AppError::CertificateChanged)),
+                    );
+                }
+                state.health_failures = 0;
+                if state.is_offline() {
+                    // Server is back: reconnect as a manual retry would
+                    return handle(state, Message::HealthChecked(Ok(health)));
+                }
+                Task::none()
+            }
+            Err(e) => {
+                state.health_failures = state.health_failures.saturating_add(1);
+                // Only an unreachable server counts as offline, not an error reply
+                if matches!(
+                    e,
+                    AppError::ConnectionFailed(_) | AppError::RequestFailed(_)
+                ) && !state.is_offline()
+                {
+                    state.connectivity = Connectivity::Offline;
+                    state.toast = Some(Toast::error("Lost connection to server"));
+                }
                 Task::none()
             }
         },
@@ -139,30 +360,28 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
         Message::FetchStats => {
             state.loading = LoadingState::Loading;
 
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
-            } else {
-                Some(state.api_key.clone())
-            };
+            let client = api_client(state);
+            let etag = state.stats_cache.etag(&());
 
-            Task::perform(
-                async move {
-                    let client = ApiClient::new(url, api_key);
-                    client.stats().await
-                },
+            perform_tracked(
+                state,
+                async move { client.stats(etag.as_deref()).await },
                 Message::StatsLoaded,
             )
         }
 
         Message::StatsLoaded(result) => {
             match result {
-                Ok(stats) => {
+                Ok(fetched) => {
+                    let Some(stats) = state.stats_cache.resolve((), fetched) else {
+                        // Cleared while the request was out; fetch in full
+                        return Task::done(Message::FetchStats);
+                    };
                     state.stats = Some(stats);
                     state.loading = LoadingState::Idle;
                 }
                 Err(e) => {
-                    state.loading = LoadingState::Error(e.to_string());
+                    load_failed(state, e);
                 }
             }
             Task::none()
@@ -172,40 +391,62 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
         Message::FetchAggregates(view_type) => {
             state.loading = LoadingState::Loading;
             state.selected_index = 0;
+            state.aggregate_filter.clear();
+            state.aggregate_window.offset = 0.0;
 
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
-            } else {
-                Some(state.api_key.clone())
-            };
-            let sort_field = state.sort_field;
-            let sort_dir = state.sort_dir;
+            let client = api_client(state);
+            let query = (view_type, state.sort_field, state.sort_dir);
+            let etag = state.aggregate_cache.etag(&query);
 
-            Task::perform(
+            perform_tracked(
+                state,
                 async move {
-                    let client = ApiClient::new(url, api_key);
-                    client.aggregates(view_type, sort_field, sort_dir).await
+                    let (view_type, sort_field, sort_dir) = query;
+                    client
+                        .aggregates(view_type, sort_field, sort_dir, etag.as_deref())
+                        .await
                 },
-                Message::AggregatesLoaded,
+                move |result| Message::AggregatesLoaded(query, result),
             )
         }
 
-        Message::AggregatesLoaded(result) => {
+        Message::AggregatesLoaded(query, result) => {
             match result {
-                Ok(response) => {
+                Ok(fetched) => {
+                    let Some(response) = state.aggregate_cache.resolve(query, fetched) else {
+                        return Task::done(Message::FetchAggregates(query.0));
+                    };
                     state.aggregates = response.rows;
+                    state.hide_blocked();
                     state.loading = LoadingState::Idle;
                 }
                 Err(e) => {
-                    state.loading = LoadingState::Error(e.to_string());
+                    load_failed(state, e);
                 }
             }
             Task::none()
         }
 
+        Message::AggregatesScrolled(viewport) => {
+            state.aggregate_window = ListWindow {
+                offset: viewport.absolute_offset().y,
+                height: viewport.bounds().height,
+            };
+            Task::none()
+        }
+
+        Message::AggregateFilterChanged(filter) => {
+            state.aggregate_filter = filter;
+            state.selected_index = 0;
+            state.aggregate_window.offset = 0.0;
+            scrollable::scroll_to(
+                scrollable::Id::new(AGGREGATES_SCROLL),
+                scrollable::AbsoluteOffset::default(),
+            )
+        }
+
         Message::SelectAggregate(index) => {
-            if index < state.aggregates.len() {
+            if index < state.filtered_aggregates().len() {
                 state.selected_index = index;
             }
             Task::none()
@@ -215,21 +456,92 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             if state.selected_index > 0 {
                 state.selected_index -= 1;
             }
-            Task::none()
+            reveal_selected_aggregate(state)
         }
 
         Message::SelectNext => {
-            if state.selected_index + 1 < state.aggregates.len() {
+            if state.selected_index + 1 < state.filtered_aggregates().len() {
                 state.selected_index += 1;
             }
+            reveal_selected_aggregate(state)
+        }
+
+        Message::SelectFirst => {
+            move_list_cursor(state, |_, _| 0);
+            reveal_selected_aggregate(state)
+        }
+
+        Message::SelectLast => {
+            move_list_cursor(state, |_, len| len.saturating_sub(1));
+            reveal_selected_aggregate(state)
+        }
+
+        Message::PageSelectUp => {
+            move_list_cursor(state, |index, _| index.saturating_sub(PAGE_JUMP_ROWS));
+            reveal_selected_aggregate(state)
+        }
+
+        Message::PageSelectDown => {
+            move_list_cursor(state, |index, _| index + PAGE_JUMP_ROWS);
+            reveal_selected_aggregate(state)
+        }
+
+        Message::StartTypeahead => {
+            state.typeahead_buffer = Some(String::new());
+            state.typeahead_last_input = Some(Instant::now());
+            Task::none()
+        }
+
+        Message::TypeaheadInput(input) => {
+            if let Some(buffer) = state.typeahead_buffer.as_mut() {
+                buffer.push_str(&input);
+                state.typeahead_last_input = Some(Instant::now());
+                jump_to_typeahead_match(state);
+            }
+            reveal_selected_aggregate(state)
+        }
+
+        Message::TypeaheadBackspace => {
+            if let Some(buffer) = state.typeahead_buffer.as_mut() {
+                buffer.pop();
+                state.typeahead_last_input = Some(Instant::now());
+                jump_to_typeahead_match(state);
+            }
+            reveal_selected_aggregate(state)
+        }
+
+        Message::CancelTypeahead => {
+            state.typeahead_buffer = None;
+            state.typeahead_last_input = None;
+            Task::none()
+        }
+
+        Message::TypeaheadTick => {
+            let idle = state
+                .typeahead_last_input
+                .is_none_or(|last| last.elapsed() >= TYPEAHEAD_TIMEOUT);
+            if idle {
+                state.typeahead_buffer = None;
+                state.typeahead_last_input = None;
+            }
             Task::none()
         }
 
         Message::DrillDown => {
-            if let Some(agg) = state.aggregates.get(state.selected_index) {
+            if let Some(agg) = state
+                .filtered_aggregates()
+                .get(state.selected_index)
+                .copied()
+            {
                 if let ViewLevel::Aggregates { view_type } = state.navigation.current().clone() {
-                    // Navigate to messages filtered by this aggregate
-                    let filter_desc = format!("{}: {}", view_type.display_name(), &agg.key);
+                    // Navigate to messages filtered by this aggregate; Time buckets
+                    // are described by the date range they cover
+                    let filter_desc = match DateRange::from_time_bucket(&agg.key) {
+                        Some(range) if view_type == ViewType::Time => {
+                            format!("{}: {}", view_type.display_name(), range.describe())
+                        }
+                        _ => format!("{}: {}", view_type.display_name(), &agg.key),
+                    };
                     let filter_type = view_type.as_str().to_string();
                     let filter_value = agg.key.clone();
 
@@ -247,6 +559,24 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             Task::none()
         }
 
+        Message::DrillDownRow(index) => {
+            if index < state.filtered_aggregates().len() {
+                state.selected_index = index;
+                return Task::done(Message::DrillDown);
+            }
+            Task::none()
+        }
+
+        Message::ToggleTimeLayout => {
+            state.time_heatmap = !state.time_heatmap;
+            // The heatmap shows every bucket, so the filter doesn't apply
+            if state.time_heatmap && !state.aggregate_filter.is_empty() {
+                state.aggregate_filter.clear();
+                state.selected_index = 0;
+            }
+            Task::none()
+        }
+
         Message::ToggleSortField => {
             state.sort_field = match state.sort_field {
                 SortField::Name => SortField::Count,
@@ -283,20 +613,22 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             state.filter_type = filter_type.clone();
             state.filter_value = filter_value.clone();
 
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
-            } else {
-                Some(state.api_key.clone())
-            };
+            let client = api_client(state);
             let offset = state.messages_offset;
             let limit = state.messages_limit;
 
-            Task::perform(
+            // Time buckets filter by date range rather than a literal value
+            let date_range = if filter_type == ViewType::Time.as_str() {
+                DateRange::from_time_bucket(&filter_value)
+            } else {
+                None
+            };
+
+            perform_tracked(
+                state,
                 async move {
-                    let client = ApiClient::new(url, api_key);
                     client
-                        .messages_filter(&filter_type, &filter_value, offset, limit)
+                        .messages_filter(&filter_type, &filter_value, date_range, offset, limit)
                         .await
                 },
                 Message::MessagesLoaded,
@@ -308,13 +640,15 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
                 Ok(response) => {
                     state.messages = response.messages;
                     state.messages_total = response.total;
+                    state.hide_blocked();
+                    state.selection_anchor = None;
                     state.loading = LoadingState::Idle;
                 }
                 Err(e) => {
-                    state.loading = LoadingState::Error(e.to_string());
+                    load_failed(state, e);
                 }
             }
-            Task::none()
+            fetch_list_avatars(state)
         }
 
         Message::SelectMessage(index) => {
@@ -324,447 +658,899 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             Task::none()
         }
 
-        Message::OpenMessage => {
-            if let Some(msg) = state.messages.get(state.message_selected_index) {
-                let message_id = msg.id;
-                state.loading = LoadingState::Loading;
-
-                // Navigate to detail view
-                state.navigation.push(ViewLevel::MessageDetail { message_id });
-
-                let url = state.server_url.clone();
-                let api_key = if state.api_key.is_empty() {
-                    None
-                } else {
-                    Some(state.api_key.clone())
-                };
-
-                return Task::perform(
-                    async move {
-                        let client = ApiClient::new(url, api_key);
-                        client.message_detail(message_id).await
-                    },
-                    Message::MessageDetailLoaded,
-                );
-            }
-            Task::none()
-        }
+        Message::OpenMessage => match state.messages.get(state.message_selected_index) {
+            Some(msg) => open_message_detail(state, msg.id),
+            None => Task::none(),
+        },
 
         Message::MessageDetailLoaded(result) => {
             match result {
                 Ok(detail) => {
+                    let sender = sender_address(&detail.from_addr).to_string();
+                    state.message_cache.insert(detail.clone());
                     state.current_message = Some(detail);
+                    state.show_full_body = false;
                     state.loading = LoadingState::Idle;
+                    return Task::batch([
+                        fetch_sender_messages(state, &sender),
+                        fetch_avatars(state, [sender]),
+                    ]);
                 }
                 Err(e) => {
-                    state.loading = LoadingState::Error(e.to_string());
+                    load_failed(state, e);
                 }
             }
             Task::none()
         }
 
-        Message::NextPage => {
-            let new_offset = state.messages_offset + state.messages_limit;
-            if new_offset < state.messages_total {
-                state.messages_offset = new_offset;
-                return Task::done(Message::FetchMessages {
-                    filter_type: state.filter_type.clone(),
-                    filter_value: state.filter_value.clone(),
-                });
-            }
+        Message::OpenMessageById(message_id) => open_message_detail(state, message_id),
+
+        Message::PrefetchTick => {
+            let Some(message_id) = state.message_cache.due(Instant::now()) else {
+                return Task::none();
+            };
+            // Runs quietly in the background, without the activity spinner
+            let client = api_client(state);
+            Task::perform(
+                async move { client.message_detail(message_id).await },
+                move |result| Message::DetailPrefetched(message_id, result),
+            )
+        }
+
+        Message::DetailPrefetched(message_id, result) => {
+            // Only the cache is filled; whatever is on screen stays put
+            state.message_cache.prefetched(message_id, result.ok());
             Task::none()
         }
 
-        Message::PreviousPage => {
-            if state.messages_offset > 0 {
-                state.messages_offset =
-                    (state.messages_offset - state.messages_limit).max(0);
-                return Task::done(Message::FetchMessages {
-                    filter_type: state.filter_type.clone(),
-                    filter_value: state.filter_value.clone(),
-                });
+        Message::SenderMessagesLoaded(sender, result) => {
+            // A failed lookup just leaves the panel hidden
+            if let Ok(response) = result {
+                state.sender_messages.insert(sender, response.messages);
             }
             Task::none()
         }
 
-        Message::PreviousMessage => {
-            if state.message_selected_index > 0 {
-                state.message_selected_index -= 1;
-                return Task::done(Message::OpenMessage);
-            }
+        Message::ShowFullBody => {
+            state.show_full_body = true;
             Task::none()
         }
 
-        Message::NextMessage => {
-            if state.message_selected_index + 1 < state.messages.len() {
-                state.message_selected_index += 1;
-                return Task::done(Message::OpenMessage);
+        Message::ToggleQuotedText(message_id) => {
+            if !state.expanded_quotes.remove(&message_id) {
+                state.expanded_quotes.insert(message_id);
             }
             Task::none()
         }
 
-        // === Threading ===
-        Message::ViewThread(thread_id) => {
-            state.thread.is_loading = true;
-            state.thread.clear();
-            state.navigation.push(ViewLevel::Thread {
-                thread_id: thread_id.clone(),
-            });
-
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
-            } else {
-                Some(state.api_key.clone())
-            };
-
-            Task::perform(
-                async move {
-                    let client = ApiClient::new(url, api_key);
-                    client.thread_messages(&thread_id).await
-                },
-                Message::ThreadLoaded,
-            )
+        Message::LoadRemoteContent(message_id) => {
+            state.remote_content_loaded.insert(message_id);
+            Task::none()
         }
 
-        Message::ThreadLoaded(result) => {
-            state.thread.is_loading = false;
-            match result {
-                Ok(messages) => {
-                    if let ViewLevel::Thread { thread_id } = state.navigation.current().clone() {
-                        state.thread.load_messages(thread_id, messages);
-                    }
-                }
-                Err(e) => {
-                    state.loading = LoadingState::Error(e.to_string());
-                }
+        Message::AllowRemoteContent(sender) => {
+            if !contains_address(&state.remote_content_senders, &sender) {
+                state.remote_content_senders.push(sender);
+                let _ = current_settings(state).save();
             }
             Task::none()
         }
 
-        Message::ToggleThreadMessage(index) => {
-            state.thread.toggle_expanded(index);
+        Message::DisallowRemoteContent(sender) => {
+            state
+                .remote_content_senders
+                .retain(|s| !s.eq_ignore_ascii_case(&sender));
+            let _ = current_settings(state).save();
             Task::none()
         }
 
-        Message::ExpandAllThread => {
-            state.thread.expand_all();
+        // === Blocked Senders ===
+        Message::BlockSender(sender) => {
+            let sender = sender_address(&sender).to_string();
+            if sender.is_empty() || contains_address(&state.blocked_senders, &sender) {
+                return Task::none();
+            }
+            state.blocked_senders.push(sender.clone());
+            state.block_sender_input.clear();
+            state.hide_blocked();
+            let _ = current_settings(state).save();
+            state.toast = Some(Toast::info(format!("Blocked {}", sender)));
             Task::none()
         }
 
-        Message::CollapseAllThread => {
-            state.thread.collapse_all();
+        Message::UnblockSender(sender) => {
+            state
+                .blocked_senders
+                .retain(|s| !s.eq_ignore_ascii_case(&sender));
+            let _ = current_settings(state).save();
+            state.toast = Some(Toast::info(format!("Unblocked {}", sender)));
             Task::none()
         }
 
-        Message::ThreadFocusPrevious => {
-            state.thread.focus_previous();
+        Message::MuteSender(sender) => {
+            let sender = sender_address(&sender).to_string();
+            if sender.is_empty() || contains_address(&state.muted_senders, &sender) {
+                return Task::none();
+            }
+            state.muted_senders.push(sender.clone());
+            let _ = current_settings(state).save();
+            state.toast = Some(Toast::info(format!("Muted {}", sender)));
             Task::none()
         }
 
-        Message::ThreadFocusNext => {
-            state.thread.focus_next();
+        Message::UnmuteSender(sender) => {
+            state
+                .muted_senders
+                .retain(|s| !s.eq_ignore_ascii_case(&sender));
+            let _ = current_settings(state).save();
+            state.toast = Some(Toast::info(format!("Unmuted {}", sender)));
             Task::none()
         }
 
-        // === Search ===
-        Message::OpenSearch => {
-            state.navigation.push(ViewLevel::Search);
-            state.search_query.clear();
-            state.search_results.clear();
-            state.search_selected_index = 0;
-            state.search_total = 0;
+        Message::MuteThread(thread_id) => {
+            if !state.is_thread_muted(&thread_id) {
+                state.muted_threads.push(thread_id);
+                let _ = current_settings(state).save();
+                state.toast = Some(Toast::info("Muted thread"));
+            }
             Task::none()
         }
 
-        Message::SearchQueryChanged(query) => {
-            state.search_query = query;
-            // Execute search if query is not empty
-            if !state.search_query.is_empty() {
-                return Task::done(Message::ExecuteSearch);
-            } else {
-                state.search_results.clear();
-                state.search_total = 0;
-            }
+        Message::UnmuteThread(thread_id) => {
+            state.muted_threads.retain(|t| *t != thread_id);
+            let _ = current_settings(state).save();
+            state.toast = Some(Toast::info("Unmuted thread"));
             Task::none()
         }
 
-        Message::ExecuteSearch => {
-            if state.search_query.is_empty() {
+        Message::PinFilter(filter) => {
+            if filter.value.is_empty() || state.is_pinned(&filter) {
                 return Task::none();
             }
+            state.toast = Some(Toast::info(format!("Pinned {}", filter.value)));
+            state.pinned_filters.push(filter);
+            let _ = current_settings(state).save();
+            Task::none()
+        }
 
-            state.is_searching = true;
-            let query = state.search_query.clone();
-            let is_deep = state.search_deep_mode;
+        Message::UnpinFilter(filter) => {
+            state.pinned_filters.retain(|p| !p.same_as(&filter));
+            let _ = current_settings(state).save();
+            Task::none()
+        }
 
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
+        Message::MovePinnedFilter { index, up } => {
+            let target = if up {
+                index.checked_sub(1)
             } else {
-                Some(state.api_key.clone())
+                Some(index + 1)
             };
+            if let Some(target) = target.filter(|t| *t < state.pinned_filters.len()) {
+                state.pinned_filters.swap(index, target);
+                let _ = current_settings(state).save();
+            }
+            Task::none()
+        }
 
-            Task::perform(
+        Message::OpenPinnedFilter(index) => {
+            let Some(filter) = state.pinned_filters.get(index).cloned() else {
+                return Task::none();
+            };
+            state.messages_offset = 0;
+            state.navigation.push(ViewLevel::Messages {
+                filter_description: filter.description(),
+            });
+
+            Task::done(Message::FetchMessages {
+                filter_type: filter.view_type.as_str().to_string(),
+                filter_value: filter.value,
+            })
+        }
+
+        Message::BlockSenderInputChanged(input) => {
+            state.block_sender_input = input;
+            Task::none()
+        }
+
+        Message::BlockedSenderDisplayChanged(display) => {
+            state.blocked_sender_display = display;
+            state.hide_blocked();
+            let _ = current_settings(state).save();
+            Task::none()
+        }
+
+        Message::ExportMessagePdf(message_id) => {
+            let Some(detail) = state.loaded_message(message_id) else {
+                return Task::none();
+            };
+
+            let bytes = crate::export::message_pdf(detail);
+            let file_name = crate::export::pdf_file_name(detail);
+            Task::perform(
                 async move {
-                    let client = ApiClient::new(url, api_key);
-                    if is_deep {
-                        client.search_deep(&query, 0, 50).await
-                    } else {
-                        client.search_fast(&query, 50).await
-                    }
+                    let Some(handle) = rfd::AsyncFileDialog::new()
+                        .set_file_name(file_name)
+                        .add_filter("PDF", &["pdf"])
+                        .save_file()
+                        .await
+                    else {
+                        return Ok(None);
+                    };
+                    tokio::fs::write(handle.path(), bytes)
+                        .await
+                        .map(|_| Some(handle.path().to_path_buf()))
+                        .map_err(|e| e.to_string())
                 },
-                Message::SearchLoaded,
+                Message::PdfExported,
             )
         }
 
-        Message::SearchLoaded(result) => {
-            state.is_searching = false;
+        Message::PdfExported(result) => {
             match result {
-                Ok(response) => {
-                    state.search_results = response.messages;
-                    state.search_total = response.total;
-                    state.search_selected_index = 0;
+                Ok(Some(path)) => {
+                    state.toast = Some(Toast::info(format!("Saved {}", path.display())));
                 }
+                Ok(None) => {}
                 Err(e) => {
-                    state.loading = LoadingState::Error(e.to_string());
+                    state.toast = Some(Toast::error(format!("Failed to export PDF: {}", e)));
                 }
             }
             Task::none()
         }
 
-        Message::ToggleSearchMode => {
-            state.search_deep_mode = !state.search_deep_mode;
-            // Re-execute search with new mode if query exists
-            if !state.search_query.is_empty() {
-                return Task::done(Message::ExecuteSearch);
-            }
+        Message::AvatarLoaded(email, result) => {
+            // Errors fall back to initials, same as a missing avatar
+            state.avatars.set(&email, result.ok().flatten());
             Task::none()
         }
 
-        Message::SelectSearchResult(index) => {
-            if index < state.search_results.len() {
-                state.search_selected_index = index;
+        Message::SessionMessageLoaded(session, result) => {
+            match result {
+                Ok(detail) => {
+                    // Rebuild the list the message was opened from, then show it
+                    let fetch_list = if session.filter_type.is_empty() {
+                        Task::none()
+                    } else {
+                        state.messages_offset = session.messages_offset;
+                        state.navigation.push(ViewLevel::Messages {
+                            filter_description: session.filter_description,
+                        });
+                        Task::done(Message::FetchMessages {
+                            filter_type: session.filter_type,
+                            filter_value: session.filter_value,
+                        })
+                    };
+                    state.navigation.push(ViewLevel::MessageDetail {
+                        message_id: detail.id,
+                    });
+                    state.current_message = Some(detail);
+                    state.show_full_body = false;
+                    fetch_list
+                }
+                // The message is gone; fall back to the default view
+                Err(_) => landing_view(state),
             }
-            Task::none()
         }
 
-        Message::OpenSearchResult => {
-            if let Some(msg) = state.search_results.get(state.search_selected_index) {
-                let message_id = msg.id;
-                state.loading = LoadingState::Loading;
-                state.navigation.push(ViewLevel::MessageDetail { message_id });
-
-                let url = state.server_url.clone();
-                let api_key = if state.api_key.is_empty() {
-                    None
-                } else {
-                    Some(state.api_key.clone())
-                };
+        Message::NextPage => {
+            let new_offset = state.messages_offset + state.messages_limit;
+            if new_offset < state.messages_total {
+                state.messages_offset = new_offset;
+                return Task::done(Message::FetchMessages {
+                    filter_type: state.filter_type.clone(),
+                    filter_value: state.filter_value.clone(),
+                });
+            }
+            Task::none()
+        }
 
-                return Task::perform(
-                    async move {
-                        let client = ApiClient::new(url, api_key);
-                        client.message_detail(message_id).await
-                    },
-                    Message::MessageDetailLoaded,
-                );
+        Message::PreviousPage => {
+            if state.messages_offset > 0 {
+                state.messages_offset =
+                    (state.messages_offset - state.messages_limit).max(0);
+                return Task::done(Message::FetchMessages {
+                    filter_type: state.filter_type.clone(),
+                    filter_value: state.filter_value.clone(),
+                });
             }
             Task::none()
         }
 
-        // === Sync ===
-        Message::OpenSync => {
-            state.navigation.push(ViewLevel::Sync);
-            // Immediately fetch sync status
-            Task::done(Message::FetchSyncStatus)
+        Message::PreviousMessage => {
+            if state.message_selected_index > 0 {
+                state.message_selected_index -= 1;
+                return Task::done(Message::OpenMessage);
+            }
+            Task::none()
         }
 
-        Message::FetchSyncStatus => {
-            state.sync_loading = true;
+        Message::NextMessage => {
+            if state.message_selected_index + 1 < state.messages.len() {
+                state.message_selected_index += 1;
+                return Task::done(Message::OpenMessage);
+            }
+            Task::none()
+        }
 
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
-            } else {
-                Some(state.api_key.clone())
+        Message::MarkAllRead => {
+            let list = match state.navigation.current() {
+                ViewLevel::Search => &mut state.search_results,
+                _ => &mut state.messages,
             };
 
-            Task::perform(
-                async move {
-                    let client = ApiClient::new(url, api_key);
-                    client.scheduler_status().await
-                },
-                Message::SyncStatusLoaded,
-            )
+            let ids: Vec<i64> = list.iter().filter(|m| m.is_unread).map(|m| m.id).collect();
+
+            if ids.is_empty() {
+                state.toast = Some(Toast::info("All messages are already read"));
+                return Task::none();
+            }
+
+            update_read_state(state, ids, true)
         }
 
-        Message::SyncStatusLoaded(result) => {
-            state.sync_loading = false;
+        Message::SetReadState { message_id, read } => {
+            update_read_state(state, vec![message_id], read)
+        }
+
+        Message::ReadStateUpdated {
+            message_ids,
+            read,
+            result,
+        } => {
+            let state_label = if read { "read" } else { "unread" };
             match result {
-                Ok(status) => {
-                    state.sync_accounts = status.accounts;
+                Ok(response) => {
+                    state.toast = Some(Toast::info(format!(
+                        "Marked {} message{} as {}",
+                        response.updated,
+                        if response.updated == 1 { "" } else { "s" },
+                        state_label
+                    )));
                 }
                 Err(e) => {
-                    state.loading = LoadingState::Error(e.to_string());
+                    // Revert the optimistic update
+                    for msg in state
+                        .messages
+                        .iter_mut()
+                        .chain(state.search_results.iter_mut())
+                        .filter(|m| message_ids.contains(&m.id))
+                    {
+                        msg.is_unread = read;
+                    }
+                    state.toast = Some(Toast::error(format!(
+                        "Failed to mark as {}: {}",
+                        state_label, e
+                    )));
                 }
             }
             Task::none()
         }
 
-        Message::TriggerSync(email) => {
-            state.syncing_account = Some(email.clone());
+        Message::DeleteOne(message_id) => {
+            state.selected_messages.clear();
+            state.selected_messages.insert(message_id);
+            Task::done(Message::ShowDeleteModal)
+        }
 
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
-            } else {
-                Some(state.api_key.clone())
-            };
+        // === Threading ===
+        Message::ViewThread(thread_id) => {
+            state.thread.is_loading = true;
+            state.thread.clear();
+            state.navigation.push(ViewLevel::Thread {
+                thread_id: thread_id.clone(),
+            });
 
-            Task::perform(
+            let client = api_client(state);
+
+            perform_tracked(
+                state,
                 async move {
-                    let client = ApiClient::new(url, api_key);
-                    client.trigger_sync(&email).await
+                    client.thread_messages(&thread_id).await
                 },
-                Message::SyncTriggered,
+                Message::ThreadLoaded,
             )
         }
 
-        Message::SyncTriggered(result) => {
-            state.syncing_account = None;
+        Message::ThreadLoaded(result) => {
+            state.thread.is_loading = false;
             match result {
-                Ok(_) => {
-                    // Refresh status after triggering
-                    return Task::done(Message::FetchSyncStatus);
+                Ok(messages) => {
+                    if let ViewLevel::Thread { thread_id } = state.navigation.current().clone() {
+                        state.thread.load_messages(thread_id, messages);
+                    }
                 }
                 Err(e) => {
-                    state.loading = LoadingState::Error(e.to_string());
+                    load_failed(state, e);
                 }
             }
             Task::none()
         }
 
-        Message::RefreshSyncStatus => {
-            // Only refresh if we're on the sync view
-            if matches!(state.navigation.current(), ViewLevel::Sync) {
-                return Task::done(Message::FetchSyncStatus);
-            }
+        Message::ToggleThreadMessage(index) => {
+            state.thread.toggle_expanded(index);
             Task::none()
         }
 
-        Message::SyncTick => {
-            // Periodic sync poll: fetch latest sync status to keep sidebar accounts updated
-            Task::done(Message::FetchSyncStatus)
+        Message::ExpandAllThread => {
+            state.thread.expand_all();
+            Task::none()
         }
 
-        // === Account Management ===
-        Message::OpenAccounts => {
-            state.navigation.push(ViewLevel::Accounts);
-            // Reset add account state
-            state.add_account_email.clear();
-            state.adding_account = false;
-            state.oauth_response = None;
-            // Fetch current account list (using scheduler status)
-            Task::done(Message::FetchSyncStatus)
+        Message::CollapseAllThread => {
+            state.thread.collapse_all();
+            Task::none()
         }
 
-        Message::AddAccountEmailChanged(email) => {
-            state.add_account_email = email;
+        Message::ThreadFocusPrevious => {
+            state.thread.focus_previous();
             Task::none()
         }
 
-        Message::StartAddAccount => {
-            if state.add_account_email.is_empty() {
+        Message::ThreadFocusNext => {
+            state.thread.focus_next();
+            Task::none()
+        }
+
+        // === Search ===
+        Message::OpenSearch => {
+            state.navigation.push(ViewLevel::Search);
+            state.search_query.clear();
+            state.search_results.clear();
+            state.search_selected_index = 0;
+            state.search_total = 0;
+            state.search_offset = 0;
+            Task::none()
+        }
+
+        Message::SearchQueryChanged(query) => {
+            state.search_query = query;
+            state.search_offset = 0;
+            // Execute search if query is not empty
+            if !state.search_query.is_empty() {
+                return Task::done(Message::ExecuteSearch);
+            } else {
+                state.search_results.clear();
+                state.search_total = 0;
+            }
+            Task::none()
+        }
+
+        Message::ExecuteSearch => {
+            if state.search_query.is_empty() {
                 return Task::none();
             }
 
-            state.adding_account = true;
-            let email = state.add_account_email.clone();
+            state.is_searching = true;
+            let query = state.search_query.clone();
+            let is_deep = state.search_deep_mode;
+            let offset = state.search_offset;
 
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
-            } else {
-                Some(state.api_key.clone())
-            };
+            let client = api_client(state);
 
-            Task::perform(
+            perform_tracked(
+                state,
                 async move {
-                    let client = ApiClient::new(url, api_key);
-                    client.initiate_oauth(&email).await
+                    if is_deep {
+                        client
+                            .search_deep(&SearchQuery::parse(&query), offset, SEARCH_PAGE_SIZE)
+                            .await
+                    } else {
+                        client.search_fast(&query, SEARCH_PAGE_SIZE).await
+                    }
                 },
-                Message::OAuthInitiated,
+                Message::SearchLoaded,
             )
         }
 
-        Message::OAuthInitiated(result) => {
+        Message::SearchLoaded(result) => {
+            state.is_searching = false;
             match result {
                 Ok(response) => {
-                    state.oauth_response = Some(response.clone());
-                    if response.device_flow {
-                        // Start polling for device flow completion
-                        state.polling_device_flow = true;
-                        // Note: In a real app, we'd set up a timer subscription
-                        // For now, manual polling via PollDeviceFlow message
-                    } else {
-                        // Open browser for OAuth
-                        return Task::done(Message::OpenOAuthBrowser(response.auth_url));
-                    }
+                    state.search_results = response.messages;
+                    state.search_total = response.total;
+                    state.search_selected_index = 0;
+                    state.hide_blocked();
+                    state.selection_anchor = None;
                 }
                 Err(e) => {
-                    state.adding_account = false;
-                    state.loading = LoadingState::Error(e.to_string());
+                    load_failed(state, e);
                 }
             }
             Task::none()
         }
 
-        Message::OpenOAuthBrowser(url) => {
-            // Open URL in default browser
-            #[cfg(target_os = "macos")]
-            {
-                let _ = std::process::Command::new("open").arg(&url).spawn();
-            }
-            #[cfg(target_os = "windows")]
-            {
-                let _ = std::process::Command::new("cmd")
-                    .args(["/C", "start", "", &url])
-                    .spawn();
+        Message::SaveCurrentSearch => {
+            let query = state.search_query.trim().to_string();
+            if query.is_empty() {
+                return Task::none();
             }
-            #[cfg(target_os = "linux")]
+
+            let deep = state.search_deep_mode;
+            if state
+                .saved_searches
+                .iter()
+                .any(|s| s.query == query && s.deep == deep)
             {
-                let _ = std::process::Command::new("xdg-open").arg(&url).spawn();
+                state.toast = Some(Toast::info("Search already saved"));
+                return Task::none();
             }
+
+            state.saved_searches.push(SavedSearch {
+                name: query.clone(),
+                query,
+                deep,
+            });
+            state.toast = Some(match SavedSearch::save_all(&state.saved_searches) {
+                Ok(()) => Toast::info("Search saved to sidebar"),
+                Err(e) => Toast::error(e),
+            });
             Task::none()
         }
 
-        Message::PollDeviceFlow => {
-            if !state.polling_device_flow {
+        Message::OpenSavedSearch(index) => {
+            let Some(saved) = state.saved_searches.get(index) else {
                 return Task::none();
-            }
-
-            let email = state.add_account_email.clone();
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
-            } else {
-                Some(state.api_key.clone())
             };
+            state.search_deep_mode = saved.deep;
+            let query = saved.query.clone();
 
-            Task::perform(
-                async move {
-                    let client = ApiClient::new(url, api_key);
-                    client.check_device_flow(&email).await
-                },
-                Message::DeviceFlowStatusReceived,
-            )
+            Task::done(Message::OpenSearch).chain(Task::done(Message::SearchQueryChanged(query)))
         }
 
-        Message::DeviceFlowStatusReceived(result) => {
-            match result {
+        Message::DeleteSavedSearch(index) => {
+            if index < state.saved_searches.len() {
+                state.saved_searches.remove(index);
+                if let Err(e) = SavedSearch::save_all(&state.saved_searches) {
+                    state.toast = Some(Toast::error(e));
+                }
+            }
+            Task::none()
+        }
+
+        Message::NextSearchPage => {
+            let new_offset = state.search_offset + SEARCH_PAGE_SIZE;
+            if state.search_deep_mode && new_offset < state.search_total {
+                state.search_offset = new_offset;
+                return Task::done(Message::ExecuteSearch);
+            }
+            Task::none()
+        }
+
+        Message::PreviousSearchPage => {
+            if state.search_deep_mode && state.search_offset > 0 {
+                state.search_offset = (state.search_offset - SEARCH_PAGE_SIZE).max(0);
+                return Task::done(Message::ExecuteSearch);
+            }
+            Task::none()
+        }
+
+        Message::ToggleSearchTips => {
+            state.show_search_tips = !state.show_search_tips;
+            Task::none()
+        }
+
+        Message::ToggleSearchMode => {
+            state.search_deep_mode = !state.search_deep_mode;
+            state.search_offset = 0;
+            // Re-execute search with new mode if query exists
+            if !state.search_query.is_empty() {
+                return Task::done(Message::ExecuteSearch);
+            }
+            Task::none()
+        }
+
+        Message::SelectSearchResult(index) => {
+            if index < state.search_results.len() {
+                state.search_selected_index = index;
+            }
+            Task::none()
+        }
+
+        Message::OpenSearchResult => match state.search_results.get(state.search_selected_index) {
+            Some(msg) => open_message_detail(state, msg.id),
+            None => Task::none(),
+        },
+
+        // === Sync ===
+        Message::OpenSync => {
+            state.navigation.push(ViewLevel::Sync);
+            // Immediately fetch sync status
+            Task::done(Message::FetchSyncStatus)
+        }
+
+        Message::FetchSyncStatus => {
+            state.sync_loading = true;
+
+            let client = api_client(state);
+
+            perform_tracked(
+                state,
+                async move {
+                    client.scheduler_status().await
+                },
+                Message::SyncStatusLoaded,
+            )
+        }
+
+        Message::SyncStatusLoaded(result) => {
+            state.sync_loading = false;
+            match result {
+                Ok(status) => {
+                    let events = state
+                        .sync_watch
+                        .update(&state.sync_accounts, &status.accounts);
+                    state.sync_accounts = status.accounts;
+                    if state.desktop_notifications {
+                        return Task::batch(events.iter().map(sync_notification));
+                    }
+                }
+                Err(e) => {
+                    load_failed(state, e);
+                }
+            }
+            Task::none()
+        }
+
+        Message::SyncNotificationClicked => {
+            let focus = match state.main_window {
+                Some(id) => window::gain_focus(id),
+                None => Task::none(),
+            };
+            Task::batch([focus, handle(state, Message::OpenSync)])
+        }
+
+        Message::TriggerSync(email) => {
+            state.syncing_account = Some(email.clone());
+
+            let client = api_client(state);
+
+            perform_tracked(
+                state,
+                async move {
+                    client.trigger_sync(&email).await
+                },
+                Message::SyncTriggered,
+            )
+        }
+
+        Message::SyncTriggered(result) => {
+            let email = state.syncing_account.take();
+            match result {
+                Ok(_) => {
+                    if let Some(email) = email {
+                        state.sync_watch.watch(email);
+                    }
+                    // Refresh status after triggering
+                    return Task::done(Message::FetchSyncStatus);
+                }
+                Err(e) => {
+                    load_failed(state, e);
+                }
+            }
+            Task::none()
+        }
+
+        Message::SetSyncPaused { email, paused } => {
+            let client = api_client(state);
+
+            perform_tracked(
+                state,
+                async move { client.set_sync_paused(&email, paused).await },
+                Message::SyncPausedSet,
+            )
+        }
+
+        Message::SyncPausedSet(result) => match result {
+            Ok(response) => {
+                state.toast = Some(Toast::info(response.message));
+                Task::done(Message::FetchSyncStatus)
+            }
+            // Older servers lack the endpoint; hide the menu item
+            Err(AppError::ApiError { status: 404, .. }) => {
+                state.sync_pause_supported = false;
+                state.toast = Some(Toast::error("This server can't pause sync"));
+                Task::none()
+            }
+            Err(e) => {
+                state.toast = Some(Toast::error(format!("Failed to update sync: {}", e)));
+                Task::none()
+            }
+        },
+
+        Message::RefreshSyncStatus => {
+            // Only refresh if we're on the sync view
+            if matches!(state.navigation.current(), ViewLevel::Sync) {
+                return Task::done(Message::FetchSyncStatus);
+            }
+            Task::none()
+        }
+
+        Message::SyncTick => {
+            if state.is_offline() {
+                // The health check subscription retries the server
+                return Task::none();
+            }
+            // Periodic sync poll: fetch latest sync status to keep sidebar accounts updated
+            Task::batch([
+                Task::done(Message::FetchSyncStatus),
+                Task::done(Message::CheckNewMessages),
+            ])
+        }
+
+        Message::CheckNewMessages => {
+            if !state.new_messages_supported {
+                return Task::none();
+            }
+            // The first check only sets the starting point, so a fresh
+            // install doesn't announce the whole archive
+            let Some(since) = state.new_messages_checked_at else {
+                state.new_messages_checked_at = Some(chrono::Utc::now());
+                let _ = current_settings(state).save();
+                return Task::none();
+            };
+
+            // Runs quietly in the background, without the activity spinner
+            let client = api_client(state);
+            Task::perform(
+                async move { client.messages_since(since).await },
+                Message::NewMessagesChecked,
+            )
+        }
+
+        Message::NewMessagesChecked(result) => {
+            let mut notification = Task::none();
+            match result {
+                Ok(response) => {
+                    let response = response.without_muted(|m| {
+                        state.is_sender_muted(&m.from_addr)
+                            || m.thread_id
+                                .as_deref()
+                                .is_some_and(|t| state.is_thread_muted(t))
+                    });
+                    if let Some(summary) = response.summary() {
+                        state.toast = Some(Toast::info(summary.clone()));
+                        if state.desktop_notifications {
+                            notification = desktop_notification("New messages", summary);
+                        }
+                    }
+                    if let Some(latest) = response.latest {
+                        state.new_messages_checked_at = Some(latest);
+                        let _ = current_settings(state).save();
+                    }
+                }
+                // Older servers lack the endpoint; stop asking
+                Err(AppError::ApiError { status: 404, .. }) => {
+                    state.new_messages_supported = false;
+                }
+                // Transient failures are retried on the next tick
+                Err(_) => {}
+            }
+            notification
+        }
+
+        // === Account Management ===
+        Message::OpenAccounts => {
+            state.navigation.push(ViewLevel::Accounts);
+            // Reset add account state, unless an import is adding one
+            if !state.account_import.is_active() {
+                state.add_account_email.clear();
+                state.adding_account = false;
+                state.oauth_response = None;
+            }
+            // Fetch current account list (using scheduler status)
+            Task::done(Message::FetchSyncStatus)
+        }
+
+        Message::FocusAddAccount => {
+            let open = if matches!(state.navigation.current(), ViewLevel::Accounts) {
+                Task::none()
+            } else {
+                handle(state, Message::OpenAccounts)
+            };
+            let focus = iced::widget::text_input::focus(iced::widget::text_input::Id::new(
+                crate::view::accounts::ADD_ACCOUNT_INPUT,
+            ));
+            Task::batch([open, focus])
+        }
+
+        Message::AccountFilterChanged(filter) => {
+            state.account_filter = filter;
+            Task::none()
+        }
+
+        Message::AddAccountEmailChanged(email) => {
+            state.add_account_email = email;
+            Task::none()
+        }
+
+        Message::StartAddAccount => {
+            if state.add_account_email.is_empty() {
+                return Task::none();
+            }
+
+            state.adding_account = true;
+            let email = state.add_account_email.clone();
+
+            let client = api_client(state);
+
+            perform_tracked(
+                state,
+                async move {
+                    client.initiate_oauth(&email).await
+                },
+                Message::OAuthInitiated,
+            )
+        }
+
+        Message::OAuthInitiated(result) => {
+            match result {
+                Ok(response) => {
+                    state.oauth_response = Some(response.clone());
+                    if response.device_flow {
+                        // Start polling for device flow completion
+                        state.polling_device_flow = true;
+                        // Note: In a real app, we'd set up a timer subscription
+                        // For now, manual polling via PollDeviceFlow message
+                    } else {
+                        // Open browser for OAuth
+                        state.account_import.set_status(ImportStatus::InBrowser);
+                        return Task::done(Message::OpenOAuthBrowser(response.auth_url));
+                    }
+                }
+                Err(e) if state.account_import.is_active() => {
+                    let next = state
+                        .account_import
+                        .advance(ImportStatus::Failed(e.to_string()));
+                    return next_imported_account(state, next);
+                }
+                Err(e) => {
+                    state.adding_account = false;
+                    load_failed(state, e);
+                }
+            }
+            Task::none()
+        }
+
+        Message::OpenOAuthBrowser(url) => {
+            open_url(&url);
+            Task::none()
+        }
+
+        Message::OpenMessageInBrowser(message_id) => {
+            if let Some(url) = state.message_web_url(message_id) {
+                open_url(&url);
+            }
+            Task::none()
+        }
+
+        Message::PollDeviceFlow => {
+            if !state.polling_device_flow {
+                return Task::none();
+            }
+
+            let email = state.add_account_email.clone();
+            let client = api_client(state);
+
+            perform_tracked(
+                state,
+                async move {
+                    client.check_device_flow(&email).await
+                },
+                Message::DeviceFlowStatusReceived,
+            )
+        }
+
+        Message::DeviceFlowStatusReceived(result) => {
+            match result {
                 Ok(status) => {
                     match status.status {
+                        DeviceFlowState::Complete if state.account_import.is_active() => {
+                            let next = state.account_import.advance(ImportStatus::Added);
+                            return Task::batch([
+                                next_imported_account(state, next),
+                                Task::done(Message::FetchSyncStatus),
+                            ]);
+                        }
                         DeviceFlowState::Complete => {
                             // Account added successfully
                             state.adding_account = false;
@@ -777,6 +1563,16 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
                         DeviceFlowState::Pending => {
                             // Keep polling - in a real app this would be on a timer
                         }
+                        DeviceFlowState::Expired | DeviceFlowState::Error
+                            if state.account_import.is_active() =>
+                        {
+                            let next = state.account_import.advance(ImportStatus::Failed(
+                                status
+                                    .error
+                                    .unwrap_or_else(|| "Device flow failed".to_string()),
+                            ));
+                            return next_imported_account(state, next);
+                        }
                         DeviceFlowState::Expired | DeviceFlowState::Error => {
                             state.adding_account = false;
                             state.polling_device_flow = false;
@@ -788,12 +1584,17 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
                 }
                 Err(e) => {
                     state.polling_device_flow = false;
-                    state.loading = LoadingState::Error(e.to_string());
+                    load_failed(state, e);
                 }
             }
             Task::none()
         }
 
+        Message::CancelAddAccount if state.account_import.is_active() => {
+            let next = state.account_import.advance(ImportStatus::Skipped);
+            next_imported_account(state, next)
+        }
+
         Message::CancelAddAccount => {
             state.adding_account = false;
             state.polling_device_flow = false;
@@ -802,31 +1603,86 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             Task::none()
         }
 
+        Message::ReauthenticateAccount(email) => {
+            // Run the add-account flow again from the Accounts view
+            Task::done(Message::OpenAccounts)
+                .chain(Task::done(Message::AddAccountEmailChanged(email)))
+                .chain(Task::done(Message::StartAddAccount))
+        }
+
+        Message::ImportAccounts => Task::perform(
+            async {
+                let Some(handle) = rfd::AsyncFileDialog::new()
+                    .add_filter("Address list", &["txt", "csv"])
+                    .pick_file()
+                    .await
+                else {
+                    return Ok(None);
+                };
+                tokio::fs::read_to_string(handle.path())
+                    .await
+                    .map(Some)
+                    .map_err(|e| e.to_string())
+            },
+            Message::AccountListLoaded,
+        ),
+
+        Message::AccountListLoaded(result) => match result {
+            Ok(Some(contents)) => {
+                let emails = parse_account_list(&contents);
+                if emails.is_empty() {
+                    state.toast = Some(Toast::error("No email addresses found in the file"));
+                    return Task::none();
+                }
+                state.account_import = AccountImport::new(emails);
+                let next = state.account_import.start();
+                next_imported_account(state, next)
+            }
+            Ok(None) => Task::none(),
+            Err(e) => {
+                state.toast = Some(Toast::error(format!("Could not read file: {}", e)));
+                Task::none()
+            }
+        },
+
+        Message::NextImportedAccount => {
+            let Some(status) = state.account_import.current_status().cloned() else {
+                return Task::none();
+            };
+            let next = state.account_import.advance(status);
+            next_imported_account(state, next)
+        }
+
+        Message::CancelAccountImport => {
+            if state.account_import.is_active() {
+                state.account_import.cancel();
+                handle(state, Message::CancelAddAccount)
+            } else {
+                state.account_import = AccountImport::default();
+                Task::none()
+            }
+        }
+
         Message::ShowRemoveAccountModal(email) => {
             state.removing_account = Some(email);
-            state.show_remove_modal = true;
+            state.modals.push(Modal::RemoveAccount);
             Task::none()
         }
 
         Message::HideRemoveAccountModal => {
             state.removing_account = None;
-            state.show_remove_modal = false;
+            state.modals.close(Modal::RemoveAccount);
             Task::none()
         }
 
         Message::ConfirmRemoveAccount => {
-            state.show_remove_modal = false;
+            state.modals.close(Modal::RemoveAccount);
             if let Some(email) = state.removing_account.take() {
-                let url = state.server_url.clone();
-                let api_key = if state.api_key.is_empty() {
-                    None
-                } else {
-                    Some(state.api_key.clone())
-                };
+                let client = api_client(state);
 
-                return Task::perform(
+                return perform_tracked(
+                    state,
                     async move {
-                        let client = ApiClient::new(url, api_key);
                         client.remove_account(&email).await
                     },
                     Message::AccountRemoved,
@@ -842,7 +1698,7 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
                     return Task::done(Message::FetchSyncStatus);
                 }
                 Err(e) => {
-                    state.loading = LoadingState::Error(e.to_string());
+                    load_failed(state, e);
                 }
             }
             Task::none()
@@ -853,6 +1709,22 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             // Copy current values to editable fields
             state.settings_server_url = state.server_url.clone();
             state.settings_api_key = state.api_key.clone();
+            state.settings_allow_insecure = state.allow_insecure;
+            state.settings_pin_certificate = state.pin_certificate;
+            state.settings_default_view = state.default_view;
+            state.settings_restore_session = state.restore_session;
+            state.settings_show_key_hints = state.show_key_hints;
+            state.settings_show_snippets = state.show_snippets;
+            state.settings_snippet_length = state.snippet_length;
+            state.settings_max_concurrent_downloads = state.max_concurrent_downloads;
+            state.settings_auto_open_downloads = state.auto_open_downloads;
+            state.settings_auto_open_extensions = state.auto_open_extensions.join(", ");
+            state.settings_load_remote_avatars = state.load_remote_avatars;
+            state.settings_desktop_notifications = state.desktop_notifications;
+            state.settings_theme = state.theme;
+            state.settings_byte_units = state.byte_units;
+            state.settings_reading_pane = state.reading_pane;
+            state.settings_export_api_key = false;
             state.settings_tab = SettingsTab::Server;
             state.connection_test_result = None;
             state.navigation.push(ViewLevel::Settings);
@@ -876,20 +1748,258 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             Task::none()
         }
 
-        Message::TestConnection => {
-            state.testing_connection = true;
+        Message::SettingsAllowInsecureToggled(enabled) => {
+            state.settings_allow_insecure = enabled;
             state.connection_test_result = None;
+            Task::none()
+        }
 
-            let url = state.settings_server_url.clone();
+        Message::SettingsPinCertificateToggled(enabled) => {
+            state.settings_pin_certificate = enabled;
+            Task::none()
+        }
+
+        Message::SettingsExportApiKeyToggled(enabled) => {
+            state.settings_export_api_key = enabled;
+            Task::none()
+        }
+
+        Message::OpenConfigFolder => {
+            if let Some(dir) = Settings::config_dir() {
+                // Nothing has been saved yet on a fresh install
+                let _ = std::fs::create_dir_all(&dir);
+                let _ = open::that(&dir);
+            }
+            Task::none()
+        }
+
+        Message::ExportSettings => {
+            let contents = match current_settings(state).to_export(state.settings_export_api_key) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    state.toast = Some(Toast::error(e));
+                    return Task::none();
+                }
+            };
+            Task::perform(
+                async move {
+                    let Some(handle) = rfd::AsyncFileDialog::new()
+                        .set_file_name("msgvault-desktop-settings.toml")
+                        .add_filter("TOML", &["toml"])
+                        .save_file()
+                        .await
+                    else {
+                        return Ok(None);
+                    };
+                    tokio::fs::write(handle.path(), contents)
+                        .await
+                        .map(|_| Some(handle.path().to_path_buf()))
+                        .map_err(|e| e.to_string())
+                },
+                Message::SettingsExported,
+            )
+        }
+
+        Message::SettingsExported(result) => {
+            match result {
+                Ok(Some(path)) => {
+                    state.toast = Some(Toast::info(format!("Saved {}", path.display())));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    state.toast = Some(Toast::error(format!("Failed to export settings: {}", e)));
+                }
+            }
+            Task::none()
+        }
+
+        Message::ExportShortcuts => {
+            let contents = state.keymap.cheatsheet();
+            Task::perform(
+                async move {
+                    let Some(handle) = rfd::AsyncFileDialog::new()
+                        .set_file_name("msgvault-shortcuts.txt")
+                        .add_filter("Text", &["txt"])
+                        .save_file()
+                        .await
+                    else {
+                        return Ok(None);
+                    };
+                    tokio::fs::write(handle.path(), contents)
+                        .await
+                        .map(|_| Some(handle.path().to_path_buf()))
+                        .map_err(|e| e.to_string())
+                },
+                Message::ShortcutsExported,
+            )
+        }
+
+        Message::ShortcutsExported(result) => {
+            match result {
+                Ok(Some(path)) => {
+                    state.toast = Some(Toast::info(format!("Saved {}", path.display())));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    state.toast = Some(Toast::error(format!("Failed to export shortcuts: {}", e)));
+                }
+            }
+            Task::none()
+        }
+
+        Message::ImportSettings => Task::perform(
+            async {
+                let Some(handle) = rfd::AsyncFileDialog::new()
+                    .add_filter("TOML", &["toml"])
+                    .pick_file()
+                    .await
+                else {
+                    return Ok(None);
+                };
+                let contents = tokio::fs::read_to_string(handle.path())
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Settings::from_export(&contents).map(Some)
+            },
+            Message::SettingsImported,
+        ),
+
+        Message::SettingsImported(result) => match result {
+            Ok(Some(settings)) => {
+                // Fill the form and save it, which applies and reconnects
+                state.settings_server_url = settings.server_url;
+                if !settings.api_key.is_empty() {
+                    state.settings_api_key = settings.api_key;
+                }
+                state.settings_allow_insecure = settings.allow_insecure;
+                state.settings_pin_certificate = settings.pin_certificate;
+                state.settings_default_view = settings.default_view;
+                state.settings_restore_session = settings.restore_session;
+                state.settings_show_key_hints = settings.show_key_hints;
+                state.settings_show_snippets = settings.show_snippets;
+                state.settings_snippet_length = settings.snippet_length;
+                state.settings_max_concurrent_downloads = settings.max_concurrent_downloads;
+                state.settings_auto_open_downloads = settings.auto_open_downloads;
+                state.settings_auto_open_extensions = settings.auto_open_extensions.join(", ");
+                state.settings_load_remote_avatars = settings.load_remote_avatars;
+                state.settings_desktop_notifications = settings.desktop_notifications;
+                state.settings_theme = settings.theme;
+                state.settings_byte_units = settings.byte_units;
+                state.settings_reading_pane = settings.reading_pane;
+                state.remote_content_senders = settings.remote_content_senders;
+                state.blocked_senders = settings.blocked_senders;
+                state.discovery_ports = settings.discovery_ports;
+                state.pinned_filters = settings.pinned_filters;
+                state.blocked_sender_display = settings.blocked_sender_display;
+                state.muted_senders = settings.muted_senders;
+                state.muted_threads = settings.muted_threads;
+
+                let reconnects = state.settings_server_url != state.server_url
+                    || state.settings_api_key != state.api_key;
+                let saved = handle(state, Message::SaveSettings);
+                if reconnects {
+                    // Data is refetched once the new server answers
+                    saved
+                } else {
+                    Task::batch([
+                        saved,
+                        Task::done(Message::FetchStats),
+                        Task::done(Message::FetchSyncStatus),
+                    ])
+                }
+            }
+            Ok(None) => Task::none(),
+            Err(e) => {
+                state.toast = Some(Toast::error(format!("Failed to import settings: {}", e)));
+                Task::none()
+            }
+        },
+
+        Message::SettingsDefaultViewChanged(view) => {
+            state.settings_default_view = view;
+            Task::none()
+        }
+
+        Message::SettingsRestoreSessionToggled(enabled) => {
+            state.settings_restore_session = enabled;
+            Task::none()
+        }
+
+        Message::SettingsShowKeyHintsToggled(enabled) => {
+            state.settings_show_key_hints = enabled;
+            Task::none()
+        }
+
+        Message::SettingsShowSnippetsToggled(enabled) => {
+            state.settings_show_snippets = enabled;
+            Task::none()
+        }
+
+        Message::SettingsSnippetLengthChanged(length) => {
+            state.settings_snippet_length = length;
+            Task::none()
+        }
+
+        Message::SettingsThemeChanged(theme) => {
+            state.settings_theme = theme;
+            Task::none()
+        }
+
+        Message::SettingsByteUnitsChanged(units) => {
+            state.settings_byte_units = units;
+            Task::none()
+        }
+
+        Message::SettingsReadingPaneChanged(mode) => {
+            state.settings_reading_pane = mode;
+            Task::none()
+        }
+
+        Message::SettingsMaxDownloadsChanged(limit) => {
+            state.settings_max_concurrent_downloads = limit;
+            Task::none()
+        }
+
+        Message::SettingsAutoOpenDownloadsToggled(enabled) => {
+            state.settings_auto_open_downloads = enabled;
+            Task::none()
+        }
+
+        Message::SettingsAutoOpenExtensionsChanged(extensions) => {
+            state.settings_auto_open_extensions = extensions;
+            Task::none()
+        }
+
+        Message::SettingsLoadRemoteAvatarsToggled(enabled) => {
+            state.settings_load_remote_avatars = enabled;
+            Task::none()
+        }
+
+        Message::SettingsDesktopNotificationsToggled(enabled) => {
+            state.settings_desktop_notifications = enabled;
+            Task::none()
+        }
+
+        Message::TestConnection => {
+            state.testing_connection = true;
+            state.connection_test_result = None;
+
+            if !state.settings_allow_insecure && is_insecure_url(&state.settings_server_url) {
+                return Task::done(Message::ConnectionTested(Err(AppError::InsecureBlocked)));
+            }
+
+            let url = state.settings_server_url.clone();
             let api_key = if state.settings_api_key.is_empty() {
                 None
             } else {
                 Some(state.settings_api_key.clone())
             };
+            let accept_invalid_certs = state.settings_allow_insecure;
 
-            Task::perform(
+            perform_tracked(
+                state,
                 async move {
-                    let client = ApiClient::new(url, api_key);
+                    let client = ApiClient::new(url, api_key, accept_invalid_certs);
                     client.health().await
                 },
                 Message::ConnectionTested,
@@ -903,21 +2013,101 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
         }
 
         Message::SaveSettings => {
+            // Keep the working connection until the new one is verified
+            if state.settings_server_url != state.server_url
+                || state.settings_api_key != state.api_key
+                || state.settings_allow_insecure != state.allow_insecure
+            {
+                state.previous_connection = Some(current_settings(state));
+            }
+
+            // A new server gets its own pin on first connect
+            if state.settings_server_url != state.server_url || !state.settings_pin_certificate {
+                state.pinned_certificate = None;
+            }
+            state.pin_certificate = state.settings_pin_certificate;
+
             // Update the app state with new values
             state.server_url = state.settings_server_url.clone();
             state.api_key = state.settings_api_key.clone();
+            state.allow_insecure = state.settings_allow_insecure;
+
+            state.default_view = state.settings_default_view;
+            state.restore_session = state.settings_restore_session;
+            state.show_key_hints = state.settings_show_key_hints;
+            state.show_snippets = state.settings_show_snippets;
+            state.snippet_length = state.settings_snippet_length;
+            state.max_concurrent_downloads = state.settings_max_concurrent_downloads;
+            state.auto_open_downloads = state.settings_auto_open_downloads;
+            state.auto_open_extensions = parse_extensions(&state.settings_auto_open_extensions);
+            state.load_remote_avatars = state.settings_load_remote_avatars;
+            if !state.load_remote_avatars {
+                state.avatars.clear();
+            }
+            state.desktop_notifications = state.settings_desktop_notifications;
+            state.theme = state.settings_theme;
+            state.theme.apply();
+            state.byte_units = state.settings_byte_units;
+            state.byte_units.apply();
+            state.reading_pane = state.settings_reading_pane;
 
             // Save to config file
-            let settings = Settings {
-                server_url: state.server_url.clone(),
-                api_key: state.api_key.clone(),
-                allow_insecure: true, // Allow HTTP for local development
-            };
+            let settings = current_settings(state);
 
-            Task::perform(
-                async move { settings.save() },
-                Message::SettingsSaved,
-            )
+            // A raised limit may free slots for queued downloads
+            Task::batch([
+                Task::perform(async move { settings.save() }, Message::SettingsSaved),
+                start_queued_downloads(state),
+                fetch_list_avatars(state),
+            ])
+        }
+
+        Message::ShowResetSettingsModal => {
+            state.modals.push(Modal::ResetSettings);
+            Task::none()
+        }
+
+        Message::HideResetSettingsModal => {
+            state.modals.close(Modal::ResetSettings);
+            Task::none()
+        }
+
+        Message::ConfirmResetSettings => {
+            state.modals.close(Modal::ResetSettings);
+
+            // Starting over would drop a draft or running downloads
+            if state.quit_warning().is_some() {
+                state.toast = Some(Toast::error(
+                    "Send or discard the open draft and let downloads finish before resetting",
+                ));
+                return Task::none();
+            }
+
+            let settings = Settings::default();
+            if let Err(e) = settings.save() {
+                state.toast = Some(Toast::error(format!("Failed to reset settings: {}", e)));
+                return Task::none();
+            }
+            settings.theme.apply();
+            settings.byte_units.apply();
+
+            // Start over as on first run; saved searches live in their own
+            // file, and open windows (with the compose they show) stay open
+            let saved_searches = std::mem::take(&mut state.saved_searches);
+            let (main_window, compose_window, window_size) =
+                (state.main_window, state.compose_window, state.window_size);
+            let message_windows = std::mem::take(&mut state.message_windows);
+            let compose = std::mem::take(&mut state.compose);
+            let downloads = std::mem::take(&mut state.downloads);
+            *state = AppState::new(&settings);
+            state.saved_searches = saved_searches;
+            state.main_window = main_window;
+            state.compose_window = compose_window;
+            state.compose = compose;
+            state.downloads = downloads;
+            state.window_size = window_size;
+            state.message_windows = message_windows;
+            Task::done(Message::StartDiscovery)
         }
 
         Message::SettingsSaved(result) => {
@@ -930,34 +2120,90 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
                     state.loading = LoadingState::Error(format!("Failed to save settings: {}", e));
                 }
             }
-            Task::none()
+
+            // Reconnect when the server or key changed
+            if state.previous_connection.is_some() {
+                Task::done(Message::CheckHealth)
+            } else {
+                Task::none()
+            }
         }
 
         // === Help ===
         Message::ShowHelp => {
-            state.show_help_modal = true;
+            state.modals.push(Modal::Help);
             Task::none()
         }
 
         Message::HideHelp => {
-            state.show_help_modal = false;
+            state.modals.close(Modal::Help);
             Task::none()
         }
 
         // === Selection ===
+        Message::RowHovered(message_id) => {
+            state.hovered_message = Some(message_id);
+            Task::none()
+        }
+
+        Message::RowUnhovered(message_id) => {
+            // Leaving one row and entering the next can arrive in either order
+            if state.hovered_message == Some(message_id) {
+                state.hovered_message = None;
+            }
+            Task::none()
+        }
+
+        Message::RowClicked(index) => {
+            // A click hides the keyboard focus ring and returns focus to the list
+            state.focus_visible = false;
+            state.focus_area = FocusArea::Content;
+            let in_search = matches!(state.navigation.current(), ViewLevel::Search);
+            let list = if in_search {
+                &state.search_results
+            } else {
+                &state.messages
+            };
+            if index >= list.len() {
+                return Task::none();
+            }
+
+            if state.modifiers.shift() {
+                // Select everything between the anchor and the clicked row
+                let anchor = state.selection_anchor.unwrap_or(index).min(list.len() - 1);
+                let (start, end) = (anchor.min(index), anchor.max(index));
+                state
+                    .selected_messages
+                    .extend(list[start..=end].iter().map(|m| m.id));
+            } else {
+                state.selection_anchor = Some(index);
+            }
+
+            if in_search {
+                state.search_selected_index = index;
+            } else {
+                state.message_selected_index = index;
+            }
+            Task::none()
+        }
+
         Message::ToggleSelection => {
             // Toggle selection based on current view
-            let message_id = match state.navigation.current() {
-                ViewLevel::Messages { .. } => {
-                    state.messages.get(state.message_selected_index).map(|m| m.id)
-                }
-                ViewLevel::Search => {
-                    state.search_results.get(state.search_selected_index).map(|m| m.id)
-                }
+            let focused = match state.navigation.current() {
+                ViewLevel::Messages { .. } => state
+                    .messages
+                    .get(state.message_selected_index)
+                    .map(|m| (state.message_selected_index, m.id)),
+                ViewLevel::Search => state
+                    .search_results
+                    .get(state.search_selected_index)
+                    .map(|m| (state.search_selected_index, m.id)),
                 _ => None,
             };
 
-            if let Some(id) = message_id {
+            if let Some((index, id)) = focused {
+                // The toggled row becomes the anchor for shift-click ranges
+                state.selection_anchor = Some(index);
                 if state.selected_messages.contains(&id) {
                     state.selected_messages.remove(&id);
                 } else {
@@ -992,31 +2238,180 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
 
         Message::ShowDeleteModal => {
             if !state.selected_messages.is_empty() {
-                state.show_delete_modal = true;
+                state.delete_confirm_input.clear();
+                state.modals.push(Modal::ConfirmDelete);
             }
             Task::none()
         }
 
+        Message::DeleteConfirmInputChanged(input) => {
+            state.delete_confirm_input = input;
+            Task::none()
+        }
+
         Message::HideDeleteModal => {
-            state.show_delete_modal = false;
+            state.modals.close(Modal::ConfirmDelete);
             Task::none()
         }
 
         Message::ConfirmDelete => {
-            state.show_delete_modal = false;
+            if !deletion_confirmed(state.selected_messages.len(), &state.delete_confirm_input) {
+                return Task::none();
+            }
+            state.modals.close(Modal::ConfirmDelete);
             // Trigger staging for deletion
             Task::done(Message::StageForDeletion)
         }
 
         Message::StageForDeletion => {
-            // TODO: Phase 6 server endpoint - POST /api/v1/deletion/stage
-            // For now, just clear selection as a placeholder
-            let count = state.selected_messages.len();
-            state.selected_messages.clear();
-            // Log to console for now (will be replaced with API call)
-            #[cfg(debug_assertions)]
-            println!("Staged {} messages for deletion", count);
-            let _ = count; // suppress unused warning in release
+            if state.deletion.is_some() || state.selected_messages.is_empty() {
+                return Task::none();
+            }
+            let mut message_ids: Vec<i64> = state.selected_messages.iter().copied().collect();
+            message_ids.sort_unstable();
+            state.deletion = Some(DeletionProgress::new(message_ids));
+            stage_next_deletion_chunk(state)
+        }
+
+        Message::DeletionChunkStaged {
+            message_ids,
+            result,
+        } => {
+            let Some(progress) = state.deletion.as_mut() else {
+                return Task::none();
+            };
+            let result = result
+                .map(|response| response.staged.max(0) as usize)
+                .map_err(|e| e.to_string());
+            progress.record(message_ids, result);
+            stage_next_deletion_chunk(state)
+        }
+
+        // === Labels ===
+        Message::OpenLabelPicker => {
+            let mut message_ids: Vec<i64> = state.selected_messages.iter().copied().collect();
+
+            // Without a selection, label the focused message
+            if message_ids.is_empty() {
+                let focused = match state.navigation.current() {
+                    ViewLevel::Messages { .. } => state
+                        .messages
+                        .get(state.message_selected_index)
+                        .map(|m| m.id),
+                    ViewLevel::Search => state
+                        .search_results
+                        .get(state.search_selected_index)
+                        .map(|m| m.id),
+                    ViewLevel::MessageDetail { message_id } => Some(*message_id),
+                    _ => None,
+                };
+                message_ids.extend(focused);
+            }
+
+            if message_ids.is_empty() {
+                return Task::none();
+            }
+
+            message_ids.sort_unstable();
+            state.label_picker = Some(LabelPicker::new(message_ids));
+            fetch_label_list(state)
+        }
+
+        Message::CloseLabelPicker => {
+            state.label_picker = None;
+            Task::none()
+        }
+
+        Message::LabelListLoaded(result) => {
+            if let Some(picker) = &mut state.label_picker {
+                picker.is_loading = false;
+                match result {
+                    Ok(fetched) => {
+                        let resolved = state.aggregate_cache.resolve(LABEL_LIST_QUERY, fetched);
+                        let Some(response) = resolved else {
+                            picker.is_loading = true;
+                            return fetch_label_list(state);
+                        };
+                        picker.available = response.rows.into_iter().map(|r| r.key).collect();
+                    }
+                    Err(e) => {
+                        state.toast = Some(Toast::error(format!("Failed to load labels: {}", e)));
+                    }
+                }
+            }
+            Task::none()
+        }
+
+        Message::LabelFilterChanged(filter) => {
+            if let Some(picker) = &mut state.label_picker {
+                picker.filter = filter;
+            }
+            Task::none()
+        }
+
+        Message::ApplyLabel { label, add } => {
+            let Some(picker) = &mut state.label_picker else {
+                return Task::none();
+            };
+
+            // A newly created label is offered for the rest of the session
+            if !picker.available.contains(&label) {
+                picker.available.push(label.clone());
+                picker.available.sort();
+            }
+            picker.filter.clear();
+            let message_ids = picker.message_ids.clone();
+
+            let (add, remove) = if add {
+                (vec![label], Vec::new())
+            } else {
+                (Vec::new(), vec![label])
+            };
+
+            // Update the rows optimistically, remembering the old labels for revert
+            let previous: Vec<(i64, Vec<String>)> = message_ids
+                .iter()
+                .filter_map(|id| state.message_labels(*id).map(|l| (*id, l.to_vec())))
+                .collect();
+            for (message_id, labels) in &previous {
+                let mut labels = labels.clone();
+                apply_label_change(&mut labels, &add, &remove);
+                state.set_message_labels(*message_id, &labels);
+            }
+
+            let client = api_client(state);
+
+            perform_tracked(
+                state,
+                async move { client.set_labels(&message_ids, &add, &remove).await },
+                move |result| Message::LabelsUpdated {
+                    previous: previous.clone(),
+                    result,
+                },
+            )
+        }
+
+        Message::LabelsUpdated { previous, result } => {
+            match result {
+                Ok(response) => {
+                    // The server's labels are authoritative
+                    for updated in &response.messages {
+                        state.set_message_labels(updated.id, &updated.labels);
+                    }
+                    let count = previous.len().max(response.messages.len());
+                    state.toast = Some(Toast::info(format!(
+                        "Updated labels on {} message{}",
+                        count,
+                        if count == 1 { "" } else { "s" }
+                    )));
+                }
+                Err(e) => {
+                    for (message_id, labels) in &previous {
+                        state.set_message_labels(*message_id, labels);
+                    }
+                    state.toast = Some(Toast::error(format!("Failed to update labels: {}", e)));
+                }
+            }
             Task::none()
         }
 
@@ -1079,15 +2474,32 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
 
         // === User Input ===
         Message::ServerUrlChanged(url) => {
+            // Don't retry the old settings while they're being edited
             state.server_url = url;
+            state.reconnect_in = None;
             Task::none()
         }
 
         Message::ApiKeyChanged(key) => {
             state.api_key = key;
+            state.reconnect_in = None;
             Task::none()
         }
 
+        Message::AllowInsecureConnection => {
+            state.allow_insecure = true;
+            let _ = current_settings(state).save();
+            Task::done(Message::CheckHealth)
+        }
+
+        Message::TrustNewCertificate => {
+            if let Some(fingerprint) = state.certificate_mismatch.take() {
+                state.pinned_certificate = Some(fingerprint);
+                let _ = current_settings(state).save();
+            }
+            Task::done(Message::CheckHealth)
+        }
+
         Message::RetryConnection => Task::done(Message::CheckHealth),
 
         // === Attachments ===
@@ -1096,46 +2508,11 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             attachment_idx,
             filename,
         } => {
-            // Mark as downloading
-            state.downloads.set_downloading(message_id, attachment_idx, 0.0);
-
-            let url = state.server_url.clone();
-            let api_key = if state.api_key.is_empty() {
-                None
-            } else {
-                Some(state.api_key.clone())
-            };
-
-            Task::perform(
-                async move {
-                    let client = reqwest::Client::builder()
-                        .timeout(std::time::Duration::from_secs(300))
-                        .build()
-                        .expect("Failed to create HTTP client");
-
-                    crate::api::download_attachment(
-                        &client,
-                        &url,
-                        api_key.as_deref(),
-                        message_id,
-                        attachment_idx,
-                        &filename,
-                    )
-                    .await
-                },
-                move |result| match result {
-                    Ok(path) => Message::DownloadComplete {
-                        message_id,
-                        attachment_idx,
-                        path,
-                    },
-                    Err(e) => Message::DownloadFailed {
-                        message_id,
-                        attachment_idx,
-                        error: e.to_string(),
-                    },
-                },
-            )
+            // Wait for a free slot if the concurrency limit is reached
+            state
+                .downloads
+                .enqueue(message_id, attachment_idx, filename);
+            start_queued_downloads(state)
         }
 
         Message::DownloadProgress {
@@ -1143,7 +2520,16 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             attachment_idx,
             progress,
         } => {
-            state.downloads.set_downloading(message_id, attachment_idx, progress);
+            // Reports can trail a cancel; don't revive the download
+            if state
+                .downloads
+                .get(message_id, attachment_idx)
+                .is_downloading()
+            {
+                state
+                    .downloads
+                    .set_downloading(message_id, attachment_idx, progress);
+            }
             Task::none()
         }
 
@@ -1152,24 +2538,163 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             attachment_idx,
             path,
         } => {
-            state.downloads.set_complete(message_id, attachment_idx, path);
-            Task::none()
+            let auto_open =
+                state.auto_open_downloads && should_auto_open(&path, &state.auto_open_extensions);
+            state
+                .downloads
+                .set_complete(message_id, attachment_idx, path.clone());
+            if auto_open {
+                Task::done(Message::OpenFile(path))
+            } else {
+                Task::none()
+            }
         }
 
         Message::DownloadFailed {
             message_id,
             attachment_idx,
             error,
+            partial_bytes,
         } => {
-            state.downloads.set_failed(message_id, attachment_idx, error);
+            // A result can slip out just before a cancel lands
+            if state
+                .downloads
+                .get(message_id, attachment_idx)
+                .is_cancelled()
+            {
+                return Task::none();
+            }
+            state
+                .downloads
+                .set_failed(message_id, attachment_idx, error);
+            state
+                .downloads
+                .set_partial(message_id, attachment_idx, partial_bytes);
             Task::none()
         }
 
-        Message::OpenFile(path) => {
-            // Open file with default application
-            let _ = open::that(&path);
-            Task::none()
-        }
+        Message::CancelDownload {
+            message_id,
+            attachment_idx,
+        } => {
+            // A running download keeps its slot until its task has stopped
+            if !state.downloads.dequeue(message_id, attachment_idx) {
+                state.downloads.cancel(message_id, attachment_idx);
+            }
+            Task::none()
+        }
+
+        Message::DownloadStopped {
+            message_id,
+            attachment_idx,
+        } => {
+            // An aborted task leaves its partial file; nothing writes to it now
+            if state.downloads.task_stopped(message_id, attachment_idx) {
+                if let Some(filename) = state.downloads.filename(message_id, attachment_idx) {
+                    crate::api::attachments::remove_partial(message_id, attachment_idx, filename);
+                }
+            }
+            download_finished(state)
+        }
+
+        Message::ToggleDownloadsPanel => {
+            state.modals.toggle(Modal::Downloads);
+            Task::none()
+        }
+
+        Message::ClearFinishedDownloads => {
+            state.downloads.clear_finished();
+            Task::none()
+        }
+
+        Message::DownloadAllAttachments(message_id) => {
+            let client = api_client(state);
+
+            perform_tracked(
+                state,
+                async move { client.message_detail(message_id).await },
+                Message::AttachmentsListed,
+            )
+        }
+
+        Message::AttachmentsListed(result) => match result {
+            Ok(detail) => {
+                // Inline images are part of the body, not files to save
+                let mut count = 0;
+                for (idx, attachment) in detail.attachments.iter().enumerate() {
+                    if attachment.is_inline() {
+                        continue;
+                    }
+                    state.downloads.enqueue(
+                        detail.id,
+                        idx,
+                        local_filename(&attachment.filename, &attachment.mime_type),
+                    );
+                    count += 1;
+                }
+                state.toast = Some(Toast::info(format!(
+                    "Downloading {} attachment{}",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                )));
+                start_queued_downloads(state)
+            }
+            Err(e) => {
+                state.toast = Some(Toast::error(format!("Failed to list attachments: {}", e)));
+                Task::none()
+            }
+        },
+
+        Message::OpenFile(path) => {
+            // Open file with default application
+            let _ = open::that(&path);
+            Task::none()
+        }
+
+        Message::RevealFile(path) => {
+            reveal_in_file_manager(&path);
+            Task::none()
+        }
+
+        Message::SaveAttachmentAs {
+            message_id,
+            attachment_idx,
+            filename,
+        } => {
+            let client = api_client(state);
+
+            Task::perform(
+                async move {
+                    let Some(handle) = rfd::AsyncFileDialog::new()
+                        .set_file_name(filename)
+                        .save_file()
+                        .await
+                    else {
+                        return Ok(None);
+                    };
+
+                    client
+                        .save_attachment(message_id, attachment_idx, handle.path(), |_| {})
+                        .await
+                        .map(Some)
+                        .map_err(|e| e.to_string())
+                },
+                Message::AttachmentSaved,
+            )
+        }
+
+        Message::AttachmentSaved(result) => {
+            match result {
+                Ok(Some(path)) => {
+                    state.toast = Some(Toast::info(format!("Saved {}", path.display())));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    state.toast = Some(Toast::error(format!("Failed to save attachment: {}", e)));
+                }
+            }
+            Task::none()
+        }
 
         // === Compose ===
         Message::OpenCompose => {
@@ -1183,260 +2708,1348 @@ pub fn handle(state: &mut AppState, message: Message) -> Task<Message> {
             Task::none()
         }
 
-        Message::OpenReply(message_id) => {
-            // TODO: Fetch message detail and populate reply
-            // For now, use current message if available
-            if let Some(msg) = &state.current_message {
-                let from_account = state
-                    .sync_accounts
-                    .first()
-                    .map(|a| a.email.clone())
-                    .unwrap_or_default();
-                let quoted = crate::model::compose::format_quoted_body(
-                    &msg.from_addr,
-                    &msg.sent_at.format("%b %d, %Y at %H:%M").to_string(),
-                    &msg.body,
-                );
-                state.compose = crate::model::ComposeState::open_reply(
-                    from_account,
-                    message_id,
-                    msg.from_addr.clone(),
-                    msg.subject.clone(),
-                    quoted,
-                );
+        Message::OpenReply(message_id) => open_compose_from(state, ComposeMode::Reply, message_id),
+
+        Message::OpenReplyAll(message_id) => {
+            open_compose_from(state, ComposeMode::ReplyAll, message_id)
+        }
+
+        Message::OpenForward(message_id) => {
+            open_compose_from(state, ComposeMode::Forward, message_id)
+        }
+
+        Message::ComposeSourceLoaded(mode, result) => {
+            match result {
+                Ok(msg) => compose_from(state, mode, msg),
+                Err(e) => {
+                    state.toast = Some(Toast::error(format!("Couldn't load message: {}", e)));
+                }
             }
             Task::none()
         }
 
-        Message::OpenReplyAll(message_id) => {
-            if let Some(msg) = &state.current_message {
-                let from_account = state
-                    .sync_accounts
-                    .first()
-                    .map(|a| a.email.clone())
-                    .unwrap_or_default();
-                let quoted = crate::model::compose::format_quoted_body(
-                    &msg.from_addr,
-                    &msg.sent_at.format("%b %d, %Y at %H:%M").to_string(),
-                    &msg.body,
-                );
-                // Combine to and cc, removing our own email
-                let mut all_recipients: Vec<String> = msg.to.clone();
-                all_recipients.push(msg.from_addr.clone());
-                all_recipients.retain(|e| e != &from_account);
-                let cc = msg.cc.iter().filter(|e| *e != &from_account).cloned().collect();
-
-                state.compose = crate::model::ComposeState::open_reply_all(
-                    from_account,
-                    message_id,
-                    all_recipients,
-                    cc,
-                    msg.subject.clone(),
-                    quoted,
-                );
+        Message::ComposeToChanged(input) => {
+            state.compose.to_input = input;
+            Task::none()
+        }
+
+        Message::ComposeAddTo => {
+            let email = state.compose.to_input.trim().to_string();
+            state.compose.add_to(email);
+            state.compose.to_input.clear();
+            Task::none()
+        }
+
+        Message::ComposeRemoveTo(index) => {
+            state.compose.remove_to(index);
+            Task::none()
+        }
+
+        Message::ComposeCcChanged(input) => {
+            state.compose.cc_input = input;
+            Task::none()
+        }
+
+        Message::ComposeAddCc => {
+            let email = state.compose.cc_input.trim().to_string();
+            state.compose.add_cc(email);
+            state.compose.cc_input.clear();
+            Task::none()
+        }
+
+        Message::ComposeRemoveCc(index) => {
+            state.compose.remove_cc(index);
+            Task::none()
+        }
+
+        Message::ComposeBccChanged(input) => {
+            state.compose.bcc_input = input;
+            Task::none()
+        }
+
+        Message::ComposeAddBcc => {
+            let email = state.compose.bcc_input.trim().to_string();
+            state.compose.add_bcc(email);
+            state.compose.bcc_input.clear();
+            Task::none()
+        }
+
+        Message::ComposeRemoveBcc(index) => {
+            state.compose.remove_bcc(index);
+            Task::none()
+        }
+
+        Message::ComposeSubjectChanged(subject) => {
+            state.compose.subject = subject;
+            state.compose.is_dirty = true;
+            Task::none()
+        }
+
+        Message::ComposeBodyChanged(body) => {
+            state.compose.body = body;
+            state.compose.is_dirty = true;
+            Task::none()
+        }
+
+        Message::ComposeFromChanged(account) => {
+            state.compose.from_account = account;
+            Task::none()
+        }
+
+        Message::ComposeToggleCcBcc => {
+            state.compose.show_cc_bcc = !state.compose.show_cc_bcc;
+            Task::none()
+        }
+
+        Message::ComposeAddAttachment => {
+            // TODO: Open file picker dialog
+            // This would require native file dialog integration
+            Task::none()
+        }
+
+        Message::ComposeAttachmentSelected(path) => {
+            if let Ok(metadata) = std::fs::metadata(&path) {
+                let filename = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "attachment".to_string());
+                state.compose.attachments.push(crate::model::AttachmentDraft {
+                    path,
+                    filename,
+                    size_bytes: metadata.len() as i64,
+                    mime_type: None,
+                });
+                state.compose.is_dirty = true;
             }
             Task::none()
         }
 
-        Message::OpenForward(message_id) => {
-            if let Some(msg) = &state.current_message {
-                let from_account = state
-                    .sync_accounts
-                    .first()
-                    .map(|a| a.email.clone())
-                    .unwrap_or_default();
-                let forward_body = format!(
-                    "From: {}\nDate: {}\nSubject: {}\nTo: {}\n\n{}",
-                    msg.from_addr,
-                    msg.sent_at.format("%b %d, %Y at %H:%M"),
-                    msg.subject,
-                    msg.to.join(", "),
-                    msg.body
-                );
-                state.compose = crate::model::ComposeState::open_forward(
-                    from_account,
-                    message_id,
-                    msg.subject.clone(),
-                    forward_body,
+        Message::ComposeRemoveAttachment(index) => {
+            if index < state.compose.attachments.len() {
+                state.compose.attachments.remove(index);
+                state.compose.is_dirty = true;
+            }
+            Task::none()
+        }
+
+        Message::ComposeSend => {
+            if !state.compose.can_send() {
+                return Task::none();
+            }
+            state.compose.is_sending = true;
+            state.compose.send_error = None;
+
+            // TODO: Implement actual send via API
+            // POST /api/v1/messages/send
+            // For now, just simulate success after a delay
+            Task::none()
+        }
+
+        Message::ComposeSent(result) => {
+            state.compose.is_sending = false;
+            match result {
+                Ok(_) => {
+                    state.compose.close();
+                }
+                Err(e) => {
+                    state.compose.send_error = Some(e.to_string());
+                }
+            }
+            Task::none()
+        }
+
+        Message::ComposeSaveDraft => {
+            // TODO: Implement draft saving via API
+            // POST /api/v1/messages/draft
+            Task::none()
+        }
+
+        Message::ComposeDraftSaved(result) => {
+            match result {
+                Ok(_draft_id) => {
+                    state.compose.is_dirty = false;
+                    // Optionally close or show confirmation
+                }
+                Err(e) => {
+                    state.compose.send_error = Some(format!("Failed to save draft: {}", e));
+                }
+            }
+            Task::none()
+        }
+
+        Message::ComposeDiscard => {
+            state.compose.close();
+            Task::none()
+        }
+
+        Message::ComposeClose => {
+            if state.compose.is_dirty && state.compose.has_content() {
+                // TODO: Show confirmation dialog
+                // For now, just close
+                state.compose.close();
+            } else {
+                state.compose.close();
+            }
+            Task::none()
+        }
+
+        Message::PopOutCompose => {
+            if let Some(id) = state.compose_window {
+                return window::gain_focus(id);
+            }
+            let (id, open) = window::open(window::Settings {
+                size: iced::Size::new(760.0, 720.0),
+                min_size: Some(iced::Size::new(520.0, 480.0)),
+                exit_on_close_request: false,
+                ..Default::default()
+            });
+            state.compose_window = Some(id);
+            open.discard()
+        }
+
+        Message::DockCompose => match state.compose_window.take() {
+            Some(id) => window::close(id),
+            None => Task::none(),
+        },
+
+        // === Notifications ===
+        Message::DismissToast => {
+            state.toast = None;
+            Task::none()
+        }
+
+        Message::DismissConfigRecovery => {
+            state.config_recovery = None;
+            Task::none()
+        }
+
+        Message::ToastTick => {
+            if state
+                .toast
+                .as_ref()
+                .is_some_and(|t| t.is_expired(Instant::now()))
+            {
+                state.toast = None;
+            }
+            Task::none()
+        }
+
+        // === Context Menu ===
+        Message::CursorMoved(id, position) => {
+            state.cursor_position = position;
+            state.cursor_window = Some(id);
+            if state.main_window != Some(id) {
+                return Task::none();
+            }
+            if let Some(divider) = state.dragging_divider {
+                state.panel_widths.drag(
+                    divider,
+                    position.x,
+                    state.window_size.width,
+                    state.sidebar_collapsed,
                 );
             }
             Task::none()
         }
 
-        Message::ComposeToChanged(input) => {
-            state.compose.to_input = input;
+        Message::DividerPressed(divider) => {
+            state.dragging_divider = Some(divider);
+            Task::none()
+        }
+
+        Message::DividerReleased => {
+            if state.dragging_divider.take().is_some() {
+                let _ = current_settings(state).save();
+            }
+            Task::none()
+        }
+
+        Message::ToggleSidebar => {
+            state.sidebar_collapsed = !state.sidebar_collapsed;
+            let _ = current_settings(state).save();
+            Task::none()
+        }
+
+        Message::CycleReadingPane => {
+            state.reading_pane = state.reading_pane.next();
+            // Keep an open settings form from saving the old mode back
+            state.settings_reading_pane = state.reading_pane;
+            let _ = current_settings(state).save();
+            state.toast = Some(Toast::info(format!(
+                "Reading pane: {}",
+                state.reading_pane.display_name()
+            )));
             Task::none()
         }
 
-        Message::ComposeAddTo => {
-            let email = state.compose.to_input.trim().to_string();
-            state.compose.add_to(email);
-            state.compose.to_input.clear();
-            Task::none()
-        }
+        Message::WindowResized(id, size) => {
+            // Only the main window's size drives the layout
+            if state.main_window == Some(id) {
+                state.window_size = size;
+            }
+            Task::none()
+        }
+
+        Message::ShowContextMenu(target) => {
+            // Right-clicking a row also moves the cursor to it
+            if let ContextTarget::Message { index, .. } = &target {
+                match state.navigation.current() {
+                    ViewLevel::Search => state.search_selected_index = *index,
+                    _ => state.message_selected_index = *index,
+                }
+            }
+            // Menus opened in message windows are drawn there
+            let window = state
+                .cursor_window
+                .filter(|id| state.main_window != Some(*id));
+            state.context_menu =
+                Some(ContextMenu::new(state.cursor_position, target).in_window(window));
+            Task::none()
+        }
+
+        Message::CloseContextMenu => {
+            state.context_menu = None;
+            Task::none()
+        }
+
+        Message::ContextMenuSelect(message) => {
+            state.context_menu = None;
+            Task::done(*message)
+        }
+
+        Message::ToggleMenu(menu) => {
+            state.context_menu = None;
+            state.open_menu = if state.open_menu == Some(menu) {
+                None
+            } else {
+                Some(menu)
+            };
+            Task::none()
+        }
+
+        Message::HoverMenu(menu) => {
+            if state.open_menu.is_some() {
+                state.open_menu = Some(menu);
+            }
+            Task::none()
+        }
+
+        Message::CloseMenu => {
+            state.open_menu = None;
+            Task::none()
+        }
+
+        Message::MenuSelect(message) => {
+            state.open_menu = None;
+            Task::done(*message)
+        }
+
+        Message::CloseWindow(id) => handle(state, Message::WindowCloseRequested(id)),
+
+        Message::WindowCloseRequested(id) => {
+            if state.compose_window == Some(id) {
+                // The compose window closes once compose does
+                handle(state, Message::ComposeClose)
+            } else if state.main_window == Some(id) {
+                // Closing the main window quits
+                handle(state, Message::Quit)
+            } else {
+                window::close(id)
+            }
+        }
+
+        Message::WindowClosed(id) => {
+            if state.main_window == Some(id) {
+                iced::exit()
+            } else {
+                if state.compose_window == Some(id) {
+                    state.compose_window = None;
+                }
+                state.message_windows.close(id);
+                Task::none()
+            }
+        }
+
+        Message::FilesHovered(id) => {
+            if compose_drop_window(state) == Some(id) {
+                state.compose.is_drop_target = true;
+            }
+            Task::none()
+        }
+
+        Message::FilesHoveredLeft(id) => {
+            if compose_drop_window(state) == Some(id) {
+                state.compose.is_drop_target = false;
+            }
+            Task::none()
+        }
+
+        Message::FileDropped(id, path) => {
+            state.compose.is_drop_target = false;
+            if compose_drop_window(state) == Some(id) && path.is_file() {
+                handle(state, Message::ComposeAttachmentSelected(path))
+            } else {
+                Task::none()
+            }
+        }
+
+        Message::OpenMessageWindow(message_id) => {
+            if let Some(id) = state.message_windows.window_for(message_id) {
+                return window::gain_focus(id);
+            }
+            let (id, open) = window::open(window::Settings {
+                size: iced::Size::new(900.0, 800.0),
+                min_size: Some(iced::Size::new(600.0, 480.0)),
+                exit_on_close_request: false,
+                ..Default::default()
+            });
+            state.message_windows.open(message_id, id);
+
+            let client = api_client(state);
+            let load = perform_tracked(
+                state,
+                async move { client.message_detail(message_id).await },
+                move |result| Message::WindowMessageLoaded(message_id, result),
+            );
+            Task::batch([open.discard(), load])
+        }
+
+        Message::WindowMessageLoaded(message_id, result) => {
+            let sender = result
+                .as_ref()
+                .ok()
+                .map(|detail| sender_address(&detail.from_addr).to_string());
+            state
+                .message_windows
+                .loaded(message_id, result.map_err(|e| e.to_string()));
+            match sender {
+                Some(sender) => Task::batch([
+                    fetch_sender_messages(state, &sender),
+                    fetch_avatars(state, [sender]),
+                ]),
+                None => Task::none(),
+            }
+        }
+
+        Message::Quit => {
+            if state.quit_warning().is_none() {
+                return iced::exit();
+            }
+            state.modals.push(Modal::ConfirmQuit);
+            // The prompt shows in the main window
+            match state.main_window {
+                Some(id) => window::gain_focus(id),
+                None => Task::none(),
+            }
+        }
+
+        Message::ConfirmQuit => {
+            // Stop the transfers and delete their partial files so nothing
+            // half-written is left in Downloads
+            for (message_id, attachment_idx, filename) in state.downloads.cancel_unfinished() {
+                crate::api::attachments::remove_partial(message_id, attachment_idx, &filename);
+            }
+            iced::exit()
+        }
+
+        Message::QuitWhenDone => {
+            state.modals.close(Modal::ConfirmQuit);
+            state.quit_when_done = true;
+            state.toast = Some(Toast::info("Quitting once downloads finish"));
+            Task::none()
+        }
+
+        Message::CancelQuit => {
+            state.modals.close(Modal::ConfirmQuit);
+            state.quit_when_done = false;
+            Task::none()
+        }
+
+        Message::CopyToClipboard(contents) => {
+            state.toast = Some(Toast::info("Copied to clipboard"));
+            iced::clipboard::write(contents)
+        }
+
+        // === Activity ===
+        Message::OperationFinished => {
+            state.active_operations = state.active_operations.saturating_sub(1);
+            Task::none()
+        }
+
+        Message::ActivityTick => {
+            state.activity_frame = state.activity_frame.wrapping_add(1);
+            Task::none()
+        }
+
+        // === Keyboard ===
+        Message::KeyPressed {
+            window,
+            key,
+            modifiers,
+            captured,
+        } => {
+            if state.main_window != Some(window) {
+                return handle_window_key_press(window, key, modifiers);
+            }
+            state.text_input_focused = captured;
+            handle_key_press(state, window, key, modifiers)
+        }
+
+        Message::ModifiersChanged(modifiers) => {
+            state.modifiers = modifiers;
+            Task::none()
+        }
+
+        Message::None => Task::none(),
+    }
+}
+
+/// Task that opens the configured default view
+fn landing_view(state: &AppState) -> Task<Message> {
+    match state.default_view {
+        DefaultView::Dashboard => Task::none(),
+        DefaultView::Senders => Task::done(Message::NavigateTo(ViewLevel::Aggregates {
+            view_type: ViewType::Senders,
+        })),
+        DefaultView::Search => Task::done(Message::OpenSearch),
+        DefaultView::Sync => Task::done(Message::OpenSync),
+    }
+}
+
+/// Task that navigates back into a saved session
+///
+/// Message details are fetched before navigating so a message deleted since
+/// the last run falls back to the default view instead of an error page.
+fn restore_session(state: &mut AppState, session: Session) -> Task<Message> {
+    match session.view.clone() {
+        SessionView::Dashboard => Task::none(),
+        SessionView::Aggregates { view_type } => {
+            Task::done(Message::NavigateTo(ViewLevel::Aggregates { view_type }))
+        }
+        SessionView::Messages => {
+            state.messages_offset = session.messages_offset;
+            state.navigation.push(ViewLevel::Messages {
+                filter_description: session.filter_description,
+            });
+            Task::done(Message::FetchMessages {
+                filter_type: session.filter_type,
+                filter_value: session.filter_value,
+            })
+        }
+        SessionView::MessageDetail { message_id } => {
+            let client = api_client(state);
+
+            perform_tracked(
+                state,
+                async move { client.message_detail(message_id).await },
+                move |result| Message::SessionMessageLoaded(session.clone(), result),
+            )
+        }
+        SessionView::Search { query } => {
+            Task::done(Message::OpenSearch).chain(Task::done(Message::SearchQueryChanged(query)))
+        }
+        SessionView::Sync => Task::done(Message::OpenSync),
+        SessionView::Accounts => Task::done(Message::OpenAccounts),
+    }
+}
+
+/// Start adding the next imported account, or wrap up when none are left
+fn next_imported_account(state: &mut AppState, next: Option<String>) -> Task<Message> {
+    state.oauth_response = None;
+    state.polling_device_flow = false;
+
+    match next {
+        Some(email) => {
+            state.add_account_email = email;
+            handle(state, Message::StartAddAccount)
+        }
+        None => {
+            state.adding_account = false;
+            state.add_account_email.clear();
+            let import = &state.account_import;
+            state.toast = Some(Toast::info(format!(
+                "Added {} of {} imported accounts",
+                import.added(),
+                import.entries.len()
+            )));
+            Task::done(Message::FetchSyncStatus)
+        }
+    }
+}
+
+/// Start the next queued download, or quit if that was waiting on this one
+fn download_finished(state: &mut AppState) -> Task<Message> {
+    if state.quit_when_done && state.quit_warning().is_none() {
+        return iced::exit();
+    }
+    start_queued_downloads(state)
+}
+
+/// Raise an OS notification for a sync event
+fn sync_notification(event: &SyncEvent) -> Task<Message> {
+    desktop_notification(event.title(), event.body())
+}
+
+/// Raise an OS notification about sync activity
+///
+/// Where the platform reports clicks (freedesktop), clicking opens the Sync
+/// view. Failures to notify are ignored.
+fn desktop_notification(title: &'static str, body: String) -> Task<Message> {
+    Task::perform(
+        async move {
+            tokio::task::spawn_blocking(move || {
+                let mut notification = notify_rust::Notification::new();
+                notification.appname("msgvault").summary(title).body(&body);
+
+                #[cfg(all(unix, not(target_os = "macos")))]
+                {
+                    let mut clicked = false;
+                    notification.action("default", "Open Sync");
+                    if let Ok(handle) = notification.show() {
+                        handle.wait_for_action(|action| clicked = action == "default");
+                    }
+                    clicked
+                }
+                #[cfg(not(all(unix, not(target_os = "macos"))))]
+                {
+                    let _ = notification.show();
+                    false
+                }
+            })
+            .await
+            .unwrap_or(false)
+        },
+        |clicked| {
+            if clicked {
+                Message::SyncNotificationClicked
+            } else {
+                Message::None
+            }
+        },
+    )
+}
+
+/// Record a failed request
+///
+/// Losing the server after connecting switches to offline mode, keeping
+/// already-loaded data on screen instead of replacing it with an error.
+fn load_failed(state: &mut AppState, error: AppError) {
+    if state.is_connected() && matches!(error, AppError::ConnectionFailed(_)) {
+        state.connectivity = Connectivity::Offline;
+        state.loading = LoadingState::Idle;
+    } else {
+        state.loading = LoadingState::Error(error.to_string());
+    }
+}
+
+/// Actions that change server data, blocked in offline mode
+fn is_write_action(message: &Message) -> bool {
+    matches!(
+        message,
+        Message::ComposeSend
+            | Message::ShowDeleteModal
+            | Message::ConfirmDelete
+            | Message::DeleteOne(_)
+            | Message::TriggerSync(_)
+            | Message::SetSyncPaused { .. }
+            | Message::MarkAllRead
+            | Message::SetReadState { .. }
+            | Message::ApplyLabel { .. }
+            | Message::StartAddAccount
+            | Message::ConfirmRemoveAccount
+    )
+}
+
+/// Fetch avatars for the senders of the loaded message list
+fn fetch_list_avatars(state: &mut AppState) -> Task<Message> {
+    let senders: Vec<String> = state
+        .messages
+        .iter()
+        .map(|m| m.from_email.clone())
+        .collect();
+    fetch_avatars(state, senders)
+}
+
+/// Start avatar fetches for addresses not yet in the cache
+///
+/// Does nothing unless remote avatars are enabled in settings.
+fn fetch_avatars(state: &mut AppState, emails: impl IntoIterator<Item = String>) -> Task<Message> {
+    if !state.load_remote_avatars {
+        return Task::none();
+    }
+
+    let mut tasks = Vec::new();
+    for email in emails {
+        if !state.avatars.begin_fetch(&email) {
+            continue;
+        }
+
+        let client = state.avatar_client.clone();
+        tasks.push(perform_tracked(
+            state,
+            {
+                let email = email.clone();
+                async move { crate::api::fetch_avatar(&client, &email).await }
+            },
+            move |result| Message::AvatarLoaded(email.clone(), result),
+        ));
+    }
+
+    Task::batch(tasks)
+}
+
+/// Open a reply, reply-all or forward of a message
+///
+/// Quotes the message from wherever it is already loaded (the detail
+/// pane, a message window or the open thread), and fetches it otherwise,
+/// e.g. when replying from a list row.
+fn open_compose_from(state: &mut AppState, mode: ComposeMode, message_id: i64) -> Task<Message> {
+    if let Some(msg) = state.loaded_message(message_id).cloned() {
+        compose_from(state, mode, msg);
+        return Task::none();
+    }
+
+    let client = api_client(state);
+    perform_tracked(
+        state,
+        async move { client.message_detail(message_id).await },
+        move |result| Message::ComposeSourceLoaded(mode.clone(), result),
+    )
+}
+
+/// Fill the compose form from the message being answered
+fn compose_from(state: &mut AppState, mode: ComposeMode, msg: MessageDetail) {
+    let from_account = state
+        .sync_accounts
+        .first()
+        .map(|a| a.email.clone())
+        .unwrap_or_default();
+    let quoted = || quote_message(&msg);
+
+    state.compose = match mode {
+        ComposeMode::New => crate::model::ComposeState::open_new(from_account),
+        ComposeMode::Reply => crate::model::ComposeState::open_reply(
+            from_account,
+            msg.id,
+            msg.from_addr.clone(),
+            msg.subject.clone(),
+            quoted(),
+        ),
+        ComposeMode::ReplyAll => {
+            // Combine to and cc, removing our own email
+            let mut all_recipients: Vec<String> = msg.to.clone();
+            all_recipients.push(msg.from_addr.clone());
+            all_recipients.retain(|e| e != &from_account);
+            let cc = msg
+                .cc
+                .iter()
+                .filter(|e| *e != &from_account)
+                .cloned()
+                .collect();
+
+            crate::model::ComposeState::open_reply_all(
+                from_account,
+                msg.id,
+                all_recipients,
+                cc,
+                msg.subject.clone(),
+                quoted(),
+            )
+        }
+        ComposeMode::Forward => {
+            let forward_body = format!(
+                "From: {}\nDate: {}\nSubject: {}\nTo: {}\n\n{}",
+                msg.from_addr,
+                msg.sent_at.format("%b %d, %Y at %H:%M"),
+                msg.subject,
+                msg.to.join(", "),
+                msg.body
+            );
+            crate::model::ComposeState::open_forward(
+                from_account,
+                msg.id,
+                msg.subject.clone(),
+                forward_body,
+            )
+        }
+    };
+}
+
+/// Fetch a sender's recent messages unless they are already cached
+///
+/// Runs in the background; the panel appears once they arrive.
+fn fetch_sender_messages(state: &mut AppState, sender: &str) -> Task<Message> {
+    let key = sender.to_lowercase();
+    if key.is_empty() || state.sender_messages.contains_key(&key) {
+        return Task::none();
+    }
+
+    let client = api_client(state);
+    let sender = sender.to_string();
+    // One extra, since the message being read is usually among them
+    let limit = MORE_FROM_SENDER as i64 + 1;
+    Task::perform(
+        async move {
+            client
+                .messages_filter(ViewType::Senders.as_str(), &sender, None, 0, limit)
+                .await
+        },
+        move |result| Message::SenderMessagesLoaded(key.clone(), result),
+    )
+}
+
+/// Whether the server's certificate differs from the pinned one
+///
+/// A mismatch is kept for the user to review; without a pin yet, the
+/// certificate is recorded.
+fn certificate_changed(state: &mut AppState, fingerprint: Option<String>) -> bool {
+    let Some(fingerprint) = fingerprint.filter(|_| state.pin_certificate) else {
+        return false;
+    };
+    match &state.pinned_certificate {
+        Some(pinned) if *pinned != fingerprint => {
+            state.certificate_mismatch = Some(fingerprint);
+            true
+        }
+        Some(_) => false,
+        None => {
+            state.pinned_certificate = Some(fingerprint);
+            let _ = current_settings(state).save();
+            false
+        }
+    }
+}
+
+/// API client for the connected server with the configured TLS policy
+fn api_client(state: &mut AppState) -> ApiClient {
+    let api_key = (!state.api_key.is_empty()).then_some(state.api_key.as_str());
+    match &state.api_client {
+        Some(client) if client.is_for(&state.server_url, api_key, state.allow_insecure) => {
+            client.clone()
+        }
+        _ => {
+            let client = ApiClient::new(
+                state.server_url.clone(),
+                api_key.map(str::to_string),
+                state.allow_insecure,
+            );
+            state.api_client = Some(client.clone());
+            client
+        }
+    }
+}
+
+/// Build the settings to persist from the current app state
+fn current_settings(state: &AppState) -> Settings {
+    Settings {
+        server_url: state.server_url.clone(),
+        api_key: state.api_key.clone(),
+        allow_insecure: state.allow_insecure,
+        pin_certificate: state.pin_certificate,
+        pinned_certificate: state.pinned_certificate.clone(),
+        default_view: state.default_view,
+        restore_session: state.restore_session,
+        show_key_hints: state.show_key_hints,
+        show_snippets: state.show_snippets,
+        snippet_length: state.snippet_length,
+        max_concurrent_downloads: state.max_concurrent_downloads,
+        auto_open_downloads: state.auto_open_downloads,
+        auto_open_extensions: state.auto_open_extensions.clone(),
+        load_remote_avatars: state.load_remote_avatars,
+        desktop_notifications: state.desktop_notifications,
+        theme: state.theme,
+        byte_units: state.byte_units,
+        reading_pane: state.reading_pane,
+        sidebar_width: state.panel_widths.sidebar,
+        message_list_width: state.panel_widths.message_list,
+        sidebar_collapsed: state.sidebar_collapsed,
+        remote_content_senders: state.remote_content_senders.clone(),
+        blocked_senders: state.blocked_senders.clone(),
+        blocked_sender_display: state.blocked_sender_display,
+        muted_senders: state.muted_senders.clone(),
+        muted_threads: state.muted_threads.clone(),
+        new_messages_checked_at: state.new_messages_checked_at,
+        discovery_ports: state.discovery_ports.clone(),
+        pinned_filters: state.pinned_filters.clone(),
+    }
+}
+
+/// Set the read state of messages optimistically and sync it to the server
+///
+/// `ReadStateUpdated` reverts the change if the request fails.
+fn update_read_state(state: &mut AppState, message_ids: Vec<i64>, read: bool) -> Task<Message> {
+    for msg in state
+        .messages
+        .iter_mut()
+        .chain(state.search_results.iter_mut())
+        .filter(|m| message_ids.contains(&m.id))
+    {
+        msg.is_unread = !read;
+    }
+
+    let client = api_client(state);
+
+    let request_ids = message_ids.clone();
+    perform_tracked(
+        state,
+        async move {
+            if read {
+                client.mark_read(&request_ids).await
+            } else {
+                client.mark_unread(&request_ids).await
+            }
+        },
+        move |result| Message::ReadStateUpdated {
+            message_ids: message_ids.clone(),
+            read,
+            result,
+        },
+    )
+}
+
+/// Run a background operation that counts toward the activity indicator
+///
+/// Same as [`Task::perform`], but bumps `active_operations` now and follows
+/// the result message with [`Message::OperationFinished`] to release it.
+fn perform_tracked<A: Send + 'static>(
+    state: &mut AppState,
+    future: impl Future<Output = A> + Send + 'static,
+    f: impl Fn(A) -> Message + Send + 'static,
+) -> Task<Message> {
+    state.active_operations += 1;
+    Task::perform(future, f).chain(Task::done(Message::OperationFinished))
+}
+
+/// Send the next chunk of a deletion, or report the outcome once all are done
+///
+/// Messages whose chunk failed stay selected so they can be retried.
+fn stage_next_deletion_chunk(state: &mut AppState) -> Task<Message> {
+    let Some(progress) = state.deletion.as_mut() else {
+        return Task::none();
+    };
+
+    if let Some(chunk) = progress.next_chunk() {
+        let client = api_client(state);
+        return perform_tracked(
+            state,
+            async move {
+                let result = client.stage_deletion(&chunk).await;
+                (chunk, result)
+            },
+            |(message_ids, result)| Message::DeletionChunkStaged {
+                message_ids,
+                result,
+            },
+        );
+    }
+
+    let Some(progress) = state.deletion.take() else {
+        return Task::none();
+    };
+    state.selected_messages = progress.failed.iter().copied().collect();
+    state.toast = Some(match &progress.last_error {
+        Some(e) => Toast::error(format!("{} ({})", progress.summary(), e)),
+        None => Toast::info(progress.summary()),
+    });
+    Task::none()
+}
+
+/// Start queued attachment downloads until the concurrency limit is reached
+fn start_queued_downloads(state: &mut AppState) -> Task<Message> {
+    let mut tasks = Vec::new();
+
+    while let Some((message_id, attachment_idx, filename)) =
+        state.downloads.next_queued(state.max_concurrent_downloads)
+    {
+        // Mark as downloading
+        state
+            .downloads
+            .set_downloading(message_id, attachment_idx, 0.0);
+
+        // Downloads share the API client's connection pool; each request
+        // sets its own longer timeout for large files
+        let client = api_client(state);
+
+        // Progress reports stream in ahead of the final result
+        let download = iced::stream::channel(16, move |mut output| async move {
+            let mut progress = output.clone();
+            let result = client
+                .download_attachment(message_id, attachment_idx, &filename, |fraction| {
+                    // Dropped if the UI is behind; the next report catches up
+                    let _ = progress.try_send(Message::DownloadProgress {
+                        message_id,
+                        attachment_idx,
+                        progress: fraction,
+                    });
+                })
+                .await;
+            let _ = output
+                .send(download_result(
+                    message_id,
+                    attachment_idx,
+                    &filename,
+                    result,
+                ))
+                .await;
+        });
+
+        // Cancelling aborts the task; either way its end frees the slot
+        let (download, handle) = Task::run(download, |message| message).abortable();
+        state
+            .downloads
+            .task_started(message_id, attachment_idx, handle);
+
+        // Counts toward the activity indicator, as with perform_tracked
+        state.active_operations += 1;
+        tasks.push(
+            download
+                .chain(Task::done(Message::DownloadStopped {
+                    message_id,
+                    attachment_idx,
+                }))
+                .chain(Task::done(Message::OperationFinished)),
+        );
+    }
+
+    Task::batch(tasks)
+}
+
+/// Message reporting how a download task ended
+fn download_result(
+    message_id: i64,
+    attachment_idx: usize,
+    filename: &str,
+    result: Result<std::path::PathBuf, AppError>,
+) -> Message {
+    match result {
+        Ok(path) => Message::DownloadComplete {
+            message_id,
+            attachment_idx,
+            path,
+        },
+        Err(e) => Message::DownloadFailed {
+            message_id,
+            attachment_idx,
+            error: e.to_string(),
+            partial_bytes: crate::api::attachments::partial_bytes(
+                message_id,
+                attachment_idx,
+                filename,
+            ),
+        },
+    }
+}
+
+/// Move the cursor of whichever list the current view shows
+///
+/// `target` receives the current index and the list length; the result is
+/// clamped to the list bounds. Views without a list are left untouched.
+fn move_list_cursor(state: &mut AppState, target: impl Fn(usize, usize) -> usize) {
+    let (index, len) = match state.navigation.current() {
+        ViewLevel::Aggregates { .. } => {
+            let len = state.filtered_aggregates().len();
+            (&mut state.selected_index, len)
+        }
+        ViewLevel::Messages { .. } => (&mut state.message_selected_index, state.messages.len()),
+        ViewLevel::Search => (&mut state.search_selected_index, state.search_results.len()),
+        _ => return,
+    };
+
+    if len == 0 {
+        return;
+    }
+
+    *index = target(*index, len).min(len - 1);
+}
 
-        Message::ComposeRemoveTo(index) => {
-            state.compose.remove_to(index);
-            Task::none()
-        }
+/// Scroll the aggregate list so the selected row is in view
+///
+/// Only the rows near the viewport are built, so keyboard moves past its
+/// edge have to scroll explicitly.
+fn reveal_selected_aggregate(state: &mut AppState) -> Task<Message> {
+    if !matches!(state.navigation.current(), ViewLevel::Aggregates { .. }) {
+        return Task::none();
+    }
+    let Some(offset) = state
+        .aggregate_window
+        .reveal(state.selected_index, AGGREGATE_ROW_PITCH)
+    else {
+        return Task::none();
+    };
 
-        Message::ComposeCcChanged(input) => {
-            state.compose.cc_input = input;
-            Task::none()
-        }
+    state.aggregate_window.offset = offset;
+    scrollable::scroll_to(
+        scrollable::Id::new(AGGREGATES_SCROLL),
+        scrollable::AbsoluteOffset { x: 0.0, y: offset },
+    )
+}
 
-        Message::ComposeAddCc => {
-            let email = state.compose.cc_input.trim().to_string();
-            state.compose.add_cc(email);
-            state.compose.cc_input.clear();
-            Task::none()
+/// Move the cursor to the first row starting with the type-ahead prefix
+///
+/// Messages match on sender name, sender address or subject; aggregates
+/// match on their key. Comparison is case-insensitive. The cursor stays put
+/// when nothing matches.
+fn jump_to_typeahead_match(state: &mut AppState) {
+    let prefix = match state.typeahead_buffer.as_deref() {
+        Some(buffer) if !buffer.is_empty() => buffer.to_lowercase(),
+        _ => return,
+    };
+    let starts_with = |value: &str| value.to_lowercase().starts_with(&prefix);
+
+    match state.navigation.current() {
+        ViewLevel::Aggregates { .. } => {
+            if let Some(index) = state
+                .filtered_aggregates()
+                .iter()
+                .position(|agg| starts_with(&agg.key))
+            {
+                state.selected_index = index;
+            }
         }
-
-        Message::ComposeRemoveCc(index) => {
-            state.compose.remove_cc(index);
-            Task::none()
+        ViewLevel::Messages { .. } => {
+            if let Some(index) = state.messages.iter().position(|msg| {
+                msg.from_name.as_deref().is_some_and(&starts_with)
+                    || starts_with(&msg.from_email)
+                    || starts_with(&msg.subject)
+            }) {
+                state.message_selected_index = index;
+            }
         }
+        _ => {}
+    }
+}
 
-        Message::ComposeBccChanged(input) => {
-            state.compose.bcc_input = input;
-            Task::none()
-        }
+/// Whether a named key is a bare modifier
+fn is_modifier_key(named: iced::keyboard::key::Named) -> bool {
+    use iced::keyboard::key::Named;
+    matches!(
+        named,
+        Named::Shift | Named::Control | Named::Alt | Named::Super | Named::Meta
+    )
+}
 
-        Message::ComposeAddBcc => {
-            let email = state.compose.bcc_input.trim().to_string();
-            state.compose.add_bcc(email);
-            state.compose.bcc_input.clear();
-            Task::none()
-        }
+/// Sidebar entries in keyboard focus order
+///
+/// Must match the order `view::sidebar::sidebar` draws them in.
+fn sidebar_entries(state: &AppState) -> Vec<Message> {
+    let mut entries = vec![
+        Message::NavigateTo(ViewLevel::Dashboard),
+        Message::OpenSearch,
+        Message::OpenSync,
+        Message::OpenCompose,
+    ];
+    entries.extend((0..state.saved_searches.len()).map(Message::OpenSavedSearch));
+    entries.extend((0..state.pinned_filters.len()).map(Message::OpenPinnedFilter));
+    entries.extend(
+        [
+            ViewType::Senders,
+            ViewType::Domains,
+            ViewType::Labels,
+            ViewType::Time,
+        ]
+        .into_iter()
+        .map(|view_type| Message::NavigateTo(ViewLevel::Aggregates { view_type })),
+    );
+    entries.extend(state.sync_accounts.iter().map(|account| {
+        Message::NavigateTo(ViewLevel::Messages {
+            filter_description: format!("Account: {}", account.email),
+        })
+    }));
+    entries.extend([
+        Message::OpenSettings,
+        Message::OpenAccounts,
+        Message::ShowHelp,
+    ]);
+    entries
+}
 
-        Message::ComposeRemoveBcc(index) => {
-            state.compose.remove_bcc(index);
-            Task::none()
-        }
+/// Handle a key while the sidebar has focus
+///
+/// Returns None for keys the sidebar doesn't use, which fall through to
+/// the global shortcuts.
+fn handle_sidebar_key(state: &mut AppState, key: &Key) -> Option<Task<Message>> {
+    use iced::keyboard::key::Named;
 
-        Message::ComposeSubjectChanged(subject) => {
-            state.compose.subject = subject;
-            state.compose.is_dirty = true;
-            Task::none()
-        }
+    let mut entries = sidebar_entries(state);
+    let last = entries.len().saturating_sub(1);
+    state.sidebar_focus_index = state.sidebar_focus_index.min(last);
 
-        Message::ComposeBodyChanged(body) => {
-            state.compose.body = body;
-            state.compose.is_dirty = true;
-            Task::none()
+    match key {
+        Key::Named(Named::ArrowUp) => {
+            state.sidebar_focus_index = state.sidebar_focus_index.saturating_sub(1);
         }
-
-        Message::ComposeFromChanged(account) => {
-            state.compose.from_account = account;
-            Task::none()
+        Key::Named(Named::ArrowDown) => {
+            state.sidebar_focus_index = (state.sidebar_focus_index + 1).min(last);
         }
-
-        Message::ComposeToggleCcBcc => {
-            state.compose.show_cc_bcc = !state.compose.show_cc_bcc;
-            Task::none()
+        Key::Character(c) if c.as_str() == "k" => {
+            state.sidebar_focus_index = state.sidebar_focus_index.saturating_sub(1);
         }
-
-        Message::ComposeAddAttachment => {
-            // TODO: Open file picker dialog
-            // This would require native file dialog integration
-            Task::none()
+        Key::Character(c) if c.as_str() == "j" => {
+            state.sidebar_focus_index = (state.sidebar_focus_index + 1).min(last);
         }
-
-        Message::ComposeAttachmentSelected(path) => {
-            if let Ok(metadata) = std::fs::metadata(&path) {
-                let filename = path
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_else(|| "attachment".to_string());
-                state.compose.attachments.push(crate::model::AttachmentDraft {
-                    path,
-                    filename,
-                    size_bytes: metadata.len() as i64,
-                    mime_type: None,
-                });
-                state.compose.is_dirty = true;
+        Key::Named(Named::Home) => state.sidebar_focus_index = 0,
+        Key::Named(Named::End) => state.sidebar_focus_index = last,
+        Key::Named(Named::Enter) | Key::Named(Named::Space) => {
+            if entries.is_empty() {
+                return Some(Task::none());
             }
-            Task::none()
+            state.focus_area = FocusArea::Content;
+            let message = entries.swap_remove(state.sidebar_focus_index);
+            return Some(Task::done(message));
         }
-
-        Message::ComposeRemoveAttachment(index) => {
-            if index < state.compose.attachments.len() {
-                state.compose.attachments.remove(index);
-                state.compose.is_dirty = true;
-            }
-            Task::none()
+        Key::Named(Named::Escape) if close_modal_message(state).is_none() => {
+            state.focus_area = FocusArea::Content;
         }
+        _ => return None,
+    }
 
-        Message::ComposeSend => {
-            if !state.compose.can_send() {
-                return Task::none();
-            }
-            state.compose.is_sending = true;
-            state.compose.send_error = None;
+    Some(Task::none())
+}
 
-            // TODO: Implement actual send via API
-            // POST /api/v1/messages/send
-            // For now, just simulate success after a delay
-            Task::none()
-        }
+/// Message closing the topmost open modal or overlay, if any
+///
+/// Follows the overlay order in `view::connected_view`, so Escape always
+/// dismisses what the user sees on top rather than navigating beneath it.
+fn close_modal_message(state: &AppState) -> Option<Message> {
+    if state.open_menu.is_some() {
+        return Some(Message::CloseMenu);
+    }
+    if state.context_menu.is_some() {
+        return Some(Message::CloseContextMenu);
+    }
 
-        Message::ComposeSent(result) => {
-            state.compose.is_sending = false;
-            match result {
-                Ok(_) => {
-                    state.compose.close();
-                }
-                Err(e) => {
-                    state.compose.send_error = Some(e.to_string());
-                }
-            }
-            Task::none()
-        }
+    match state.modals.top() {
+        Some(Modal::Help) => Some(Message::HideHelp),
+        Some(Modal::ConfirmDelete) => Some(Message::HideDeleteModal),
+        Some(Modal::RemoveAccount) => Some(Message::HideRemoveAccountModal),
+        Some(Modal::Downloads) => Some(Message::ToggleDownloadsPanel),
+        Some(Modal::ResetSettings) => Some(Message::HideResetSettingsModal),
+        Some(Modal::ConfirmQuit) => Some(Message::CancelQuit),
+        None if state.label_picker.is_some() => Some(Message::CloseLabelPicker),
+        None if state.compose.is_open => Some(Message::ComposeClose),
+        None => None,
+    }
+}
 
-        Message::ComposeSaveDraft => {
-            // TODO: Implement draft saving via API
-            // POST /api/v1/messages/draft
-            Task::none()
-        }
+/// Show a message in the detail view, from the cache when it was prefetched
+fn open_message_detail(state: &mut AppState, message_id: i64) -> Task<Message> {
+    state
+        .navigation
+        .push(ViewLevel::MessageDetail { message_id });
 
-        Message::ComposeDraftSaved(result) => {
-            match result {
-                Ok(_draft_id) => {
-                    state.compose.is_dirty = false;
-                    // Optionally close or show confirmation
-                }
-                Err(e) => {
-                    state.compose.send_error = Some(format!("Failed to save draft: {}", e));
-                }
-            }
-            Task::none()
-        }
+    if let Some(detail) = state.message_cache.get(message_id) {
+        return handle(state, Message::MessageDetailLoaded(Ok(detail)));
+    }
 
-        Message::ComposeDiscard => {
-            state.compose.close();
-            Task::none()
-        }
+    state.loading = LoadingState::Loading;
+    let client = api_client(state);
+    perform_tracked(
+        state,
+        async move { client.message_detail(message_id).await },
+        Message::MessageDetailLoaded,
+    )
+}
 
-        Message::ComposeClose => {
-            if state.compose.is_dirty && state.compose.has_content() {
-                // TODO: Show confirmation dialog
-                // For now, just close
-                state.compose.close();
-            } else {
-                state.compose.close();
-            }
-            Task::none()
-        }
+/// Load the labels offered by the label picker
+fn fetch_label_list(state: &mut AppState) -> Task<Message> {
+    let client = api_client(state);
+    let etag = state.aggregate_cache.etag(&LABEL_LIST_QUERY);
+    perform_tracked(
+        state,
+        async move {
+            let (view_type, sort_field, sort_dir) = LABEL_LIST_QUERY;
+            client
+                .aggregates(view_type, sort_field, sort_dir, etag.as_deref())
+                .await
+        },
+        Message::LabelListLoaded,
+    )
+}
 
-        // === Keyboard ===
-        Message::KeyPressed(key, modifiers) => handle_key_press(state, key, modifiers),
+/// Open a URL in the default browser
+fn open_url(url: &str) {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = std::process::Command::new("open").arg(url).spawn();
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let _ = std::process::Command::new("cmd")
+            .args(["/C", "start", "", url])
+            .spawn();
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let _ = std::process::Command::new("xdg-open").arg(url).spawn();
+    }
+}
 
-        Message::None => Task::none(),
+/// Show a file selected in Finder, Explorer, or (elsewhere) its folder
+///
+/// Linux file managers have no common way to select a file, so the
+/// containing folder is opened instead.
+fn reveal_in_file_manager(path: &std::path::Path) {
+    if cfg!(target_os = "macos") {
+        let _ = std::process::Command::new("open")
+            .arg("-R")
+            .arg(path)
+            .spawn();
+    } else if cfg!(target_os = "windows") {
+        let _ = std::process::Command::new("explorer")
+            .arg(format!("/select,{}", path.display()))
+            .spawn();
+    } else if let Some(parent) = path.parent() {
+        let _ = open::that(parent);
+    }
+}
+
+/// The window compose is shown in, which files can be dropped onto
+fn compose_drop_window(state: &AppState) -> Option<window::Id> {
+    if state.compose.is_open {
+        state.compose_window.or(state.main_window)
+    } else {
+        None
+    }
+}
+
+/// Handle keys pressed in a compose or message window
+///
+/// These windows have no shortcuts of their own; Escape or Cmd+W closes
+/// them and Cmd+Q still quits.
+fn handle_window_key_press(window: window::Id, key: Key, modifiers: Modifiers) -> Task<Message> {
+    match &key {
+        Key::Named(iced::keyboard::key::Named::Escape) => Task::done(Message::CloseWindow(window)),
+        Key::Character(c) if modifiers.command() && !modifiers.alt() => match c.as_str() {
+            "w" => Task::done(Message::CloseWindow(window)),
+            "q" => Task::done(Message::Quit),
+            _ => Task::none(),
+        },
+        _ => Task::none(),
     }
 }
 
-/// Handle keyboard shortcuts
-fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Task<Message> {
+/// Handle keyboard shortcuts in the main window
+fn handle_key_press(
+    state: &mut AppState,
+    window: window::Id,
+    key: Key,
+    modifiers: Modifiers,
+) -> Task<Message> {
+    // Application chords from the menu bar, on Cmd (macOS) or Ctrl
+    if modifiers.command() && !modifiers.alt() {
+        if let Key::Character(c) = &key {
+            match c.as_str() {
+                "q" => return Task::done(Message::Quit),
+                "w" => return Task::done(Message::CloseWindow(window)),
+                "," if state.is_connected() => return Task::done(Message::OpenSettings),
+                "f" if state.is_connected() => return Task::done(Message::OpenSearch),
+                "\\" if state.is_connected() => return Task::done(Message::ToggleSidebar),
+                "]" if state.is_connected() => return Task::done(Message::CycleReadingPane),
+                _ => {}
+            }
+        }
+    }
+
     // Only handle keys when connected
     if !state.is_connected() {
         return Task::none();
@@ -1449,10 +4062,82 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
     let in_thread = matches!(state.navigation.current(), ViewLevel::Thread { .. });
     let in_search = matches!(state.navigation.current(), ViewLevel::Search);
 
+    // Type-ahead captures printable keys; anything else ends it and is handled normally
+    if state.typeahead_buffer.is_some() {
+        if !(in_aggregates || in_messages) {
+            state.typeahead_buffer = None;
+            state.typeahead_last_input = None;
+        } else if !modifiers.control() && !modifiers.command() && !modifiers.alt() {
+            match &key {
+                Key::Character(c) => return Task::done(Message::TypeaheadInput(c.to_string())),
+                Key::Named(iced::keyboard::key::Named::Space) => {
+                    return Task::done(Message::TypeaheadInput(" ".to_string()))
+                }
+                Key::Named(iced::keyboard::key::Named::Backspace) => {
+                    return Task::done(Message::TypeaheadBackspace)
+                }
+                Key::Named(iced::keyboard::key::Named::Escape) => {
+                    return Task::done(Message::CancelTypeahead)
+                }
+                _ => {
+                    state.typeahead_buffer = None;
+                    state.typeahead_last_input = None;
+                }
+            }
+        } else {
+            state.typeahead_buffer = None;
+            state.typeahead_last_input = None;
+        }
+    }
+
+    // While a text field has focus, plain keys are typing; only Escape and
+    // chords act globally. Character shortcuts never fire with Ctrl/Cmd/Alt,
+    // so system chords (copy, select all) are left alone.
+    let is_chord = modifiers.control() || modifiers.command() || modifiers.alt();
+    let is_escape = matches!(key, Key::Named(iced::keyboard::key::Named::Escape));
+    if state.text_input_focused && !is_chord && !is_escape {
+        return Task::none();
+    }
+    if is_chord && matches!(key, Key::Character(_)) {
+        return Task::none();
+    }
+
+    // The label picker's filter input takes the keyboard while open
+    if state.label_picker.is_some() {
+        return match key {
+            Key::Named(iced::keyboard::key::Named::Escape) => Task::done(Message::CloseLabelPicker),
+            _ => Task::none(),
+        };
+    }
+
+    // Keyboard use shows the focus ring until the next click
+    if !matches!(key, Key::Named(named) if is_modifier_key(named)) {
+        state.focus_visible = true;
+    }
+
+    // F6 moves focus between the sidebar and the content
+    if matches!(key, Key::Named(iced::keyboard::key::Named::F6)) {
+        state.focus_area = match state.focus_area {
+            FocusArea::Content => FocusArea::Sidebar,
+            FocusArea::Sidebar => FocusArea::Content,
+        };
+        return Task::none();
+    }
+
+    if state.focus_area == FocusArea::Sidebar {
+        if let Some(task) = handle_sidebar_key(state, &key) {
+            return task;
+        }
+    }
+
+    let keymap = &state.keymap;
+
     match key {
-        // Escape - go back
+        // Escape - close the topmost modal, or go back when none is open
         Key::Named(iced::keyboard::key::Named::Escape) => {
-            if state.navigation.can_go_back() {
+            if let Some(close) = close_modal_message(state) {
+                Task::done(close)
+            } else if state.navigation.can_go_back() {
                 Task::done(Message::GoBack)
             } else {
                 Task::none()
@@ -1525,6 +4210,60 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
             }
         }
 
+        // g - type-ahead jump to a row by prefix
+        Key::Character(ref c)
+            if keymap.matches(Action::JumpTo, c, modifiers) && (in_aggregates || in_messages) =>
+        {
+            Task::done(Message::StartTypeahead)
+        }
+
+        // v - toggle heatmap/list layout for the Time aggregate
+        Key::Character(ref c)
+            if keymap.matches(Action::ToggleTimeLayout, c, modifiers)
+                && matches!(
+                    state.navigation.current(),
+                    ViewLevel::Aggregates {
+                        view_type: ViewType::Time
+                    }
+                ) =>
+        {
+            Task::done(Message::ToggleTimeLayout)
+        }
+
+        // Home/End - jump to first/last row
+        Key::Named(iced::keyboard::key::Named::Home) => {
+            if in_aggregates || in_messages || in_search {
+                Task::done(Message::SelectFirst)
+            } else {
+                Task::none()
+            }
+        }
+
+        Key::Named(iced::keyboard::key::Named::End) => {
+            if in_aggregates || in_messages || in_search {
+                Task::done(Message::SelectLast)
+            } else {
+                Task::none()
+            }
+        }
+
+        // PageUp/PageDown - move selection by a page
+        Key::Named(iced::keyboard::key::Named::PageUp) => {
+            if in_aggregates || in_messages || in_search {
+                Task::done(Message::PageSelectUp)
+            } else {
+                Task::none()
+            }
+        }
+
+        Key::Named(iced::keyboard::key::Named::PageDown) => {
+            if in_aggregates || in_messages || in_search {
+                Task::done(Message::PageSelectDown)
+            } else {
+                Task::none()
+            }
+        }
+
         // Left/Right - prev/next message in detail view
         Key::Named(iced::keyboard::key::Named::ArrowLeft) => {
             if in_detail {
@@ -1543,7 +4282,7 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
         }
 
         // j/k - vim-style navigation
-        Key::Character(ref c) if c == "j" && !modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::MoveDown, c, modifiers) => {
             if in_aggregates {
                 Task::done(Message::SelectNext)
             } else if in_messages {
@@ -1559,7 +4298,7 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
             }
         }
 
-        Key::Character(ref c) if c == "k" && !modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::MoveUp, c, modifiers) => {
             if in_aggregates {
                 Task::done(Message::SelectPrevious)
             } else if in_messages {
@@ -1578,12 +4317,12 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
         }
 
         // / - open search (not in search view)
-        Key::Character(ref c) if c == "/" && !in_search => {
+        Key::Character(ref c) if keymap.matches(Action::Search, c, modifiers) && !in_search => {
             Task::done(Message::OpenSearch)
         }
 
         // n/p - next/prev page in messages
-        Key::Character(ref c) if c == "n" && !modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::NextPage, c, modifiers) => {
             if in_messages {
                 Task::done(Message::NextPage)
             } else {
@@ -1591,7 +4330,7 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
             }
         }
 
-        Key::Character(ref c) if c == "p" && !modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::PreviousPage, c, modifiers) => {
             if in_messages {
                 Task::done(Message::PreviousPage)
             } else {
@@ -1600,7 +4339,7 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
         }
 
         // s - toggle sort field (aggregates only)
-        Key::Character(ref c) if c == "s" && !modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::ToggleSortField, c, modifiers) => {
             if in_aggregates {
                 Task::done(Message::ToggleSortField)
             } else {
@@ -1609,7 +4348,7 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
         }
 
         // r - toggle sort direction (aggregates only)
-        Key::Character(ref c) if c == "r" && !modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::ToggleSortDirection, c, modifiers) => {
             if in_aggregates {
                 Task::done(Message::ToggleSortDirection)
             } else {
@@ -1617,15 +4356,9 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
             }
         }
 
-        // q - quit (handled by window, but we could show confirmation)
-        Key::Character(ref c) if c == "q" && !modifiers.shift() => {
-            // For now, do nothing - quit is handled by window close
-            Task::none()
-        }
-
         // ? - help
-        Key::Character(ref c) if c == "?" => {
-            if state.show_help_modal {
+        Key::Character(ref c) if keymap.matches(Action::Help, c, modifiers) => {
+            if state.modals.is_open(Modal::Help) {
                 Task::done(Message::HideHelp)
             } else {
                 Task::done(Message::ShowHelp)
@@ -1642,7 +4375,7 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
         }
 
         // A (shift) - select all visible
-        Key::Character(ref c) if c == "A" && modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::SelectAll, c, modifiers) => {
             if in_messages || in_search {
                 Task::done(Message::SelectAll)
             } else {
@@ -1651,12 +4384,12 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
         }
 
         // x - clear selection
-        Key::Character(ref c) if c == "x" && !modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::ClearSelection, c, modifiers) => {
             Task::done(Message::ClearSelection)
         }
 
         // d - show delete confirmation for selected
-        Key::Character(ref c) if c == "d" && !modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::Delete, c, modifiers) => {
             if !state.selected_messages.is_empty() {
                 Task::done(Message::ShowDeleteModal)
             } else {
@@ -1664,28 +4397,66 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
             }
         }
 
+        // M (shift) - mark all loaded messages as read
+        Key::Character(ref c) if keymap.matches(Action::MarkAllRead, c, modifiers) => {
+            if in_messages || in_search {
+                Task::done(Message::MarkAllRead)
+            } else {
+                Task::none()
+            }
+        }
+
+        // l - label the selection (or the focused message)
+        Key::Character(ref c) if keymap.matches(Action::Label, c, modifiers) => {
+            if in_messages || in_search || in_detail {
+                Task::done(Message::OpenLabelPicker)
+            } else {
+                Task::none()
+            }
+        }
+
+        // D (shift) - downloads panel
+        Key::Character(ref c) if keymap.matches(Action::Downloads, c, modifiers) => {
+            Task::done(Message::ToggleDownloadsPanel)
+        }
+
+        // Y (shift) - copy the focused sender or domain (Yank)
+        Key::Character(ref c) if keymap.matches(Action::CopyKey, c, modifiers) => {
+            match state.navigation.current() {
+                ViewLevel::Aggregates {
+                    view_type: ViewType::Senders | ViewType::Domains,
+                } => match state.filtered_aggregates().get(state.selected_index) {
+                    Some(agg) => Task::done(Message::CopyToClipboard(agg.key.clone())),
+                    None => Task::none(),
+                },
+                _ => Task::none(),
+            }
+        }
+
         // y - open sync status view (sYnc)
-        Key::Character(ref c) if c == "y" && !modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::Sync, c, modifiers) => {
             Task::done(Message::OpenSync)
         }
 
         // a - open accounts view
-        Key::Character(ref c) if c == "a" && !modifiers.shift() => {
+        Key::Character(ref c) if keymap.matches(Action::Accounts, c, modifiers) => {
             Task::done(Message::OpenAccounts)
         }
 
         // comma - open settings (standard macOS shortcut)
-        Key::Character(ref c) if c == "," => {
+        Key::Character(ref c) if keymap.matches(Action::Settings, c, modifiers) => {
             Task::done(Message::OpenSettings)
         }
 
         // c - compose new message
-        Key::Character(ref c) if c == "c" && !modifiers.shift() && !state.compose.is_open => {
+        Key::Character(ref c)
+            if keymap.matches(Action::Compose, c, modifiers) && !state.compose.is_open =>
+        {
             Task::done(Message::OpenCompose)
         }
 
         // r - reply (when viewing message detail)
-        Key::Character(ref c) if c == "r" && !modifiers.shift() && in_detail => {
+        Key::Character(ref c) if keymap.matches(Action::Reply, c, modifiers) && in_detail => {
             if let ViewLevel::MessageDetail { message_id } = state.navigation.current() {
                 Task::done(Message::OpenReply(*message_id))
             } else {
@@ -1694,7 +4465,7 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
         }
 
         // R (shift) - reply all (when viewing message detail)
-        Key::Character(ref c) if c == "R" && modifiers.shift() && in_detail => {
+        Key::Character(ref c) if keymap.matches(Action::ReplyAll, c, modifiers) && in_detail => {
             if let ViewLevel::MessageDetail { message_id } = state.navigation.current() {
                 Task::done(Message::OpenReplyAll(*message_id))
             } else {
@@ -1703,7 +4474,7 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
         }
 
         // f - forward (when viewing message detail)
-        Key::Character(ref c) if c == "f" && !modifiers.shift() && in_detail => {
+        Key::Character(ref c) if keymap.matches(Action::Forward, c, modifiers) && in_detail => {
             if let ViewLevel::MessageDetail { message_id } = state.navigation.current() {
                 Task::done(Message::OpenForward(*message_id))
             } else {
@@ -1712,7 +4483,7 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
         }
 
         // t - view full thread (when viewing message detail)
-        Key::Character(ref c) if c == "t" && !modifiers.shift() && in_detail => {
+        Key::Character(ref c) if keymap.matches(Action::ViewThread, c, modifiers) && in_detail => {
             if let Some(msg) = &state.current_message {
                 if let Some(thread_id) = &msg.thread_id {
                     Task::done(Message::ViewThread(thread_id.clone()))
@@ -1725,12 +4496,12 @@ fn handle_key_press(state: &mut AppState, key: Key, modifiers: Modifiers) -> Tas
         }
 
         // e - expand all (in thread view)
-        Key::Character(ref c) if c == "e" && !modifiers.shift() && in_thread => {
+        Key::Character(ref c) if keymap.matches(Action::ExpandAll, c, modifiers) && in_thread => {
             Task::done(Message::ExpandAllThread)
         }
 
         // E (shift+e) - collapse all (in thread view)
-        Key::Character(ref c) if c == "E" && modifiers.shift() && in_thread => {
+        Key::Character(ref c) if keymap.matches(Action::CollapseAll, c, modifiers) && in_thread => {
             Task::done(Message::CollapseAllThread)
         }
 
@@ -1,23 +1,29 @@
 //! Account management view
 //!
-//! Displays account list with add/remove functionality and OAuth flow UI.
+//! Displays account list with add/remove functionality, bulk import from a
+//! file and OAuth flow UI.
 
 use crate::api::types::{AccountSyncStatus, OAuthInitResponse};
 use crate::message::Message;
+use crate::model::account_import::ImportStatus;
+use crate::model::AccountImport;
 use crate::theme::{colors, components, icons, spacing, typography};
-use crate::view::widgets::avatar;
+use crate::view::widgets::{avatar, confirm_modal, empty_state, with_tooltip, Position};
 use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
 use iced::{Background, Border, Element, Length, Theme};
 
+/// ID of the add-account email field, focused by the empty state's action
+pub const ADD_ACCOUNT_INPUT: &str = "add-account-email";
+
 /// Render the accounts view
 pub fn accounts_view<'a>(
     accounts: &'a [AccountSyncStatus],
+    filter: &'a str,
     is_loading: bool,
     add_email: &'a str,
     adding_account: bool,
     oauth_response: Option<&'a OAuthInitResponse>,
-    show_remove_modal: bool,
-    removing_account: Option<&'a str>,
+    import: &'a AccountImport,
 ) -> Element<'a, Message> {
     // Header
     let title = text("Accounts")
@@ -28,6 +34,13 @@ pub fn accounts_view<'a>(
     // Add account section
     let add_section = add_account_section(add_email, adding_account, oauth_response);
 
+    // Progress of an import from a file, once one has started
+    let import_progress: Element<'a, Message> = if import.entries.is_empty() {
+        Space::with_height(0).into()
+    } else {
+        import_section(import)
+    };
+
     // Account list
     let account_list: Element<'a, Message> = if is_loading && accounts.is_empty() {
         container(
@@ -38,59 +51,94 @@ pub fn accounts_view<'a>(
         .padding(spacing::XL)
         .into()
     } else if accounts.is_empty() {
-        container(
-            text("No accounts configured. Add one below.")
-                .size(typography::SIZE_SM)
-                .style(components::text_muted),
+        empty_state(
+            icons::ACCOUNTS,
+            "No accounts yet",
+            "Add an email account to start archiving it",
+            Some(("Add account", Message::FocusAddAccount)),
         )
-        .padding(spacing::XL)
-        .into()
     } else {
         let account_rows: Vec<Element<'a, Message>> = accounts
             .iter()
+            .filter(|a| a.matches(filter))
             .map(account_row)
             .collect();
 
-        scrollable(column(account_rows).spacing(spacing::SM))
-            .height(Length::Fill)
-            .into()
+        column![
+            account_filter_bar(filter, account_rows.len(), accounts.len()),
+            if account_rows.is_empty() {
+                no_matching_accounts()
+            } else {
+                scrollable(column(account_rows).spacing(spacing::SM))
+                    .height(Length::Fill)
+                    .into()
+            },
+        ]
+        .spacing(spacing::MD)
+        .into()
     };
 
-    // Keyboard hints in FONT_MONO
-    let hints = text("a: add account | Esc: back")
-        .size(typography::SIZE_2XS)
-        .font(typography::FONT_MONO)
-        .style(components::text_muted);
-
-    let main_content: Element<'a, Message> = column![
+    column![
         title,
         Space::with_height(spacing::XL),
         account_list,
         Space::with_height(spacing::XL),
         add_section,
-        Space::with_height(spacing::SM),
-        hints,
+        import_progress,
     ]
     .spacing(spacing::XS)
     .padding(spacing::XL)
     .width(Length::Fill)
     .height(Length::Fill)
-    .into();
-
-    // Overlay remove confirmation modal if showing
-    if show_remove_modal {
-        if let Some(email) = removing_account {
-            iced::widget::stack![
-                main_content,
-                remove_confirmation_modal(email)
-            ]
-            .into()
-        } else {
-            main_content
-        }
+    .into()
+}
+
+/// Filter box for finding an account by email or name
+///
+/// Shared with the sync view; both filter by the same text.
+pub fn account_filter_bar<'a>(filter: &'a str, shown: usize, total: usize) -> Element<'a, Message> {
+    let input = text_input("Filter accounts by email or name", filter)
+        .on_input(Message::AccountFilterChanged)
+        .padding(spacing::SM)
+        .width(Length::Fill)
+        .style(components::text_input_style);
+
+    let clear: Element<'a, Message> = if filter.is_empty() {
+        Space::with_width(0).into()
     } else {
-        main_content
-    }
+        with_tooltip(
+            button(text(icons::DELETE).size(typography::SIZE_XS))
+                .padding([spacing::XS, spacing::SM])
+                .style(components::button_ghost)
+                .on_press(Message::AccountFilterChanged(String::new())),
+            "Clear filter",
+            Position::Bottom,
+        )
+    };
+
+    let count = text(if filter.is_empty() {
+        format!("{} accounts", total)
+    } else {
+        format!("{} of {}", shown, total)
+    })
+    .size(typography::SIZE_XS)
+    .style(components::text_muted);
+
+    row![input, clear, count]
+        .spacing(spacing::SM)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+/// Placeholder shown when the filter hides every account
+pub fn no_matching_accounts<'a>() -> Element<'a, Message> {
+    container(
+        text("No accounts match the filter")
+            .size(typography::SIZE_SM)
+            .style(components::text_muted),
+    )
+    .padding(spacing::XL)
+    .into()
 }
 
 /// Add account section with email input and OAuth status
@@ -136,6 +184,7 @@ fn add_account_section<'a>(
 
     // Normal add account form
     let email_input = text_input("Email address (e.g., user@gmail.com)", email)
+        .id(text_input::Id::new(ADD_ACCOUNT_INPUT))
         .on_input(Message::AddAccountEmailChanged)
         .padding(spacing::MD)
         .width(Length::Fill)
@@ -152,12 +201,105 @@ fn add_account_section<'a>(
             .on_press(Message::StartAddAccount)
     };
 
+    let import_button = with_tooltip(
+        button(text("Import from File\u{2026}").size(typography::SIZE_SM))
+            .padding([spacing::SM, spacing::LG])
+            .style(components::button_secondary)
+            .on_press(Message::ImportAccounts),
+        "Add every address in a text or CSV file, one at a time",
+        Position::Top,
+    );
+
     container(
         column![
             section_title,
             Space::with_height(spacing::SM),
-            row![email_input, Space::with_width(spacing::SM), add_button]
-                .align_y(iced::Alignment::Center),
+            row![
+                email_input,
+                Space::with_width(spacing::SM),
+                add_button,
+                Space::with_width(spacing::SM),
+                import_button,
+            ]
+            .align_y(iced::Alignment::Center),
+        ]
+        .spacing(spacing::XS),
+    )
+    .style(section_style)
+    .padding(spacing::LG)
+    .width(Length::Fill)
+    .into()
+}
+
+/// Imported accounts with the status of each
+fn import_section(import: &AccountImport) -> Element<'_, Message> {
+    let title = text(format!(
+        "Import \u{00B7} {} of {} added",
+        import.added(),
+        import.entries.len()
+    ))
+    .size(typography::SIZE_MD)
+    .font(typography::FONT_MEDIUM)
+    .style(components::text_primary);
+
+    // Browser sign-ins can't be detected, so the user moves on
+    let next_button: Element<'_, Message> =
+        if import.current_status() == Some(&ImportStatus::InBrowser) {
+            button(text("Next Account").size(typography::SIZE_SM))
+                .padding([spacing::SM, spacing::LG])
+                .style(components::button_primary)
+                .on_press(Message::NextImportedAccount)
+                .into()
+        } else {
+            Space::with_width(0).into()
+        };
+
+    let cancel_button = button(
+        text(if import.is_active() {
+            "Cancel Import"
+        } else {
+            "Dismiss"
+        })
+        .size(typography::SIZE_SM),
+    )
+    .padding([spacing::SM, spacing::LG])
+    .style(components::button_secondary)
+    .on_press(Message::CancelAccountImport);
+
+    let rows = column(import.entries.iter().map(|entry| {
+        let status_style = match entry.status {
+            ImportStatus::Added => components::text_success,
+            ImportStatus::Failed(_) => components::text_error,
+            ImportStatus::Authorizing | ImportStatus::InBrowser => components::text_accent,
+            ImportStatus::Queued | ImportStatus::Skipped => components::text_muted,
+        };
+        row![
+            text(entry.email.as_str())
+                .size(typography::SIZE_SM)
+                .style(components::text_primary)
+                .width(Length::FillPortion(2)),
+            text(entry.status.label())
+                .size(typography::SIZE_XS)
+                .style(status_style)
+                .width(Length::FillPortion(1)),
+        ]
+        .align_y(iced::Alignment::Center)
+        .into()
+    }))
+    .spacing(spacing::XS);
+
+    container(
+        column![
+            row![
+                title,
+                Space::with_width(Length::Fill),
+                next_button,
+                Space::with_width(spacing::SM),
+                cancel_button,
+            ]
+            .align_y(iced::Alignment::Center),
+            Space::with_height(spacing::SM),
+            container(scrollable(rows)).max_height(200.0),
         ]
         .spacing(spacing::XS),
     )
@@ -256,10 +398,10 @@ fn account_row(account: &AccountSyncStatus) -> Element<'_, Message> {
 
     // Status badge with RADIUS_SM
     let status_color = match account.status {
-        crate::api::types::SyncState::Idle => colors::ACCENT_SUCCESS,
-        crate::api::types::SyncState::Running => colors::ACCENT_INFO,
-        crate::api::types::SyncState::Paused => colors::ACCENT_WARNING,
-        crate::api::types::SyncState::Error => colors::ACCENT_ERROR,
+        crate::api::types::SyncState::Idle => colors::accent_success(),
+        crate::api::types::SyncState::Running => colors::accent_info(),
+        crate::api::types::SyncState::Paused => colors::accent_warning(),
+        crate::api::types::SyncState::Error => colors::accent_error(),
     };
 
     let status_badge = container(
@@ -282,13 +424,14 @@ fn account_row(account: &AccountSyncStatus) -> Element<'_, Message> {
         ..Default::default()
     });
 
-    let remove_button = button(
-        text(icons::DELETE)
-            .size(typography::SIZE_SM)
-    )
-    .padding([spacing::XS, spacing::SM])
-    .style(components::button_danger)
-    .on_press(Message::ShowRemoveAccountModal(account.email.clone()));
+    let remove_button = with_tooltip(
+        button(text(icons::DELETE).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::SM])
+            .style(components::button_danger)
+            .on_press(Message::ShowRemoveAccountModal(account.email.clone())),
+        "Remove account",
+        Position::Left,
+    );
 
     let left_col = row![
         avatar_widget,
@@ -314,75 +457,28 @@ fn account_row(account: &AccountSyncStatus) -> Element<'_, Message> {
 }
 
 /// Remove account confirmation modal
-fn remove_confirmation_modal(email: &str) -> Element<'static, Message> {
-    // Semi-transparent backdrop
-    let backdrop = container(Space::new(Length::Fill, Length::Fill))
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .style(components::modal_backdrop_style);
-
-    // Modal dialog
-    let title = text("Remove Account")
-        .size(typography::SIZE_LG)
-        .font(typography::FONT_MEDIUM)
-        .style(components::text_primary);
-
-    let message = text(format!(
-        "Are you sure you want to remove {}?",
-        email
-    ))
-    .size(typography::SIZE_SM)
-    .style(components::text_secondary);
-
-    let warning = text("This will stop syncing this account. Existing messages will not be deleted.")
-        .size(typography::SIZE_XS)
-        .style(components::text_muted);
-
-    let cancel_button = button(text("Cancel").size(typography::SIZE_SM))
-        .padding([spacing::SM, spacing::LG])
-        .style(components::button_secondary)
-        .on_press(Message::HideRemoveAccountModal);
-
-    let confirm_button = button(text("Remove").size(typography::SIZE_SM))
-        .padding([spacing::SM, spacing::LG])
-        .style(components::button_danger)
-        .on_press(Message::ConfirmRemoveAccount);
-
-    let buttons = row![cancel_button, Space::with_width(spacing::SM), confirm_button]
-        .align_y(iced::Alignment::Center);
-
-    let dialog_content = column![
-        title,
-        Space::with_height(spacing::LG),
-        message,
-        Space::with_height(spacing::SM),
-        warning,
-        Space::with_height(spacing::XL),
-        buttons,
-    ]
-    .spacing(spacing::XS)
-    .padding(spacing::XL)
-    .align_x(iced::Alignment::Center);
-
-    let dialog = container(dialog_content)
-        .style(components::modal_dialog_style)
-        .padding(spacing::SM);
-
-    iced::widget::stack![
-        backdrop,
-        iced::widget::center(dialog)
-    ]
-    .into()
+pub fn remove_confirmation_modal(email: &str) -> Element<'static, Message> {
+    confirm_modal(
+        "Remove Account",
+        format!(
+            "Are you sure you want to remove {}?\n\nThis will stop syncing this account. Existing messages will not be deleted.",
+            email
+        ),
+        "Remove",
+        Message::ConfirmRemoveAccount,
+        Message::HideRemoveAccountModal,
+        true,
+    )
 }
 
 /// Section container style
 fn section_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(colors::BG_SURFACE)),
+        background: Some(Background::Color(colors::bg_surface())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             width: 1.0,
-            color: colors::BORDER_SUBTLE,
+            color: colors::border_subtle(),
         },
         ..Default::default()
     }
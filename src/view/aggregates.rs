@@ -1,64 +1,164 @@
 //! Aggregates list view
 //!
 //! Displays a scrollable list of aggregate rows (senders, domains, labels, etc.)
-//! with column headers and keyboard hints.
+//! with column headers, a filter box and a type-ahead prompt. Only the rows
+//! near the viewport are built, since a large archive can have tens of
+//! thousands of senders.
 
 use crate::api::types::{AggregateRow, SortDirection, SortField, ViewType};
 use crate::message::Message;
+use crate::model::{Action, AppState, ContextTarget, Keymap};
 use crate::theme::{colors, components, icons, spacing, typography};
-use crate::view::widgets::format_bytes;
-use iced::widget::{column, container, row, scrollable, text, Space};
+use crate::view::widgets::{
+    badge, focus_ring, format_bytes, heatmap, icon_button_with_tooltip, tooltip_label,
+    with_tooltip, BadgeStyle, Position,
+};
+use iced::widget::{
+    button, column, container, mouse_area, row, scrollable, text, text_input, Space,
+};
 use iced::{Background, Border, Element, Length};
 
+/// ID of the aggregate list's scrollable, for scrolling to the selection
+pub const AGGREGATES_SCROLL: &str = "aggregates-list";
+
+/// Height of each aggregate row including the gap below it; rows are fixed
+/// to this so the visible range can be computed from the scroll offset
+pub const AGGREGATE_ROW_PITCH: f32 = 38.0;
+
 /// Render the aggregates list view
-pub fn aggregates_view<'a>(
-    view_type: &ViewType,
-    aggregates: &'a [AggregateRow],
-    selected_index: usize,
-    sort_field: SortField,
-    sort_dir: SortDirection,
-) -> Element<'a, Message> {
+pub fn aggregates_view<'a>(view_type: &ViewType, state: &'a AppState) -> Element<'a, Message> {
+    let aggregates = &state.aggregates;
+    let selected_index = state.selected_index;
+    let sort_field = state.sort_field;
+    let sort_dir = state.sort_dir;
+    let typeahead = state.typeahead_buffer.as_deref();
+    let show_heatmap = state.time_heatmap;
+    let show_focus = state.content_focus_visible();
+
     // Header with view type and sort info
-    let header = header_row(view_type, sort_field, sort_dir);
+    let header = header_row(view_type, sort_field, sort_dir, &state.keymap);
 
-    // Column headers
-    let column_headers = column_header_row();
+    // Time buckets render as a calendar heatmap unless the list is toggled on
+    let heatmap = if *view_type == ViewType::Time && show_heatmap {
+        heatmap(aggregates, selected_index)
+    } else {
+        None
+    };
+
+    // Filter box and column headers (hidden in heatmap layout)
+    let filtered = state.filtered_aggregates();
+    let (filter_bar, column_headers): (Element<'a, Message>, Element<'a, Message>) =
+        if heatmap.is_some() {
+            (Space::with_height(0).into(), Space::with_height(0).into())
+        } else {
+            (
+                filter_bar(
+                    view_type,
+                    &state.aggregate_filter,
+                    filtered.len(),
+                    aggregates.len(),
+                ),
+                column_header_row(),
+            )
+        };
 
     // Scrollable list of aggregate rows
-    let list_content: Element<'a, Message> = if aggregates.is_empty() {
+    let list_content: Element<'a, Message> = if let Some(heatmap) = heatmap {
+        heatmap_panel(heatmap, aggregates.get(selected_index))
+    } else if filtered.is_empty() {
+        let empty = if aggregates.is_empty() {
+            "No data available"
+        } else {
+            "No rows match the filter"
+        };
         container(
-            text("No data available")
+            text(empty)
                 .size(typography::SIZE_SM)
                 .style(components::text_muted),
         )
         .padding(spacing::XL)
         .into()
     } else {
-        let rows: Vec<Element<'a, Message>> = aggregates
+        let copyable = matches!(view_type, ViewType::Senders | ViewType::Domains);
+        let visible = state
+            .aggregate_window
+            .visible_rows(filtered.len(), AGGREGATE_ROW_PITCH);
+        let above = visible.start as f32 * AGGREGATE_ROW_PITCH;
+        let below = (filtered.len() - visible.end) as f32 * AGGREGATE_ROW_PITCH;
+
+        let rows: Vec<Element<'a, Message>> = filtered[visible.clone()]
             .iter()
-            .enumerate()
-            .map(|(i, agg)| aggregate_row(agg, i == selected_index))
+            .copied()
+            .zip(visible)
+            .map(|(agg, i)| {
+                let is_selected = i == selected_index;
+                // The focused address or domain can be copied straight away
+                let copy = (is_selected && copyable).then(|| {
+                    icon_button_with_tooltip(
+                        icons::COPY,
+                        tooltip_label("Copy", state.keymap.key(Action::CopyKey)),
+                        Message::CopyToClipboard(agg.key.clone()),
+                    )
+                });
+                let row = if *view_type == ViewType::Senders {
+                    // Right-click offers sender actions such as blocking
+                    mouse_area(aggregate_row(
+                        agg,
+                        is_selected,
+                        state.is_blocked(&agg.key),
+                        copy,
+                    ))
+                    .on_right_press(Message::ShowContextMenu(ContextTarget::Sender {
+                        email: agg.key.clone(),
+                    }))
+                    .into()
+                } else if *view_type == ViewType::Domains {
+                    // Right-click offers pinning to the sidebar
+                    mouse_area(aggregate_row(agg, is_selected, false, copy))
+                        .on_right_press(Message::ShowContextMenu(ContextTarget::Domain {
+                            domain: agg.key.clone(),
+                        }))
+                        .into()
+                } else {
+                    aggregate_row(agg, is_selected, false, None)
+                };
+                container(focus_ring(row, show_focus && is_selected))
+                    .height(Length::Fixed(AGGREGATE_ROW_PITCH))
+                    .into()
+            })
             .collect();
 
-        scrollable(column(rows).spacing(spacing::SPACE_1))
+        // Spacers stand in for the rows that aren't built
+        let list = column![Space::with_height(above)]
+            .extend(rows)
+            .push(Space::with_height(below));
+
+        scrollable(list)
+            .id(scrollable::Id::new(AGGREGATES_SCROLL))
+            .on_scroll(Message::AggregatesScrolled)
             .height(Length::Fill)
             .into()
     };
 
-    // Keyboard hints in FONT_MONO
-    let hints = text("Up/Down: navigate | Enter: select | Tab: switch view | s: toggle sort | Esc: back")
-        .size(typography::SIZE_2XS)
-        .font(typography::FONT_MONO)
-        .style(components::text_muted);
+    // Type-ahead prompt while a prefix is being typed
+    let typeahead_prompt: Element<'a, Message> = match typeahead {
+        Some(prefix) => text(format!("Jump to: {}_", prefix))
+            .size(typography::SIZE_2XS)
+            .font(typography::FONT_MONO)
+            .style(components::text_accent)
+            .into(),
+        None => Space::with_height(0).into(),
+    };
 
     column![
         header,
         Space::with_height(spacing::MD),
+        filter_bar,
         column_headers,
         Space::with_height(spacing::XS),
         list_content,
         Space::with_height(spacing::SM),
-        hints,
+        typeahead_prompt,
     ]
     .spacing(spacing::XS)
     .padding(spacing::XL)
@@ -67,11 +167,43 @@ pub fn aggregates_view<'a>(
     .into()
 }
 
-/// Header row showing current view type and sort info
+/// Heatmap with a caption describing the selected bucket
+fn heatmap_panel<'a>(
+    heatmap: Element<'a, Message>,
+    selected: Option<&AggregateRow>,
+) -> Element<'a, Message> {
+    let caption = match selected {
+        Some(agg) => format!(
+            "{}: {} messages, {}",
+            agg.key,
+            agg.count,
+            format_bytes(agg.total_size)
+        ),
+        None => String::new(),
+    };
+
+    column![
+        scrollable(heatmap)
+            .direction(scrollable::Direction::Horizontal(
+                scrollable::Scrollbar::default()
+            ))
+            .width(Length::Fill),
+        text(caption)
+            .size(typography::SIZE_XS)
+            .font(typography::FONT_MONO)
+            .style(components::text_secondary),
+    ]
+    .spacing(spacing::SM)
+    .height(Length::Fill)
+    .into()
+}
+
+/// Header row showing current view type and sort controls
 fn header_row<'a>(
     view_type: &ViewType,
     sort_field: SortField,
     sort_dir: SortDirection,
+    keymap: &Keymap,
 ) -> Element<'a, Message> {
     let title = text(view_type.display_name())
         .size(typography::SIZE_LG)
@@ -83,15 +215,87 @@ fn header_row<'a>(
         SortDirection::Asc => icons::ARROW_UP,
     };
 
-    let sort_info = text(format!(
-        "Sorted by: {} {}",
-        sort_field.as_str(),
-        sort_indicator
-    ))
+    let sort_field_button = with_tooltip(
+        button(
+            text(format!("Sorted by: {}", sort_field.as_str()))
+                .size(typography::SIZE_XS)
+                .style(components::text_muted),
+        )
+        .padding([spacing::SPACE_1, spacing::XS])
+        .style(components::button_ghost)
+        .on_press(Message::ToggleSortField),
+        tooltip_label(
+            "Sort by name, count or size",
+            keymap.key(Action::ToggleSortField),
+        ),
+        Position::Bottom,
+    );
+
+    let sort_dir_button = with_tooltip(
+        button(
+            text(sort_indicator)
+                .size(typography::SIZE_XS)
+                .style(components::text_muted),
+        )
+        .padding([spacing::SPACE_1, spacing::XS])
+        .style(components::button_ghost)
+        .on_press(Message::ToggleSortDirection),
+        tooltip_label(
+            "Reverse sort order",
+            keymap.key(Action::ToggleSortDirection),
+        ),
+        Position::Bottom,
+    );
+
+    row![
+        title,
+        Space::with_width(Length::Fill),
+        sort_field_button,
+        sort_dir_button
+    ]
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+/// Filter box narrowing the loaded rows by name, with the match count
+fn filter_bar<'a>(
+    view_type: &ViewType,
+    filter: &'a str,
+    shown: usize,
+    total: usize,
+) -> Element<'a, Message> {
+    let input = text_input(
+        &format!("Filter {}", view_type.display_name().to_lowercase()),
+        filter,
+    )
+    .on_input(Message::AggregateFilterChanged)
+    .padding(spacing::SM)
+    .width(Length::Fill)
+    .style(components::text_input_style);
+
+    let clear: Element<'a, Message> = if filter.is_empty() {
+        Space::with_width(0).into()
+    } else {
+        with_tooltip(
+            button(text(icons::DELETE).size(typography::SIZE_XS))
+                .padding([spacing::XS, spacing::SM])
+                .style(components::button_ghost)
+                .on_press(Message::AggregateFilterChanged(String::new())),
+            "Clear filter",
+            Position::Bottom,
+        )
+    };
+
+    let count = text(if filter.is_empty() {
+        format!("{} rows", total)
+    } else {
+        format!("{} of {}", shown, total)
+    })
     .size(typography::SIZE_XS)
     .style(components::text_muted);
 
-    row![title, Space::with_width(Length::Fill), sort_info]
+    row![input, clear, count]
+        .spacing(spacing::SM)
         .align_y(iced::Alignment::Center)
         .into()
 }
@@ -125,7 +329,7 @@ fn column_header_row<'a>() -> Element<'a, Message> {
             .padding([spacing::XS, spacing::SM]),
     )
     .style(|_| container::Style {
-        background: Some(Background::Color(colors::BG_ELEVATED)),
+        background: Some(Background::Color(colors::bg_elevated())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             ..Default::default()
@@ -137,11 +341,26 @@ fn column_header_row<'a>() -> Element<'a, Message> {
 }
 
 /// Single aggregate row with hover/focus states
-fn aggregate_row<'a>(agg: &'a AggregateRow, is_selected: bool) -> Element<'a, Message> {
-    let name = text(&agg.key)
+///
+/// `copy` is a button shown after the name, on the focused row only.
+fn aggregate_row<'a>(
+    agg: &'a AggregateRow,
+    is_selected: bool,
+    is_blocked: bool,
+    copy: Option<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    let key = text(&agg.key)
         .size(typography::SIZE_SM)
-        .style(components::text_primary)
-        .width(Length::FillPortion(3));
+        .style(components::text_primary);
+    let name = if is_blocked {
+        row![key, badge("Blocked", BadgeStyle::Error)]
+    } else {
+        row![key]
+    }
+    .push_maybe(copy)
+    .spacing(spacing::XS)
+    .align_y(iced::Alignment::Center)
+    .width(Length::FillPortion(3));
     let count = text(format!("{}", agg.count))
         .size(typography::SIZE_SM)
         .font(typography::FONT_MONO)
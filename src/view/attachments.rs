@@ -1,16 +1,22 @@
 //! Attachment list view component
 //!
-//! Displays attachments with download buttons and progress indicators.
+//! Displays attachments with download buttons, progress indicators, and a
+//! menu of Save As/Open/Reveal actions.
 
 use crate::api::types::Attachment;
 use crate::message::Message;
-use crate::model::downloads::{DownloadState, DownloadTracker};
+use crate::model::downloads::{local_filename, DownloadState, DownloadTracker};
+use crate::model::ContextTarget;
 use crate::theme::{colors, components, icons, spacing, typography};
-use crate::view::widgets::format_bytes;
+use crate::view::util::truncate;
+use crate::view::widgets::{file_icon, format_bytes, icon_button_with_tooltip};
 use iced::widget::{button, column, container, progress_bar, row, text, Space};
 use iced::{Background, Border, Element, Length};
 
 /// Render the attachments section for a message
+///
+/// Images embedded in the body are listed apart from the files the sender
+/// attached.
 pub fn attachments_section<'a>(
     message_id: i64,
     attachments: &'a [Attachment],
@@ -20,29 +26,55 @@ pub fn attachments_section<'a>(
         return column![].into();
     }
 
-    let title = text("Attachments")
-        .size(typography::SIZE_SM)
-        .font(typography::FONT_MEDIUM)
-        .style(components::text_primary);
+    // Rows keep the attachment's index in the message, which downloads use
+    let group = |title: &'a str, inline: bool| -> Option<Element<'a, Message>> {
+        let rows: Vec<Element<'a, Message>> = attachments
+            .iter()
+            .enumerate()
+            .filter(|(_, att)| att.is_inline() == inline)
+            .map(|(idx, att)| {
+                attachment_row(
+                    message_id,
+                    idx,
+                    att,
+                    downloads.get(message_id, idx),
+                    downloads.partial_bytes(message_id, idx),
+                )
+            })
+            .collect();
+        if rows.is_empty() {
+            return None;
+        }
 
-    let attachment_rows: Vec<Element<'a, Message>> = attachments
-        .iter()
-        .enumerate()
-        .map(|(idx, att)| attachment_row(message_id, idx, att, downloads.get(message_id, idx)))
-        .collect();
+        let title = text(format!("{} ({})", title, rows.len()))
+            .size(typography::SIZE_SM)
+            .font(typography::FONT_MEDIUM)
+            .style(if inline {
+                components::text_secondary
+            } else {
+                components::text_primary
+            });
+        Some(
+            column![title, Space::with_height(spacing::SM)]
+                .push(column(rows).spacing(spacing::XS))
+                .into(),
+        )
+    };
 
     container(
-        column![title, Space::with_height(spacing::SM),]
-            .push(column(attachment_rows).spacing(spacing::XS)),
+        column![]
+            .push_maybe(group("Attachments", false))
+            .push_maybe(group("Inline images", true))
+            .spacing(spacing::MD),
     )
     .width(Length::Fill)
     .padding(spacing::MD)
     .style(|_| container::Style {
-        background: Some(Background::Color(colors::BG_ELEVATED)),
+        background: Some(Background::Color(colors::bg_elevated())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             width: 1.0,
-            color: colors::BORDER_SUBTLE,
+            color: colors::border_subtle(),
         },
         ..Default::default()
     })
@@ -55,29 +87,10 @@ fn attachment_row<'a>(
     idx: usize,
     attachment: &'a Attachment,
     download_state: &'a DownloadState,
+    partial_bytes: u64,
 ) -> Element<'a, Message> {
-    // File type icon from theme icons module
-    let icon_label = icons::file_icon(&attachment.filename);
-
-    // File type label in a small colored container with copper accent
-    let icon_badge = container(
-        text(icon_label)
-            .size(typography::SIZE_2XS)
-            .font(typography::FONT_MONO)
-            .style(components::text_accent),
-    )
-    .padding([spacing::SPACE_1, spacing::XS])
-    .style(|_| container::Style {
-        background: Some(Background::Color(colors::with_alpha(
-            colors::ACCENT_PRIMARY,
-            0.15,
-        ))),
-        border: Border {
-            radius: spacing::RADIUS_SM.into(),
-            ..Default::default()
-        },
-        ..Default::default()
-    });
+    let icon_badge = file_icon(&attachment.filename, Some(&attachment.mime_type));
+    let local_name = local_filename(&attachment.filename, &attachment.mime_type);
 
     let filename = text(&attachment.filename)
         .size(typography::SIZE_SM)
@@ -102,10 +115,27 @@ fn attachment_row<'a>(
             .on_press(Message::DownloadAttachment {
                 message_id,
                 attachment_idx: idx,
-                filename: attachment.filename.clone(),
+                filename: local_name.clone(),
             });
             download_btn.into()
         }
+        DownloadState::Queued => {
+            let queued_text = text("Queued")
+                .size(typography::SIZE_XS)
+                .style(components::text_muted);
+
+            let cancel_btn = button(text("Cancel").size(typography::SIZE_XS))
+                .padding([spacing::XS, spacing::SM])
+                .style(components::button_ghost)
+                .on_press(Message::CancelDownload {
+                    message_id,
+                    attachment_idx: idx,
+                });
+
+            row![queued_text, Space::with_width(spacing::SM), cancel_btn]
+                .align_y(iced::Alignment::Center)
+                .into()
+        }
         DownloadState::Downloading { progress } => {
             // Show progress bar
             let progress_text = text(format!("{}%", (*progress * 100.0) as i32))
@@ -118,7 +148,39 @@ fn attachment_row<'a>(
                 .width(Length::Fixed(80.0))
                 .style(|_| progress_bar_style());
 
-            row![bar, Space::with_width(spacing::XS), progress_text]
+            let cancel_btn = button(text("Cancel").size(typography::SIZE_XS))
+                .padding([spacing::XS, spacing::SM])
+                .style(components::button_ghost)
+                .on_press(Message::CancelDownload {
+                    message_id,
+                    attachment_idx: idx,
+                });
+
+            row![
+                bar,
+                Space::with_width(spacing::XS),
+                progress_text,
+                Space::with_width(spacing::SM),
+                cancel_btn
+            ]
+            .align_y(iced::Alignment::Center)
+            .into()
+        }
+        DownloadState::Cancelled => {
+            let cancelled_text = text("Cancelled")
+                .size(typography::SIZE_XS)
+                .style(components::text_muted);
+
+            let download_btn = button(text("Download").size(typography::SIZE_XS))
+                .padding([spacing::XS, spacing::SM])
+                .style(components::button_secondary)
+                .on_press(Message::DownloadAttachment {
+                    message_id,
+                    attachment_idx: idx,
+                    filename: local_name.clone(),
+                });
+
+            row![cancelled_text, Space::with_width(spacing::SM), download_btn]
                 .align_y(iced::Alignment::Center)
                 .into()
         }
@@ -146,17 +208,24 @@ fn attachment_row<'a>(
         }
         DownloadState::Failed { error } => {
             // Show error with retry button
-            let error_text = text(truncate_error(error, 20))
+            let error_text = text(truncate(error, 20))
                 .size(typography::SIZE_XS)
                 .style(components::text_error);
 
-            let retry_btn = button(text("Retry").size(typography::SIZE_XS))
+            // Retry resumes from the partial file when one was kept
+            let retry_label = if partial_bytes > 0 {
+                format!("Resume ({})", format_bytes(partial_bytes as i64))
+            } else {
+                "Retry".to_string()
+            };
+
+            let retry_btn = button(text(retry_label).size(typography::SIZE_XS))
                 .padding([spacing::XS, spacing::SM])
                 .style(components::button_secondary)
                 .on_press(Message::DownloadAttachment {
                     message_id,
                     attachment_idx: idx,
-                    filename: attachment.filename.clone(),
+                    filename: local_name.clone(),
                 });
 
             row![error_text, Space::with_width(spacing::SM), retry_btn]
@@ -174,12 +243,22 @@ fn attachment_row<'a>(
             size,
             Space::with_width(Length::Fill),
             action_element,
+            Space::with_width(spacing::XS),
+            icon_button_with_tooltip(
+                icons::DOTS,
+                "More actions",
+                Message::ShowContextMenu(ContextTarget::Attachment {
+                    message_id,
+                    attachment_idx: idx,
+                    filename: local_name.clone(),
+                }),
+            ),
         ]
         .align_y(iced::Alignment::Center),
     )
     .padding([spacing::XS, spacing::SM])
     .style(|_| container::Style {
-        background: Some(Background::Color(colors::BG_SURFACE)),
+        background: Some(Background::Color(colors::bg_surface())),
         border: Border {
             radius: spacing::RADIUS_SM.into(),
             ..Default::default()
@@ -189,20 +268,11 @@ fn attachment_row<'a>(
     .into()
 }
 
-/// Truncate error message for display
-fn truncate_error(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len])
-    }
-}
-
 /// Progress bar style
-fn progress_bar_style() -> progress_bar::Style {
+pub fn progress_bar_style() -> progress_bar::Style {
     progress_bar::Style {
-        background: Background::Color(colors::BG_SURFACE),
-        bar: Background::Color(colors::ACCENT_PRIMARY),
+        background: Background::Color(colors::bg_surface()),
+        bar: Background::Color(colors::accent_primary()),
         border: Border {
             radius: spacing::RADIUS_SM.into(),
             ..Default::default()
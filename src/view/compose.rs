@@ -1,12 +1,15 @@
 //! Compose email modal view
 //!
 //! Full-featured email composition with recipients, subject, body, and attachments.
+//! Shown as a modal by default, or in its own window once popped out.
 
 use crate::message::Message;
 use crate::model::ComposeState;
 use crate::theme::{colors, components, icons, spacing, typography};
-use crate::view::widgets::format_bytes;
-use iced::widget::{button, column, container, horizontal_rule, row, text, text_input, Space};
+use crate::view::widgets::{file_icon, format_bytes, with_tooltip, Position};
+use iced::widget::{
+    button, column, container, horizontal_rule, row, scrollable, text, text_input, Column, Space,
+};
 use iced::{Background, Border, Element, Length};
 
 /// Render the compose modal overlay
@@ -22,15 +25,34 @@ pub fn compose_modal(compose: &ComposeState) -> Element<'static, Message> {
         .style(components::modal_backdrop_style);
 
     // Modal dialog
-    let dialog = compose_dialog(compose);
+    let dialog = container(compose_form(compose, false).width(Length::Fixed(700.0)))
+        .style(components::modal_dialog_style);
 
     iced::widget::stack![backdrop, iced::widget::center(dialog)].into()
 }
 
-/// The compose dialog content
-fn compose_dialog(compose: &ComposeState) -> Element<'static, Message> {
-    // Header with mode title and close button
-    let header = compose_header(compose);
+/// Render the compose form filling a popped-out window
+pub fn compose_window_view(compose: &ComposeState) -> Element<'static, Message> {
+    let content: Element<'static, Message> = if compose.is_open {
+        scrollable(compose_form(compose, true).width(Length::Fill)).into()
+    } else {
+        Space::new(Length::Fill, Length::Fill).into()
+    };
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(|_| container::Style {
+            background: Some(Background::Color(colors::bg_base())),
+            ..Default::default()
+        })
+        .into()
+}
+
+/// The compose form, shared by the modal and the window
+fn compose_form(compose: &ComposeState, popped_out: bool) -> Column<'static, Message> {
+    // Header with mode title, pop-out/dock and close buttons
+    let header = compose_header(compose, popped_out);
 
     // From selector
     let from_section = from_section(compose);
@@ -73,7 +95,7 @@ fn compose_dialog(compose: &ComposeState) -> Element<'static, Message> {
         .padding([spacing::SM, spacing::MD])
         .style(|_| container::Style {
             background: Some(Background::Color(colors::with_alpha(
-                colors::ACCENT_ERROR,
+                colors::accent_error(),
                 0.15,
             ))),
             border: Border {
@@ -87,7 +109,7 @@ fn compose_dialog(compose: &ComposeState) -> Element<'static, Message> {
         Space::with_height(0).into()
     };
 
-    let content = column![
+    column![
         header,
         Space::with_height(spacing::MD),
         from_section,
@@ -104,11 +126,6 @@ fn compose_dialog(compose: &ComposeState) -> Element<'static, Message> {
     ]
     .spacing(spacing::XS)
     .padding(spacing::LG)
-    .width(Length::Fixed(700.0));
-
-    container(content)
-        .style(components::modal_dialog_style)
-        .into()
 }
 
 /// Section divider line
@@ -121,21 +138,50 @@ fn section_divider() -> Element<'static, Message> {
         .into()
 }
 
-/// Header with title and close button
-fn compose_header(compose: &ComposeState) -> Element<'static, Message> {
+/// Header with title, pop-out (or dock) and close buttons
+fn compose_header(compose: &ComposeState, popped_out: bool) -> Element<'static, Message> {
     let title = text(compose.mode.display_name())
         .size(typography::SIZE_LG)
         .font(typography::FONT_MEDIUM)
         .style(components::text_primary);
 
-    let close_btn = button(text(icons::DELETE).size(typography::SIZE_MD))
-        .padding([spacing::XS, spacing::SM])
-        .style(components::button_ghost)
-        .on_press(Message::ComposeClose);
+    let close_btn = with_tooltip(
+        button(text(icons::DELETE).size(typography::SIZE_MD))
+            .padding([spacing::XS, spacing::SM])
+            .style(components::button_ghost)
+            .on_press(Message::ComposeClose),
+        "Close (Esc)",
+        Position::Bottom,
+    );
+
+    let window_btn = if popped_out {
+        with_tooltip(
+            button(text(icons::DOCK).size(typography::SIZE_MD))
+                .padding([spacing::XS, spacing::SM])
+                .style(components::button_ghost)
+                .on_press(Message::DockCompose),
+            "Return to main window",
+            Position::Bottom,
+        )
+    } else {
+        with_tooltip(
+            button(text(icons::POP_OUT).size(typography::SIZE_MD))
+                .padding([spacing::XS, spacing::SM])
+                .style(components::button_ghost)
+                .on_press(Message::PopOutCompose),
+            "Open in new window",
+            Position::Bottom,
+        )
+    };
 
-    row![title, Space::with_width(Length::Fill), close_btn]
-        .align_y(iced::Alignment::Center)
-        .into()
+    row![
+        title,
+        Space::with_width(Length::Fill),
+        window_btn,
+        close_btn
+    ]
+    .align_y(iced::Alignment::Center)
+    .into()
 }
 
 /// From account selector
@@ -252,25 +298,29 @@ fn recipient_chip(email: String, on_remove: Message) -> Element<'static, Message
             .size(typography::SIZE_XS)
             .style(components::text_primary),
         Space::with_width(spacing::XS),
-        button(
-            text(icons::DELETE)
-                .size(typography::SIZE_2XS)
-                .style(components::text_accent)
-        )
-        .padding([0, spacing::XS])
-        .style(components::button_ghost)
-        .on_press(on_remove),
+        with_tooltip(
+            button(
+                text(icons::DELETE)
+                    .size(typography::SIZE_2XS)
+                    .style(components::text_accent)
+            )
+            .padding([0, spacing::XS])
+            .style(components::button_ghost)
+            .on_press(on_remove),
+            "Remove recipient",
+            Position::Top,
+        ),
     ]
     .align_y(iced::Alignment::Center);
 
     container(content)
         .padding([spacing::SPACE_1, spacing::SM])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_ELEVATED)),
+            background: Some(Background::Color(colors::bg_elevated())),
             border: Border {
                 radius: spacing::RADIUS_SM.into(),
                 width: 1.0,
-                color: colors::BORDER_SUBTLE,
+                color: colors::border_subtle(),
             },
             ..Default::default()
         })
@@ -302,15 +352,36 @@ fn body_section(compose: &ComposeState) -> Element<'static, Message> {
         .size(typography::SIZE_SM)
         .style(components::text_input_style);
 
-    container(body_input)
+    // Highlighted as the drop target while files are dragged over
+    let (border_width, border_color) = if compose.is_drop_target {
+        (2.0, colors::accent_primary())
+    } else {
+        (1.0, colors::border_subtle())
+    };
+
+    let body: Element<'static, Message> = if compose.is_drop_target {
+        iced::widget::stack![
+            body_input,
+            iced::widget::center(
+                text("Drop files to attach")
+                    .size(typography::SIZE_SM)
+                    .style(components::text_accent),
+            ),
+        ]
+        .into()
+    } else {
+        body_input.into()
+    };
+
+    container(body)
         .width(Length::Fill)
         .height(Length::Fixed(200.0))
-        .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SURFACE)),
+        .style(move |_| container::Style {
+            background: Some(Background::Color(colors::bg_surface())),
             border: Border {
                 radius: spacing::RADIUS_MD.into(),
-                width: 1.0,
-                color: colors::BORDER_SUBTLE,
+                width: border_width,
+                color: border_color,
             },
             ..Default::default()
         })
@@ -335,41 +406,27 @@ fn attachments_section(compose: &ComposeState) -> Element<'static, Message> {
         .iter()
         .enumerate()
         .map(|(i, att)| {
-            let file_icon = icons::file_icon(&att.filename);
             let filename = text(att.filename.clone())
                 .size(typography::SIZE_SM)
                 .style(components::text_primary);
             let size = text(format!("({})", format_bytes(att.size_bytes)))
                 .size(typography::SIZE_XS)
                 .style(components::text_muted);
-            let remove_btn = button(
-                text(icons::DELETE)
-                    .size(typography::SIZE_SM)
-                    .style(components::text_accent)
-            )
-            .padding([0, spacing::XS])
-            .style(components::button_ghost)
-            .on_press(Message::ComposeRemoveAttachment(i));
+            let remove_btn = with_tooltip(
+                button(
+                    text(icons::DELETE)
+                        .size(typography::SIZE_SM)
+                        .style(components::text_accent),
+                )
+                .padding([0, spacing::XS])
+                .style(components::button_ghost)
+                .on_press(Message::ComposeRemoveAttachment(i)),
+                "Remove attachment",
+                Position::Left,
+            );
 
             row![
-                container(
-                    text(file_icon)
-                        .size(typography::SIZE_2XS)
-                        .font(typography::FONT_MONO)
-                        .style(components::text_accent)
-                )
-                .padding([spacing::SPACE_1, spacing::XS])
-                .style(|_| container::Style {
-                    background: Some(Background::Color(colors::with_alpha(
-                        colors::ACCENT_PRIMARY,
-                        0.15,
-                    ))),
-                    border: Border {
-                        radius: spacing::RADIUS_SM.into(),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                }),
+                file_icon(&att.filename, att.mime_type.as_deref()),
                 Space::with_width(spacing::SM),
                 filename,
                 Space::with_width(spacing::XS),
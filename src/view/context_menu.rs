@@ -0,0 +1,369 @@
+//! Context menu overlay
+//!
+//! Popup menu opened by right-clicking a message row, sender, domain or
+//! sidebar entry, or from an attachment's menu button. Items dispatch the same messages as the
+//! equivalent buttons and shortcuts.
+
+use crate::api::types::{SyncState, ViewType};
+use crate::config::PinnedFilter;
+use crate::message::Message;
+use crate::model::context_menu::{MENU_ITEM_HEIGHT, MENU_PADDING, MENU_WIDTH};
+use crate::model::{AppState, ContextMenu, ContextTarget, ViewLevel};
+use crate::theme::{components, icons, spacing, typography};
+use iced::widget::{button, column, container, mouse_area, row, stack, text, Space};
+use iced::{Element, Length};
+
+/// A single menu entry
+struct MenuItem {
+    icon: &'static str,
+    label: &'static str,
+    message: Message,
+}
+
+impl MenuItem {
+    fn new(icon: &'static str, label: &'static str, message: Message) -> Self {
+        Self {
+            icon,
+            label,
+            message,
+        }
+    }
+}
+
+/// Render the open context menu over the rest of the window
+pub fn context_menu_overlay<'a>(
+    state: &'a AppState,
+    menu: &'a ContextMenu,
+) -> Element<'a, Message> {
+    let items = match &menu.target {
+        ContextTarget::Message { message_id, index } => message_items(state, *message_id, *index),
+        ContextTarget::Account { email } => account_items(state, email),
+        ContextTarget::SavedSearch { index } => saved_search_items(*index),
+        ContextTarget::PinnedFilter { index } => pinned_items(state, *index),
+        ContextTarget::Sender { email } => sender_items(state, email),
+        ContextTarget::Domain { domain } => domain_items(state, domain),
+        ContextTarget::Attachment {
+            message_id,
+            attachment_idx,
+            filename,
+        } => attachment_items(state, *message_id, *attachment_idx, filename),
+    };
+
+    // Clicking anywhere outside the menu dismisses it
+    let backdrop = mouse_area(
+        container(Space::new(Length::Fill, Length::Fill))
+            .width(Length::Fill)
+            .height(Length::Fill),
+    )
+    .on_press(Message::CloseContextMenu)
+    .on_right_press(Message::CloseContextMenu);
+
+    let origin = menu.origin(items.len(), state.window_size);
+
+    let entries: Vec<Element<'a, Message>> = items.into_iter().map(menu_entry).collect();
+
+    let popup = container(column(entries))
+        .width(Length::Fixed(MENU_WIDTH))
+        .padding([MENU_PADDING, 0.0])
+        .style(components::modal_dialog_style);
+
+    let positioned = column![
+        Space::with_height(origin.y),
+        row![Space::with_width(origin.x), popup],
+    ];
+
+    stack![backdrop, positioned].into()
+}
+
+/// Actions offered for a message row
+fn message_items(state: &AppState, message_id: i64, index: usize) -> Vec<MenuItem> {
+    let (list, open) = match state.navigation.current() {
+        ViewLevel::Search => (&state.search_results, Message::OpenSearchResult),
+        _ => (&state.messages, Message::OpenMessage),
+    };
+
+    let Some(msg) = list.get(index).filter(|m| m.id == message_id) else {
+        return Vec::new();
+    };
+
+    let mut items = vec![
+        MenuItem::new(icons::OPEN, "Open", open),
+        MenuItem::new(
+            icons::POP_OUT,
+            "Open in new window",
+            Message::OpenMessageWindow(message_id),
+        ),
+        MenuItem::new(icons::REPLY, "Reply", Message::OpenReply(message_id)),
+        MenuItem::new(icons::FORWARD, "Forward", Message::OpenForward(message_id)),
+        if msg.is_unread {
+            MenuItem::new(
+                icons::DOT_EMPTY,
+                "Mark as read",
+                Message::SetReadState {
+                    message_id,
+                    read: true,
+                },
+            )
+        } else {
+            MenuItem::new(
+                icons::DOT_FILLED,
+                "Mark as unread",
+                Message::SetReadState {
+                    message_id,
+                    read: false,
+                },
+            )
+        },
+    ];
+
+    if msg.has_attachments {
+        items.push(MenuItem::new(
+            icons::DOWNLOAD,
+            "Download attachments",
+            Message::DownloadAllAttachments(message_id),
+        ));
+    }
+
+    items.push(MenuItem::new(
+        icons::DIAMOND_SM,
+        "Copy sender address",
+        Message::CopyToClipboard(msg.from_email.clone()),
+    ));
+    items.push(block_item(state, &msg.from_email));
+    items.push(MenuItem::new(
+        icons::DELETE,
+        "Delete",
+        Message::DeleteOne(message_id),
+    ));
+
+    items
+}
+
+/// Actions offered for a sidebar account
+fn account_items(state: &AppState, email: &str) -> Vec<MenuItem> {
+    let paused = state
+        .sync_accounts
+        .iter()
+        .any(|a| a.email == email && a.status == SyncState::Paused);
+
+    let mut items = vec![MenuItem::new(
+        icons::SYNC,
+        "Sync now",
+        Message::TriggerSync(email.to_string()),
+    )];
+    if state.sync_pause_supported {
+        items.push(if paused {
+            MenuItem::new(
+                icons::EXPAND,
+                "Resume sync",
+                Message::SetSyncPaused {
+                    email: email.to_string(),
+                    paused: false,
+                },
+            )
+        } else {
+            MenuItem::new(
+                icons::DOTS,
+                "Pause sync",
+                Message::SetSyncPaused {
+                    email: email.to_string(),
+                    paused: true,
+                },
+            )
+        });
+    }
+    items.extend([
+        MenuItem::new(
+            icons::ACCOUNTS,
+            "Re-authenticate",
+            Message::ReauthenticateAccount(email.to_string()),
+        ),
+        MenuItem::new(icons::DASHBOARD, "View sync status", Message::OpenSync),
+        MenuItem::new(
+            icons::DELETE,
+            "Remove",
+            Message::ShowRemoveAccountModal(email.to_string()),
+        ),
+    ]);
+
+    items
+}
+
+/// Actions offered for a row in the Senders view
+fn sender_items(state: &AppState, email: &str) -> Vec<MenuItem> {
+    vec![
+        MenuItem::new(
+            icons::DIAMOND_SM,
+            "Copy address",
+            Message::CopyToClipboard(email.to_string()),
+        ),
+        pin_item(state, PinnedFilter::sender(email)),
+        block_item(state, email),
+    ]
+}
+
+/// Actions offered for a row in the Domains view
+fn domain_items(state: &AppState, domain: &str) -> Vec<MenuItem> {
+    vec![
+        MenuItem::new(
+            icons::DIAMOND_SM,
+            "Copy domain",
+            Message::CopyToClipboard(domain.to_string()),
+        ),
+        pin_item(
+            state,
+            PinnedFilter {
+                view_type: ViewType::Domains,
+                value: domain.to_string(),
+            },
+        ),
+    ]
+}
+
+/// Pin a sender or domain to the sidebar, or unpin one already there
+fn pin_item(state: &AppState, filter: PinnedFilter) -> MenuItem {
+    if state.is_pinned(&filter) {
+        MenuItem::new(
+            icons::PIN,
+            "Unpin from sidebar",
+            Message::UnpinFilter(filter),
+        )
+    } else {
+        MenuItem::new(icons::PIN, "Pin to sidebar", Message::PinFilter(filter))
+    }
+}
+
+/// Actions offered for a pinned sender or domain in the sidebar
+fn pinned_items(state: &AppState, index: usize) -> Vec<MenuItem> {
+    let Some(filter) = state.pinned_filters.get(index) else {
+        return Vec::new();
+    };
+    let mut items = vec![MenuItem::new(
+        icons::OPEN,
+        "Open",
+        Message::OpenPinnedFilter(index),
+    )];
+    if index > 0 {
+        items.push(MenuItem::new(
+            icons::ARROW_UP,
+            "Move up",
+            Message::MovePinnedFilter { index, up: true },
+        ));
+    }
+    if index + 1 < state.pinned_filters.len() {
+        items.push(MenuItem::new(
+            icons::ARROW_DOWN,
+            "Move down",
+            Message::MovePinnedFilter { index, up: false },
+        ));
+    }
+    items.push(MenuItem::new(
+        icons::DELETE,
+        "Unpin",
+        Message::UnpinFilter(filter.clone()),
+    ));
+    items
+}
+
+/// Block a sender, or unblock one that is marked as blocked
+fn block_item(state: &AppState, email: &str) -> MenuItem {
+    if state.is_blocked(email) {
+        MenuItem::new(
+            icons::BLOCK,
+            "Unblock sender",
+            Message::UnblockSender(email.to_string()),
+        )
+    } else {
+        MenuItem::new(
+            icons::BLOCK,
+            "Block sender",
+            Message::BlockSender(email.to_string()),
+        )
+    }
+}
+
+/// Actions offered for a saved search in the sidebar
+fn saved_search_items(index: usize) -> Vec<MenuItem> {
+    vec![
+        MenuItem::new(icons::SEARCH, "Open", Message::OpenSavedSearch(index)),
+        MenuItem::new(icons::DELETE, "Delete", Message::DeleteSavedSearch(index)),
+    ]
+}
+
+/// Actions offered for an attachment
+///
+/// Iced can't start an OS drag, so these stand in for dragging the file out.
+/// Open and Reveal need the file on disk and replace Download once it is.
+fn attachment_items(
+    state: &AppState,
+    message_id: i64,
+    attachment_idx: usize,
+    filename: &str,
+) -> Vec<MenuItem> {
+    let mut items = vec![MenuItem::new(
+        icons::DOWNLOAD,
+        "Save As\u{2026}",
+        Message::SaveAttachmentAs {
+            message_id,
+            attachment_idx,
+            filename: filename.to_string(),
+        },
+    )];
+
+    match state.downloads.get(message_id, attachment_idx).path() {
+        Some(path) => {
+            items.push(MenuItem::new(
+                icons::OPEN,
+                "Open",
+                Message::OpenFile(path.clone()),
+            ));
+            items.push(MenuItem::new(
+                icons::DIAMOND_SM,
+                reveal_label(),
+                Message::RevealFile(path.clone()),
+            ));
+        }
+        None => items.push(MenuItem::new(
+            icons::ARROW_DOWN,
+            "Download",
+            Message::DownloadAttachment {
+                message_id,
+                attachment_idx,
+                filename: filename.to_string(),
+            },
+        )),
+    }
+
+    items
+}
+
+/// Label for showing a file in the platform's file manager
+fn reveal_label() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "Show in Finder"
+    } else if cfg!(target_os = "windows") {
+        "Show in Explorer"
+    } else {
+        "Show in folder"
+    }
+}
+
+/// Render one clickable menu entry
+fn menu_entry<'a>(item: MenuItem) -> Element<'a, Message> {
+    button(
+        row![
+            text(item.icon)
+                .size(typography::SIZE_XS)
+                .width(Length::Fixed(20.0)),
+            text(item.label).size(typography::SIZE_SM),
+        ]
+        .spacing(spacing::XS)
+        .align_y(iced::Alignment::Center),
+    )
+    .width(Length::Fill)
+    .height(Length::Fixed(MENU_ITEM_HEIGHT))
+    .padding([spacing::XS, spacing::MD])
+    .style(components::button_ghost)
+    .on_press(Message::ContextMenuSelect(Box::new(item.message)))
+    .into()
+}
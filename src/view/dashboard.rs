@@ -3,6 +3,7 @@
 //! Shows archive statistics and quick navigation.
 
 use crate::api::types::{StatsResponse, ViewType};
+use crate::export::stats_csv;
 use crate::message::Message;
 use crate::model::ViewLevel;
 use crate::theme::{colors, components, spacing, typography};
@@ -39,13 +40,14 @@ pub fn dashboard<'a>(stats: &StatsResponse) -> Element<'a, Message> {
     ]
     .spacing(spacing::SM);
 
-    // Keyboard hints
-    let hints = text("Press Tab to cycle views \u{2022} Esc to go back \u{2022} ? for help")
-        .size(typography::SIZE_XS)
-        .font(typography::FONT_MONO)
-        .style(components::text_muted);
+    // Copy the stats for pasting into a spreadsheet
+    let copy_button = button(text("Copy as CSV").size(typography::SIZE_SM))
+        .padding([spacing::SM, spacing::LG])
+        .style(components::button_secondary)
+        .on_press(Message::CopyToClipboard(stats_csv(stats)));
 
     column![
+        row![Space::with_width(Length::Fill), copy_button],
         hero_stat,
         Space::with_height(spacing::LG),
         secondary_row,
@@ -53,8 +55,6 @@ pub fn dashboard<'a>(stats: &StatsResponse) -> Element<'a, Message> {
         nav_title,
         Space::with_height(spacing::SM),
         nav_buttons,
-        Space::with_height(Length::Fill),
-        hints,
     ]
     .spacing(spacing::SM)
     .padding(spacing::XL)
@@ -82,11 +82,11 @@ fn hero_stat_card<'a>(label: &'a str, value: String) -> Element<'a, Message> {
     .padding([spacing::XXL, spacing::XL])
     .center_x(Length::Fill)
     .style(|_| container::Style {
-        background: Some(Background::Color(colors::BG_SURFACE)),
+        background: Some(Background::Color(colors::bg_surface())),
         border: Border {
             radius: spacing::RADIUS_LG.into(),
             width: 1.0,
-            color: colors::BORDER_SUBTLE,
+            color: colors::border_subtle(),
         },
         ..Default::default()
     })
@@ -111,11 +111,11 @@ fn secondary_stat_card<'a>(label: &'a str, value: String) -> Element<'a, Message
     .width(Length::Fill)
     .padding(spacing::LG)
     .style(|_| container::Style {
-        background: Some(Background::Color(colors::BG_SURFACE)),
+        background: Some(Background::Color(colors::bg_surface())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             width: 1.0,
-            color: colors::BORDER_SUBTLE,
+            color: colors::border_subtle(),
         },
         ..Default::default()
     })
@@ -0,0 +1,230 @@
+//! Downloads panel
+//!
+//! Modal listing every requested attachment download with its progress and
+//! per-item actions (cancel, retry, open).
+
+use crate::message::Message;
+use crate::model::downloads::{DownloadState, DownloadTracker};
+use crate::theme::{colors, components, icons, spacing, typography};
+use crate::view::attachments::progress_bar_style;
+use crate::view::widgets::{file_icon, format_bytes};
+use iced::widget::{
+    button, center, column, container, progress_bar, row, scrollable, stack, text, Space,
+};
+use iced::{Background, Border, Element, Length};
+
+/// Width of the downloads dialog in pixels
+const PANEL_WIDTH: f32 = 520.0;
+
+/// Maximum height of the scrollable download list in pixels
+const LIST_MAX_HEIGHT: f32 = 360.0;
+
+/// Render the downloads panel overlay
+pub fn downloads_panel(downloads: &DownloadTracker, limit: usize) -> Element<'_, Message> {
+    // Semi-transparent backdrop
+    let backdrop = container(Space::new(Length::Fill, Length::Fill))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(components::modal_backdrop_style);
+
+    let title = text("Downloads")
+        .size(typography::SIZE_LG)
+        .font(typography::FONT_SEMIBOLD)
+        .style(components::text_primary);
+
+    let summary = text(format!(
+        "{} active, {} queued \u{2022} up to {} at once",
+        downloads.active_count(),
+        downloads.queue.len(),
+        limit
+    ))
+    .size(typography::SIZE_XS)
+    .font(typography::FONT_MONO)
+    .style(components::text_muted);
+
+    let list: Element<'_, Message> = if downloads.entries.is_empty() {
+        text("No downloads yet")
+            .size(typography::SIZE_SM)
+            .style(components::text_muted)
+            .into()
+    } else {
+        let rows: Vec<Element<'_, Message>> = downloads
+            .entries
+            .iter()
+            .map(|((message_id, idx), filename)| {
+                download_row(
+                    *message_id,
+                    *idx,
+                    filename,
+                    downloads.get(*message_id, *idx),
+                    downloads.partial_bytes(*message_id, *idx),
+                )
+            })
+            .collect();
+
+        scrollable(column(rows).spacing(spacing::XS))
+            .height(Length::Shrink)
+            .into()
+    };
+
+    let clear_button = button(text("Clear Finished").size(typography::SIZE_SM))
+        .padding([spacing::SM, spacing::LG])
+        .style(components::button_ghost)
+        .on_press(Message::ClearFinishedDownloads);
+
+    let close_button = button(
+        text("Close")
+            .size(typography::SIZE_SM)
+            .font(typography::FONT_MEDIUM),
+    )
+    .padding([spacing::SM, spacing::LG])
+    .style(components::button_secondary)
+    .on_press(Message::ToggleDownloadsPanel);
+
+    let dialog_content = column![
+        title,
+        summary,
+        Space::with_height(spacing::LG),
+        container(list).max_height(LIST_MAX_HEIGHT),
+        Space::with_height(spacing::XL),
+        row![clear_button, Space::with_width(Length::Fill), close_button],
+    ]
+    .spacing(spacing::XS)
+    .padding(spacing::XL)
+    .width(Length::Fixed(PANEL_WIDTH));
+
+    let dialog = container(dialog_content)
+        .style(components::modal_dialog_style)
+        .padding(spacing::SM);
+
+    stack![backdrop, center(dialog)].into()
+}
+
+/// Render a single download with its status and actions
+fn download_row<'a>(
+    message_id: i64,
+    idx: usize,
+    filename: &'a str,
+    download_state: &'a DownloadState,
+    partial_bytes: u64,
+) -> Element<'a, Message> {
+    let name = text(filename)
+        .size(typography::SIZE_SM)
+        .style(components::text_secondary);
+
+    let action = |label: String, on_press: Message| {
+        button(text(label).size(typography::SIZE_XS))
+            .padding([spacing::XS, spacing::SM])
+            .style(components::button_secondary)
+            .on_press(on_press)
+    };
+
+    let status: Element<'a, Message> = match download_state {
+        DownloadState::NotStarted | DownloadState::Queued => row![
+            text("Queued")
+                .size(typography::SIZE_XS)
+                .style(components::text_muted),
+            Space::with_width(spacing::SM),
+            action(
+                "Cancel".to_string(),
+                Message::CancelDownload {
+                    message_id,
+                    attachment_idx: idx,
+                },
+            ),
+        ]
+        .align_y(iced::Alignment::Center)
+        .into(),
+        DownloadState::Downloading { progress } => row![
+            progress_bar(0.0..=1.0, *progress)
+                .height(Length::Fixed(6.0))
+                .width(Length::Fixed(100.0))
+                .style(|_| progress_bar_style()),
+            Space::with_width(spacing::XS),
+            text(format!("{}%", (*progress * 100.0) as i32))
+                .size(typography::SIZE_XS)
+                .font(typography::FONT_MONO)
+                .style(components::text_muted),
+            Space::with_width(spacing::SM),
+            action(
+                "Cancel".to_string(),
+                Message::CancelDownload {
+                    message_id,
+                    attachment_idx: idx,
+                },
+            ),
+        ]
+        .align_y(iced::Alignment::Center)
+        .into(),
+        DownloadState::Complete { path } => row![
+            text(icons::CHECK)
+                .size(typography::SIZE_XS)
+                .style(components::text_success),
+            Space::with_width(spacing::SM),
+            action("Open".to_string(), Message::OpenFile(path.clone())),
+        ]
+        .align_y(iced::Alignment::Center)
+        .into(),
+        DownloadState::Cancelled => row![
+            text("Cancelled")
+                .size(typography::SIZE_XS)
+                .style(components::text_muted),
+            Space::with_width(spacing::SM),
+            action(
+                "Retry".to_string(),
+                Message::DownloadAttachment {
+                    message_id,
+                    attachment_idx: idx,
+                    filename: filename.to_string(),
+                },
+            ),
+        ]
+        .align_y(iced::Alignment::Center)
+        .into(),
+        DownloadState::Failed { .. } => {
+            let retry_label = if partial_bytes > 0 {
+                format!("Resume ({})", format_bytes(partial_bytes as i64))
+            } else {
+                "Retry".to_string()
+            };
+
+            row![
+                text("Failed")
+                    .size(typography::SIZE_XS)
+                    .style(components::text_error),
+                Space::with_width(spacing::SM),
+                action(
+                    retry_label,
+                    Message::DownloadAttachment {
+                        message_id,
+                        attachment_idx: idx,
+                        filename: filename.to_string(),
+                    },
+                ),
+            ]
+            .align_y(iced::Alignment::Center)
+            .into()
+        }
+    };
+
+    container(
+        row![
+            file_icon(filename, None),
+            Space::with_width(spacing::SM),
+            name,
+            Space::with_width(Length::Fill),
+            status,
+        ]
+        .align_y(iced::Alignment::Center),
+    )
+    .padding([spacing::XS, spacing::SM])
+    .style(|_| container::Style {
+        background: Some(Background::Color(colors::bg_surface())),
+        border: Border {
+            radius: spacing::RADIUS_SM.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .into()
+}
@@ -0,0 +1,159 @@
+//! Label picker modal
+//!
+//! Adds or removes a label on every selected message at once. Each label
+//! shows whether all, some or none of the messages carry it.
+
+use crate::message::Message;
+use crate::model::labels::{label_coverage, LabelCoverage};
+use crate::model::{AppState, LabelPicker};
+use crate::theme::{components, icons, spacing, typography};
+use iced::widget::{
+    button, center, column, container, row, scrollable, stack, text, text_input, Space,
+};
+use iced::{Element, Length};
+use std::collections::HashMap;
+
+/// Width of the label picker dialog in pixels
+const PICKER_WIDTH: f32 = 360.0;
+
+/// Maximum height of the scrollable label list in pixels
+const LIST_MAX_HEIGHT: f32 = 320.0;
+
+/// Render the label picker overlay
+pub fn label_picker_modal<'a>(
+    state: &'a AppState,
+    picker: &'a LabelPicker,
+) -> Element<'a, Message> {
+    // Semi-transparent backdrop
+    let backdrop = container(Space::new(Length::Fill, Length::Fill))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(components::modal_backdrop_style);
+
+    let count = picker.message_ids.len();
+    let title = text(format!(
+        "Label {} message{}",
+        count,
+        if count == 1 { "" } else { "s" }
+    ))
+    .size(typography::SIZE_LG)
+    .font(typography::FONT_SEMIBOLD)
+    .style(components::text_primary);
+
+    // Enter creates the typed label when it does not exist yet
+    let filter_input = text_input("Filter or create a label...", &picker.filter)
+        .on_input(Message::LabelFilterChanged)
+        .on_submit_maybe(picker.new_label().map(|label| Message::ApplyLabel {
+            label: label.to_string(),
+            add: true,
+        }))
+        .padding(spacing::SM)
+        .size(typography::SIZE_SM)
+        .style(components::text_input_style);
+
+    // Current labels of each targeted message
+    let labels: HashMap<i64, &[String]> = picker
+        .message_ids
+        .iter()
+        .filter_map(|id| state.message_labels(*id).map(|l| (*id, l)))
+        .collect();
+
+    let mut entries: Vec<Element<'a, Message>> = picker
+        .filtered()
+        .into_iter()
+        .map(|label| label_entry(label, label_coverage(label, &labels)))
+        .collect();
+
+    if let Some(label) = picker.new_label() {
+        entries.push(
+            button(
+                text(format!("Create \"{}\"", label))
+                    .size(typography::SIZE_SM)
+                    .style(components::text_accent),
+            )
+            .width(Length::Fill)
+            .padding([spacing::XS, spacing::SM])
+            .style(components::button_ghost)
+            .on_press(Message::ApplyLabel {
+                label: label.to_string(),
+                add: true,
+            })
+            .into(),
+        );
+    }
+
+    let list: Element<'a, Message> = if picker.is_loading {
+        text("Loading labels...")
+            .size(typography::SIZE_SM)
+            .style(components::text_muted)
+            .into()
+    } else if entries.is_empty() {
+        text("No labels")
+            .size(typography::SIZE_SM)
+            .style(components::text_muted)
+            .into()
+    } else {
+        scrollable(column(entries).spacing(spacing::SPACE_1))
+            .height(Length::Shrink)
+            .into()
+    };
+
+    let close_button = button(
+        text("Done")
+            .size(typography::SIZE_SM)
+            .font(typography::FONT_MEDIUM),
+    )
+    .padding([spacing::SM, spacing::LG])
+    .style(components::button_secondary)
+    .on_press(Message::CloseLabelPicker);
+
+    let dialog_content = column![
+        title,
+        Space::with_height(spacing::MD),
+        filter_input,
+        Space::with_height(spacing::SM),
+        container(list).max_height(LIST_MAX_HEIGHT),
+        Space::with_height(spacing::LG),
+        row![Space::with_width(Length::Fill), close_button],
+    ]
+    .padding(spacing::XL)
+    .width(Length::Fixed(PICKER_WIDTH));
+
+    let dialog = container(dialog_content)
+        .style(components::modal_dialog_style)
+        .padding(spacing::SM);
+
+    // Center the dialog on the backdrop
+    stack![backdrop, center(dialog)].into()
+}
+
+/// Render one label; clicking adds it to all messages unless all already have it
+fn label_entry<'a>(label: &str, coverage: LabelCoverage) -> Element<'a, Message> {
+    let marker = match coverage {
+        LabelCoverage::All => icons::CHECK,
+        LabelCoverage::Some => "\u{2013}",
+        LabelCoverage::None => "",
+    };
+
+    button(
+        row![
+            text(marker)
+                .size(typography::SIZE_XS)
+                .style(components::text_accent)
+                .width(Length::Fixed(20.0)),
+            text(label.to_string())
+                .size(typography::SIZE_SM)
+                .style(components::text_primary),
+        ]
+        .spacing(spacing::XS)
+        .align_y(iced::Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding([spacing::XS, spacing::SM])
+    .style(components::button_ghost)
+    .on_press(Message::ApplyLabel {
+        label: label.to_string(),
+        add: coverage != LabelCoverage::All,
+    })
+    .into()
+}
@@ -1,26 +1,31 @@
 //! Three-panel email client layout
 //!
 //! Provides the main application layout with sidebar, message list, and detail pane.
+//! The panels are separated by dividers that can be dragged to resize them.
 
 use crate::message::Message;
-use crate::theme::{colors, spacing};
-use iced::widget::{container, row};
-use iced::{Background, Border, Element, Length};
-
-/// Minimum message list width
-pub const MESSAGE_LIST_MIN_WIDTH: f32 = 350.0;
+use crate::model::panels::{DIVIDER_WIDTH, SIDEBAR_RAIL_WIDTH};
+use crate::model::{Divider, PanelWidths};
+use crate::theme::colors;
+use iced::widget::{container, mouse_area, row, Space};
+use iced::{mouse, Background, Border, Element, Length};
 
 /// Create a three-panel layout
+///
+/// `widths` should already be clamped to the window. A collapsed sidebar
+/// is drawn as a fixed-width rail with no divider.
 pub fn three_panel_layout<'a>(
     sidebar: Element<'a, Message>,
     message_list: Element<'a, Message>,
     detail: Option<Element<'a, Message>>,
+    widths: PanelWidths,
+    sidebar_collapsed: bool,
 ) -> Element<'a, Message> {
     let sidebar_container = container(sidebar)
-        .width(Length::Fixed(spacing::SIDEBAR_WIDTH))
+        .width(Length::Fixed(sidebar_width(widths, sidebar_collapsed)))
         .height(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_DEEP)),
+            background: Some(Background::Color(colors::bg_deep())),
             border: Border {
                 width: 0.0,
                 ..Default::default()
@@ -28,13 +33,19 @@ pub fn three_panel_layout<'a>(
             ..Default::default()
         });
 
+    let list_width = if detail.is_some() {
+        Length::Fixed(widths.message_list)
+    } else {
+        Length::Fill
+    };
+
     let list_container = container(message_list)
-        .width(Length::FillPortion(2))
+        .width(list_width)
         .height(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SURFACE)),
+            background: Some(Background::Color(colors::bg_surface())),
             border: Border {
-                color: colors::BORDER_SUBTLE,
+                color: colors::border_subtle(),
                 width: 1.0,
                 radius: 0.0.into(),
             },
@@ -43,34 +54,43 @@ pub fn three_panel_layout<'a>(
 
     let content = if let Some(detail_view) = detail {
         let detail_container = container(detail_view)
-            .width(Length::FillPortion(3))
+            .width(Length::Fill)
             .height(Length::Fill)
             .style(|_| container::Style {
-                background: Some(Background::Color(colors::BG_BASE)),
+                background: Some(Background::Color(colors::bg_base())),
                 ..Default::default()
             });
 
-        row![sidebar_container, list_container, detail_container]
+        row![
+            sidebar_container,
+            sidebar_divider(sidebar_collapsed),
+            list_container,
+            divider(Divider::MessageList),
+            detail_container
+        ]
     } else {
-        row![sidebar_container, list_container]
+        row![
+            sidebar_container,
+            sidebar_divider(sidebar_collapsed),
+            list_container
+        ]
     };
 
-    content
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .into()
+    content.width(Length::Fill).height(Length::Fill).into()
 }
 
 /// Create a two-panel layout (sidebar + content)
 pub fn two_panel_layout<'a>(
     sidebar: Element<'a, Message>,
     content: Element<'a, Message>,
+    widths: PanelWidths,
+    sidebar_collapsed: bool,
 ) -> Element<'a, Message> {
     let sidebar_container = container(sidebar)
-        .width(Length::Fixed(spacing::SIDEBAR_WIDTH))
+        .width(Length::Fixed(sidebar_width(widths, sidebar_collapsed)))
         .height(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_DEEP)),
+            background: Some(Background::Color(colors::bg_deep())),
             ..Default::default()
         });
 
@@ -78,12 +98,49 @@ pub fn two_panel_layout<'a>(
         .width(Length::Fill)
         .height(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_SURFACE)),
+            background: Some(Background::Color(colors::bg_surface())),
             ..Default::default()
         });
 
-    row![sidebar_container, content_container]
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .into()
+    row![
+        sidebar_container,
+        sidebar_divider(sidebar_collapsed),
+        content_container
+    ]
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+/// Drawn width of the sidebar, excluding its divider
+fn sidebar_width(widths: PanelWidths, collapsed: bool) -> f32 {
+    if collapsed {
+        SIDEBAR_RAIL_WIDTH
+    } else {
+        widths.sidebar
+    }
+}
+
+/// Divider after the sidebar; the collapsed rail can't be resized
+fn sidebar_divider<'a>(collapsed: bool) -> Element<'a, Message> {
+    if collapsed {
+        Space::with_width(0).into()
+    } else {
+        divider(Divider::Sidebar)
+    }
+}
+
+/// Drag handle between two panels
+fn divider<'a>(divider: Divider) -> Element<'a, Message> {
+    mouse_area(
+        container(Space::new(Length::Fixed(DIVIDER_WIDTH), Length::Fill)).style(|_| {
+            container::Style {
+                background: Some(Background::Color(colors::bg_deep())),
+                ..Default::default()
+            }
+        }),
+    )
+    .on_press(Message::DividerPressed(divider))
+    .interaction(mouse::Interaction::ResizingHorizontally)
+    .into()
 }
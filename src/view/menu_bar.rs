@@ -0,0 +1,235 @@
+//! Application menu bar
+//!
+//! File/Edit/View/Help menus along the top of the window. Items dispatch
+//! the same messages as the equivalent buttons and shortcuts, and show the
+//! shortcut so it can be learned from the menu.
+
+use crate::message::Message;
+use crate::model::menu_bar::{chord_label, MENU_BAR_HEIGHT, MENU_TITLE_WIDTH, MENU_WIDTH};
+use crate::model::{Action, AppState, MenuBarMenu, ViewLevel};
+use crate::theme::{colors, components, spacing, typography};
+use iced::widget::{button, column, container, mouse_area, row, stack, text, Space};
+use iced::{Background, Border, Element, Length};
+
+/// A single menu entry; `message` is None for disabled items
+struct MenuItem {
+    label: &'static str,
+    shortcut: String,
+    message: Option<Message>,
+}
+
+impl MenuItem {
+    fn new(label: &'static str, shortcut: impl Into<String>, message: Message) -> Self {
+        Self {
+            label,
+            shortcut: shortcut.into(),
+            message: Some(message),
+        }
+    }
+
+    /// Keep the item only while `enabled`, greyed out otherwise
+    fn enabled_if(mut self, enabled: bool) -> Self {
+        if !enabled {
+            self.message = None;
+        }
+        self
+    }
+}
+
+/// Render the bar of menu titles
+pub fn menu_bar(state: &AppState) -> Element<'_, Message> {
+    let titles: Vec<Element<'_, Message>> = MenuBarMenu::all()
+        .iter()
+        .map(|&menu| {
+            let style = if state.open_menu == Some(menu) {
+                components::button_secondary
+            } else {
+                components::button_ghost
+            };
+            mouse_area(
+                button(text(menu.title()).size(typography::SIZE_SM))
+                    .width(Length::Fixed(MENU_TITLE_WIDTH))
+                    .height(Length::Fill)
+                    .padding([spacing::XS, spacing::SM])
+                    .style(style)
+                    .on_press(Message::ToggleMenu(menu)),
+            )
+            .on_enter(Message::HoverMenu(menu))
+            .into()
+        })
+        .collect();
+
+    container(row(titles))
+        .width(Length::Fill)
+        .height(Length::Fixed(MENU_BAR_HEIGHT))
+        .style(|_| container::Style {
+            background: Some(Background::Color(colors::bg_deep())),
+            border: Border {
+                width: 1.0,
+                color: colors::border_subtle(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Render the open menu below its title, over the rest of the window
+pub fn menu_bar_overlay(state: &AppState, menu: MenuBarMenu) -> Element<'_, Message> {
+    let items = match menu {
+        MenuBarMenu::File => file_items(state),
+        MenuBarMenu::Edit => edit_items(state),
+        MenuBarMenu::View => view_items(state),
+        MenuBarMenu::Help => help_items(state),
+    };
+
+    // Clicking anywhere outside the menu dismisses it
+    let backdrop = mouse_area(
+        container(Space::new(Length::Fill, Length::Fill))
+            .width(Length::Fill)
+            .height(Length::Fill),
+    )
+    .on_press(Message::CloseMenu)
+    .on_right_press(Message::CloseMenu);
+
+    let entries: Vec<Element<'_, Message>> = items.into_iter().map(menu_entry).collect();
+
+    let popup = container(column(entries))
+        .width(Length::Fixed(MENU_WIDTH))
+        .padding([spacing::XS, 0])
+        .style(components::modal_dialog_style);
+
+    // The bar stays clickable so another title can be picked directly
+    let positioned = column![
+        menu_bar(state),
+        row![Space::with_width(menu.offset()), popup],
+    ];
+
+    stack![backdrop, positioned].into()
+}
+
+fn file_items(state: &AppState) -> Vec<MenuItem> {
+    vec![
+        MenuItem::new(
+            "New Message",
+            state.keymap.key(Action::Compose),
+            Message::OpenCompose,
+        ),
+        MenuItem::new("Settings\u{2026}", chord_label(","), Message::OpenSettings),
+        MenuItem::new("Import Settings\u{2026}", "", Message::ImportSettings),
+        MenuItem::new("Export Settings\u{2026}", "", Message::ExportSettings),
+        MenuItem::new(
+            "Close Window",
+            chord_label("w"),
+            state
+                .main_window
+                .map_or(Message::None, Message::CloseWindow),
+        ),
+        MenuItem::new("Quit msgvault", chord_label("q"), Message::Quit),
+    ]
+}
+
+fn edit_items(state: &AppState) -> Vec<MenuItem> {
+    let in_list = matches!(
+        state.navigation.current(),
+        ViewLevel::Messages { .. } | ViewLevel::Search
+    );
+    let has_selection = !state.selected_messages.is_empty();
+    let keymap = &state.keymap;
+
+    vec![
+        MenuItem::new("Find", chord_label("f"), Message::OpenSearch),
+        MenuItem::new(
+            "Select All",
+            keymap.key(Action::SelectAll),
+            Message::SelectAll,
+        )
+        .enabled_if(in_list),
+        MenuItem::new(
+            "Clear Selection",
+            keymap.key(Action::ClearSelection),
+            Message::ClearSelection,
+        )
+        .enabled_if(has_selection),
+        MenuItem::new(
+            "Delete\u{2026}",
+            keymap.key(Action::Delete),
+            Message::ShowDeleteModal,
+        )
+        .enabled_if(has_selection),
+    ]
+}
+
+fn view_items(state: &AppState) -> Vec<MenuItem> {
+    let keymap = &state.keymap;
+
+    vec![
+        MenuItem::new(
+            if state.sidebar_collapsed {
+                "Expand Sidebar"
+            } else {
+                "Collapse Sidebar"
+            },
+            chord_label("\\"),
+            Message::ToggleSidebar,
+        ),
+        MenuItem::new(
+            "Switch Reading Pane Layout",
+            chord_label("]"),
+            Message::CycleReadingPane,
+        ),
+        MenuItem::new("Dashboard", "", Message::NavigateTo(ViewLevel::Dashboard)),
+        MenuItem::new(
+            "Accounts",
+            keymap.key(Action::Accounts),
+            Message::OpenAccounts,
+        ),
+        MenuItem::new("Sync Status", keymap.key(Action::Sync), Message::OpenSync),
+        MenuItem::new(
+            "Downloads",
+            keymap.key(Action::Downloads),
+            Message::ToggleDownloadsPanel,
+        ),
+        MenuItem::new("Back", keymap.key(Action::Back), Message::GoBack)
+            .enabled_if(state.navigation.can_go_back()),
+    ]
+}
+
+fn help_items(state: &AppState) -> Vec<MenuItem> {
+    vec![MenuItem::new(
+        "Keyboard Shortcuts",
+        state.keymap.key(Action::Help),
+        Message::ShowHelp,
+    )]
+}
+
+/// Render one menu entry with its shortcut right-aligned
+fn menu_entry<'a>(item: MenuItem) -> Element<'a, Message> {
+    let label_style = if item.message.is_some() {
+        components::text_primary
+    } else {
+        components::text_muted
+    };
+
+    let entry = button(
+        row![
+            text(item.label)
+                .size(typography::SIZE_SM)
+                .style(label_style),
+            Space::with_width(Length::Fill),
+            text(item.shortcut)
+                .size(typography::SIZE_XS)
+                .style(components::text_muted),
+        ]
+        .align_y(iced::Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding([spacing::XS, spacing::MD])
+    .style(components::button_ghost);
+
+    match item.message {
+        Some(message) => entry.on_press(Message::MenuSelect(Box::new(message))),
+        None => entry,
+    }
+    .into()
+}
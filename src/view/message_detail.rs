@@ -1,55 +1,74 @@
 //! Message detail view
 //!
-//! Displays a single message in full detail with header, body, and attachments.
+//! Displays a single message in full detail with header, body, and attachments,
+//! in the main window or in a message window of its own.
 
-use crate::api::types::MessageDetail;
+use crate::api::types::{MessageDetail, MessageSummary};
+use crate::config::PinnedFilter;
 use crate::message::Message;
-use crate::model::downloads::DownloadTracker;
+use crate::model::body::{split_quoted, truncate_body, BODY_PREVIEW_CHARS};
+use crate::model::remote_content::remote_image_count;
+use crate::model::{
+    quote_message, sender_address, Action, AppState, AuthResults, AvatarCache, MessageWindow, Trust,
+};
 use crate::theme::{colors, components, icons, spacing, typography};
 use crate::view::attachments::attachments_section;
-use crate::view::widgets::avatar;
-use chrono::{DateTime, Local, Utc};
-use iced::widget::{button, column, container, row, scrollable, text, Space};
+use crate::view::context_menu::context_menu_overlay;
+use crate::view::util::{extract_name, format_date, format_list_date, truncate};
+use crate::view::widgets::{
+    avatar_or_image, badge, error, format_bytes, icon_button_with_tooltip, loading, tooltip_label,
+    with_tooltip, BadgeStyle, Position,
+};
+use iced::widget::{button, column, container, lazy, row, scrollable, stack, text, Space};
 use iced::{Background, Border, Element, Length};
 
 /// Render the message detail view
+///
+/// In a message window there is nowhere to go back to, and the whole body
+/// is shown since the window is there for reading it.
 pub fn message_detail_view<'a>(
     message: &'a MessageDetail,
-    downloads: &'a DownloadTracker,
+    state: &'a AppState,
+    in_window: bool,
 ) -> Element<'a, Message> {
     // Action bar at top
-    let action_bar = action_bar_section(message.id);
+    let action_bar = action_bar_section(message, state, in_window);
 
     // Header section
-    let header = header_section(message);
+    let header = header_section(message, &state.avatars);
 
     // Horizontal divider between header and body
     let divider_top = horizontal_divider();
 
+    // Notice for remote images held back from the HTML body
+    let remote_notice = remote_content_bar(message, state);
+
     // Body section (scrollable)
-    let body = body_section(&message.body);
+    let body = body_section(
+        message.id,
+        &message.body,
+        state.show_full_body || in_window,
+        state.expanded_quotes.contains(&message.id),
+    );
 
     // Horizontal divider between body and attachments
     let divider_bottom = horizontal_divider();
 
     // Attachments section with download support
-    let attachments = attachments_section(message.id, &message.attachments, downloads);
+    let attachments = attachments_section(message.id, &message.attachments, &state.downloads);
 
-    // Keyboard hints
-    let hints = text("Esc: back | \u{2190}/\u{2192}: prev/next message")
-        .size(typography::SIZE_XS)
-        .font(typography::FONT_MONO)
-        .style(components::text_muted);
+    // Other recent messages from the same sender
+    let related = more_from_sender_section(state.more_from_sender(message), in_window);
 
     column![
         action_bar,
         header,
+        remote_notice,
         divider_top,
         body,
         divider_bottom,
         attachments,
-        Space::with_height(Length::Fill),
-        hints,
+        related,
     ]
     .spacing(spacing::SM)
     .padding(spacing::LG)
@@ -58,55 +77,181 @@ pub fn message_detail_view<'a>(
     .into()
 }
 
-/// Render the action bar with back, reply, forward, compose buttons
-fn action_bar_section(message_id: i64) -> Element<'static, Message> {
-    let back_btn = button(
-        text(icons::ARROW_LEFT)
-            .size(typography::SIZE_MD),
-    )
-    .padding([spacing::XS, spacing::SM])
-    .style(components::button_ghost)
-    .on_press(Message::GoBack);
-
-    let reply_btn = button(
-        text(icons::REPLY)
-            .size(typography::SIZE_SM),
-    )
-    .padding([spacing::XS, spacing::SM])
-    .style(components::button_ghost)
-    .on_press(Message::OpenReply(message_id));
+/// Render the contents of a message window
+pub fn message_window_view<'a>(
+    window: &'a MessageWindow,
+    state: &'a AppState,
+) -> Element<'a, Message> {
+    let content = match (&window.detail, &window.error) {
+        (Some(detail), _) => message_detail_view(detail, state, true),
+        (None, Some(e)) => error(e),
+        (None, None) => loading("Loading message..."),
+    };
 
-    let reply_all_btn = button(
-        text(icons::REPLY_ALL)
-            .size(typography::SIZE_SM),
-    )
-    .padding([spacing::XS, spacing::SM])
-    .style(components::button_ghost)
-    .on_press(Message::OpenReplyAll(message_id));
+    let content = container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(|_| container::Style {
+            background: Some(Background::Color(colors::bg_base())),
+            ..Default::default()
+        });
+
+    // Context menus opened in this window (e.g. an attachment's) show here
+    match state
+        .context_menu
+        .as_ref()
+        .filter(|m| m.window == Some(window.window))
+    {
+        Some(menu) => stack![content, context_menu_overlay(state, menu)].into(),
+        None => content.into(),
+    }
+}
 
-    let forward_btn = button(
-        text(icons::FORWARD)
-            .size(typography::SIZE_SM),
-    )
-    .padding([spacing::XS, spacing::SM])
-    .style(components::button_ghost)
-    .on_press(Message::OpenForward(message_id));
+/// Render the action bar with back, reply, forward, PDF export, compose buttons
+fn action_bar_section(
+    message: &MessageDetail,
+    state: &AppState,
+    in_window: bool,
+) -> Element<'static, Message> {
+    let message_id = message.id;
+    let keymap = &state.keymap;
+    let pinned_sender = PinnedFilter::sender(sender_address(&message.from_addr));
+    let action_button = |icon, action, key: &str, message| {
+        icon_button_with_tooltip(icon, tooltip_label(action, key), message)
+    };
 
-    let compose_btn = button(
-        text(icons::COMPOSE)
-            .size(typography::SIZE_SM),
-    )
-    .padding([spacing::XS, spacing::SM])
-    .style(components::button_ghost)
-    .on_press(Message::OpenCompose);
+    // Back and pop-out only make sense in the main window
+    let navigation: Element<'static, Message> = if in_window {
+        Space::with_width(0).into()
+    } else {
+        row![
+            action_button(
+                icons::ARROW_LEFT,
+                "Back",
+                keymap.key(Action::Back),
+                Message::GoBack
+            ),
+            action_button(
+                icons::POP_OUT,
+                "Open in new window",
+                "",
+                Message::OpenMessageWindow(message_id)
+            ),
+        ]
+        .spacing(spacing::XS)
+        .into()
+    };
 
     row![
-        back_btn,
+        navigation,
         Space::with_width(Length::Fill),
-        reply_btn,
-        reply_all_btn,
-        forward_btn,
-        compose_btn,
+        action_button(
+            icons::REPLY,
+            "Reply",
+            keymap.key(Action::Reply),
+            Message::OpenReply(message_id)
+        ),
+        action_button(
+            icons::REPLY_ALL,
+            "Reply all",
+            keymap.key(Action::ReplyAll),
+            Message::OpenReplyAll(message_id)
+        ),
+        action_button(
+            icons::FORWARD,
+            "Forward",
+            keymap.key(Action::Forward),
+            Message::OpenForward(message_id)
+        ),
+        action_button(
+            icons::FileKind::Pdf.code(),
+            "Export as PDF",
+            "",
+            Message::ExportMessagePdf(message_id)
+        ),
+        action_button(
+            icons::QUOTE,
+            "Copy as quote",
+            "",
+            Message::CopyToClipboard(quote_message(message))
+        ),
+        // Only servers that advertise a web view can show the message there
+        if state.server_web_ui {
+            action_button(
+                icons::OPEN,
+                "Open in browser",
+                "",
+                Message::OpenMessageInBrowser(message_id),
+            )
+        } else {
+            Space::with_width(0).into()
+        },
+        if state.is_pinned(&pinned_sender) {
+            action_button(
+                icons::PIN,
+                "Unpin sender",
+                "",
+                Message::UnpinFilter(pinned_sender),
+            )
+        } else {
+            action_button(
+                icons::PIN,
+                "Pin sender to sidebar",
+                "",
+                Message::PinFilter(pinned_sender),
+            )
+        },
+        if state.is_blocked(&message.from_addr) {
+            action_button(
+                icons::BLOCK,
+                "Unblock sender",
+                "",
+                Message::UnblockSender(sender_address(&message.from_addr).to_string()),
+            )
+        } else {
+            action_button(
+                icons::BLOCK,
+                "Block sender",
+                "",
+                Message::BlockSender(message.from_addr.clone()),
+            )
+        },
+        if state.is_sender_muted(&message.from_addr) {
+            action_button(
+                icons::MUTE,
+                "Unmute sender",
+                "",
+                Message::UnmuteSender(sender_address(&message.from_addr).to_string()),
+            )
+        } else {
+            action_button(
+                icons::MUTE,
+                "Mute new mail from sender",
+                "",
+                Message::MuteSender(message.from_addr.clone()),
+            )
+        },
+        match &message.thread_id {
+            Some(thread_id) if state.is_thread_muted(thread_id) => action_button(
+                icons::MUTE,
+                "Unmute thread",
+                "",
+                Message::UnmuteThread(thread_id.clone())
+            ),
+            Some(thread_id) => action_button(
+                icons::MUTE,
+                "Mute new mail in thread",
+                "",
+                Message::MuteThread(thread_id.clone())
+            ),
+            None => Space::with_width(0).into(),
+        },
+        action_button(
+            icons::COMPOSE,
+            "New message",
+            keymap.key(Action::Compose),
+            Message::OpenCompose
+        ),
     ]
     .spacing(spacing::XS)
     .align_y(iced::Alignment::Center)
@@ -114,13 +259,72 @@ fn action_bar_section(message_id: i64) -> Element<'static, Message> {
     .into()
 }
 
+/// Render the "remote content blocked" bar, if anything was blocked
+fn remote_content_bar<'a>(message: &'a MessageDetail, state: &AppState) -> Element<'a, Message> {
+    let blocked = message
+        .body_html
+        .as_deref()
+        .map(remote_image_count)
+        .unwrap_or(0);
+    if blocked == 0 || state.remote_content_allowed(message) {
+        return Space::with_height(0).into();
+    }
+
+    let sender = sender_address(&message.from_addr).to_string();
+    let notice = text(format!(
+        "Remote content blocked \u{2014} {} image{} would contact other servers",
+        blocked,
+        if blocked == 1 { "" } else { "s" }
+    ))
+    .size(typography::SIZE_SM)
+    .style(components::text_secondary);
+
+    container(
+        row![
+            text(icons::CROSS)
+                .size(typography::SIZE_SM)
+                .style(components::text_warning),
+            notice,
+            Space::with_width(Length::Fill),
+            button(text("Load images").size(typography::SIZE_XS))
+                .padding([spacing::XS, spacing::SM])
+                .style(components::button_secondary)
+                .on_press(Message::LoadRemoteContent(message.id)),
+            button(text(format!("Always load from {}", sender)).size(typography::SIZE_XS))
+                .padding([spacing::XS, spacing::SM])
+                .style(components::button_ghost)
+                .on_press(Message::AllowRemoteContent(sender)),
+        ]
+        .spacing(spacing::SM)
+        .align_y(iced::Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding([spacing::SM, spacing::MD])
+    .style(|_| container::Style {
+        background: Some(Background::Color(colors::with_alpha(
+            colors::accent_warning(),
+            0.12,
+        ))),
+        border: Border {
+            radius: spacing::RADIUS_SM.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
 /// Render the message header section
-fn header_section<'a>(message: &'a MessageDetail) -> Element<'a, Message> {
+fn header_section<'a>(message: &'a MessageDetail, avatars: &AvatarCache) -> Element<'a, Message> {
     // Get sender name from email
     let sender_name = extract_name(&message.from_addr);
 
     // Avatar
-    let avatar_widget = avatar(&sender_name, 48);
+    let avatar_widget = avatar_or_image(
+        &sender_name,
+        avatars.get(sender_address(&message.from_addr)),
+        48,
+    );
 
     // Subject (SIZE_XL, FONT_SEMIBOLD, TEXT_PRIMARY)
     let subject = text(&message.subject)
@@ -136,6 +340,24 @@ fn header_section<'a>(message: &'a MessageDetail) -> Element<'a, Message> {
         .size(typography::SIZE_XS)
         .style(components::text_muted);
 
+    // Sender authentication badge, when the receiving server recorded results
+    let trust_badge: Element<'a, Message> = match AuthResults::from_headers(&message.headers) {
+        Some(results) => {
+            let trust = results.trust();
+            let style = match trust {
+                Trust::Verified => BadgeStyle::Success,
+                Trust::Partial => BadgeStyle::Warning,
+                Trust::Unverified => BadgeStyle::Error,
+            };
+            with_tooltip(
+                badge(trust.label(), style),
+                format!("Sender authentication: {}", results.summary()),
+                Position::Bottom,
+            )
+        }
+        None => Space::with_height(0).into(),
+    };
+
     // Labels (if present)
     let labels_section: Element<'a, Message> = if !message.labels.is_empty() {
         let labels_row: Vec<Element<'a, Message>> = message
@@ -150,7 +372,7 @@ fn header_section<'a>(message: &'a MessageDetail) -> Element<'a, Message> {
                 .padding([2, spacing::SM])
                 .style(|_| container::Style {
                     background: Some(Background::Color(colors::with_alpha(
-                        colors::ACCENT_PRIMARY,
+                        colors::accent_primary(),
                         0.15,
                     ))),
                     border: Border {
@@ -181,7 +403,9 @@ fn header_section<'a>(message: &'a MessageDetail) -> Element<'a, Message> {
         ]
         .width(Length::Fill),
         Space::with_width(spacing::MD),
-        date_text,
+        column![date_text, trust_badge]
+            .spacing(spacing::XS)
+            .align_x(iced::Alignment::End),
     ]
     .align_y(iced::Alignment::Start);
 
@@ -190,7 +414,7 @@ fn header_section<'a>(message: &'a MessageDetail) -> Element<'a, Message> {
         .width(Length::Fill)
         .padding(spacing::LG)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_ELEVATED)),
+            background: Some(Background::Color(colors::bg_elevated())),
             border: Border {
                 radius: spacing::RADIUS_LG.into(),
                 ..Default::default()
@@ -231,26 +455,107 @@ fn horizontal_divider<'a>() -> Element<'a, Message> {
         .width(Length::Fill)
         .height(Length::Fixed(1.0))
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BORDER_SUBTLE)),
+            background: Some(Background::Color(colors::border_subtle())),
             ..Default::default()
         })
         .into()
 }
 
-/// Render the scrollable body section
-fn body_section<'a>(body: &'a str) -> Element<'a, Message> {
-    let body_text = if body.is_empty() {
+/// Render "More from this sender", hidden when there are none
+///
+/// In a message window each opens in another window, as the main window
+/// may be showing something else.
+fn more_from_sender_section<'a>(
+    messages: Vec<&'a MessageSummary>,
+    in_window: bool,
+) -> Element<'a, Message> {
+    if messages.is_empty() {
+        return Space::with_height(0).into();
+    }
+
+    let title = text("More from this sender")
+        .size(typography::SIZE_XS)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_muted);
+
+    let rows = column(messages.into_iter().map(|msg| {
+        let open = if in_window {
+            Message::OpenMessageWindow(msg.id)
+        } else {
+            Message::OpenMessageById(msg.id)
+        };
+        button(
+            row![
+                text(truncate(&msg.subject, 60))
+                    .size(typography::SIZE_SM)
+                    .style(components::text_primary),
+                Space::with_width(Length::Fill),
+                text(format_list_date(&msg.sent_at))
+                    .size(typography::SIZE_XS)
+                    .style(components::text_muted),
+            ]
+            .align_y(iced::Alignment::Center),
+        )
+        .width(Length::Fill)
+        .padding([spacing::XS, spacing::SM])
+        .style(components::button_ghost)
+        .on_press(open)
+        .into()
+    }));
+
+    column![horizontal_divider(), title, rows]
+        .spacing(spacing::XS)
+        .into()
+}
+
+/// Render the scrollable body section, capped until expanded
+fn body_section(
+    message_id: i64,
+    body: &str,
+    show_full: bool,
+    quotes_expanded: bool,
+) -> Element<'_, Message> {
+    let preview = if show_full {
+        None
+    } else {
+        truncate_body(body, BODY_PREVIEW_CHARS)
+    };
+
+    let body_content: Element<'_, Message> = if body.is_empty() {
         text("(No message body)")
             .size(typography::SIZE_SM)
             .style(components::text_muted)
+            .into()
     } else {
-        text(body)
-            .size(typography::SIZE_SM)
-            .style(components::text_secondary)
+        // Laying out a huge body is slow, so only rebuild it when the
+        // message or the amount shown changes
+        let body_text = lazy(
+            (message_id, preview.is_some(), quotes_expanded),
+            move |_| body_segments(message_id, preview.unwrap_or(body), quotes_expanded),
+        );
+
+        match preview {
+            Some(_) => column![
+                body_text,
+                Space::with_height(spacing::MD),
+                button(
+                    text(format!(
+                        "Show full message ({})",
+                        format_bytes(body.len() as i64)
+                    ))
+                    .size(typography::SIZE_SM),
+                )
+                .padding([spacing::XS, spacing::MD])
+                .style(components::button_secondary)
+                .on_press(Message::ShowFullBody),
+            ]
+            .into(),
+            None => body_text.into(),
+        }
     };
 
     scrollable(
-        container(body_text)
+        container(body_content)
             .width(Length::Fill)
             .padding([spacing::MD, 0]),
     )
@@ -258,21 +563,55 @@ fn body_section<'a>(body: &'a str) -> Element<'a, Message> {
     .into()
 }
 
-/// Extract name from email address
-fn extract_name(email: &str) -> String {
-    // Try to extract display name from "Name <email>" format
-    if let Some(idx) = email.find('<') {
-        let name = email[..idx].trim().trim_matches('"');
-        if !name.is_empty() {
-            return name.to_string();
+/// Render body text with quoted history collapsed behind a toggle
+fn body_segments(message_id: i64, body: &str, quotes_expanded: bool) -> Element<'static, Message> {
+    let mut parts: Vec<Element<'static, Message>> = Vec::new();
+    let mut shown_toggle = false;
+
+    for segment in split_quoted(body) {
+        let content = segment.text.trim_end_matches(['\r', '\n']).to_string();
+        if !segment.quoted {
+            parts.push(
+                text(content)
+                    .size(typography::SIZE_SM)
+                    .style(components::text_secondary)
+                    .into(),
+            );
+            continue;
+        }
+
+        // Collapsed quotes show only the toggle; expanded ones get a single
+        // hide button above the first quote
+        if !quotes_expanded || !shown_toggle {
+            let label = if quotes_expanded {
+                "Hide quoted text"
+            } else {
+                "Show quoted text ···"
+            };
+            parts.push(
+                button(
+                    text(label)
+                        .size(typography::SIZE_XS)
+                        .style(components::text_muted),
+                )
+                .padding([spacing::SPACE_1, spacing::SM])
+                .style(components::button_ghost)
+                .on_press(Message::ToggleQuotedText(message_id))
+                .into(),
+            );
+            shown_toggle = true;
+        }
+
+        if quotes_expanded {
+            parts.push(
+                text(content)
+                    .size(typography::SIZE_SM)
+                    .style(components::text_muted)
+                    .into(),
+            );
         }
     }
-    // Fall back to email local part
-    email.split('@').next().unwrap_or(email).to_string()
-}
 
-/// Format a DateTime for display
-fn format_date(dt: &DateTime<Utc>) -> String {
-    let local: DateTime<Local> = dt.with_timezone(&Local);
-    local.format("%a, %b %d, %Y at %I:%M %p").to_string()
+    column(parts).spacing(spacing::SM).into()
 }
+
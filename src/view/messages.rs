@@ -1,26 +1,34 @@
 //! Messages list view
 //!
 //! Displays a 3-line message list with Foundry Dark styling.
-//! Each row shows sender + time, subject + attachment, and snippet.
+//! Each row shows sender + time, subject + attachment, and (unless turned
+//! off) a snippet.
 
 use crate::api::types::MessageSummary;
 use crate::message::Message;
+use crate::model::{Action, AppState, AvatarCache, ContextTarget, Keymap};
 use crate::theme::{colors, components, icons, spacing, typography};
-use crate::view::widgets::{avatar, format_bytes};
-use chrono::{DateTime, Datelike, Local, Utc};
-use iced::widget::{column, container, row, scrollable, text, Space};
+use crate::view::util::{format_list_date, truncate};
+use crate::view::widgets::{
+    avatar_or_image, badge, empty_state, focus_ring, format_bytes, tooltip_label, unread_dot,
+    with_tooltip, BadgeStyle, Position,
+};
+use iced::widget::{button, column, container, mouse_area, row, scrollable, text, Space};
 use iced::{Background, Border, Element, Length};
-use std::collections::HashSet;
+
+/// Maximum label chips shown on a message row
+const MAX_ROW_LABELS: usize = 3;
 
 /// Render the messages list view
-pub fn messages_view<'a>(
-    filter_description: String,
-    messages: &'a [MessageSummary],
-    selected_index: usize,
-    offset: i64,
-    total: i64,
-    selected_messages: &'a HashSet<i64>,
-) -> Element<'a, Message> {
+pub fn messages_view(filter_description: String, state: &AppState) -> Element<'_, Message> {
+    let messages = &state.messages;
+    let selected_index = state.message_selected_index;
+    let offset = state.messages_offset;
+    let total = state.messages_total;
+    let selected_messages = &state.selected_messages;
+    let typeahead = state.typeahead_buffer.as_deref();
+    let show_focus = state.content_focus_visible();
+
     // Header with filter description and counts
     let header = header_section(
         filter_description,
@@ -28,21 +36,42 @@ pub fn messages_view<'a>(
         messages.len(),
         total,
         selected_messages.len(),
+        state.selection_size(messages),
     );
 
     // Message list
-    let list_content: Element<'a, Message> = if messages.is_empty() {
-        empty_state()
+    let list_content: Element<'_, Message> = if messages.is_empty() {
+        empty_state(
+            icons::DIAMOND_SM,
+            "No messages",
+            "Nothing in the archive matches this filter",
+            Some(("Clear filters", Message::GoBack)),
+        )
     } else {
-        let rows: Vec<Element<'a, Message>> = messages
+        let rows: Vec<Element<'_, Message>> = messages
             .iter()
             .enumerate()
             .map(|(i, msg)| {
-                message_row(
-                    msg,
-                    i == selected_index,
-                    selected_messages.contains(&msg.id),
-                )
+                // Click focuses (shift-click selects a range); right-click opens the menu
+                let row =
+                    mouse_area(message_row(
+                        msg,
+                        i == selected_index,
+                        selected_messages.contains(&msg.id),
+                        state.is_blocked(&msg.from_email),
+                        &state.avatars,
+                        state.list_snippet_length(),
+                        (state.hovered_message == Some(msg.id))
+                            .then(|| hover_actions(msg.id, &state.keymap)),
+                    ))
+                    .on_press(Message::RowClicked(i))
+                    .on_enter(Message::RowHovered(msg.id))
+                    .on_exit(Message::RowUnhovered(msg.id))
+                    .on_right_press(Message::ShowContextMenu(ContextTarget::Message {
+                        message_id: msg.id,
+                        index: i,
+                    }));
+                focus_ring(row, show_focus && i == selected_index)
             })
             .collect();
 
@@ -52,7 +81,7 @@ pub fn messages_view<'a>(
     };
 
     // Pagination and hints
-    let footer = footer_section(offset, messages.len(), total);
+    let footer = footer_section(offset, messages.len(), total, typeahead);
 
     column![header, Space::with_height(spacing::SM), list_content, footer,]
         .spacing(spacing::XS)
@@ -69,6 +98,7 @@ fn header_section(
     page_count: usize,
     total: i64,
     selection_count: usize,
+    selection_size: i64,
 ) -> Element<'static, Message> {
     let title = text(filter_description)
         .size(typography::SIZE_LG)
@@ -77,14 +107,18 @@ fn header_section(
 
     let selection_badge: Element<'static, Message> = if selection_count > 0 {
         container(
-            text(format!("{} selected", selection_count))
+            text(format!(
+                "{} selected \u{00b7} {}",
+                selection_count,
+                format_bytes(selection_size)
+            ))
                 .size(typography::SIZE_XS)
                 .style(components::text_accent),
         )
         .padding([2, spacing::SM])
         .style(|_| container::Style {
             background: Some(Background::Color(colors::with_alpha(
-                colors::ACCENT_PRIMARY,
+                colors::accent_primary(),
                 0.15,
             ))),
             border: Border {
@@ -118,39 +152,17 @@ fn header_section(
     .into()
 }
 
-/// Empty state when no messages
-fn empty_state<'a>() -> Element<'a, Message> {
-    container(
-        column![
-            text(icons::DIAMOND_SM)
-                .size(typography::SIZE_2XL)
-                .style(components::text_muted),
-            Space::with_height(spacing::MD),
-            text("No messages")
-                .size(typography::SIZE_MD)
-                .font(typography::FONT_SEMIBOLD)
-                .style(components::text_secondary),
-            Space::with_height(spacing::XS),
-            text("Try adjusting your filters or browsing a different view")
-                .size(typography::SIZE_SM)
-                .style(components::text_muted),
-        ]
-        .spacing(spacing::XS)
-        .align_x(iced::Alignment::Center),
-    )
-    .width(Length::Fill)
-    .height(Length::Fill)
-    .center_x(Length::Fill)
-    .center_y(Length::Fill)
-    .padding(spacing::XXL)
-    .into()
-}
-
 /// Single message row — 3-line layout with focus/selection states
+///
+/// `actions` replaces the time while the row is hovered.
 fn message_row<'a>(
     msg: &'a MessageSummary,
     is_focused: bool,
     is_selected: bool,
+    is_blocked: bool,
+    avatars: &AvatarCache,
+    snippet_length: Option<usize>,
+    actions: Option<Element<'a, Message>>,
 ) -> Element<'a, Message> {
     // Determine display name
     let display_name = msg
@@ -161,7 +173,7 @@ fn message_row<'a>(
         .unwrap_or(&msg.from_email);
 
     // Avatar (36px — slightly smaller for denser rows)
-    let avatar_widget = avatar(display_name, 36);
+    let avatar_widget = avatar_or_image(display_name, avatars.get(&msg.from_email), 36);
 
     // Selection checkbox — only rendered when selected
     let checkbox: Element<'a, Message> = if is_selected {
@@ -177,7 +189,7 @@ fn message_row<'a>(
         .center_x(Length::Fixed(18.0))
         .center_y(Length::Fixed(18.0))
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::ACCENT_PRIMARY)),
+            background: Some(Background::Color(colors::accent_primary())),
             border: Border {
                 radius: spacing::RADIUS_SM.into(),
                 ..Default::default()
@@ -191,24 +203,53 @@ fn message_row<'a>(
     };
 
     // --- Line 1: Sender name + right-aligned time ---
-    let sender_name = text(truncate_string(display_name, 30))
+    let sender_name = text(truncate(display_name, 30))
         .size(typography::SIZE_SM)
         .font(typography::FONT_MEDIUM)
         .style(components::text_primary);
 
-    let time_text = text(format_relative_time(&msg.sent_at))
-        .size(typography::SIZE_XS)
-        .style(components::text_muted);
+    let time_text: Element<'a, Message> = match actions {
+        Some(actions) => actions,
+        None => text(format_list_date(&msg.sent_at))
+            .size(typography::SIZE_XS)
+            .style(components::text_muted)
+            .into(),
+    };
+
+    let read_marker: Element<'a, Message> = if msg.is_unread {
+        row![
+            with_tooltip(unread_dot(), "Unread", Position::Top),
+            Space::with_width(spacing::XS)
+        ]
+        .align_y(iced::Alignment::Center)
+        .into()
+    } else {
+        Space::with_width(0).into()
+    };
+
+    // Only present when blocked senders are marked rather than hidden
+    let blocked_marker: Element<'a, Message> = if is_blocked {
+        row![
+            Space::with_width(spacing::XS),
+            badge("Blocked", BadgeStyle::Error)
+        ]
+        .into()
+    } else {
+        Space::with_width(0).into()
+    };
 
     let line1 = row![
+        read_marker,
         sender_name,
+        thread_count_badge(msg),
+        blocked_marker,
         Space::with_width(Length::Fill),
         time_text,
     ]
     .align_y(iced::Alignment::Center);
 
     // --- Line 2: Subject + right-aligned attachment icon ---
-    let subject_text = text(truncate_string(&msg.subject, 55))
+    let subject_text = text(truncate(&msg.subject, 55))
         .size(typography::SIZE_SM)
         .style(components::text_secondary);
 
@@ -231,26 +272,38 @@ fn message_row<'a>(
             .into()
     };
 
+    // Label chips (first few only, to keep rows compact)
+    let label_chips = row(msg
+        .labels
+        .iter()
+        .take(MAX_ROW_LABELS)
+        .map(|label| badge(label, BadgeStyle::Default)))
+    .spacing(spacing::XS);
+
     let line2 = row![
         subject_text,
+        Space::with_width(spacing::SM),
+        label_chips,
         Space::with_width(Length::Fill),
         attachment_and_size,
     ]
     .align_y(iced::Alignment::Center);
 
-    // --- Line 3: Snippet (truncated to ~80 chars) ---
-    let snippet_str = if msg.snippet.is_empty() {
-        "\u{00A0}".to_string() // non-breaking space to preserve row height
-    } else {
-        truncate_string(&msg.snippet, 80)
-    };
-
-    let line3 = text(snippet_str)
-        .size(typography::SIZE_XS)
-        .style(components::text_muted);
+    // --- Line 3: Snippet, truncated to the configured length ---
+    // Hidden previews leave a denser 2-line row
+    let line3 = snippet_length.map(|length| {
+        let snippet_str = if msg.snippet.is_empty() {
+            "\u{00A0}".to_string() // non-breaking space to preserve row height
+        } else {
+            truncate(&msg.snippet, length)
+        };
+        text(snippet_str)
+            .size(typography::SIZE_XS)
+            .style(components::text_muted)
+    });
 
-    // 3-line content column
-    let content = column![line1, line2, line3,]
+    let content = column![line1, line2]
+        .push_maybe(line3)
         .spacing(1)
         .width(Length::Fill);
 
@@ -270,16 +323,16 @@ fn message_row<'a>(
     // Selected: subtle copper tint (8% alpha)
     // Default: surface bg
     let bg_color = if is_focused {
-        colors::SELECTION_BG
+        colors::selection_bg()
     } else if is_selected {
-        colors::with_alpha(colors::ACCENT_PRIMARY, 0.08)
+        colors::with_alpha(colors::accent_primary(), 0.08)
     } else {
-        colors::BG_SURFACE
+        colors::bg_surface()
     };
 
     let left_border_width: f32 = if is_focused { 2.0 } else { 0.0 };
     let left_border_color = if is_focused {
-        colors::ACCENT_PRIMARY
+        colors::accent_primary()
     } else {
         iced::Color::TRANSPARENT
     };
@@ -320,8 +373,13 @@ fn message_row<'a>(
     }
 }
 
-/// Footer with pagination and keyboard hints
-fn footer_section(offset: i64, _page_count: usize, total: i64) -> Element<'static, Message> {
+/// Footer with pagination and the type-ahead prompt
+fn footer_section(
+    offset: i64,
+    _page_count: usize,
+    total: i64,
+    typeahead: Option<&str>,
+) -> Element<'static, Message> {
     let pagination = text(format!(
         "Page {} of {}",
         (offset / 50) + 1,
@@ -330,57 +388,72 @@ fn footer_section(offset: i64, _page_count: usize, total: i64) -> Element<'stati
     .size(typography::SIZE_XS)
     .style(components::text_muted);
 
-    let hints = text("j/k navigate  Enter open  Space select  d delete  n/p pages")
-        .size(typography::SIZE_2XS)
-        .font(typography::FONT_MONO)
-        .style(components::text_muted);
+    // Type-ahead prompt while a prefix is being typed
+    let typeahead_prompt: Element<'static, Message> = match typeahead {
+        Some(prefix) => text(format!("Jump to: {}_", prefix))
+            .size(typography::SIZE_2XS)
+            .font(typography::FONT_MONO)
+            .style(components::text_accent)
+            .into(),
+        None => Space::with_width(0).into(),
+    };
 
     row![
         pagination,
         Space::with_width(Length::Fill),
-        hints,
+        typeahead_prompt,
     ]
     .align_y(iced::Alignment::Center)
     .into()
 }
 
-/// Format datetime as relative time (Today, Yesterday, or date)
-fn format_relative_time(dt: &DateTime<Utc>) -> String {
-    let local: DateTime<Local> = dt.with_timezone(&Local);
-    let now = Local::now();
-
-    // If today, show time
-    if local.date_naive() == now.date_naive() {
-        return local.format("%H:%M").to_string();
-    }
-
-    // If yesterday
-    let yesterday = now.date_naive().pred_opt().unwrap_or(now.date_naive());
-    if local.date_naive() == yesterday {
-        return "Yesterday".to_string();
-    }
-
-    // If this week (within 7 days)
-    let days_ago = (now.date_naive() - local.date_naive()).num_days();
-    if days_ago < 7 {
-        return local.format("%A").to_string(); // Day name
-    }
-
-    // If this year
-    if local.year() == now.year() {
-        return local.format("%b %d").to_string();
+/// Message count for rows that belong to a longer conversation; clicking
+/// it opens the thread
+fn thread_count_badge<'a>(msg: &MessageSummary) -> Element<'a, Message> {
+    match &msg.thread_id {
+        Some(thread_id) if msg.thread_count > 1 => row![
+            Space::with_width(spacing::XS),
+            with_tooltip(
+                button(badge(&msg.thread_count.to_string(), BadgeStyle::Default))
+                    .padding(0)
+                    .style(components::button_ghost)
+                    .on_press(Message::ViewThread(thread_id.clone())),
+                "View conversation",
+                Position::Top,
+            ),
+        ]
+        .into(),
+        _ => Space::with_width(0).into(),
     }
-
-    // Otherwise full date
-    local.format("%b %d, %Y").to_string()
 }
 
-/// Truncate a string with ellipsis
-fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_string()
-    } else {
-        let truncated: String = s.chars().take(max_len.saturating_sub(1)).collect();
-        format!("{}\u{2026}", truncated)
-    }
+/// Reply and forward buttons shown on the hovered row
+fn hover_actions<'a>(message_id: i64, keymap: &Keymap) -> Element<'a, Message> {
+    let action = |icon: &'static str, label: &str, key: &str, message: Message| {
+        with_tooltip(
+            button(text(icon).size(typography::SIZE_XS))
+                .padding([0, spacing::XS])
+                .style(components::button_ghost)
+                .on_press(message),
+            tooltip_label(label, key),
+            Position::Top,
+        )
+    };
+
+    row![
+        action(
+            icons::REPLY,
+            "Reply",
+            keymap.key(Action::Reply),
+            Message::OpenReply(message_id)
+        ),
+        action(
+            icons::FORWARD,
+            "Forward",
+            keymap.key(Action::Forward),
+            Message::OpenForward(message_id)
+        ),
+    ]
+    .spacing(spacing::XS)
+    .into()
 }
@@ -7,8 +7,12 @@ pub mod accounts;
 pub mod aggregates;
 pub mod attachments;
 pub mod compose;
+pub mod context_menu;
 pub mod dashboard;
+pub mod downloads;
+pub mod labels;
 pub mod layout;
+pub mod menu_bar;
 pub mod message_detail;
 pub mod messages;
 pub mod search;
@@ -18,14 +22,19 @@ pub mod status_bar;
 pub mod sync;
 pub mod thread;
 pub mod toolbar;
+pub mod util;
 pub mod widgets;
 pub mod wizard;
 
-pub use accounts::accounts_view;
+pub use accounts::{accounts_view, remove_confirmation_modal};
 pub use aggregates::aggregates_view;
-pub use compose::compose_modal;
+pub use compose::{compose_modal, compose_window_view};
+pub use context_menu::context_menu_overlay;
+pub use downloads::downloads_panel;
+pub use labels::label_picker_modal;
 pub use layout::{three_panel_layout, two_panel_layout};
-pub use message_detail::message_detail_view;
+pub use menu_bar::{menu_bar, menu_bar_overlay};
+pub use message_detail::{message_detail_view, message_window_view};
 pub use messages::messages_view;
 pub use search::search_view;
 pub use settings::settings_view;
@@ -36,26 +45,48 @@ pub use thread::thread_view;
 pub use toolbar::toolbar;
 pub use wizard::wizard_view;
 
+use crate::api::is_insecure_url;
+use crate::api::types::MIN_SERVER_VERSION;
+use crate::config::{ConfigRecovery, ReadingPaneMode};
 use crate::message::Message;
-use crate::model::{AppState, ConnectionStatus, LoadingState, ViewLevel, WizardStep};
+use crate::model::deletion::{deletion_confirmed, LARGE_DELETION};
+use crate::model::{
+    AppState, ConnectionStatus, Keymap, LoadingState, Modal, ViewLevel, WizardStep, HELP_SECTIONS,
+};
 use crate::theme::{colors, components, icons, spacing, typography};
 use dashboard::dashboard;
-use iced::widget::{button, center, column, container, row, stack, text, text_input, Space};
+use iced::widget::{
+    button, center, column, container, row, scrollable, stack, text, text_input, Space,
+};
 use iced::{Background, Border, Element, Length};
-use widgets::{breadcrumb, error, loading};
+use util::truncate;
+use widgets::{
+    breadcrumb, confirm_modal, confirm_modal_gated, confirm_modal_with, empty_state, error,
+    keyboard_hints, loading, toast,
+};
+
+/// Widget ID of the connection screen's server URL field
+pub const SERVER_URL_INPUT: &str = "connection-server-url";
+
+/// Render a window: popped-out compose, a message window, or the main window
+pub fn render_window(state: &AppState, window: iced::window::Id) -> Element<'_, Message> {
+    if state.compose_window == Some(window) {
+        compose_window_view(&state.compose)
+    } else if let Some((_, message_window)) = state.message_windows.by_window(window) {
+        message_window_view(message_window, state)
+    } else {
+        render(state)
+    }
+}
 
 /// Render the application view based on current state
 pub fn render(state: &AppState) -> Element<'_, Message> {
     let content = if state.first_run && state.wizard_step != WizardStep::Complete {
         // Show wizard for first-run setup
-        wizard_view(
-            state.wizard_step,
-            state.discovering,
-            &state.discovery_steps,
-            state.discovery_result.as_ref(),
-            &state.server_url,
-            &state.api_key,
-        )
+        wizard_view(state)
+    } else if state.connection_status == ConnectionStatus::Starting {
+        // Saved settings are being tried; only a failure shows the form
+        splash_view(state)
     } else if !state.is_connected() {
         // Show connection view (for reconnection after setup)
         connection_view(state)
@@ -64,17 +95,45 @@ pub fn render(state: &AppState) -> Element<'_, Message> {
         connected_view(state)
     };
 
+    // A reset config is announced above whichever screen is showing
+    let content = match &state.config_recovery {
+        Some(recovery) => column![config_recovery_banner(recovery), content].into(),
+        None => content,
+    };
+
     // BG_DEEP outermost background, no padding (sidebar handles its own)
     container(content)
         .width(Length::Fill)
         .height(Length::Fill)
         .style(|_theme| container::Style {
-            background: Some(Background::Color(colors::BG_DEEP)),
+            background: Some(Background::Color(colors::bg_deep())),
             ..Default::default()
         })
         .into()
 }
 
+/// Splash shown while the first health check after launch is in flight
+fn splash_view(state: &AppState) -> Element<'_, Message> {
+    center(
+        column![
+            text(icons::DIAMOND)
+                .size(typography::SIZE_3XL)
+                .style(components::text_accent),
+            Space::with_height(spacing::MD),
+            text("msgvault")
+                .size(typography::SIZE_2XL)
+                .font(typography::FONT_SEMIBOLD)
+                .style(components::text_primary),
+            Space::with_height(spacing::SM),
+            text(format!("Connecting to {}\u{2026}", state.server_url))
+                .size(typography::SIZE_SM)
+                .style(components::text_muted),
+        ]
+        .align_x(iced::Alignment::Center),
+    )
+    .into()
+}
+
 /// Connection setup view - shown on first run or when disconnected
 fn connection_view(state: &AppState) -> Element<'_, Message> {
     // Logo mark
@@ -95,6 +154,7 @@ fn connection_view(state: &AppState) -> Element<'_, Message> {
         "Server URL (e.g., http://localhost:8080)",
         &state.server_url,
     )
+    .id(text_input::Id::new(SERVER_URL_INPUT))
     .on_input(Message::ServerUrlChanged)
     .padding(spacing::MD)
     .width(Length::Fixed(400.0))
@@ -116,9 +176,15 @@ fn connection_view(state: &AppState) -> Element<'_, Message> {
     .style(components::button_primary)
     .on_press(Message::CheckHealth);
 
+    // Look for the server again, e.g. after starting it
+    let rescan_button = button(text("Scan for server").size(typography::SIZE_SM))
+        .padding([spacing::SM, spacing::LG])
+        .style(components::button_ghost)
+        .on_press(Message::StartDiscovery);
+
     let status_text: Element<'_, Message> = match &state.connection_status {
         ConnectionStatus::Unknown => Space::with_height(typography::SIZE_SM).into(),
-        ConnectionStatus::Connecting => text("Connecting...")
+        ConnectionStatus::Starting | ConnectionStatus::Connecting => text("Connecting...")
             .size(typography::SIZE_SM)
             .style(components::text_muted)
             .into(),
@@ -126,12 +192,78 @@ fn connection_view(state: &AppState) -> Element<'_, Message> {
             .size(typography::SIZE_SM)
             .style(components::text_success)
             .into(),
-        ConnectionStatus::Failed(err) => text(format!("Failed: {}", truncate_error(err, 50)))
+        ConnectionStatus::Failed(err) => text(format!("Failed: {}", truncate(err, 50)))
             .size(typography::SIZE_SM)
             .style(components::text_error)
             .into(),
     };
 
+    // An unreachable server is retried on a countdown the user can cut short
+    let retry_controls: Element<'_, Message> = match state.reconnect_in {
+        Some(seconds) => column![
+            Space::with_height(spacing::XS),
+            text(format!("Retrying in {}s\u{2026}", seconds))
+                .size(typography::SIZE_XS)
+                .style(components::text_muted),
+            Space::with_height(spacing::XS),
+            row![
+                button(text("Retry now").size(typography::SIZE_SM))
+                    .padding([spacing::XS, spacing::MD])
+                    .style(components::button_secondary)
+                    .on_press(Message::CheckHealth),
+                button(text("Edit settings").size(typography::SIZE_SM))
+                    .padding([spacing::XS, spacing::MD])
+                    .style(components::button_ghost)
+                    .on_press(Message::EditConnectionSettings),
+            ]
+            .spacing(spacing::SM),
+        ]
+        .align_x(iced::Alignment::Center)
+        .into(),
+        None => Space::with_height(0).into(),
+    };
+
+    // Blocked plaintext HTTP and a changed pinned certificate can be resolved
+    // here, since settings need a connection
+    let insecure_prompt: Element<'_, Message> = if !state.allow_insecure
+        && is_insecure_url(&state.server_url)
+    {
+        column![
+            Space::with_height(spacing::SM),
+            text("This URL uses unencrypted HTTP to a remote server.")
+                .size(typography::SIZE_XS)
+                .style(components::text_warning),
+            Space::with_height(spacing::XS),
+            button(text("Allow insecure connection").size(typography::SIZE_SM))
+                .padding([spacing::XS, spacing::MD])
+                .style(components::button_secondary)
+                .on_press(Message::AllowInsecureConnection),
+        ]
+        .align_x(iced::Alignment::Center)
+        .into()
+    } else if let Some(fingerprint) = &state.certificate_mismatch {
+        column![
+                Space::with_height(spacing::SM),
+                text("The server presented a different certificate than the pinned one. Only trust it if you changed the server's certificate.")
+                    .size(typography::SIZE_XS)
+                    .width(Length::Fixed(400.0))
+                    .style(components::text_warning),
+                text(truncate(fingerprint, 48))
+                    .size(typography::SIZE_2XS)
+                    .font(typography::FONT_MONO)
+                    .style(components::text_muted),
+                Space::with_height(spacing::XS),
+                button(text("Trust new certificate").size(typography::SIZE_SM))
+                    .padding([spacing::XS, spacing::MD])
+                    .style(components::button_secondary)
+                    .on_press(Message::TrustNewCertificate),
+            ]
+            .align_x(iced::Alignment::Center)
+            .into()
+    } else {
+        Space::with_height(0).into()
+    };
+
     // Card container with RADIUS_XL for the form
     let form_card = container(
         column![
@@ -145,9 +277,16 @@ fn connection_view(state: &AppState) -> Element<'_, Message> {
             Space::with_height(spacing::MD),
             api_key_input,
             Space::with_height(spacing::XL),
-            connect_button,
+            row![
+                connect_button,
+                Space::with_width(spacing::SM),
+                rescan_button
+            ]
+            .align_y(iced::Alignment::Center),
             Space::with_height(spacing::MD),
             status_text,
+            retry_controls,
+            insecure_prompt,
         ]
         .spacing(spacing::XS)
         .align_x(iced::Alignment::Center),
@@ -175,12 +314,28 @@ fn connected_view(state: &AppState) -> Element<'_, Message> {
     let labels: Vec<String> = vec![];
 
     // Create sidebar
-    let sidebar_element = sidebar(state.navigation.current(), &account_emails, &labels);
+    let sidebar_element = sidebar(
+        state.navigation.current(),
+        &account_emails,
+        &labels,
+        &state.saved_searches,
+        &state.pinned_filters,
+        state.sidebar_focus(),
+        state.sidebar_collapsed,
+    );
 
     // Toolbar
     let has_selection = !state.selected_messages.is_empty();
     let selection_count = state.selected_messages.len();
-    let toolbar_bar = toolbar(state.navigation.current(), has_selection, selection_count);
+    let activity = state
+        .is_busy()
+        .then_some((state.activity_frame, state.active_operations));
+    let toolbar_bar = toolbar(
+        state.navigation.current(),
+        has_selection,
+        selection_count,
+        activity,
+    );
 
     // Main content based on loading state and current view
     let content = match &state.loading {
@@ -198,77 +353,270 @@ fn connected_view(state: &AppState) -> Element<'_, Message> {
     .height(Length::Fill)
     .into();
 
-    // Use three-panel layout for message detail view
-    let panels: Element<'_, Message> = match state.navigation.current() {
-        ViewLevel::MessageDetail { .. } => {
+    // Three-panel layout for message detail view, and for the message
+    // list too when the reading pane is always shown
+    let widths = state
+        .panel_widths
+        .clamped(state.window_size.width, state.sidebar_collapsed);
+    let panels: Element<'_, Message> = match (state.navigation.current(), state.reading_pane) {
+        (
+            ViewLevel::MessageDetail { .. },
+            ReadingPaneMode::WhenReading | ReadingPaneMode::Always,
+        ) => {
             // Three-panel: sidebar + message list + detail
             let filter_desc = state
                 .navigation
                 .current_filter_description()
                 .unwrap_or_else(|| "Messages".to_string());
 
-            let list_content = messages_view(
-                filter_desc,
-                &state.messages,
-                state.message_selected_index,
-                state.messages_offset,
-                state.messages_total,
-                &state.selected_messages,
-            );
+            let list_content = messages_view(filter_desc, state);
 
             let detail_content = if let Some(detail) = &state.current_message {
-                Some(message_detail_view(detail, &state.downloads))
+                Some(message_detail_view(detail, state, false))
             } else {
                 Some(loading("Loading message..."))
             };
 
-            three_panel_layout(sidebar_element, list_content, detail_content)
+            three_panel_layout(
+                sidebar_element,
+                list_content,
+                detail_content,
+                widths,
+                state.sidebar_collapsed,
+            )
         }
+        (ViewLevel::Messages { .. }, ReadingPaneMode::Always) => three_panel_layout(
+            sidebar_element,
+            content_with_toolbar,
+            Some(reading_pane_preview(state)),
+            widths,
+            state.sidebar_collapsed,
+        ),
         _ => {
             // Two-panel: sidebar + content (with toolbar)
-            two_panel_layout(sidebar_element, content_with_toolbar)
+            two_panel_layout(
+                sidebar_element,
+                content_with_toolbar,
+                widths,
+                state.sidebar_collapsed,
+            )
         }
     };
 
     // Status bar at the bottom
-    let total_messages = state.stats.as_ref().map(|s| s.total_messages);
-    let bottom_bar = status_bar(
-        &state.connection_status,
-        &state.server_url,
-        &state.sync_accounts,
-        state.syncing_account.as_deref(),
-        total_messages,
-    );
+    let bottom_bar = status_bar(state);
+
+    // Context-specific shortcut hints above the status bar
+    let hints: Element<'_, Message> = if state.show_key_hints {
+        keyboard_hints(state.navigation.current(), &state.keymap)
+    } else {
+        Space::with_height(0).into()
+    };
 
     // Main area (panels) fills available space; status bar is fixed height at bottom
+    let banner: Element<'_, Message> = if state.is_offline() {
+        offline_banner()
+    } else if let Some(version) = state
+        .outdated_server_version
+        .as_deref()
+        .filter(|_| !state.server_version_notice_dismissed)
+    {
+        server_version_banner(version)
+    } else {
+        Space::with_height(0).into()
+    };
+
     let main_view: Element<'_, Message> = column![
+        menu_bar(state),
+        banner,
         container(panels)
             .width(Length::Fill)
             .height(Length::Fill),
+        hints,
         bottom_bar,
     ]
     .width(Length::Fill)
     .height(Length::Fill)
     .into();
 
-    // Overlay modals if showing
-    if state.compose.is_open {
-        stack![main_view, compose_modal(&state.compose)].into()
-    } else if state.show_help_modal {
-        stack![main_view, help_modal()].into()
-    } else if state.show_delete_modal {
-        stack![
-            main_view,
-            delete_confirmation_modal(state.selected_messages.len())
-        ]
-        .into()
+    // Confirmation toast floats above the content
+    let main_view = match &state.toast {
+        Some(t) => stack![main_view, toast(t)].into(),
+        None => main_view,
+    };
+
+    // Overlays, bottom to top: compose and the label picker, then the
+    // modal stack in opening order, then the context and menu bar menus
+    let mut layers = vec![main_view];
+    if state.compose.is_open && state.compose_window.is_none() {
+        layers.push(compose_modal(&state.compose));
+    }
+    if let Some(picker) = &state.label_picker {
+        layers.push(label_picker_modal(state, picker));
+    }
+    layers.extend(
+        state
+            .modals
+            .iter()
+            .filter_map(|modal| modal_layer(state, modal)),
+    );
+    if let Some(menu) = state.context_menu.as_ref().filter(|m| m.window.is_none()) {
+        layers.push(context_menu_overlay(state, menu));
+    }
+    if let Some(menu) = state.open_menu {
+        layers.push(menu_bar_overlay(state, menu));
+    }
+
+    if layers.len() == 1 {
+        layers.remove(0)
     } else {
-        main_view
+        stack(layers).into()
+    }
+}
+
+/// Render one entry of the modal stack
+fn modal_layer(state: &AppState, modal: Modal) -> Option<Element<'_, Message>> {
+    match modal {
+        Modal::Help => Some(help_modal(&state.keymap)),
+        Modal::ConfirmDelete => Some(delete_confirmation_modal(
+            state.selected_messages.len(),
+            &state.delete_confirm_input,
+        )),
+        Modal::RemoveAccount => state
+            .removing_account
+            .as_deref()
+            .map(remove_confirmation_modal),
+        Modal::Downloads => Some(downloads_panel(
+            &state.downloads,
+            state.max_concurrent_downloads,
+        )),
+        Modal::ResetSettings => Some(reset_settings_modal()),
+        Modal::ConfirmQuit => state
+            .quit_warning()
+            .map(|warning| quit_confirmation_modal(warning, state)),
     }
 }
 
+/// Persistent banner shown while the server is unreachable
+fn offline_banner() -> Element<'static, Message> {
+    let retry = button(text("Retry").size(typography::SIZE_XS))
+        .padding([spacing::SPACE_1, spacing::SM])
+        .style(components::button_secondary)
+        .on_press(Message::CheckHealth);
+
+    container(
+        row![
+            text(icons::DOT_EMPTY)
+                .size(typography::SIZE_XS)
+                .style(components::text_error),
+            text("Offline — showing previously loaded data. Sending, deleting and syncing are disabled.")
+                .size(typography::SIZE_SM)
+                .style(components::text_secondary),
+            Space::with_width(Length::Fill),
+            retry,
+        ]
+        .spacing(spacing::SM)
+        .align_y(iced::Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding([spacing::XS, spacing::LG])
+    .style(|_| container::Style {
+        background: Some(Background::Color(colors::with_alpha(colors::accent_warning(), 0.12))),
+        ..Default::default()
+    })
+    .into()
+}
+
+/// Dismissible notice that the server is older than this app expects
+fn server_version_banner(version: &str) -> Element<'_, Message> {
+    let dismiss = button(text("Dismiss").size(typography::SIZE_XS))
+        .padding([spacing::SPACE_1, spacing::SM])
+        .style(components::button_ghost)
+        .on_press(Message::DismissServerVersionNotice);
+
+    container(
+        row![
+            text(icons::DIAMOND)
+                .size(typography::SIZE_XS)
+                .style(components::text_warning),
+            text(format!(
+                "Some features require msgvault server \u{2265} {} (this server is {}). Upgrade the server if actions fail.",
+                MIN_SERVER_VERSION, version
+            ))
+            .size(typography::SIZE_SM)
+            .style(components::text_secondary),
+            Space::with_width(Length::Fill),
+            dismiss,
+        ]
+        .spacing(spacing::SM)
+        .align_y(iced::Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding([spacing::XS, spacing::LG])
+    .style(|_| container::Style {
+        background: Some(Background::Color(colors::with_alpha(colors::accent_warning(), 0.12))),
+        ..Default::default()
+    })
+    .into()
+}
+
+/// Notice that the config file couldn't be parsed and was reset
+fn config_recovery_banner(recovery: &ConfigRecovery) -> Element<'_, Message> {
+    let show_backup = button(text("Show backup").size(typography::SIZE_XS))
+        .padding([spacing::SPACE_1, spacing::SM])
+        .style(components::button_secondary)
+        .on_press(Message::OpenFile(
+            recovery
+                .backup_path
+                .parent()
+                .map(|dir| dir.to_path_buf())
+                .unwrap_or_else(|| recovery.backup_path.clone()),
+        ));
+
+    let dismiss = button(text("Dismiss").size(typography::SIZE_XS))
+        .padding([spacing::SPACE_1, spacing::SM])
+        .style(components::button_ghost)
+        .on_press(Message::DismissConfigRecovery);
+
+    container(
+        row![
+            text(icons::DOT_EMPTY)
+                .size(typography::SIZE_XS)
+                .style(components::text_warning),
+            column![
+                text("Settings were reset because the config file couldn't be read.")
+                    .size(typography::SIZE_SM)
+                    .style(components::text_secondary),
+                text(format!(
+                    "The original was saved to {} ({})",
+                    recovery.backup_path.display(),
+                    recovery.error.lines().next().unwrap_or_default()
+                ))
+                .size(typography::SIZE_XS)
+                .style(components::text_muted),
+            ]
+            .spacing(spacing::SPACE_1),
+            Space::with_width(Length::Fill),
+            show_backup,
+            dismiss,
+        ]
+        .spacing(spacing::SM)
+        .align_y(iced::Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding([spacing::XS, spacing::LG])
+    .style(|_| container::Style {
+        background: Some(Background::Color(colors::with_alpha(
+            colors::accent_warning(),
+            0.12,
+        ))),
+        ..Default::default()
+    })
+    .into()
+}
+
 /// Keyboard shortcuts help modal
-fn help_modal() -> Element<'static, Message> {
+fn help_modal(keymap: &Keymap) -> Element<'static, Message> {
     // Semi-transparent backdrop
     let backdrop = container(Space::new(Length::Fill, Length::Fill))
         .width(Length::Fill)
@@ -281,37 +629,17 @@ fn help_modal() -> Element<'static, Message> {
         .font(typography::FONT_SEMIBOLD)
         .style(components::text_primary);
 
-    let shortcuts = column![
-        shortcut_section("Navigation"),
-        shortcut_row("j", "Move down"),
-        shortcut_row("k", "Move up"),
-        shortcut_row("Enter", "Open / Drill down"),
-        shortcut_row("Esc", "Go back"),
-        shortcut_row("Tab", "Cycle view types"),
-        Space::with_height(spacing::MD),
-        shortcut_section("Views"),
-        shortcut_row("/", "Search"),
-        shortcut_row("y", "Sync status"),
-        shortcut_row("a", "Accounts"),
-        shortcut_row(",", "Settings"),
-        Space::with_height(spacing::MD),
-        shortcut_section("Actions"),
-        shortcut_row("Space", "Toggle selection"),
-        shortcut_row("Shift+A", "Select all"),
-        shortcut_row("x", "Clear selection"),
-        shortcut_row("d", "Delete selected"),
-        shortcut_row("s", "Toggle sort field"),
-        shortcut_row("r", "Reverse sort"),
-        Space::with_height(spacing::MD),
-        shortcut_section("Messages"),
-        shortcut_row("n", "Next page"),
-        shortcut_row("p", "Previous page"),
-        shortcut_row("\u{2190} / \u{2192}", "Prev/next message"),
-        Space::with_height(spacing::MD),
-        shortcut_section("General"),
-        shortcut_row("?", "Toggle this help"),
-    ]
-    .spacing(spacing::XS);
+    // Built from the keymap so it shows the keys actually bound
+    let mut shortcuts = column![].spacing(spacing::XS);
+    for (i, (section, entries)) in HELP_SECTIONS.iter().enumerate() {
+        if i > 0 {
+            shortcuts = shortcuts.push(Space::with_height(spacing::MD));
+        }
+        shortcuts = shortcuts.push(shortcut_section(section));
+        for (key, description) in *entries {
+            shortcuts = shortcuts.push(shortcut_row(&keymap.help_key(*key), description));
+        }
+    }
 
     let close_button = button(
         text("Close")
@@ -322,12 +650,29 @@ fn help_modal() -> Element<'static, Message> {
     .style(components::button_secondary)
     .on_press(Message::HideHelp);
 
+    let copy_button = button(text("Copy shortcuts").size(typography::SIZE_SM))
+        .padding([spacing::SM, spacing::LG])
+        .style(components::button_ghost)
+        .on_press(Message::CopyToClipboard(keymap.cheatsheet()));
+
+    let export_button = button(text("Export as Text\u{2026}").size(typography::SIZE_SM))
+        .padding([spacing::SM, spacing::LG])
+        .style(components::button_ghost)
+        .on_press(Message::ExportShortcuts);
+
     let dialog_content = column![
         title,
         Space::with_height(spacing::LG),
-        shortcuts,
+        container(scrollable(shortcuts)).max_height(520.0),
         Space::with_height(spacing::XL),
-        close_button,
+        row![
+            close_button,
+            Space::with_width(spacing::XL),
+            copy_button,
+            export_button,
+        ]
+        .spacing(spacing::XS)
+        .align_y(iced::Alignment::Center),
     ]
     .spacing(spacing::XS)
     .padding(spacing::XL)
@@ -359,11 +704,11 @@ fn shortcut_row(key: &str, description: &str) -> Element<'static, Message> {
     )
     .padding([spacing::SPACE_1, spacing::XS])
     .style(|_theme| container::Style {
-        background: Some(Background::Color(colors::BG_ELEVATED)),
+        background: Some(Background::Color(colors::bg_elevated())),
         border: Border {
             radius: spacing::RADIUS_SM.into(),
             width: 1.0,
-            color: colors::BORDER_SUBTLE,
+            color: colors::border_subtle(),
         },
         ..Default::default()
     });
@@ -380,70 +725,79 @@ fn shortcut_row(key: &str, description: &str) -> Element<'static, Message> {
 }
 
 /// Delete confirmation modal overlay
-fn delete_confirmation_modal(count: usize) -> Element<'static, Message> {
-    // Semi-transparent backdrop
-    let backdrop = container(Space::new(Length::Fill, Length::Fill))
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .style(components::modal_backdrop_style);
-
-    // Modal dialog
-    let title = text("Confirm Delete")
-        .size(typography::SIZE_LG)
-        .font(typography::FONT_SEMIBOLD)
-        .style(components::text_primary);
-
-    let message = text(format!(
-        "Are you sure you want to stage {} message{} for deletion?",
-        count,
-        if count == 1 { "" } else { "s" }
-    ))
-    .size(typography::SIZE_SM)
-    .style(components::text_secondary);
+///
+/// Large deletions stay disabled until the count is typed in.
+fn delete_confirmation_modal(count: usize, typed: &str) -> Element<'_, Message> {
+    if count >= LARGE_DELETION {
+        let gate = column![
+            text(format!("Type {} to confirm", count))
+                .size(typography::SIZE_XS)
+                .style(components::text_warning),
+            text_input(&count.to_string(), typed)
+                .on_input(Message::DeleteConfirmInputChanged)
+                .on_submit(Message::ConfirmDelete)
+                .padding(spacing::SM)
+                .width(Length::Fixed(200.0))
+                .style(components::text_input_style),
+        ]
+        .spacing(spacing::XS)
+        .align_x(iced::Alignment::Center);
+
+        return confirm_modal_gated(
+            "Confirm Large Delete",
+            format!(
+                "This stages {} messages for deletion. Check the selection before going on.",
+                count
+            ),
+            gate.into(),
+            "Delete",
+            deletion_confirmed(count, typed).then_some(Message::ConfirmDelete),
+            Message::HideDeleteModal,
+        );
+    }
 
-    let cancel_button = button(
-        text("Cancel")
-            .size(typography::SIZE_SM)
-            .font(typography::FONT_MEDIUM),
+    confirm_modal(
+        "Confirm Delete",
+        format!(
+            "Are you sure you want to stage {} message{} for deletion?",
+            count,
+            if count == 1 { "" } else { "s" }
+        ),
+        "Delete",
+        Message::ConfirmDelete,
+        Message::HideDeleteModal,
+        true,
     )
-    .padding([spacing::SM, spacing::LG])
-    .style(components::button_secondary)
-    .on_press(Message::HideDeleteModal);
+}
 
-    let confirm_button = button(
-        text("Delete")
-            .size(typography::SIZE_SM)
-            .font(typography::FONT_MEDIUM)
-            .style(components::text_error),
+/// Confirmation for resetting all settings
+fn reset_settings_modal() -> Element<'static, Message> {
+    confirm_modal(
+        "Reset Settings",
+        "Reset all settings to their defaults? The server URL and API key are \
+         cleared and setup starts again. Saved searches are kept.",
+        "Reset",
+        Message::ConfirmResetSettings,
+        Message::HideResetSettingsModal,
+        true,
     )
-    .padding([spacing::SM, spacing::LG])
-    .style(components::button_danger)
-    .on_press(Message::ConfirmDelete);
-
-    let buttons = row![
-        cancel_button,
-        Space::with_width(spacing::MD),
-        confirm_button
-    ]
-    .align_y(iced::Alignment::Center);
-
-    let dialog_content = column![
-        title,
-        Space::with_height(spacing::LG),
-        message,
-        Space::with_height(spacing::XL),
-        buttons,
-    ]
-    .spacing(spacing::XS)
-    .padding(spacing::XL)
-    .align_x(iced::Alignment::Center);
-
-    let dialog = container(dialog_content)
-        .style(components::modal_dialog_style)
-        .padding(spacing::SM);
+}
 
-    // Center the dialog on the backdrop
-    stack![backdrop, center(dialog)].into()
+/// Confirmation for quitting with work in progress
+///
+/// With only downloads outstanding, quitting can wait for them instead.
+fn quit_confirmation_modal(warning: String, state: &AppState) -> Element<'static, Message> {
+    let compose = &state.compose;
+    let only_downloads = !(compose.is_open && compose.is_dirty && compose.has_content());
+    confirm_modal_with(
+        "Quit msgvault?",
+        warning,
+        "Quit Now",
+        Message::ConfirmQuit,
+        Message::CancelQuit,
+        true,
+        only_downloads.then_some(("Quit When Done", Message::QuitWhenDone)),
+    )
 }
 
 /// Render the header with breadcrumb navigation
@@ -474,6 +828,25 @@ fn header_view(state: &AppState) -> Element<'_, Message> {
     .into()
 }
 
+/// Reading pane beside the message list, showing the focused message
+///
+/// The message appears once its prefetch lands, so moving through the
+/// list quickly doesn't fetch every row passed over.
+fn reading_pane_preview(state: &AppState) -> Element<'_, Message> {
+    match state.focused_message_id() {
+        Some(id) => match state.message_cache.peek(id) {
+            Some(detail) => message_detail_view(detail, state, false),
+            None => loading("Loading message..."),
+        },
+        None => empty_state(
+            icons::DIAMOND_SM,
+            "No message selected",
+            "Select a message to read it here",
+            None,
+        ),
+    }
+}
+
 /// Render content based on current view level
 fn view_content(state: &AppState) -> Element<'_, Message> {
     match state.navigation.current() {
@@ -487,13 +860,7 @@ fn view_content(state: &AppState) -> Element<'_, Message> {
         }
         ViewLevel::Aggregates { view_type } => {
             // Show aggregate list view
-            aggregates_view(
-                view_type,
-                &state.aggregates,
-                state.selected_index,
-                state.sort_field,
-                state.sort_dir,
-            )
+            aggregates_view(view_type, state)
         }
         ViewLevel::SubAggregates {
             parent_key,
@@ -513,43 +880,37 @@ fn view_content(state: &AppState) -> Element<'_, Message> {
         }
         ViewLevel::Messages { filter_description } => {
             // Show message list view
-            messages_view(
-                filter_description.clone(),
-                &state.messages,
-                state.message_selected_index,
-                state.messages_offset,
-                state.messages_total,
-                &state.selected_messages,
-            )
+            messages_view(filter_description.clone(), state)
         }
         ViewLevel::MessageDetail { .. } => {
             // Show message detail view
             if let Some(detail) = &state.current_message {
-                message_detail_view(detail, &state.downloads)
+                message_detail_view(detail, state, false)
             } else {
                 loading("Loading message...")
             }
         }
         ViewLevel::Thread { .. } => {
             // Thread/conversation view
-            thread_view(&state.thread)
+            thread_view(
+                &state.thread,
+                &state.downloads,
+                state
+                    .thread
+                    .thread_id
+                    .as_deref()
+                    .is_some_and(|t| state.is_thread_muted(t)),
+            )
         }
         ViewLevel::Search => {
             // Show search view
-            search_view(
-                &state.search_query,
-                state.search_deep_mode,
-                &state.search_results,
-                state.search_selected_index,
-                state.search_total,
-                state.is_searching,
-                &state.selected_messages,
-            )
+            search_view(state)
         }
         ViewLevel::Sync => {
             // Show sync status view
             sync_view(
                 &state.sync_accounts,
+                &state.account_filter,
                 state.sync_loading,
                 state.syncing_account.as_deref(),
             )
@@ -558,32 +919,18 @@ fn view_content(state: &AppState) -> Element<'_, Message> {
             // Show accounts management view
             accounts_view(
                 &state.sync_accounts,
+                &state.account_filter,
                 state.sync_loading,
                 &state.add_account_email,
                 state.adding_account,
                 state.oauth_response.as_ref(),
-                state.show_remove_modal,
-                state.removing_account.as_deref(),
+                &state.account_import,
             )
         }
         ViewLevel::Settings => {
             // Show settings view
-            settings_view(
-                state.settings_tab,
-                &state.settings_server_url,
-                &state.settings_api_key,
-                state.testing_connection,
-                state.connection_test_result.as_ref(),
-            )
+            settings_view(state)
         }
     }
 }
 
-/// Truncate error messages for display
-fn truncate_error(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len])
-    }
-}
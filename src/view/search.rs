@@ -4,25 +4,48 @@
 
 use crate::api::types::MessageSummary;
 use crate::message::Message;
+use crate::model::{AppState, ContextTarget, SEARCH_PAGE_SIZE};
 use crate::theme::{colors, components, icons, spacing, typography};
-use crate::view::widgets::format_bytes;
-use chrono::{DateTime, Datelike, Local, Utc};
-use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
+use crate::view::util::{format_list_date, truncate};
+use crate::view::widgets::{
+    badge, empty_state, focus_ring, format_bytes, with_tooltip, BadgeStyle, Position,
+};
+use iced::widget::{
+    button, column, container, mouse_area, row, scrollable, text, text_input, Space,
+};
 use iced::{Background, Border, Element, Length};
-use std::collections::HashSet;
+
+/// Filters understood by deep search, with an example of each
+const SEARCH_TIPS: &[(&str, &str)] = &[
+    ("from:alice@example.com", "Sender address or name"),
+    ("to:bob", "Recipient address or name"),
+    (
+        "subject:\"quarterly report\"",
+        "Subject text; quote phrases",
+    ),
+    ("has:attachment", "Only messages with attachments"),
+    ("after:2024-01-01", "Sent on or after a date"),
+    ("before:2024-07-01", "Sent before a date"),
+];
 
 /// Render the search view
-pub fn search_view<'a>(
-    query: &'a str,
-    is_deep: bool,
-    results: &'a [MessageSummary],
-    selected_index: usize,
-    total: i64,
-    is_searching: bool,
-    selected_messages: &'a HashSet<i64>,
-) -> Element<'a, Message> {
-    // Search input bar
-    let search_input = text_input("Search messages...", query)
+pub fn search_view(state: &AppState) -> Element<'_, Message> {
+    let query = state.search_query.as_str();
+    let is_deep = state.search_deep_mode;
+    let results = &state.search_results;
+    let selected_index = state.search_selected_index;
+    let total = state.search_total;
+    let is_searching = state.is_searching;
+    let selected_messages = &state.selected_messages;
+    let show_focus = state.content_focus_visible();
+
+    // Search input bar; deep search understands filters
+    let placeholder = if is_deep {
+        "Search messages... (from: to: subject: has:attachment after: before:)"
+    } else {
+        "Search messages..."
+    };
+    let search_input = text_input(placeholder, query)
         .on_input(Message::SearchQueryChanged)
         .padding(spacing::MD)
         .width(Length::Fill)
@@ -63,8 +86,20 @@ pub fn search_view<'a>(
 
     let mode_toggle = row![fast_button, deep_button].spacing(spacing::XS);
 
-    let search_bar = row![search_input, Space::with_width(spacing::MD), mode_toggle]
-        .align_y(iced::Alignment::Center);
+    // Save the query as a sidebar smart folder
+    let save_button = button(text("Save").size(typography::SIZE_SM))
+        .padding([spacing::SM, spacing::LG])
+        .style(components::button_secondary)
+        .on_press_maybe((!query.trim().is_empty()).then_some(Message::SaveCurrentSearch));
+
+    let search_bar = row![
+        search_input,
+        Space::with_width(spacing::MD),
+        mode_toggle,
+        Space::with_width(spacing::SM),
+        save_button,
+    ]
+    .align_y(iced::Alignment::Center);
 
     // Results count in TEXT_MUTED
     let results_count = text(format!("{} results", total))
@@ -76,7 +111,11 @@ pub fn search_view<'a>(
 
     // Selection count
     let selection_info = if !selected_messages.is_empty() {
-        text(format!("{} selected", selected_messages.len()))
+        text(format!(
+            "{} selected \u{00b7} {}",
+            selected_messages.len(),
+            format_bytes(state.selection_size(results))
+        ))
             .size(typography::SIZE_XS)
             .style(components::text_accent)
     } else {
@@ -84,7 +123,7 @@ pub fn search_view<'a>(
     };
 
     // Results list content
-    let list_content: Element<'a, Message> = if is_searching {
+    let list_content: Element<'_, Message> = if is_searching {
         container(
             column![
                 text(icons::DOTS)
@@ -102,27 +141,15 @@ pub fn search_view<'a>(
         .center_x(Length::Fill)
         .padding(spacing::XXL)
         .into()
+    } else if results.is_empty() && state.show_search_tips {
+        search_tips(is_deep)
     } else if results.is_empty() && !query.is_empty() {
-        container(
-            column![
-                text(icons::SEARCH)
-                    .size(typography::SIZE_XL)
-                    .style(components::text_muted),
-                text("No results found")
-                    .size(typography::SIZE_MD)
-                    .font(typography::FONT_SEMIBOLD)
-                    .style(components::text_secondary),
-                text("Try different search terms")
-                    .size(typography::SIZE_SM)
-                    .style(components::text_muted),
-            ]
-            .spacing(spacing::SM)
-            .align_x(iced::Alignment::Center),
+        empty_state(
+            icons::SEARCH,
+            "No results found",
+            "Try different search terms, or narrow it down with filters",
+            Some(("Search tips", Message::ToggleSearchTips)),
         )
-        .width(Length::Fill)
-        .center_x(Length::Fill)
-        .padding(spacing::XXL)
-        .into()
     } else if results.is_empty() {
         container(
             column![
@@ -145,10 +172,25 @@ pub fn search_view<'a>(
         .padding(spacing::XXL)
         .into()
     } else {
-        let rows: Vec<Element<'a, Message>> = results
+        let rows: Vec<Element<'_, Message>> = results
             .iter()
             .enumerate()
-            .map(|(i, msg)| message_row(msg, i == selected_index, selected_messages.contains(&msg.id)))
+            .map(|(i, msg)| {
+                // Click focuses (shift-click selects a range); right-click opens the menu
+                let row =
+                    mouse_area(message_row(
+                        msg,
+                        i == selected_index,
+                        selected_messages.contains(&msg.id),
+                        state.is_blocked(&msg.from_email),
+                    ))
+                    .on_press(Message::RowClicked(i))
+                    .on_right_press(Message::ShowContextMenu(ContextTarget::Message {
+                        message_id: msg.id,
+                        index: i,
+                    }));
+                focus_ring(row, show_focus && i == selected_index)
+            })
             .collect();
 
         scrollable(column(rows).spacing(spacing::SPACE_1))
@@ -156,11 +198,12 @@ pub fn search_view<'a>(
             .into()
     };
 
-    // Keyboard hints in FONT_MONO
-    let hints = text("Enter: open | Tab: toggle mode | Space: select | A: all | x: clear | d: delete")
-        .size(typography::SIZE_2XS)
-        .font(typography::FONT_MONO)
-        .style(components::text_muted);
+    // Page controls only when deep search has more than one page
+    let pagination: Element<'_, Message> = if is_deep && total > SEARCH_PAGE_SIZE {
+        pagination_row(state.search_offset, results.len(), total)
+    } else {
+        Space::with_height(0).into()
+    };
 
     column![
         search_bar,
@@ -170,8 +213,7 @@ pub fn search_view<'a>(
         column_headers,
         Space::with_height(spacing::XS),
         list_content,
-        Space::with_height(spacing::SM),
-        hints,
+        pagination,
     ]
     .spacing(spacing::XS)
     .padding(spacing::XL)
@@ -180,6 +222,48 @@ pub fn search_view<'a>(
     .into()
 }
 
+/// Previous/next page controls for deep search results
+fn pagination_row(offset: i64, page_count: usize, total: i64) -> Element<'static, Message> {
+    let page_info = text(format!(
+        "{}\u{2013}{} of {} \u{2022} Page {} of {}",
+        offset + 1,
+        offset + page_count as i64,
+        total,
+        (offset / SEARCH_PAGE_SIZE) + 1,
+        ((total.max(1) - 1) / SEARCH_PAGE_SIZE) + 1
+    ))
+    .size(typography::SIZE_XS)
+    .style(components::text_muted);
+
+    let prev_button = with_tooltip(
+        button(text(icons::ARROW_LEFT).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::SM])
+            .style(components::button_ghost)
+            .on_press_maybe((offset > 0).then_some(Message::PreviousSearchPage)),
+        "Previous page",
+        Position::Top,
+    );
+
+    let next_button = with_tooltip(
+        button(text(icons::ARROW_RIGHT).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::SM])
+            .style(components::button_ghost)
+            .on_press_maybe((offset + SEARCH_PAGE_SIZE < total).then_some(Message::NextSearchPage)),
+        "Next page",
+        Position::Top,
+    );
+
+    row![
+        page_info,
+        Space::with_width(Length::Fill),
+        prev_button,
+        next_button,
+    ]
+    .spacing(spacing::XS)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
 /// Column header row
 fn column_header_row<'a>() -> Element<'a, Message> {
     let select_header = text("")
@@ -222,7 +306,7 @@ fn column_header_row<'a>() -> Element<'a, Message> {
         .padding([spacing::XS, spacing::SM]),
     )
     .style(|_| container::Style {
-        background: Some(Background::Color(colors::BG_ELEVATED)),
+        background: Some(Background::Color(colors::bg_elevated())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             ..Default::default()
@@ -234,7 +318,12 @@ fn column_header_row<'a>() -> Element<'a, Message> {
 }
 
 /// Single message row (reused pattern from messages.rs)
-fn message_row<'a>(msg: &'a MessageSummary, is_cursor: bool, is_checked: bool) -> Element<'a, Message> {
+fn message_row<'a>(
+    msg: &'a MessageSummary,
+    is_cursor: bool,
+    is_checked: bool,
+    is_blocked: bool,
+) -> Element<'a, Message> {
     // Selection checkbox indicator
     let checkbox_indicator = if is_checked { icons::CHECK } else { icons::DOT_EMPTY };
     let checkbox = text(checkbox_indicator)
@@ -246,8 +335,14 @@ fn message_row<'a>(msg: &'a MessageSummary, is_cursor: bool, is_checked: bool) -
         })
         .width(Length::Fixed(24.0));
 
-    let subject = text(truncate_string(&msg.subject, 50))
+    // Unread messages get a bold subject
+    let subject = text(truncate(&msg.subject, 50))
         .size(typography::SIZE_SM)
+        .font(if msg.is_unread {
+            typography::FONT_SEMIBOLD
+        } else {
+            typography::FONT_PRIMARY
+        })
         .style(components::text_primary)
         .width(Length::FillPortion(4));
 
@@ -257,12 +352,20 @@ fn message_row<'a>(msg: &'a MessageSummary, is_cursor: bool, is_checked: bool) -
         .filter(|n| !n.is_empty())
         .map(|n| n.as_str())
         .unwrap_or(&msg.from_email);
-    let from = text(truncate_string(from_display, 30))
+    let from_text = text(truncate(from_display, 30))
         .size(typography::SIZE_SM)
-        .style(components::text_secondary)
-        .width(Length::FillPortion(3));
+        .style(components::text_secondary);
+    // Blocked senders only appear here when they are marked rather than hidden
+    let from = if is_blocked {
+        row![from_text, badge("Blocked", BadgeStyle::Error)]
+            .spacing(spacing::XS)
+            .align_y(iced::Alignment::Center)
+    } else {
+        row![from_text]
+    }
+    .width(Length::FillPortion(3));
 
-    let date = text(format_date(&msg.sent_at))
+    let date = text(format_list_date(&msg.sent_at))
         .size(typography::SIZE_XS)
         .font(typography::FONT_MONO)
         .style(components::text_muted)
@@ -288,7 +391,7 @@ fn message_row<'a>(msg: &'a MessageSummary, is_cursor: bool, is_checked: bool) -
         container(row_content).style(components::selected_row_style)
     } else if is_checked {
         container(row_content).style(|_| container::Style {
-            background: Some(Background::Color(colors::SELECTION_BG)),
+            background: Some(Background::Color(colors::selection_bg())),
             border: Border {
                 radius: spacing::RADIUS_MD.into(),
                 ..Default::default()
@@ -308,30 +411,60 @@ fn message_row<'a>(msg: &'a MessageSummary, is_cursor: bool, is_checked: bool) -
     style.width(Length::Fill).into()
 }
 
-/// Format a datetime for display
-fn format_date(dt: &DateTime<Utc>) -> String {
-    let local: DateTime<Local> = dt.with_timezone(&Local);
-    let now = Local::now();
-
-    // If today, show time only
-    if local.date_naive() == now.date_naive() {
-        return local.format("%H:%M").to_string();
-    }
-
-    // If this year, show month and day
-    if local.year() == now.year() {
-        return local.format("%b %d").to_string();
-    }
-
-    // Otherwise show full date
-    local.format("%Y-%m-%d").to_string()
-}
+/// Filter syntax reference, shown from the no-results state
+fn search_tips<'a>(is_deep: bool) -> Element<'a, Message> {
+    let rows: Vec<Element<'a, Message>> = SEARCH_TIPS
+        .iter()
+        .map(|(example, description)| {
+            row![
+                text(*example)
+                    .size(typography::SIZE_SM)
+                    .font(typography::FONT_MONO)
+                    .style(components::text_accent)
+                    .width(Length::Fixed(240.0)),
+                text(*description)
+                    .size(typography::SIZE_SM)
+                    .style(components::text_secondary),
+            ]
+            .into()
+        })
+        .collect();
 
-/// Truncate a string to a maximum length, adding ellipsis if needed
-fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
+    // Filters are sent as text in fast mode, so point at deep search
+    let mode_hint: Element<'a, Message> = if is_deep {
+        Space::with_height(0).into()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
-    }
+        button(text("Switch to Deep search").size(typography::SIZE_SM))
+            .padding([spacing::SM, spacing::LG])
+            .style(components::button_primary)
+            .on_press(Message::ToggleSearchMode)
+            .into()
+    };
+
+    container(
+        column![
+            text("Search tips")
+                .size(typography::SIZE_MD)
+                .font(typography::FONT_SEMIBOLD)
+                .style(components::text_secondary),
+            text("Deep search understands these filters; combine them with free text")
+                .size(typography::SIZE_SM)
+                .style(components::text_muted),
+            Space::with_height(spacing::SM),
+            column(rows).spacing(spacing::XS),
+            Space::with_height(spacing::MD),
+            row![
+                mode_hint,
+                button(text("Hide tips").size(typography::SIZE_SM))
+                    .padding([spacing::SM, spacing::LG])
+                    .style(components::button_secondary)
+                    .on_press(Message::ToggleSearchTips),
+            ]
+            .spacing(spacing::SM),
+        ]
+        .spacing(spacing::XS),
+    )
+    .width(Length::Fill)
+    .padding(spacing::XXL)
+    .into()
 }
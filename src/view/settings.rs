@@ -1,21 +1,23 @@
 //! Settings view
 //!
-//! Configuration UI with tabs for server settings and display preferences.
+//! Configuration UI with tabs for server settings, display preferences and
+//! blocked senders.
 
+use crate::api::is_insecure_url;
+use crate::config::{
+    BlockedSenderDisplay, ByteUnits, DefaultView, ReadingPaneMode, Settings, ThemeChoice,
+    DOWNLOAD_LIMIT_OPTIONS, SNIPPET_LENGTH_OPTIONS,
+};
 use crate::message::Message;
-use crate::model::SettingsTab;
-use crate::theme::{colors, components, spacing, typography};
+use crate::model::{AppState, SettingsTab};
+use crate::theme::{colors, components, icons, spacing, typography};
+use crate::view::util::truncate;
+use crate::view::widgets::{with_tooltip, Position};
 use iced::widget::{button, column, container, row, text, text_input, Space};
 use iced::{Background, Border, Element, Length, Theme};
 
-/// Render the settings view
-pub fn settings_view<'a>(
-    current_tab: SettingsTab,
-    server_url: &'a str,
-    api_key: &'a str,
-    testing_connection: bool,
-    connection_result: Option<&'a Result<(), String>>,
-) -> Element<'a, Message> {
+/// Render the settings view from the editable settings form state
+pub fn settings_view(state: &AppState) -> Element<'_, Message> {
     // Header
     let title = text("Settings")
         .size(typography::SIZE_XL)
@@ -23,12 +25,13 @@ pub fn settings_view<'a>(
         .style(components::text_primary);
 
     // Tab bar
-    let tab_bar = tab_bar_widget(current_tab);
+    let tab_bar = tab_bar_widget(state.settings_tab);
 
     // Tab content
-    let content = match current_tab {
-        SettingsTab::Server => server_tab(server_url, api_key, testing_connection, connection_result),
-        SettingsTab::Display => display_tab(),
+    let content = match state.settings_tab {
+        SettingsTab::Server => server_tab(state),
+        SettingsTab::Display => display_tab(state),
+        SettingsTab::Blocked => blocked_tab(state),
     };
 
     // Save button
@@ -37,11 +40,20 @@ pub fn settings_view<'a>(
         .style(components::button_primary)
         .on_press(Message::SaveSettings);
 
-    // Keyboard hints in FONT_MONO
-    let hints = text(",: settings | Esc: back (without saving)")
-        .size(typography::SIZE_2XS)
-        .font(typography::FONT_MONO)
-        .style(components::text_muted);
+    // Move the configuration between machines
+    let export_button = button(text("Export Settings").size(typography::SIZE_SM))
+        .padding([spacing::SM, spacing::LG])
+        .style(components::button_secondary)
+        .on_press(Message::ExportSettings);
+    let import_button = button(text("Import Settings").size(typography::SIZE_SM))
+        .padding([spacing::SM, spacing::LG])
+        .style(components::button_secondary)
+        .on_press(Message::ImportSettings);
+    let include_key = toggle_option(
+        "Include API key in export",
+        state.settings_export_api_key,
+        Message::SettingsExportApiKeyToggled,
+    );
 
     column![
         title,
@@ -50,9 +62,16 @@ pub fn settings_view<'a>(
         Space::with_height(spacing::XL),
         content,
         Space::with_height(Length::Fill),
-        row![Space::with_width(Length::Fill), save_button],
-        Space::with_height(spacing::SM),
-        hints,
+        row![
+            export_button,
+            Space::with_width(spacing::SM),
+            import_button,
+            Space::with_width(spacing::LG),
+            container(include_key).width(Length::Fixed(280.0)),
+            Space::with_width(Length::Fill),
+            save_button,
+        ]
+        .align_y(iced::Alignment::Center),
     ]
     .spacing(spacing::XS)
     .padding(spacing::XL)
@@ -63,16 +82,33 @@ pub fn settings_view<'a>(
 
 /// Tab bar for switching between settings sections
 fn tab_bar_widget(current: SettingsTab) -> Element<'static, Message> {
-    let server_tab = tab_button("Server", SettingsTab::Server, current == SettingsTab::Server);
-    let display_tab = tab_button("Display", SettingsTab::Display, current == SettingsTab::Display);
+    let server_tab = tab_button(
+        "Server",
+        SettingsTab::Server,
+        current == SettingsTab::Server,
+    );
+    let display_tab = tab_button(
+        "Display",
+        SettingsTab::Display,
+        current == SettingsTab::Display,
+    );
+    let blocked_tab = tab_button(
+        "Blocked Senders",
+        SettingsTab::Blocked,
+        current == SettingsTab::Blocked,
+    );
 
-    container(
-        row![server_tab, Space::with_width(spacing::XS), display_tab],
-    )
+    container(row![
+        server_tab,
+        Space::with_width(spacing::XS),
+        display_tab,
+        Space::with_width(spacing::XS),
+        blocked_tab,
+    ])
     .style(|_| container::Style {
         border: Border {
             width: 0.0,
-            color: colors::BORDER_SUBTLE,
+            color: colors::border_subtle(),
             radius: 0.0.into(),
         },
         ..Default::default()
@@ -94,12 +130,12 @@ fn tab_button(label: &'static str, tab: SettingsTab, is_active: bool) -> Element
         let styled_btn = btn
             .style(|_theme: &Theme, status| {
                 let bg = match status {
-                    button::Status::Hovered => colors::BG_ELEVATED,
-                    _ => colors::BG_SURFACE,
+                    button::Status::Hovered => colors::bg_elevated(),
+                    _ => colors::bg_surface(),
                 };
                 button::Style {
                     background: Some(Background::Color(bg)),
-                    text_color: colors::ACCENT_PRIMARY,
+                    text_color: colors::accent_primary(),
                     border: Border {
                         radius: spacing::RADIUS_MD.into(),
                         ..Default::default()
@@ -114,7 +150,7 @@ fn tab_button(label: &'static str, tab: SettingsTab, is_active: bool) -> Element
             .style(|_| container::Style {
                 border: Border {
                     width: 2.0,
-                    color: colors::ACCENT_PRIMARY,
+                    color: colors::accent_primary(),
                     radius: spacing::RADIUS_MD.into(),
                 },
                 ..Default::default()
@@ -128,12 +164,13 @@ fn tab_button(label: &'static str, tab: SettingsTab, is_active: bool) -> Element
 }
 
 /// Server settings tab content
-fn server_tab<'a>(
-    server_url: &'a str,
-    api_key: &'a str,
-    testing: bool,
-    result: Option<&'a Result<(), String>>,
-) -> Element<'a, Message> {
+fn server_tab(state: &AppState) -> Element<'_, Message> {
+    let server_url = state.settings_server_url.as_str();
+    let api_key = state.settings_api_key.as_str();
+    let allow_insecure = state.settings_allow_insecure;
+    let testing = state.testing_connection;
+    let result = state.connection_test_result.as_ref();
+
     // Section header
     let section_header = text("Connection")
         .size(typography::SIZE_LG)
@@ -163,6 +200,45 @@ fn server_tab<'a>(
         .style(components::text_input_style)
         .secure(true);
 
+    // Relaxed security is called out next to the toggle
+    let insecure_note: Element<'_, Message> = if !is_insecure_url(server_url) {
+        if allow_insecure && server_url.starts_with("https://") {
+            text("Certificate validation is off. Anyone on the network could impersonate this server.")
+                .size(typography::SIZE_XS)
+                .style(components::text_warning)
+                .into()
+        } else {
+            Space::new(0, 0).into()
+        }
+    } else if allow_insecure {
+        text("This server uses unencrypted HTTP. Your API key and mail can be read on the network.")
+            .size(typography::SIZE_XS)
+            .style(components::text_warning)
+            .into()
+    } else {
+        text("Unencrypted HTTP to a remote server is blocked. Use https:// or allow insecure connections.")
+            .size(typography::SIZE_XS)
+            .style(components::text_error)
+            .into()
+    };
+
+    // Pinned fingerprint, or when it will be recorded
+    let pin_note: Element<'_, Message> = if !state.settings_pin_certificate {
+        Space::new(0, 0).into()
+    } else {
+        let note = match &state.pinned_certificate {
+            Some(fingerprint) if server_url == state.server_url => {
+                format!("Pinned: {}", truncate(fingerprint, 48))
+            }
+            _ => "The certificate will be pinned on the next connection".to_string(),
+        };
+        text(note)
+            .size(typography::SIZE_XS)
+            .font(typography::FONT_MONO)
+            .style(components::text_muted)
+            .into()
+    };
+
     // Test connection button and result
     let test_button = if testing {
         button(text("Testing...").size(typography::SIZE_SM))
@@ -175,12 +251,12 @@ fn server_tab<'a>(
             .on_press(Message::TestConnection)
     };
 
-    let test_result: Element<'a, Message> = match result {
+    let test_result: Element<'_, Message> = match result {
         Some(Ok(())) => text("Connected successfully!")
             .size(typography::SIZE_SM)
             .style(components::text_success)
             .into(),
-        Some(Err(e)) => text(format!("Failed: {}", truncate_error(e, 50)))
+        Some(Err(e)) => text(format!("Failed: {}", truncate(e, 50)))
             .size(typography::SIZE_SM)
             .style(components::text_error)
             .into(),
@@ -196,9 +272,26 @@ fn server_tab<'a>(
             Space::with_height(spacing::LG),
             api_key_label,
             api_key_input,
+            Space::with_height(spacing::LG),
+            toggle_option(
+                "Allow insecure connections (HTTP, unverified certificates)",
+                allow_insecure,
+                Message::SettingsAllowInsecureToggled,
+            ),
+            insecure_note,
+            toggle_option(
+                "Pin server certificate",
+                state.settings_pin_certificate,
+                Message::SettingsPinCertificateToggled,
+            ),
+            pin_note,
             Space::with_height(spacing::XL),
             row![test_button, Space::with_width(spacing::LG), test_result]
                 .align_y(iced::Alignment::Center),
+            Space::with_height(spacing::XL),
+            support_info(state),
+            Space::with_height(spacing::XL),
+            reset_button(),
         ]
         .spacing(spacing::XS),
     )
@@ -208,22 +301,238 @@ fn server_tab<'a>(
     .into()
 }
 
-/// Display settings tab content
-fn display_tab<'a>() -> Element<'a, Message> {
+/// Server URL and config file location, copyable for bug reports
+fn support_info(state: &AppState) -> Element<'_, Message> {
+    let label = text("Support")
+        .size(typography::SIZE_SM)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_secondary);
+
+    let copy_row = |name: &'static str, tip: &'static str, value: String| -> Element<'_, Message> {
+        row![
+            text(name)
+                .size(typography::SIZE_XS)
+                .width(Length::Fixed(80.0))
+                .style(components::text_muted),
+            text(truncate(&value, 60))
+                .size(typography::SIZE_XS)
+                .font(typography::FONT_MONO)
+                .style(components::text_primary),
+            Space::with_width(Length::Fill),
+            with_tooltip(
+                button(text(icons::COPY).size(typography::SIZE_XS))
+                    .padding([spacing::XS, spacing::SM])
+                    .style(components::button_ghost)
+                    .on_press(Message::CopyToClipboard(value)),
+                tip,
+                Position::Left,
+            ),
+        ]
+        .align_y(iced::Alignment::Center)
+        .into()
+    };
+
+    let config_path = Settings::config_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "(unavailable)".to_string());
+
+    column![
+        label,
+        copy_row("Server URL", "Copy server URL", state.server_url.clone()),
+        copy_row("Config file", "Copy config file path", config_path),
+        Space::with_height(spacing::XS),
+        button(text("Open config folder").size(typography::SIZE_SM))
+            .padding([spacing::SM, spacing::LG])
+            .style(components::button_secondary)
+            .on_press(Message::OpenConfigFolder),
+    ]
+    .spacing(spacing::XS)
+    .into()
+}
+
+/// Display settings tab content, from the editable settings form state
+fn display_tab(state: &AppState) -> Element<'_, Message> {
+    let default_view = state.settings_default_view;
+    let max_downloads = state.settings_max_concurrent_downloads;
+    let theme = state.settings_theme;
+    let byte_units = state.settings_byte_units;
+
     // Section header
     let section_header = text("Display Settings")
         .size(typography::SIZE_LG)
         .font(typography::FONT_MEDIUM)
         .style(components::text_primary);
 
+    let default_view_label = text("Open on Connect")
+        .size(typography::SIZE_SM)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_secondary);
+
+    // Segmented choice of landing view
+    let default_view_options = row(DefaultView::all().iter().map(|&view| {
+        let btn = button(text(view.display_name()).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::MD])
+            .on_press(Message::SettingsDefaultViewChanged(view));
+        if view == default_view {
+            btn.style(components::button_primary).into()
+        } else {
+            btn.style(components::button_secondary).into()
+        }
+    }))
+    .spacing(spacing::XS);
+
+    let theme_label = text("Theme")
+        .size(typography::SIZE_SM)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_secondary);
+
+    // Segmented choice of color palette
+    let theme_options = row(ThemeChoice::all().iter().map(|&choice| {
+        let btn = button(text(choice.display_name()).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::MD])
+            .on_press(Message::SettingsThemeChanged(choice));
+        if choice == theme {
+            btn.style(components::button_primary).into()
+        } else {
+            btn.style(components::button_secondary).into()
+        }
+    }))
+    .spacing(spacing::XS);
+
+    let reading_pane_label = text("Reading Pane")
+        .size(typography::SIZE_SM)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_secondary);
+
+    // Segmented choice of where opened messages are shown
+    let reading_pane_options = row(ReadingPaneMode::all().iter().map(|&mode| {
+        let btn = button(text(mode.display_name()).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::MD])
+            .on_press(Message::SettingsReadingPaneChanged(mode));
+        if mode == state.settings_reading_pane {
+            btn.style(components::button_primary).into()
+        } else {
+            btn.style(components::button_secondary).into()
+        }
+    }))
+    .spacing(spacing::XS);
+
+    let byte_units_label = text("File Sizes")
+        .size(typography::SIZE_SM)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_secondary);
+
+    // Segmented choice of size units
+    let byte_units_options = row(ByteUnits::all().iter().map(|&units| {
+        let btn = button(text(units.display_name()).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::MD])
+            .on_press(Message::SettingsByteUnitsChanged(units));
+        if units == byte_units {
+            btn.style(components::button_primary).into()
+        } else {
+            btn.style(components::button_secondary).into()
+        }
+    }))
+    .spacing(spacing::XS);
+
+    let snippet_length_label = text("Preview Length")
+        .size(typography::SIZE_SM)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_secondary);
+
+    // Segmented choice of preview length; moot while previews are hidden
+    let snippet_length = state.settings_snippet_length;
+    let snippet_length_options = row(SNIPPET_LENGTH_OPTIONS.iter().map(|&length| {
+        let btn = button(text(format!("{} chars", length)).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::MD])
+            .on_press_maybe(
+                state
+                    .settings_show_snippets
+                    .then_some(Message::SettingsSnippetLengthChanged(length)),
+            );
+        if length == snippet_length {
+            btn.style(components::button_primary).into()
+        } else {
+            btn.style(components::button_secondary).into()
+        }
+    }))
+    .spacing(spacing::XS);
+
+    let max_downloads_label = text("Simultaneous Downloads")
+        .size(typography::SIZE_SM)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_secondary);
+
+    // Segmented choice of download concurrency limit
+    let max_downloads_options = row(DOWNLOAD_LIMIT_OPTIONS.iter().map(|&limit| {
+        let btn = button(text(limit.to_string()).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::MD])
+            .on_press(Message::SettingsMaxDownloadsChanged(limit));
+        if limit == max_downloads {
+            btn.style(components::button_primary).into()
+        } else {
+            btn.style(components::button_secondary).into()
+        }
+    }))
+    .spacing(spacing::XS);
+
     container(
         column![
             section_header,
             Space::with_height(spacing::LG),
-            text("Theme: System Default")
-                .size(typography::SIZE_SM)
-                .style(components::text_secondary),
+            default_view_label,
+            default_view_options,
             Space::with_height(spacing::SM),
+            toggle_option(
+                "Restore last view on startup",
+                state.settings_restore_session,
+                Message::SettingsRestoreSessionToggled,
+            ),
+            toggle_option(
+                "Show keyboard hints",
+                state.settings_show_key_hints,
+                Message::SettingsShowKeyHintsToggled,
+            ),
+            toggle_option(
+                "Load remote avatars (Gravatar)",
+                state.settings_load_remote_avatars,
+                Message::SettingsLoadRemoteAvatarsToggled,
+            ),
+            toggle_option(
+                "Desktop notifications for sync",
+                state.settings_desktop_notifications,
+                Message::SettingsDesktopNotificationsToggled,
+            ),
+            Space::with_height(spacing::LG),
+            toggle_option(
+                "Show message previews in lists",
+                state.settings_show_snippets,
+                Message::SettingsShowSnippetsToggled,
+            ),
+            snippet_length_label,
+            snippet_length_options,
+            Space::with_height(spacing::LG),
+            reading_pane_label,
+            reading_pane_options,
+            Space::with_height(spacing::LG),
+            remote_content_senders(state),
+            Space::with_height(spacing::LG),
+            max_downloads_label,
+            max_downloads_options,
+            Space::with_height(spacing::SM),
+            toggle_option(
+                "Open downloads when finished",
+                state.settings_auto_open_downloads,
+                Message::SettingsAutoOpenDownloadsToggled,
+            ),
+            auto_open_extensions(state),
+            Space::with_height(spacing::LG),
+            theme_label,
+            theme_options,
+            Space::with_height(spacing::LG),
+            byte_units_label,
+            byte_units_options,
+            Space::with_height(spacing::LG),
             text("Date Format: Auto")
                 .size(typography::SIZE_SM)
                 .style(components::text_secondary),
@@ -231,6 +540,8 @@ fn display_tab<'a>() -> Element<'a, Message> {
             text("(More display options coming soon)")
                 .size(typography::SIZE_XS)
                 .style(components::text_muted),
+            Space::with_height(spacing::XL),
+            reset_button(),
         ]
         .spacing(spacing::XS),
     )
@@ -240,23 +551,219 @@ fn display_tab<'a>() -> Element<'a, Message> {
     .into()
 }
 
-/// Truncate error message for display
-fn truncate_error(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
+/// Senders allowed to load remote content, each removable
+///
+/// Changes apply straight away rather than through the form, like the
+/// choice made from a message.
+fn remote_content_senders(state: &AppState) -> Element<'_, Message> {
+    let label = text("Remote Images")
+        .size(typography::SIZE_SM)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_secondary);
+
+    let senders: Element<'_, Message> = if state.remote_content_senders.is_empty() {
+        text("Blocked for every sender")
+            .size(typography::SIZE_XS)
+            .style(components::text_muted)
+            .into()
     } else {
-        format!("{}...", &s[..max_len])
+        column(state.remote_content_senders.iter().map(|sender| {
+            row![
+                text(sender.as_str())
+                    .size(typography::SIZE_SM)
+                    .style(components::text_primary),
+                Space::with_width(Length::Fill),
+                with_tooltip(
+                    button(text(icons::DELETE).size(typography::SIZE_XS))
+                        .padding([spacing::XS, spacing::SM])
+                        .style(components::button_ghost)
+                        .on_press(Message::DisallowRemoteContent(sender.clone())),
+                    "Block remote images again",
+                    Position::Left,
+                ),
+            ]
+            .align_y(iced::Alignment::Center)
+            .into()
+        }))
+        .spacing(spacing::XS)
+        .into()
+    };
+
+    column![
+        label,
+        text("Always loaded from these senders")
+            .size(typography::SIZE_XS)
+            .style(components::text_muted),
+        senders,
+    ]
+    .spacing(spacing::XS)
+    .into()
+}
+
+/// Blocked senders tab content
+///
+/// Like the remote image list, changes apply straight away rather than
+/// through the form.
+fn blocked_tab(state: &AppState) -> Element<'_, Message> {
+    let section_header = text("Blocked Senders")
+        .size(typography::SIZE_LG)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_primary);
+
+    let explanation = text(
+        "Messages from these senders are hidden or marked in this app only. \
+         Nothing is deleted: the messages stay in the archive on the server.",
+    )
+    .size(typography::SIZE_XS)
+    .style(components::text_muted);
+
+    let input = state.block_sender_input.trim();
+    let add_input = text_input("sender@example.com", &state.block_sender_input)
+        .on_input(Message::BlockSenderInputChanged)
+        .on_submit(Message::BlockSender(input.to_string()))
+        .padding(spacing::SM)
+        .width(Length::Fill)
+        .style(components::text_input_style);
+    let add_button = button(text("Block").size(typography::SIZE_SM))
+        .padding([spacing::SM, spacing::LG])
+        .style(components::button_secondary)
+        .on_press_maybe((!input.is_empty()).then(|| Message::BlockSender(input.to_string())));
+
+    let display_label = text("Blocked Messages")
+        .size(typography::SIZE_SM)
+        .font(typography::FONT_MEDIUM)
+        .style(components::text_secondary);
+
+    // Segmented choice of hiding or marking
+    let display_options = row(BlockedSenderDisplay::all().iter().map(|&display| {
+        let btn = button(text(display.display_name()).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::MD])
+            .on_press(Message::BlockedSenderDisplayChanged(display));
+        if display == state.blocked_sender_display {
+            btn.style(components::button_primary).into()
+        } else {
+            btn.style(components::button_secondary).into()
+        }
+    }))
+    .spacing(spacing::XS);
+
+    let senders: Element<'_, Message> = if state.blocked_senders.is_empty() {
+        text("No senders blocked")
+            .size(typography::SIZE_XS)
+            .style(components::text_muted)
+            .into()
+    } else {
+        column(state.blocked_senders.iter().map(|sender| {
+            row![
+                text(sender.as_str())
+                    .size(typography::SIZE_SM)
+                    .style(components::text_primary),
+                Space::with_width(Length::Fill),
+                button(text("Unblock").size(typography::SIZE_XS))
+                    .padding([spacing::XS, spacing::SM])
+                    .style(components::button_ghost)
+                    .on_press(Message::UnblockSender(sender.clone())),
+            ]
+            .align_y(iced::Alignment::Center)
+            .into()
+        }))
+        .spacing(spacing::XS)
+        .into()
+    };
+
+    container(
+        column![
+            section_header,
+            explanation,
+            Space::with_height(spacing::LG),
+            display_label,
+            display_options,
+            Space::with_height(spacing::LG),
+            row![add_input, add_button]
+                .spacing(spacing::SM)
+                .align_y(iced::Alignment::Center),
+            Space::with_height(spacing::SM),
+            senders,
+        ]
+        .spacing(spacing::XS),
+    )
+    .style(section_style)
+    .padding(spacing::XL)
+    .width(Length::Fill)
+    .into()
+}
+
+/// Button opening the reset-to-defaults confirmation
+fn reset_button<'a>() -> Element<'a, Message> {
+    button(
+        text("Reset to Defaults")
+            .size(typography::SIZE_SM)
+            .style(components::text_error),
+    )
+    .padding([spacing::SM, spacing::LG])
+    .style(components::button_danger)
+    .on_press(Message::ShowResetSettingsModal)
+    .into()
+}
+
+/// File types that open automatically, shown while auto-open is on
+fn auto_open_extensions(state: &AppState) -> Element<'_, Message> {
+    if !state.settings_auto_open_downloads {
+        return Space::with_height(0).into();
     }
+
+    column![
+        text_input("pdf, png, jpg", &state.settings_auto_open_extensions)
+            .on_input(Message::SettingsAutoOpenExtensionsChanged)
+            .padding(spacing::SM)
+            .width(Length::Fill)
+            .style(components::text_input_style),
+        text("Only these extensions open. Programs, scripts and archives never do.")
+            .size(typography::SIZE_XS)
+            .style(components::text_muted),
+    ]
+    .spacing(spacing::XS)
+    .into()
+}
+
+/// Labelled Off/On switch for a boolean setting
+fn toggle_option<'a>(
+    label: &'static str,
+    enabled: bool,
+    on_toggle: fn(bool) -> Message,
+) -> Element<'a, Message> {
+    let option = |name: &'static str, value: bool| {
+        let btn = button(text(name).size(typography::SIZE_XS))
+            .padding([spacing::XS, spacing::SM])
+            .on_press(on_toggle(value));
+        if value == enabled {
+            btn.style(components::button_primary)
+        } else {
+            btn.style(components::button_secondary)
+        }
+    };
+
+    row![
+        text(label)
+            .size(typography::SIZE_SM)
+            .style(components::text_secondary),
+        Space::with_width(Length::Fill),
+        option("Off", false),
+        option("On", true),
+    ]
+    .spacing(spacing::XS)
+    .align_y(iced::Alignment::Center)
+    .into()
 }
 
 /// Section container style with RADIUS_MD
 fn section_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(colors::BG_SURFACE)),
+        background: Some(Background::Color(colors::bg_surface())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             width: 1.0,
-            color: colors::BORDER_SUBTLE,
+            color: colors::border_subtle(),
         },
         ..Default::default()
     }
@@ -2,13 +2,19 @@
 //!
 //! Foundry Dark design: warm browns, copper accent, icon-driven nav.
 //! Shows logo mark, navigation, browse sections, labels, accounts,
-//! and a bottom status bar with connection indicator.
+//! and a bottom status bar with connection indicator. Collapsed, it
+//! becomes a rail of icons with tooltips naming each item.
 
 use crate::api::types::ViewType;
+use crate::config::{PinnedFilter, SavedSearch};
 use crate::message::Message;
-use crate::model::ViewLevel;
+use crate::model::menu_bar::chord_label;
+use crate::model::{ContextTarget, ViewLevel};
 use crate::theme::{colors, components, icons, spacing, typography};
-use iced::widget::{button, column, container, horizontal_rule, row, scrollable, text, Space};
+use crate::view::widgets::{with_tooltip, Position};
+use iced::widget::{
+    button, column, container, horizontal_rule, mouse_area, row, scrollable, text, Space,
+};
 use iced::{Background, Border, Color, Element, Length};
 
 // === Avatar palette (deterministic dot color per account) ===
@@ -72,40 +78,81 @@ fn dot_color_for(name: &str) -> Color {
     AVATAR_PALETTE[hash % AVATAR_PALETTE.len()]
 }
 
+/// Focusable entries in the navigation section
+const NAV_ITEMS: usize = 4;
+
+/// Focusable entries in the browse section
+const BROWSE_ITEMS: usize = 4;
+
 // ───────────────────────────────────────────────────────────────
 // Public entry point
 // ───────────────────────────────────────────────────────────────
 
 /// Render the full sidebar.
+///
+/// `focus` is the keyboard-focused entry, counting the navigation items,
+/// saved searches, pinned entries, browse items, accounts and bottom items
+/// in that order
+/// (labels are not focusable). `update::sidebar_entries` must list the
+/// same entries in the same order.
+///
+/// `collapsed` draws the icon-only rail; labels are left out of it.
 pub fn sidebar<'a>(
     current_view: &ViewLevel,
     accounts: &[String],
     labels: &[String],
+    saved_searches: &[SavedSearch],
+    pinned: &[PinnedFilter],
+    focus: Option<usize>,
+    collapsed: bool,
 ) -> Element<'a, Message> {
-    let header = sidebar_header();
-    let nav = nav_section(current_view);
-    let browse = browse_section(current_view);
+    // Focus relative to the start of a section
+    let focus_from = |start: usize| focus.and_then(|f| f.checked_sub(start));
+    let saved_start = NAV_ITEMS;
+    let pinned_start = saved_start + saved_searches.len();
+    let browse_start = pinned_start + pinned.len();
+    let accounts_start = browse_start + BROWSE_ITEMS;
+    let bottom_start = accounts_start + accounts.len();
+
+    let header = sidebar_header(collapsed);
+    let nav = nav_section(current_view, focus, collapsed);
+
+    let saved_el: Element<'a, Message> = if !saved_searches.is_empty() {
+        saved_searches_section(saved_searches, focus_from(saved_start), collapsed)
+    } else {
+        Space::with_height(0).into()
+    };
+    let pinned_el: Element<'a, Message> = if !pinned.is_empty() {
+        pinned_section(pinned, focus_from(pinned_start), collapsed)
+    } else {
+        Space::with_height(0).into()
+    };
+    let browse = browse_section(current_view, focus_from(browse_start), collapsed);
 
-    let labels_el: Element<'a, Message> = if !labels.is_empty() {
+    let labels_el: Element<'a, Message> = if !labels.is_empty() && !collapsed {
         labels_section_view(labels)
     } else {
         Space::with_height(0).into()
     };
 
     let accounts_el: Element<'a, Message> = if !accounts.is_empty() {
-        accounts_section_view(accounts)
+        accounts_section_view(accounts, focus_from(accounts_start), collapsed)
     } else {
         Space::with_height(0).into()
     };
 
     let divider = divider_line();
-    let bottom = bottom_navigation();
+    let bottom = bottom_navigation(focus_from(bottom_start), collapsed);
 
     let content = column![
         header,
         Space::with_height(spacing::LG),
         nav,
         Space::with_height(spacing::XL),
+        saved_el,
+        Space::with_height(spacing::XL),
+        pinned_el,
+        Space::with_height(spacing::XL),
         browse,
         Space::with_height(spacing::XL),
         labels_el,
@@ -116,7 +163,10 @@ pub fn sidebar<'a>(
         Space::with_height(spacing::SM),
         bottom,
     ]
-    .padding([spacing::LG, spacing::MD])
+    .padding([
+        spacing::LG,
+        if collapsed { spacing::XS } else { spacing::MD },
+    ])
     .width(Length::Fill);
 
     scrollable(content).height(Length::Fill).into()
@@ -127,7 +177,30 @@ pub fn sidebar<'a>(
 // ───────────────────────────────────────────────────────────────
 
 /// "◆ msgvault" logo mark. Diamond in copper, text in primary.
-fn sidebar_header() -> Element<'static, Message> {
+///
+/// Followed by the collapse toggle; the rail shows only the toggle.
+fn sidebar_header(collapsed: bool) -> Element<'static, Message> {
+    let (icon, action) = if collapsed {
+        (icons::SIDEBAR_EXPAND, "Expand sidebar")
+    } else {
+        (icons::SIDEBAR_COLLAPSE, "Collapse sidebar")
+    };
+    let toggle = rail_tooltip(
+        button(
+            text(icon)
+                .size(typography::SIZE_SM)
+                .style(components::text_muted),
+        )
+        .padding([spacing::XS, spacing::SM])
+        .style(components::button_ghost)
+        .on_press(Message::ToggleSidebar),
+        format!("{} ({})", action, chord_label("\\")),
+    );
+
+    if collapsed {
+        return container(toggle).center_x(Length::Fill).into();
+    }
+
     row![
         text(icons::DIAMOND)
             .size(typography::SIZE_LG)
@@ -138,6 +211,8 @@ fn sidebar_header() -> Element<'static, Message> {
             .size(typography::SIZE_LG)
             .font(typography::FONT_SEMIBOLD)
             .style(components::text_primary),
+        Space::with_width(Length::Fill),
+        toggle,
     ]
     .align_y(iced::Alignment::Center)
     .into()
@@ -148,7 +223,13 @@ fn sidebar_header() -> Element<'static, Message> {
 // ───────────────────────────────────────────────────────────────
 
 /// Uppercase, letter-spaced section label (e.g. "N A V I G A T E").
-fn section_label(label: &'static str) -> Element<'static, Message> {
+///
+/// The collapsed rail has no room for labels and leaves them out.
+fn section_label(label: &'static str, collapsed: bool) -> Element<'static, Message> {
+    if collapsed {
+        return Space::with_height(0).into();
+    }
+
     let upper = label.to_uppercase();
     let spaced: String = upper
         .chars()
@@ -175,39 +256,251 @@ fn section_label(label: &'static str) -> Element<'static, Message> {
 // Navigation section
 // ───────────────────────────────────────────────────────────────
 
-fn nav_section(current_view: &ViewLevel) -> Element<'static, Message> {
+fn nav_section(
+    current_view: &ViewLevel,
+    focus: Option<usize>,
+    collapsed: bool,
+) -> Element<'static, Message> {
     let is_dashboard = matches!(current_view, ViewLevel::Dashboard);
     let is_search = matches!(current_view, ViewLevel::Search);
     let is_sync = matches!(current_view, ViewLevel::Sync);
 
     column![
-        section_label("Navigate"),
+        section_label("Navigate", collapsed),
         Space::with_height(spacing::XS),
-        nav_item(icons::DASHBOARD, "Dashboard", Message::NavigateTo(ViewLevel::Dashboard), is_dashboard, None),
-        nav_item(icons::SEARCH, "Search", Message::OpenSearch, is_search, Some("/")),
-        nav_item(icons::SYNC, "Sync Status", Message::OpenSync, is_sync, None),
+        nav_item(
+            icons::DASHBOARD,
+            "Dashboard",
+            Message::NavigateTo(ViewLevel::Dashboard),
+            is_dashboard,
+            focus == Some(0),
+            None,
+            collapsed
+        ),
+        nav_item(
+            icons::SEARCH,
+            "Search",
+            Message::OpenSearch,
+            is_search,
+            focus == Some(1),
+            Some("/"),
+            collapsed
+        ),
+        nav_item(
+            icons::SYNC,
+            "Sync Status",
+            Message::OpenSync,
+            is_sync,
+            focus == Some(2),
+            None,
+            collapsed
+        ),
+        nav_item(
+            icons::COMPOSE,
+            "Compose",
+            Message::OpenCompose,
+            false,
+            focus == Some(3),
+            Some("c"),
+            collapsed
+        ),
     ]
     .spacing(spacing::SPACE_1)
     .into()
 }
 
+// ───────────────────────────────────────────────────────────────
+// Saved searches section (smart folders)
+// ───────────────────────────────────────────────────────────────
+
+fn saved_searches_section(
+    saved_searches: &[SavedSearch],
+    focus: Option<usize>,
+    collapsed: bool,
+) -> Element<'static, Message> {
+    let mut content = column![
+        section_label("Saved Searches", collapsed),
+        Space::with_height(spacing::XS),
+    ]
+    .spacing(spacing::SPACE_1);
+
+    for (index, saved) in saved_searches.iter().enumerate() {
+        content = content.push(saved_search_item(
+            index,
+            saved,
+            focus == Some(index),
+            collapsed,
+        ));
+    }
+
+    content.into()
+}
+
+fn saved_search_item(
+    index: usize,
+    saved: &SavedSearch,
+    is_focused: bool,
+    collapsed: bool,
+) -> Element<'static, Message> {
+    let icon = text(icons::SEARCH)
+        .size(typography::SIZE_XS)
+        .style(components::text_muted);
+    let content: Element<'static, Message> = if collapsed {
+        container(icon).center_x(Length::Fill).into()
+    } else {
+        row![
+            icon,
+            Space::with_width(spacing::SM),
+            text(truncate_name(&saved.name))
+                .size(typography::SIZE_SM)
+                .font(typography::FONT_PRIMARY),
+        ]
+        .align_y(iced::Alignment::Center)
+        .into()
+    };
+
+    let item = button(content)
+        .width(Length::Fill)
+        .padding([spacing::XS, spacing::SM])
+        .style(move |_theme: &iced::Theme, _status| button::Style {
+            background: None,
+            text_color: colors::text_secondary(),
+            border: focus_border(is_focused),
+            ..Default::default()
+        })
+        .on_press(Message::OpenSavedSearch(index));
+
+    // Right-click offers open/delete
+    let item =
+        mouse_area(item).on_right_press(Message::ShowContextMenu(ContextTarget::SavedSearch {
+            index,
+        }));
+
+    if collapsed {
+        rail_tooltip(item, saved.name.clone())
+    } else {
+        item.into()
+    }
+}
+
+// ───────────────────────────────────────────────────────────────
+// Pinned senders and domains
+// ───────────────────────────────────────────────────────────────
+
+fn pinned_section(
+    pinned: &[PinnedFilter],
+    focus: Option<usize>,
+    collapsed: bool,
+) -> Element<'static, Message> {
+    let mut content = column![
+        section_label("Pinned", collapsed),
+        Space::with_height(spacing::XS),
+    ]
+    .spacing(spacing::SPACE_1);
+
+    for (index, filter) in pinned.iter().enumerate() {
+        content = content.push(pinned_item(index, filter, focus == Some(index), collapsed));
+    }
+
+    content.into()
+}
+
+fn pinned_item(
+    index: usize,
+    filter: &PinnedFilter,
+    is_focused: bool,
+    collapsed: bool,
+) -> Element<'static, Message> {
+    let icon = text(icons::PIN)
+        .size(typography::SIZE_XS)
+        .style(components::text_accent);
+    let content: Element<'static, Message> = if collapsed {
+        container(icon).center_x(Length::Fill).into()
+    } else {
+        row![
+            icon,
+            Space::with_width(spacing::SM),
+            text(truncate_name(&filter.value))
+                .size(typography::SIZE_SM)
+                .font(typography::FONT_PRIMARY),
+        ]
+        .align_y(iced::Alignment::Center)
+        .into()
+    };
+
+    let item = button(content)
+        .width(Length::Fill)
+        .padding([spacing::XS, spacing::SM])
+        .style(move |_theme: &iced::Theme, _status| button::Style {
+            background: None,
+            text_color: colors::text_secondary(),
+            border: focus_border(is_focused),
+            ..Default::default()
+        })
+        .on_press(Message::OpenPinnedFilter(index));
+
+    // Right-click offers reordering and unpinning
+    let item =
+        mouse_area(item).on_right_press(Message::ShowContextMenu(ContextTarget::PinnedFilter {
+            index,
+        }));
+
+    if collapsed {
+        rail_tooltip(item, filter.description())
+    } else {
+        item.into()
+    }
+}
+
 // ───────────────────────────────────────────────────────────────
 // Browse section
 // ───────────────────────────────────────────────────────────────
 
-fn browse_section(current_view: &ViewLevel) -> Element<'static, Message> {
+fn browse_section(
+    current_view: &ViewLevel,
+    focus: Option<usize>,
+    collapsed: bool,
+) -> Element<'static, Message> {
     let active_view_type = match current_view {
         ViewLevel::Aggregates { view_type } => Some(*view_type),
         _ => None,
     };
 
     column![
-        section_label("Browse"),
+        section_label("Browse", collapsed),
         Space::with_height(spacing::XS),
-        browse_item(icons::ACCOUNTS, "Senders", ViewType::Senders, active_view_type),
-        browse_item(icons::DOT_FILLED, "Domains", ViewType::Domains, active_view_type),
-        browse_item(icons::DIAMOND_SM, "Labels", ViewType::Labels, active_view_type),
-        browse_item(icons::DOTS, "Time", ViewType::Time, active_view_type),
+        browse_item(
+            icons::ACCOUNTS,
+            "Senders",
+            ViewType::Senders,
+            active_view_type,
+            focus == Some(0),
+            collapsed
+        ),
+        browse_item(
+            icons::DOT_FILLED,
+            "Domains",
+            ViewType::Domains,
+            active_view_type,
+            focus == Some(1),
+            collapsed
+        ),
+        browse_item(
+            icons::DIAMOND_SM,
+            "Labels",
+            ViewType::Labels,
+            active_view_type,
+            focus == Some(2),
+            collapsed
+        ),
+        browse_item(
+            icons::DOTS,
+            "Time",
+            ViewType::Time,
+            active_view_type,
+            focus == Some(3),
+            collapsed
+        ),
     ]
     .spacing(spacing::SPACE_1)
     .into()
@@ -218,6 +511,8 @@ fn browse_item(
     label: &'static str,
     view_type: ViewType,
     active: Option<ViewType>,
+    is_focused: bool,
+    collapsed: bool,
 ) -> Element<'static, Message> {
     let is_active = active == Some(view_type);
     nav_item(
@@ -225,7 +520,9 @@ fn browse_item(
         label,
         Message::NavigateTo(ViewLevel::Aggregates { view_type }),
         is_active,
+        is_focused,
         None,
+        collapsed,
     )
 }
 
@@ -235,7 +532,7 @@ fn browse_item(
 
 fn labels_section_view(labels: &[String]) -> Element<'static, Message> {
     let mut content = column![
-        section_label("Labels"),
+        section_label("Labels", false),
         Space::with_height(spacing::XS),
     ]
     .spacing(spacing::SPACE_1);
@@ -266,7 +563,7 @@ fn label_item(label: &str) -> Element<'static, Message> {
     .padding([spacing::XS, spacing::SM])
     .style(|_theme: &iced::Theme, _status| button::Style {
         background: None,
-        text_color: colors::TEXT_SECONDARY,
+        text_color: colors::text_secondary(),
         border: Border {
             radius: spacing::RADIUS_SM.into(),
             ..Default::default()
@@ -283,53 +580,70 @@ fn label_item(label: &str) -> Element<'static, Message> {
 // Accounts section (colored dots from avatar palette)
 // ───────────────────────────────────────────────────────────────
 
-fn accounts_section_view(accounts: &[String]) -> Element<'static, Message> {
+fn accounts_section_view(
+    accounts: &[String],
+    focus: Option<usize>,
+    collapsed: bool,
+) -> Element<'static, Message> {
     let mut content = column![
-        section_label("Accounts"),
+        section_label("Accounts", collapsed),
         Space::with_height(spacing::XS),
     ]
     .spacing(spacing::SPACE_1);
 
-    for account in accounts.iter() {
-        content = content.push(account_item(account));
+    for (index, account) in accounts.iter().enumerate() {
+        content = content.push(account_item(account, focus == Some(index), collapsed));
     }
 
     content.into()
 }
 
-fn account_item(account: &str) -> Element<'static, Message> {
+fn account_item(account: &str, is_focused: bool, collapsed: bool) -> Element<'static, Message> {
     let account_owned = account.to_string();
     let dot_col = dot_color_for(account);
 
-    button(
+    let dot = text(icons::DOT_FILLED)
+        .size(typography::SIZE_2XS)
+        .style(move |_| iced::widget::text::Style {
+            color: Some(dot_col),
+        });
+    let content: Element<'static, Message> = if collapsed {
+        container(dot).center_x(Length::Fill).into()
+    } else {
         row![
-            text(icons::DOT_FILLED)
-                .size(typography::SIZE_2XS)
-                .style(move |_| iced::widget::text::Style {
-                    color: Some(dot_col),
-                }),
+            dot,
             Space::with_width(spacing::SM),
             text(truncate_email(&account_owned))
                 .size(typography::SIZE_XS)
                 .font(typography::FONT_PRIMARY),
         ]
-        .align_y(iced::Alignment::Center),
-    )
-    .width(Length::Fill)
-    .padding([spacing::XS, spacing::SM])
-    .style(|_theme: &iced::Theme, _status| button::Style {
-        background: None,
-        text_color: colors::TEXT_MUTED,
-        border: Border {
-            radius: spacing::RADIUS_SM.into(),
+        .align_y(iced::Alignment::Center)
+        .into()
+    };
+
+    let item = button(content)
+        .width(Length::Fill)
+        .padding([spacing::XS, spacing::SM])
+        .style(move |_theme: &iced::Theme, _status| button::Style {
+            background: None,
+            text_color: colors::text_muted(),
+            border: focus_border(is_focused),
             ..Default::default()
-        },
-        ..Default::default()
-    })
-    .on_press(Message::NavigateTo(ViewLevel::Messages {
-        filter_description: format!("Account: {}", account_owned),
-    }))
-    .into()
+        })
+        .on_press(Message::NavigateTo(ViewLevel::Messages {
+            filter_description: format!("Account: {}", account_owned),
+        }));
+
+    // Right-click opens the account actions menu
+    let item = mouse_area(item).on_right_press(Message::ShowContextMenu(ContextTarget::Account {
+        email: account.to_string(),
+    }));
+
+    if collapsed {
+        rail_tooltip(item, account.to_string())
+    } else {
+        item.into()
+    }
 }
 
 // ───────────────────────────────────────────────────────────────
@@ -340,7 +654,7 @@ fn account_item(account: &str) -> Element<'static, Message> {
 fn divider_line() -> Element<'static, Message> {
     container(horizontal_rule(1))
         .style(|_| container::Style {
-            text_color: Some(colors::BORDER_SUBTLE),
+            text_color: Some(colors::border_subtle()),
             ..Default::default()
         })
         .width(Length::Fill)
@@ -351,11 +665,35 @@ fn divider_line() -> Element<'static, Message> {
 // Bottom navigation
 // ───────────────────────────────────────────────────────────────
 
-fn bottom_navigation() -> Element<'static, Message> {
+fn bottom_navigation(focus: Option<usize>, collapsed: bool) -> Element<'static, Message> {
     column![
-        nav_item(icons::SETTINGS, "Settings", Message::OpenSettings, false, Some(",")),
-        nav_item(icons::ACCOUNTS, "Accounts", Message::OpenAccounts, false, Some("a")),
-        nav_item(icons::HELP, "Help", Message::ShowHelp, false, Some("?")),
+        nav_item(
+            icons::SETTINGS,
+            "Settings",
+            Message::OpenSettings,
+            false,
+            focus == Some(0),
+            Some(","),
+            collapsed
+        ),
+        nav_item(
+            icons::ACCOUNTS,
+            "Accounts",
+            Message::OpenAccounts,
+            false,
+            focus == Some(1),
+            Some("a"),
+            collapsed
+        ),
+        nav_item(
+            icons::HELP,
+            "Help",
+            Message::ShowHelp,
+            false,
+            focus == Some(2),
+            Some("?"),
+            collapsed
+        ),
     ]
     .spacing(spacing::SPACE_1)
     .into()
@@ -370,79 +708,128 @@ fn bottom_navigation() -> Element<'static, Message> {
 /// Active items get SELECTION_BG background + copper left border via
 /// box-shadow emulation (left padding + colored container).
 /// Inactive items get transparent background with hover highlight.
+/// The keyboard-focused item gets the focus ring.
+/// Collapsed items show only the icon, with the label and shortcut in a
+/// tooltip.
 fn nav_item(
     icon: &'static str,
     label: &'static str,
     message: Message,
     is_active: bool,
+    is_focused: bool,
     shortcut: Option<&'static str>,
+    collapsed: bool,
 ) -> Element<'static, Message> {
-    let mut content = row![
-        text(icon)
-            .size(typography::SIZE_SM)
-            .style(if is_active {
-                components::text_accent as fn(&iced::Theme) -> iced::widget::text::Style
-            } else {
-                components::text_muted as fn(&iced::Theme) -> iced::widget::text::Style
-            }),
-        Space::with_width(spacing::SM),
-        text(label)
-            .size(typography::SIZE_SM)
-            .font(typography::FONT_PRIMARY),
-    ]
-    .align_y(iced::Alignment::Center);
+    let icon = text(icon).size(typography::SIZE_SM).style(if is_active {
+        components::text_accent as fn(&iced::Theme) -> iced::widget::text::Style
+    } else {
+        components::text_muted as fn(&iced::Theme) -> iced::widget::text::Style
+    });
 
-    if let Some(key) = shortcut {
-        content = content.push(Space::with_width(Length::Fill));
-        content = content.push(
-            text(key)
-                .size(typography::SIZE_2XS)
-                .font(typography::FONT_MONO)
-                .style(components::text_muted),
-        );
-    }
+    let content: Element<'static, Message> = if collapsed {
+        container(icon).center_x(Length::Fill).into()
+    } else {
+        let mut content = row![
+            icon,
+            Space::with_width(spacing::SM),
+            text(label)
+                .size(typography::SIZE_SM)
+                .font(typography::FONT_PRIMARY),
+        ]
+        .align_y(iced::Alignment::Center);
+
+        if let Some(key) = shortcut {
+            content = content.push(Space::with_width(Length::Fill));
+            content = content.push(
+                text(key)
+                    .size(typography::SIZE_2XS)
+                    .font(typography::FONT_MONO)
+                    .style(components::text_muted),
+            );
+        }
+        content.into()
+    };
 
-    button(content)
+    let item = button(content)
         .width(Length::Fill)
         .padding([spacing::XS, spacing::SM])
         .style(move |_theme: &iced::Theme, status: button::Status| {
             if is_active {
                 button::Style {
-                    background: Some(Background::Color(colors::SELECTION_BG)),
-                    text_color: colors::TEXT_PRIMARY,
+                    background: Some(Background::Color(colors::selection_bg())),
+                    text_color: colors::text_primary(),
                     border: Border {
                         radius: spacing::RADIUS_SM.into(),
                         width: 2.0,
-                        color: colors::ACCENT_PRIMARY,
+                        color: if is_focused {
+                            colors::accent_hover()
+                        } else {
+                            colors::accent_primary()
+                        },
                     },
                     ..Default::default()
                 }
             } else {
                 let bg = match status {
-                    button::Status::Hovered => Some(Background::Color(colors::BG_ELEVATED)),
-                    button::Status::Pressed => Some(Background::Color(colors::BG_OVERLAY)),
+                    button::Status::Hovered => Some(Background::Color(colors::bg_elevated())),
+                    button::Status::Pressed => Some(Background::Color(colors::bg_overlay())),
                     _ => None,
                 };
                 button::Style {
                     background: bg,
-                    text_color: colors::TEXT_SECONDARY,
-                    border: Border {
-                        radius: spacing::RADIUS_SM.into(),
-                        ..Default::default()
-                    },
+                    text_color: colors::text_secondary(),
+                    border: focus_border(is_focused),
                     ..Default::default()
                 }
             }
         })
-        .on_press(message)
-        .into()
+        .on_press(message);
+
+    if !collapsed {
+        return item.into();
+    }
+    let tip = match shortcut {
+        Some(key) => format!("{} ({})", label, key),
+        None => label.to_string(),
+    };
+    rail_tooltip(item, tip)
 }
 
 // ───────────────────────────────────────────────────────────────
 // Helpers
 // ───────────────────────────────────────────────────────────────
 
+/// Name a rail item in a tooltip to its right
+fn rail_tooltip<'a>(item: impl Into<Element<'a, Message>>, label: String) -> Element<'a, Message> {
+    with_tooltip(item, label, Position::Right)
+}
+
+/// Item border: the focus ring when focused, otherwise just rounding
+fn focus_border(is_focused: bool) -> Border {
+    if is_focused {
+        Border {
+            radius: spacing::RADIUS_SM.into(),
+            width: 2.0,
+            color: colors::focus_ring(),
+        }
+    } else {
+        Border {
+            radius: spacing::RADIUS_SM.into(),
+            ..Default::default()
+        }
+    }
+}
+
 /// Truncate email for display (max 25 chars).
+/// Truncate free-form text for the sidebar (character-safe)
+fn truncate_name(name: &str) -> String {
+    if name.chars().count() > 25 {
+        format!("{}...", name.chars().take(22).collect::<String>())
+    } else {
+        name.to_string()
+    }
+}
+
 fn truncate_email(email: &str) -> String {
     if email.len() > 25 {
         format!("{}...", &email[..22])
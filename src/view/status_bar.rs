@@ -1,40 +1,61 @@
 //! Status bar component
 //!
 //! Thin bar at the bottom of the connected view showing connection status,
-//! sync activity, and message count.
+//! sync activity, downloads, the selection, account scope, and how many
+//! messages the current view holds.
 
-use crate::api::types::AccountSyncStatus;
+use crate::api::is_insecure_url;
+use crate::api::types::{AccountSyncStatus, SyncState};
 use crate::message::Message;
-use crate::model::ConnectionStatus;
+use crate::model::downloads::DownloadTracker;
+use crate::model::DeletionProgress;
+use crate::model::{AppState, ConnectionStatus, Connectivity, ViewLevel};
 use crate::theme::{colors, components, icons, spacing, typography};
-use iced::widget::{container, row, text, Space};
+use crate::view::attachments::progress_bar_style;
+use crate::view::widgets::{with_tooltip, Position};
+use iced::widget::{button, container, progress_bar, row, text, Space};
 use iced::{Background, Border, Element, Length};
 
 /// Height of the status bar in pixels
 const STATUS_BAR_HEIGHT: f32 = 28.0;
 
 /// Render the status bar
-pub fn status_bar<'a>(
-    connection_status: &ConnectionStatus,
-    server_url: &str,
-    sync_accounts: &[AccountSyncStatus],
-    syncing_account: Option<&str>,
-    total_messages: Option<i64>,
-) -> Element<'a, Message> {
-    // --- Left: connection indicator ---
-    let connection_element = connection_indicator(connection_status, server_url);
+pub fn status_bar(state: &AppState) -> Element<'_, Message> {
+    let server_url = state.server_url.as_str();
+    let sync_accounts = &state.sync_accounts;
+    let syncing_account = state.syncing_account.as_deref();
+    let downloads = &state.downloads;
+
+    // --- Left: connection indicator and security warning ---
+    let connection_element =
+        connection_indicator(&state.connection_status, state.connectivity, server_url);
+    let security_element = security_warning(server_url, state.allow_insecure);
 
     // --- Center: sync status ---
     let sync_element = sync_status(sync_accounts, syncing_account);
 
-    // --- Right: message count ---
-    let count_element = message_count(total_messages);
+    // --- Right: downloads, selection, account scope and message count ---
+    let deletion_element = deletion_indicator(state.deletion.as_ref());
+    let downloads_element = downloads_indicator(downloads);
+    let selection_element = selection_count(state.selected_messages.len());
+    let scope_element = account_scope(sync_accounts);
+    let count_element = message_count(state);
 
     let bar_content = row![
         connection_element,
+        Space::with_width(spacing::MD),
+        security_element,
         Space::with_width(Length::Fill),
         sync_element,
         Space::with_width(Length::Fill),
+        deletion_element,
+        Space::with_width(spacing::MD),
+        downloads_element,
+        Space::with_width(spacing::MD),
+        selection_element,
+        Space::with_width(spacing::MD),
+        scope_element,
+        Space::with_width(spacing::MD),
         count_element,
     ]
     .align_y(iced::Alignment::Center)
@@ -44,10 +65,10 @@ pub fn status_bar<'a>(
         .width(Length::Fill)
         .height(Length::Fixed(STATUS_BAR_HEIGHT))
         .style(|_theme| container::Style {
-            background: Some(Background::Color(colors::BG_DEEP)),
+            background: Some(Background::Color(colors::bg_deep())),
             border: Border {
                 width: 1.0,
-                color: colors::BORDER_SUBTLE,
+                color: colors::border_subtle(),
                 radius: 0.0.into(),
             },
             ..Default::default()
@@ -58,15 +79,21 @@ pub fn status_bar<'a>(
 /// Connection status indicator (left side)
 fn connection_indicator<'a>(
     status: &ConnectionStatus,
+    connectivity: Connectivity,
     server_url: &str,
 ) -> Element<'a, Message> {
     let (dot, dot_style, label): (&str, fn(&iced::Theme) -> text::Style, String) = match status {
+        ConnectionStatus::Connected if connectivity == Connectivity::Offline => (
+            icons::DOT_EMPTY,
+            components::text_error,
+            format!("Offline — {} unreachable", display_url(server_url)),
+        ),
         ConnectionStatus::Connected => (
             icons::DOT_FILLED,
             components::text_success,
             format!("Connected to {}", display_url(server_url)),
         ),
-        ConnectionStatus::Connecting => (
+        ConnectionStatus::Starting | ConnectionStatus::Connecting => (
             icons::DOT_FILLED,
             components::text_muted,
             "Connecting...".to_string(),
@@ -83,10 +110,18 @@ fn connection_indicator<'a>(
         ),
     };
 
+    // The dot explains itself on hover, including why a connection failed
+    let tip = match status {
+        ConnectionStatus::Failed(error) => format!("Connection failed: {}", error),
+        _ => label.clone(),
+    };
+
     row![
-        text(dot)
-            .size(typography::SIZE_2XS)
-            .style(dot_style),
+        with_tooltip(
+            text(dot).size(typography::SIZE_2XS).style(dot_style),
+            tip,
+            Position::Top,
+        ),
         Space::with_width(spacing::XS),
         text(label)
             .size(typography::SIZE_2XS)
@@ -97,20 +132,126 @@ fn connection_indicator<'a>(
     .into()
 }
 
+/// Warning shown while the connection is unencrypted or unverified
+fn security_warning<'a>(server_url: &str, allow_insecure: bool) -> Element<'a, Message> {
+    let label = if is_insecure_url(server_url) {
+        "Unencrypted HTTP"
+    } else if allow_insecure && server_url.starts_with("https://") {
+        "Certificate not verified"
+    } else {
+        return Space::with_width(0).into();
+    };
+
+    text(label)
+        .size(typography::SIZE_2XS)
+        .font(typography::FONT_MONO)
+        .style(components::text_warning)
+        .into()
+}
+
 /// Sync status display (center)
 fn sync_status<'a>(
     accounts: &[AccountSyncStatus],
     syncing_account: Option<&str>,
 ) -> Element<'a, Message> {
-    let label = if let Some(email) = syncing_account {
+    let running = count_in_state(accounts, SyncState::Running);
+    let mut label = if let Some(email) = syncing_account {
         // Currently syncing an account
         let short = truncate_email(email);
         format!("Syncing {}...", short)
+    } else if running > 0 {
+        // Scheduled syncs the app didn't start
+        format!("Syncing {} {}...", running, plural(running, "account"))
     } else {
         // Show last sync time from most recently synced account
         most_recent_sync_label(accounts)
     };
 
+    let failing = count_in_state(accounts, SyncState::Error);
+    if failing > 0 {
+        label.push_str(&format!(" \u{00b7} {} failing", failing));
+    }
+
+    text(label)
+        .size(typography::SIZE_2XS)
+        .font(typography::FONT_MONO)
+        .style(if failing > 0 {
+            components::text_warning
+        } else {
+            components::text_muted
+        })
+        .into()
+}
+
+/// Progress of a bulk deletion being staged (right side)
+fn deletion_indicator<'a>(deletion: Option<&DeletionProgress>) -> Element<'a, Message> {
+    let Some(progress) = deletion else {
+        return Space::with_width(0).into();
+    };
+
+    row![
+        text(format!(
+            "Staging {}/{} for deletion",
+            progress.sent, progress.total
+        ))
+        .size(typography::SIZE_2XS)
+        .font(typography::FONT_MONO)
+        .style(components::text_muted),
+        progress_bar(0.0..=1.0, progress.fraction())
+            .height(Length::Fixed(6.0))
+            .width(Length::Fixed(80.0))
+            .style(|_| progress_bar_style()),
+    ]
+    .spacing(spacing::XS)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+/// Downloads button (right side), hidden until something was downloaded
+fn downloads_indicator<'a>(downloads: &DownloadTracker) -> Element<'a, Message> {
+    if downloads.entries.is_empty() {
+        return Space::with_width(0).into();
+    }
+
+    let pending = downloads.pending_count();
+    let label = if pending > 0 {
+        format!("{} {} downloading", icons::DOWNLOAD, pending)
+    } else {
+        format!("{} Downloads", icons::DOWNLOAD)
+    };
+
+    button(
+        text(label)
+            .size(typography::SIZE_2XS)
+            .font(typography::FONT_MONO),
+    )
+    .padding([0, spacing::XS])
+    .style(components::button_ghost)
+    .on_press(Message::ToggleDownloadsPanel)
+    .into()
+}
+
+/// Number of selected messages (right side), hidden with no selection
+fn selection_count<'a>(selected: usize) -> Element<'a, Message> {
+    if selected == 0 {
+        return Space::with_width(0).into();
+    }
+
+    text(format!("{} selected", format_count(selected as i64)))
+        .size(typography::SIZE_2XS)
+        .font(typography::FONT_MONO)
+        .style(components::text_accent)
+        .into()
+}
+
+/// Which accounts the archive covers (right side)
+fn account_scope<'a>(accounts: &[AccountSyncStatus]) -> Element<'a, Message> {
+    let label = match accounts {
+        [] => return Space::with_width(0).into(),
+        [only] => truncate_email(&only.email).to_string(),
+        all => format!("All {} accounts", all.len()),
+    };
+
     text(label)
         .size(typography::SIZE_2XS)
         .font(typography::FONT_MONO)
@@ -119,10 +260,29 @@ fn sync_status<'a>(
 }
 
 /// Message count display (right side)
-fn message_count<'a>(total: Option<i64>) -> Element<'a, Message> {
-    let label = match total {
-        Some(n) => format_count(n),
-        None => "\u{2014}".to_string(), // em-dash
+///
+/// Counts the current filter or search while one is shown, and the whole
+/// archive otherwise.
+fn message_count(state: &AppState) -> Element<'_, Message> {
+    let label = match state.navigation.current() {
+        ViewLevel::Messages { .. } | ViewLevel::MessageDetail { .. }
+            if state.navigation.current_filter_description().is_some() =>
+        {
+            format!(
+                "{} {}",
+                format_count(state.messages_total),
+                plural(state.messages_total as usize, "message")
+            )
+        }
+        ViewLevel::Search if !state.search_query.is_empty() => format!(
+            "{} {}",
+            format_count(state.search_total),
+            plural(state.search_total as usize, "result")
+        ),
+        _ => match state.stats.as_ref() {
+            Some(stats) => format!("{} total", format_count(stats.total_messages)),
+            None => "\u{2014}".to_string(), // em-dash
+        },
     };
 
     text(label)
@@ -150,6 +310,20 @@ fn truncate_email(email: &str) -> &str {
     }
 }
 
+/// Number of accounts whose scheduler is in `sync_state`
+fn count_in_state(accounts: &[AccountSyncStatus], sync_state: SyncState) -> usize {
+    accounts.iter().filter(|a| a.status == sync_state).count()
+}
+
+/// `word`, with an "s" unless there is exactly one
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        word.to_string()
+    } else {
+        format!("{}s", word)
+    }
+}
+
 /// Derive a human-readable "last sync" label from the most recent account
 fn most_recent_sync_label(accounts: &[AccountSyncStatus]) -> String {
     // Find the most recent last_sync_at across all accounts
@@ -5,13 +5,16 @@
 
 use crate::api::types::{AccountSyncStatus, SyncState};
 use crate::message::Message;
-use crate::theme::{colors, components, spacing, typography};
+use crate::theme::{colors, components, icons, spacing, typography};
+use crate::view::accounts::{account_filter_bar, no_matching_accounts};
+use crate::view::widgets::empty_state;
 use iced::widget::{button, column, container, row, scrollable, text, Space};
 use iced::{Background, Border, Color, Element, Length};
 
 /// Render the sync status view
 pub fn sync_view<'a>(
     accounts: &'a [AccountSyncStatus],
+    filter: &'a str,
     is_loading: bool,
     syncing_account: Option<&'a str>,
 ) -> Element<'a, Message> {
@@ -39,36 +42,37 @@ pub fn sync_view<'a>(
         .padding(spacing::XL)
         .into()
     } else if accounts.is_empty() {
-        container(
-            text("No accounts configured")
-                .size(typography::SIZE_SM)
-                .style(components::text_muted),
+        empty_state(
+            icons::SYNC,
+            "Nothing to sync",
+            "Sync status appears here once an account is added",
+            Some(("Add account", Message::FocusAddAccount)),
         )
-        .padding(spacing::XL)
-        .into()
     } else {
         let account_rows: Vec<Element<'a, Message>> = accounts
             .iter()
+            .filter(|a| a.matches(filter))
             .map(|account| account_row(account, syncing_account))
             .collect();
 
-        scrollable(column(account_rows).spacing(spacing::SM))
-            .height(Length::Fill)
-            .into()
+        column![
+            account_filter_bar(filter, account_rows.len(), accounts.len()),
+            if account_rows.is_empty() {
+                no_matching_accounts()
+            } else {
+                scrollable(column(account_rows).spacing(spacing::SM))
+                    .height(Length::Fill)
+                    .into()
+            },
+        ]
+        .spacing(spacing::MD)
+        .into()
     };
 
-    // Keyboard hints in FONT_MONO
-    let hints = text("y: refresh | Esc: back")
-        .size(typography::SIZE_2XS)
-        .font(typography::FONT_MONO)
-        .style(components::text_muted);
-
     column![
         header,
         Space::with_height(spacing::XL),
         content,
-        Space::with_height(spacing::SM),
-        hints,
     ]
     .spacing(spacing::XS)
     .padding(spacing::XL)
@@ -102,10 +106,10 @@ fn account_row<'a>(
 
     // Status indicator with semantic colors
     let (status_color, status_icon) = match account.status {
-        SyncState::Idle => (colors::ACCENT_SUCCESS, icons_dot()),
-        SyncState::Running => (colors::ACCENT_INFO, icons_dot()),
-        SyncState::Paused => (colors::ACCENT_WARNING, icons_dot()),
-        SyncState::Error => (colors::ACCENT_ERROR, icons_dot()),
+        SyncState::Idle => (colors::accent_success(), icons_dot()),
+        SyncState::Running => (colors::accent_info(), icons_dot()),
+        SyncState::Paused => (colors::accent_warning(), icons_dot()),
+        SyncState::Error => (colors::accent_error(), icons_dot()),
     };
 
     let status_badge = container(
@@ -2,17 +2,22 @@
 //!
 //! Displays all messages in a thread with expand/collapse capability.
 
-use crate::api::types::MessageDetail;
+use crate::api::types::{Attachment, MessageDetail};
 use crate::message::Message;
+use crate::model::downloads::{local_filename, DownloadState, DownloadTracker};
 use crate::model::ThreadState;
 use crate::theme::{colors, components, icons, spacing, typography};
-use crate::view::widgets::{avatar, format_bytes};
-use chrono::{DateTime, Local, Utc};
+use crate::view::util::{extract_name, format_date};
+use crate::view::widgets::{avatar, empty_state, file_icon, format_bytes};
 use iced::widget::{button, column, container, row, scrollable, text, Space};
-use iced::{Background, Border, Element, Length};
+use iced::{Background, Border, Element, Length, Theme};
 
 /// Render the thread/conversation view
-pub fn thread_view(thread_state: &ThreadState) -> Element<'_, Message> {
+pub fn thread_view<'a>(
+    thread_state: &'a ThreadState,
+    downloads: &'a DownloadTracker,
+    muted: bool,
+) -> Element<'a, Message> {
     if thread_state.is_loading {
         return loading_view();
     }
@@ -25,34 +30,30 @@ pub fn thread_view(thread_state: &ThreadState) -> Element<'_, Message> {
     let header = thread_header(thread_state);
 
     // Message list
-    let messages_col = thread_state
-        .messages
-        .iter()
-        .enumerate()
-        .fold(column![].spacing(spacing::SM), |col, (idx, msg)| {
+    let messages_col = thread_state.messages.iter().enumerate().fold(
+        column![].spacing(spacing::SM),
+        |col, (idx, msg)| {
             let is_expanded = thread_state.is_expanded(idx);
             let is_focused = idx == thread_state.focused_index;
-            col.push(thread_message_card(msg, idx, is_expanded, is_focused))
-        });
+            col.push(thread_message_card(
+                msg,
+                idx,
+                is_expanded,
+                is_focused,
+                downloads,
+            ))
+        },
+    );
 
     // Action buttons at the bottom
-    let actions = action_buttons(thread_state);
-
-    // Keyboard hints in FONT_MONO
-    let hints = text("e: expand all | E: collapse all | Enter: toggle focused | j/k: navigate | Esc: back")
-        .size(typography::SIZE_2XS)
-        .font(typography::FONT_MONO)
-        .style(components::text_muted);
+    let actions = action_buttons(thread_state, muted);
 
     column![
         header,
         Space::with_height(spacing::MD),
-        scrollable(messages_col.padding(spacing::SM))
-            .height(Length::FillPortion(5)),
+        scrollable(messages_col.padding(spacing::SM)).height(Length::FillPortion(5)),
         Space::with_height(spacing::MD),
         actions,
-        Space::with_height(Length::Fill),
-        hints,
     ]
     .spacing(spacing::XS)
     .padding(spacing::LG)
@@ -120,7 +121,7 @@ fn thread_header(thread_state: &ThreadState) -> Element<'_, Message> {
     .width(Length::Fill)
     .padding(spacing::MD)
     .style(|_| container::Style {
-        background: Some(Background::Color(colors::BG_ELEVATED)),
+        background: Some(Background::Color(colors::bg_elevated())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             ..Default::default()
@@ -131,28 +132,36 @@ fn thread_header(thread_state: &ThreadState) -> Element<'_, Message> {
 }
 
 /// Single message card in the thread (collapsed or expanded)
-fn thread_message_card(
-    message: &MessageDetail,
+fn thread_message_card<'a>(
+    message: &'a MessageDetail,
     index: usize,
     is_expanded: bool,
     is_focused: bool,
-) -> Element<'_, Message> {
+    downloads: &'a DownloadTracker,
+) -> Element<'a, Message> {
     // Different styling for focused vs non-focused
     let border_color = if is_focused {
-        colors::ACCENT_PRIMARY
+        colors::accent_primary()
     } else {
-        colors::BORDER_SUBTLE
+        colors::border_subtle()
     };
 
     let bg_color = if is_focused {
-        colors::with_alpha(colors::ACCENT_PRIMARY, 0.08)
+        colors::with_alpha(colors::accent_primary(), 0.08)
     } else {
-        colors::BG_SURFACE
+        colors::bg_surface()
     };
 
     if is_expanded {
         // Expanded view - full message
-        expanded_message_view(message, index, is_focused, border_color, bg_color)
+        expanded_message_view(
+            message,
+            index,
+            is_focused,
+            border_color,
+            bg_color,
+            downloads,
+        )
     } else {
         // Collapsed view - just header
         collapsed_message_view(message, index, is_focused, border_color, bg_color)
@@ -205,7 +214,7 @@ fn collapsed_message_view(
         .width(Length::Fill)
         .style(move |_theme, status| {
             let hover_bg = match status {
-                button::Status::Hovered => colors::BG_ELEVATED,
+                button::Status::Hovered => colors::bg_elevated(),
                 _ => bg_color,
             };
             button::Style {
@@ -223,13 +232,14 @@ fn collapsed_message_view(
 }
 
 /// Expanded message view (full body)
-fn expanded_message_view(
-    message: &MessageDetail,
+fn expanded_message_view<'a>(
+    message: &'a MessageDetail,
     index: usize,
     is_focused: bool,
     border_color: iced::Color,
     bg_color: iced::Color,
-) -> Element<'_, Message> {
+    downloads: &'a DownloadTracker,
+) -> Element<'a, Message> {
     let sender_name = extract_name(&message.from_addr);
     let avatar_widget = avatar(&sender_name, 40);
 
@@ -311,69 +321,33 @@ fn expanded_message_view(
             .style(components::text_secondary)
     };
 
-    // Attachments (if any) -- use icons::file_icon
-    let attachments_section: Element<'_, Message> = if !message.attachments.is_empty() {
-        let att_list: Vec<Element<'_, Message>> = message
+    // Attachments (if any)
+    // Images embedded in the body get their own row after the real files
+    let attachment_group = |title: &'static str, inline: bool| -> Option<Element<'_, Message>> {
+        let chips: Vec<Element<'_, Message>> = message
             .attachments
             .iter()
-            .map(|att| {
-                let icon = icons::file_icon(&att.filename);
-                container(
-                    row![
-                        container(
-                            text(icon)
-                                .size(typography::SIZE_2XS)
-                                .font(typography::FONT_MONO)
-                                .style(components::text_accent)
-                        )
-                        .padding([spacing::SPACE_1, spacing::XS])
-                        .style(|_| container::Style {
-                            background: Some(Background::Color(colors::with_alpha(
-                                colors::ACCENT_PRIMARY,
-                                0.15,
-                            ))),
-                            border: Border {
-                                radius: spacing::RADIUS_SM.into(),
-                                ..Default::default()
-                            },
-                            ..Default::default()
-                        }),
-                        Space::with_width(spacing::XS),
-                        text(&att.filename)
-                            .size(typography::SIZE_XS)
-                            .style(components::text_secondary),
-                        Space::with_width(spacing::XS),
-                        text(format!("({})", format_bytes(att.size_bytes)))
-                            .size(typography::SIZE_XS)
-                            .style(components::text_muted),
-                    ]
-                    .align_y(iced::Alignment::Center)
-                )
-                .padding([spacing::XS, spacing::SM])
-                .style(|_| container::Style {
-                    background: Some(Background::Color(colors::BG_ELEVATED)),
-                    border: Border {
-                        radius: spacing::RADIUS_SM.into(),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .into()
-            })
+            .enumerate()
+            .filter(|(_, att)| att.is_inline() == inline)
+            .map(|(idx, att)| attachment_chip(message.id, idx, att, downloads.get(message.id, idx)))
             .collect();
-
-        column![
-            text("Attachments")
-                .size(typography::SIZE_XS)
-                .font(typography::FONT_MEDIUM)
-                .style(components::text_muted),
-            Space::with_height(spacing::XS),
-            row(att_list).spacing(spacing::XS),
-        ]
-        .into()
-    } else {
-        Space::with_height(0).into()
+        (!chips.is_empty()).then(|| {
+            column![
+                text(title)
+                    .size(typography::SIZE_XS)
+                    .font(typography::FONT_MEDIUM)
+                    .style(components::text_muted),
+                Space::with_height(spacing::XS),
+                row(chips).spacing(spacing::XS),
+            ]
+            .into()
+        })
     };
+    let attachments_section: Element<'_, Message> = column![]
+        .push_maybe(attachment_group("Attachments", false))
+        .push_maybe(attachment_group("Inline images", true))
+        .spacing(spacing::SM)
+        .into();
 
     // Labels (if any)
     let labels_section: Element<'_, Message> = if !message.labels.is_empty() {
@@ -389,7 +363,7 @@ fn expanded_message_view(
                 .padding([spacing::SPACE_1, spacing::SM])
                 .style(|_| container::Style {
                     background: Some(Background::Color(colors::with_alpha(
-                        colors::ACCENT_PRIMARY,
+                        colors::accent_primary(),
                         0.15,
                     ))),
                     border: Border {
@@ -436,8 +410,80 @@ fn expanded_message_view(
     .into()
 }
 
+/// Attachment in an expanded card: downloads on click, then opens
+fn attachment_chip<'a>(
+    message_id: i64,
+    idx: usize,
+    attachment: &'a Attachment,
+    download_state: &'a DownloadState,
+) -> Element<'a, Message> {
+    let download = Message::DownloadAttachment {
+        message_id,
+        attachment_idx: idx,
+        filename: local_filename(&attachment.filename, &attachment.mime_type),
+    };
+    let (status, style, on_press): (String, fn(&Theme) -> text::Style, _) = match download_state {
+        DownloadState::NotStarted | DownloadState::Cancelled => (
+            icons::DOWNLOAD.to_string(),
+            components::text_muted,
+            Some(download),
+        ),
+        DownloadState::Queued => ("Queued".to_string(), components::text_muted, None),
+        DownloadState::Downloading { progress } => (
+            format!("{}%", (*progress * 100.0) as i32),
+            components::text_muted,
+            None,
+        ),
+        DownloadState::Complete { path } => (
+            icons::OPEN.to_string(),
+            components::text_success,
+            Some(Message::OpenFile(path.clone())),
+        ),
+        DownloadState::Failed { .. } => {
+            ("Retry".to_string(), components::text_error, Some(download))
+        }
+    };
+
+    button(
+        row![
+            file_icon(&attachment.filename, Some(&attachment.mime_type)),
+            Space::with_width(spacing::XS),
+            text(&attachment.filename)
+                .size(typography::SIZE_XS)
+                .style(components::text_secondary),
+            Space::with_width(spacing::XS),
+            text(format!("({})", format_bytes(attachment.size_bytes)))
+                .size(typography::SIZE_XS)
+                .style(components::text_muted),
+            Space::with_width(spacing::SM),
+            text(status)
+                .size(typography::SIZE_XS)
+                .font(typography::FONT_MONO)
+                .style(style),
+        ]
+        .align_y(iced::Alignment::Center),
+    )
+    .padding([spacing::XS, spacing::SM])
+    .style(|_, status| {
+        let bg = match status {
+            button::Status::Hovered => colors::bg_overlay(),
+            _ => colors::bg_elevated(),
+        };
+        button::Style {
+            background: Some(Background::Color(bg)),
+            border: Border {
+                radius: spacing::RADIUS_SM.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    })
+    .on_press_maybe(on_press)
+    .into()
+}
+
 /// Action buttons at the bottom of the thread view
-fn action_buttons(thread_state: &ThreadState) -> Element<'_, Message> {
+fn action_buttons(thread_state: &ThreadState, muted: bool) -> Element<'_, Message> {
     // Get the last message for reply actions
     let last_message_id = thread_state
         .messages
@@ -481,13 +527,35 @@ fn action_buttons(thread_state: &ThreadState) -> Element<'_, Message> {
     .style(components::button_secondary)
     .on_press(Message::OpenForward(last_message_id));
 
+    // Muting keeps new messages in the thread from being announced
+    let mute_btn = thread_state.thread_id.clone().map(|thread_id| {
+        let (label, message) = if muted {
+            ("Unmute", Message::UnmuteThread(thread_id))
+        } else {
+            ("Mute", Message::MuteThread(thread_id))
+        };
+        button(
+            row![
+                text(icons::MUTE).size(typography::SIZE_SM),
+                Space::with_width(spacing::XS),
+                text(label).size(typography::SIZE_SM),
+            ]
+            .align_y(iced::Alignment::Center),
+        )
+        .padding([spacing::SM, spacing::LG])
+        .style(components::button_ghost)
+        .on_press(message)
+    });
+
     row![
         reply_btn,
         Space::with_width(spacing::SM),
         reply_all_btn,
         Space::with_width(spacing::SM),
         forward_btn,
+        Space::with_width(Length::Fill),
     ]
+    .push_maybe(mute_btn)
     .align_y(iced::Alignment::Center)
     .into()
 }
@@ -511,34 +579,11 @@ fn loading_view() -> Element<'static, Message> {
 
 /// Empty view when no messages
 fn empty_view() -> Element<'static, Message> {
-    container(
-        column![
-            text("No messages in this conversation")
-                .size(typography::SIZE_MD)
-                .style(components::text_muted),
-        ]
-        .align_x(iced::Alignment::Center)
+    empty_state(
+        icons::DIAMOND_SM,
+        "No messages in this conversation",
+        "They may have been deleted since the thread was opened",
+        Some(("Back", Message::GoBack)),
     )
-    .width(Length::Fill)
-    .height(Length::Fill)
-    .center_x(Length::Fill)
-    .center_y(Length::Fill)
-    .into()
 }
 
-/// Extract name from email address
-fn extract_name(email: &str) -> String {
-    if let Some(idx) = email.find('<') {
-        let name = email[..idx].trim().trim_matches('"');
-        if !name.is_empty() {
-            return name.to_string();
-        }
-    }
-    email.split('@').next().unwrap_or(email).to_string()
-}
-
-/// Format a DateTime for display
-fn format_date(dt: &DateTime<Utc>) -> String {
-    let local: DateTime<Local> = dt.with_timezone(&Local);
-    local.format("%b %d, %Y at %I:%M %p").to_string()
-}
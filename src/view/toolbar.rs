@@ -6,6 +6,7 @@
 use crate::message::Message;
 use crate::model::ViewLevel;
 use crate::theme::{colors, components, icons, spacing, typography};
+use crate::view::widgets::activity_spinner;
 use iced::widget::{button, container, horizontal_rule, row, text, Space};
 use iced::{Background, Border, Element, Length};
 
@@ -17,26 +18,23 @@ pub fn toolbar<'a>(
     current_view: &ViewLevel,
     has_selection: bool,
     selection_count: usize,
+    activity: Option<(usize, usize)>,
 ) -> Element<'a, Message> {
     let left_actions = left_actions(current_view);
-    let right_actions = right_actions(current_view, has_selection, selection_count);
+    let right_actions = right_actions(current_view, has_selection, selection_count, activity);
 
-    let bar = row![
-        left_actions,
-        Space::with_width(Length::Fill),
-        right_actions,
-    ]
-    .align_y(iced::Alignment::Center)
-    .padding([0, spacing::LG])
-    .height(Length::Fixed(TOOLBAR_HEIGHT));
+    let bar = row![left_actions, Space::with_width(Length::Fill), right_actions,]
+        .align_y(iced::Alignment::Center)
+        .padding([0, spacing::LG])
+        .height(Length::Fixed(TOOLBAR_HEIGHT));
 
     container(bar)
         .width(Length::Fill)
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BG_BASE)),
+            background: Some(Background::Color(colors::bg_base())),
             border: Border {
                 width: 0.0,
-                color: colors::BORDER_SUBTLE,
+                color: colors::border_subtle(),
                 radius: 0.0.into(),
             },
             ..Default::default()
@@ -47,32 +45,26 @@ pub fn toolbar<'a>(
 /// Left-side actions (view-specific)
 fn left_actions<'a>(current_view: &ViewLevel) -> Element<'a, Message> {
     match current_view {
-        ViewLevel::Dashboard => {
-            row![
-                view_label("Dashboard"),
-            ]
-            .spacing(spacing::SM)
-            .align_y(iced::Alignment::Center)
-            .into()
-        }
-        ViewLevel::Aggregates { view_type } => {
-            row![
-                view_label(&view_type.display_name()),
-                toolbar_separator(),
-                toolbar_button(icons::SYNC, "Refresh", Message::FetchAggregates(*view_type)),
-            ]
-            .spacing(spacing::SM)
-            .align_y(iced::Alignment::Center)
-            .into()
-        }
-        ViewLevel::Messages { filter_description } => {
-            row![
-                view_label(filter_description),
-            ]
+        ViewLevel::Dashboard => row![view_label("Dashboard"),]
             .spacing(spacing::SM)
             .align_y(iced::Alignment::Center)
-            .into()
-        }
+            .into(),
+        ViewLevel::Aggregates { view_type } => row![
+            view_label(&view_type.display_name()),
+            toolbar_separator(),
+            toolbar_button(icons::SYNC, "Refresh", Message::FetchAggregates(*view_type)),
+        ]
+        .spacing(spacing::SM)
+        .align_y(iced::Alignment::Center)
+        .into(),
+        ViewLevel::Messages { filter_description } => row![
+            view_label(filter_description),
+            toolbar_separator(),
+            toolbar_button(icons::CHECK, "Mark all read", Message::MarkAllRead),
+        ]
+        .spacing(spacing::SM)
+        .align_y(iced::Alignment::Center)
+        .into(),
         ViewLevel::MessageDetail { .. } => {
             row![
                 toolbar_button(icons::ARROW_LEFT, "Back", Message::GoBack),
@@ -84,62 +76,55 @@ fn left_actions<'a>(current_view: &ViewLevel) -> Element<'a, Message> {
             .align_y(iced::Alignment::Center)
             .into()
         }
-        ViewLevel::Search => {
-            row![
-                view_label("Search"),
-            ]
+        ViewLevel::Search => row![view_label("Search"),]
             .spacing(spacing::SM)
             .align_y(iced::Alignment::Center)
-            .into()
-        }
-        ViewLevel::Sync => {
-            row![
-                view_label("Sync Status"),
-                toolbar_separator(),
-                toolbar_button(icons::SYNC, "Refresh", Message::FetchSyncStatus),
-            ]
+            .into(),
+        ViewLevel::Sync => row![
+            view_label("Sync Status"),
+            toolbar_separator(),
+            toolbar_button(icons::SYNC, "Refresh", Message::FetchSyncStatus),
+        ]
+        .spacing(spacing::SM)
+        .align_y(iced::Alignment::Center)
+        .into(),
+        ViewLevel::Accounts => row![view_label("Accounts"),]
             .spacing(spacing::SM)
             .align_y(iced::Alignment::Center)
-            .into()
-        }
-        ViewLevel::Accounts => {
-            row![
-                view_label("Accounts"),
-            ]
+            .into(),
+        ViewLevel::Settings => row![view_label("Settings"),]
             .spacing(spacing::SM)
             .align_y(iced::Alignment::Center)
-            .into()
-        }
-        ViewLevel::Settings => {
-            row![
-                view_label("Settings"),
-            ]
-            .spacing(spacing::SM)
-            .align_y(iced::Alignment::Center)
-            .into()
-        }
-        ViewLevel::Thread { .. } => {
-            row![
-                toolbar_button(icons::ARROW_LEFT, "Back", Message::GoBack),
-                toolbar_separator(),
-                view_label("Thread"),
-            ]
-            .spacing(spacing::SM)
-            .align_y(iced::Alignment::Center)
-            .into()
-        }
+            .into(),
+        ViewLevel::Thread { .. } => row![
+            toolbar_button(icons::ARROW_LEFT, "Back", Message::GoBack),
+            toolbar_separator(),
+            view_label("Thread"),
+        ]
+        .spacing(spacing::SM)
+        .align_y(iced::Alignment::Center)
+        .into(),
         _ => Space::with_width(0).into(),
     }
 }
 
 /// Right-side actions (selection-aware + global)
+///
+/// `activity` is the spinner frame and operation count while background
+/// work is running.
 fn right_actions<'a>(
     current_view: &ViewLevel,
     has_selection: bool,
     selection_count: usize,
+    activity: Option<(usize, usize)>,
 ) -> Element<'a, Message> {
     let mut items = row![].spacing(spacing::SM).align_y(iced::Alignment::Center);
 
+    if let Some((frame, operations)) = activity {
+        items = items.push(activity_spinner(frame, operations));
+        items = items.push(toolbar_separator());
+    }
+
     // Show selection actions when messages are selected
     if has_selection {
         items = items.push(
@@ -148,8 +133,21 @@ fn right_actions<'a>(
                 .font(typography::FONT_MONO)
                 .style(components::text_accent),
         );
-        items = items.push(toolbar_button(icons::CROSS, "Clear", Message::ClearSelection));
-        items = items.push(toolbar_button(icons::DELETE, "Delete", Message::ShowDeleteModal));
+        items = items.push(toolbar_button(
+            icons::CROSS,
+            "Clear",
+            Message::ClearSelection,
+        ));
+        items = items.push(toolbar_button(
+            icons::LABEL,
+            "Label",
+            Message::OpenLabelPicker,
+        ));
+        items = items.push(toolbar_button(
+            icons::DELETE,
+            "Delete",
+            Message::ShowDeleteModal,
+        ));
         items = items.push(toolbar_separator());
     }
 
@@ -187,13 +185,13 @@ fn toolbar_button(icon: &str, label: &str, message: Message) -> Element<'static,
     .padding([spacing::SPACE_1, spacing::SM])
     .style(|_theme: &iced::Theme, status| {
         let bg = match status {
-            button::Status::Hovered => colors::BG_ELEVATED,
-            button::Status::Pressed => colors::BG_OVERLAY,
+            button::Status::Hovered => colors::bg_elevated(),
+            button::Status::Pressed => colors::bg_overlay(),
             _ => colors::TRANSPARENT,
         };
         button::Style {
             background: Some(Background::Color(bg)),
-            text_color: colors::TEXT_SECONDARY,
+            text_color: colors::text_secondary(),
             border: Border {
                 radius: spacing::RADIUS_SM.into(),
                 ..Default::default()
@@ -218,7 +216,7 @@ fn view_label(label: &str) -> Element<'static, Message> {
 fn toolbar_separator() -> Element<'static, Message> {
     container(Space::new(1, 16))
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::BORDER_SUBTLE)),
+            background: Some(Background::Color(colors::border_subtle())),
             ..Default::default()
         })
         .into()
@@ -0,0 +1,85 @@
+//! Shared view helpers
+//!
+//! Name, date and text formatting used by several views, kept in one place
+//! so every view shows them the same way.
+
+use crate::model::sender_address;
+use chrono::{DateTime, Datelike, Local, Utc};
+
+/// Extract a display name from an address
+///
+/// Uses the name in `Name <email>` form, falling back to the local part.
+pub fn extract_name(email: &str) -> String {
+    if let Some(idx) = email.find('<') {
+        let name = email[..idx].trim().trim_matches('"');
+        if !name.is_empty() {
+            return name.to_string();
+        }
+    }
+    let address = sender_address(email);
+    address.split('@').next().unwrap_or(address).to_string()
+}
+
+/// Format a date in full, for message headers
+pub fn format_date(dt: &DateTime<Utc>) -> String {
+    let local: DateTime<Local> = dt.with_timezone(&Local);
+    local.format("%a, %b %d, %Y at %I:%M %p").to_string()
+}
+
+/// Format a date compactly relative to now, for list rows
+///
+/// Today shows the time, then "Yesterday", the weekday within a week,
+/// month and day within the year, and the full date otherwise.
+pub fn format_list_date(dt: &DateTime<Utc>) -> String {
+    let local: DateTime<Local> = dt.with_timezone(&Local);
+    let now = Local::now();
+
+    if local.date_naive() == now.date_naive() {
+        return local.format("%H:%M").to_string();
+    }
+
+    let yesterday = now.date_naive().pred_opt().unwrap_or(now.date_naive());
+    if local.date_naive() == yesterday {
+        return "Yesterday".to_string();
+    }
+
+    let days_ago = (now.date_naive() - local.date_naive()).num_days();
+    if days_ago < 7 {
+        return local.format("%A").to_string();
+    }
+
+    if local.year() == now.year() {
+        return local.format("%b %d").to_string();
+    }
+
+    local.format("%b %d, %Y").to_string()
+}
+
+/// Truncate text to at most `max_len` characters, ending with an ellipsis
+pub fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else {
+        let truncated: String = s.chars().take(max_len.saturating_sub(1)).collect();
+        format!("{}\u{2026}", truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_name() {
+        assert_eq!(extract_name("\"Alice Smith\" <alice@x.com>"), "Alice Smith");
+        assert_eq!(extract_name("<bob@x.com>"), "bob");
+        assert_eq!(extract_name("carol@x.com"), "carol");
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("abcdef", 4), "abc\u{2026}");
+        assert_eq!(truncate("héllo wörld", 5), "héll\u{2026}");
+    }
+}
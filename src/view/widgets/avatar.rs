@@ -1,10 +1,12 @@
 //! Avatar widget
 //!
-//! Displays a circular avatar with initials from a name.
+//! Displays a circular avatar with initials from a name, or a fetched
+//! avatar image when one is available.
 
 use crate::message::Message;
 // Theme imports not currently used but will be needed for future styling
-use iced::widget::{center, container, text};
+use iced::widget::image::Handle;
+use iced::widget::{center, container, image, text};
 use iced::{Background, Border, Color, Element, Length};
 
 /// Create an avatar circle with initials
@@ -32,6 +34,21 @@ pub fn avatar(name: &str, size: u16) -> Element<'static, Message> {
         .into()
 }
 
+/// Create an avatar from a fetched image, falling back to initials
+pub fn avatar_or_image(
+    name: &str,
+    handle: Option<&Handle>,
+    size: u16,
+) -> Element<'static, Message> {
+    match handle {
+        Some(handle) => image(handle.clone())
+            .width(Length::Fixed(size as f32))
+            .height(Length::Fixed(size as f32))
+            .into(),
+        None => avatar(name, size),
+    }
+}
+
 /// Get initials from a name (up to 2 characters)
 fn get_initials(name: &str) -> String {
     let trimmed = name.trim();
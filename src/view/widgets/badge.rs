@@ -28,28 +28,28 @@ pub enum BadgeStyle {
 pub fn badge(content: &str, style: BadgeStyle) -> Element<'static, Message> {
     let (bg_color, text_color) = match style {
         BadgeStyle::Default => (
-            colors::with_alpha(colors::TEXT_PRIMARY, 0.15),
-            colors::TEXT_SECONDARY,
+            colors::with_alpha(colors::text_primary(), 0.15),
+            colors::text_secondary(),
         ),
         BadgeStyle::Primary => (
-            colors::with_alpha(colors::ACCENT_PRIMARY, 0.2),
-            colors::ACCENT_PRIMARY,
+            colors::with_alpha(colors::accent_primary(), 0.2),
+            colors::accent_primary(),
         ),
         BadgeStyle::Success => (
-            colors::with_alpha(colors::ACCENT_SUCCESS, 0.2),
-            colors::ACCENT_SUCCESS,
+            colors::with_alpha(colors::accent_success(), 0.2),
+            colors::accent_success(),
         ),
         BadgeStyle::Warning => (
-            colors::with_alpha(colors::ACCENT_WARNING, 0.2),
-            colors::ACCENT_WARNING,
+            colors::with_alpha(colors::accent_warning(), 0.2),
+            colors::accent_warning(),
         ),
         BadgeStyle::Error => (
-            colors::with_alpha(colors::ACCENT_ERROR, 0.2),
-            colors::ACCENT_ERROR,
+            colors::with_alpha(colors::accent_error(), 0.2),
+            colors::accent_error(),
         ),
         BadgeStyle::Muted => (
-            colors::with_alpha(colors::TEXT_MUTED, 0.1),
-            colors::TEXT_MUTED,
+            colors::with_alpha(colors::text_muted(), 0.1),
+            colors::text_muted(),
         ),
     };
 
@@ -83,13 +83,16 @@ pub fn count_badge(count: i64) -> Element<'static, Message> {
     let badge_text = text(display)
         .size(typography::SIZE_XS)
         .style(|_| iced::widget::text::Style {
-            color: Some(colors::TEXT_MUTED),
+            color: Some(colors::text_muted()),
         });
 
     container(badge_text)
         .padding([2, spacing::XS])
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::with_alpha(colors::TEXT_MUTED, 0.1))),
+            background: Some(Background::Color(colors::with_alpha(
+                colors::text_muted(),
+                0.1,
+            ))),
             border: Border {
                 radius: 10.0.into(),
                 ..Default::default()
@@ -105,7 +108,7 @@ pub fn unread_dot() -> Element<'static, Message> {
         .width(Length::Fixed(8.0))
         .height(Length::Fixed(8.0))
         .style(|_| container::Style {
-            background: Some(Background::Color(colors::ACCENT_PRIMARY)),
+            background: Some(Background::Color(colors::accent_primary())),
             border: Border {
                 radius: 4.0.into(),
                 ..Default::default()
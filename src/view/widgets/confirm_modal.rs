@@ -0,0 +1,166 @@
+//! Confirmation modal widget
+//!
+//! Centered yes/no dialog over a dimmed backdrop, shared by every
+//! confirmation prompt.
+
+use crate::message::Message;
+use crate::theme::{components, spacing, typography};
+use iced::widget::{button, center, column, container, row, stack, text, Space};
+use iced::{Element, Length};
+
+/// Render a confirmation dialog as a full-window overlay layer
+///
+/// `danger` styles the confirm button for destructive actions.
+pub fn confirm_modal<'a>(
+    title: &str,
+    message: impl Into<String>,
+    confirm_label: &str,
+    confirm_msg: Message,
+    cancel_msg: Message,
+    danger: bool,
+) -> Element<'a, Message> {
+    confirm_modal_with(
+        title,
+        message,
+        confirm_label,
+        confirm_msg,
+        cancel_msg,
+        danger,
+        None,
+    )
+}
+
+/// Confirmation dialog with an optional third, non-destructive choice
+/// between Cancel and the confirm button
+pub fn confirm_modal_with<'a>(
+    title: &str,
+    message: impl Into<String>,
+    confirm_label: &str,
+    confirm_msg: Message,
+    cancel_msg: Message,
+    danger: bool,
+    alternative: Option<(&str, Message)>,
+) -> Element<'a, Message> {
+    let alternative: Element<'a, Message> = match alternative {
+        Some((label, msg)) => row![
+            Space::with_width(spacing::MD),
+            button(
+                text(label.to_string())
+                    .size(typography::SIZE_SM)
+                    .font(typography::FONT_MEDIUM),
+            )
+            .padding([spacing::SM, spacing::LG])
+            .style(components::button_secondary)
+            .on_press(msg),
+        ]
+        .into(),
+        None => Space::with_width(0).into(),
+    };
+
+    let buttons = row![
+        cancel_button(cancel_msg),
+        alternative,
+        Space::with_width(spacing::MD),
+        confirm_button(confirm_label, Some(confirm_msg), danger)
+    ]
+    .align_y(iced::Alignment::Center);
+
+    dialog(title, message.into(), None, buttons.into())
+}
+
+/// Destructive confirmation that stays disabled until `gate` (typically a
+/// text input the user must fill in) is satisfied
+///
+/// `confirm_msg` is `None` while the gate is not satisfied.
+pub fn confirm_modal_gated<'a>(
+    title: &str,
+    message: impl Into<String>,
+    gate: Element<'a, Message>,
+    confirm_label: &str,
+    confirm_msg: Option<Message>,
+    cancel_msg: Message,
+) -> Element<'a, Message> {
+    let buttons = row![
+        cancel_button(cancel_msg),
+        Space::with_width(spacing::MD),
+        confirm_button(confirm_label, confirm_msg, true)
+    ]
+    .align_y(iced::Alignment::Center);
+
+    dialog(title, message.into(), Some(gate), buttons.into())
+}
+
+/// Cancel button shared by every dialog
+fn cancel_button<'a>(cancel_msg: Message) -> Element<'a, Message> {
+    button(
+        text("Cancel")
+            .size(typography::SIZE_SM)
+            .font(typography::FONT_MEDIUM),
+    )
+    .padding([spacing::SM, spacing::LG])
+    .style(components::button_secondary)
+    .on_press(cancel_msg)
+    .into()
+}
+
+/// Confirm button, disabled without a message
+fn confirm_button<'a>(
+    label: &str,
+    confirm_msg: Option<Message>,
+    danger: bool,
+) -> Element<'a, Message> {
+    let confirm_text = text(label.to_string())
+        .size(typography::SIZE_SM)
+        .font(typography::FONT_MEDIUM);
+    let confirm_button = if danger {
+        button(confirm_text.style(components::text_error)).style(components::button_danger)
+    } else {
+        button(confirm_text).style(components::button_primary)
+    };
+
+    confirm_button
+        .padding([spacing::SM, spacing::LG])
+        .on_press_maybe(confirm_msg)
+        .into()
+}
+
+/// Title, message, optional gate and buttons, centered over a dimmed
+/// backdrop
+fn dialog<'a>(
+    title: &str,
+    message: String,
+    gate: Option<Element<'a, Message>>,
+    buttons: Element<'a, Message>,
+) -> Element<'a, Message> {
+    // Semi-transparent backdrop
+    let backdrop = container(Space::new(Length::Fill, Length::Fill))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(components::modal_backdrop_style);
+
+    let title = text(title.to_string())
+        .size(typography::SIZE_LG)
+        .font(typography::FONT_SEMIBOLD)
+        .style(components::text_primary);
+
+    let message = text(message)
+        .size(typography::SIZE_SM)
+        .style(components::text_secondary);
+
+    let gate = gate.map(|gate| column![Space::with_height(spacing::MD), gate]);
+
+    let dialog_content = column![title, Space::with_height(spacing::LG), message,]
+        .push_maybe(gate)
+        .push(Space::with_height(spacing::XL))
+        .push(buttons)
+        .spacing(spacing::XS)
+        .padding(spacing::XL)
+        .align_x(iced::Alignment::Center);
+
+    let dialog = container(dialog_content)
+        .style(components::modal_dialog_style)
+        .padding(spacing::SM);
+
+    // Center the dialog on the backdrop
+    stack![backdrop, center(dialog)].into()
+}
@@ -0,0 +1,52 @@
+//! File icon widget
+//!
+//! Small tinted tag with a file type code, shown wherever attachments are
+//! listed.
+
+use crate::message::Message;
+use crate::theme::icons::FileKind;
+use crate::theme::{colors, spacing, typography};
+use iced::widget::{container, text};
+use iced::{Background, Border, Color, Element, Length};
+
+/// Width of the icon, wide enough for a four-letter code
+const ICON_WIDTH: f32 = 34.0;
+
+/// Render the icon for a file based on its MIME type, or its extension
+/// when the type is unknown
+pub fn file_icon(filename: &str, mime_type: Option<&str>) -> Element<'static, Message> {
+    let kind = FileKind::detect(filename, mime_type);
+    let color = kind_color(kind);
+
+    container(
+        text(kind.code())
+            .size(typography::SIZE_2XS)
+            .font(typography::FONT_MONO)
+            .color(color),
+    )
+    .width(Length::Fixed(ICON_WIDTH))
+    .center_x(Length::Fixed(ICON_WIDTH))
+    .padding([spacing::SPACE_1, 0])
+    .style(move |_| container::Style {
+        background: Some(Background::Color(colors::with_alpha(color, 0.15))),
+        border: Border {
+            radius: spacing::RADIUS_SM.into(),
+            width: 1.0,
+            color: colors::with_alpha(color, 0.3),
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+/// Accent color for each file type
+fn kind_color(kind: FileKind) -> Color {
+    match kind {
+        FileKind::Pdf => colors::accent_error(),
+        FileKind::Document | FileKind::Text => colors::accent_info(),
+        FileKind::Spreadsheet => colors::accent_success(),
+        FileKind::Presentation | FileKind::Image => colors::accent_warning(),
+        FileKind::Archive | FileKind::Audio | FileKind::Video => colors::accent_primary(),
+        FileKind::Generic => colors::text_muted(),
+    }
+}
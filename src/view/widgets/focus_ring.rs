@@ -0,0 +1,28 @@
+//! Focus ring widget
+//!
+//! Outlines the element keyboard navigation currently acts on.
+
+use crate::message::Message;
+use crate::theme::components;
+use iced::widget::container;
+use iced::{Element, Length};
+
+/// Room around the content for the ring, so it is not drawn under it
+const RING_WIDTH: u16 = 2;
+
+/// Wrap an element so it shows the focus ring while it has keyboard focus
+///
+/// Unfocused elements keep the same padding, so moving focus does not
+/// shift the layout.
+pub fn focus_ring<'a>(
+    content: impl Into<Element<'a, Message>>,
+    focused: bool,
+) -> Element<'a, Message> {
+    let wrapper = container(content).width(Length::Fill).padding(RING_WIDTH);
+
+    if focused {
+        wrapper.style(components::focus_ring_style).into()
+    } else {
+        wrapper.into()
+    }
+}
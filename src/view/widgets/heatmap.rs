@@ -0,0 +1,300 @@
+//! Calendar heatmap widget
+//!
+//! Renders Time aggregate buckets as a GitHub-style contribution grid on a
+//! canvas. Clicking a cell drills into that bucket's messages.
+
+use crate::api::types::{AggregateRow, DateRange};
+use crate::message::Message;
+use crate::theme::colors;
+use chrono::Datelike;
+use iced::mouse;
+use iced::widget::canvas::{self, event, Canvas, Event, Frame, Geometry, Path, Stroke};
+use iced::{Element, Length, Point, Rectangle, Renderer, Size, Theme};
+
+/// Side length of a single cell in pixels
+const CELL_SIZE: f32 = 14.0;
+/// Gap between cells in pixels
+const CELL_GAP: f32 = 3.0;
+
+/// A single positioned heatmap cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeatmapCell {
+    /// Index of the aggregate row this cell represents
+    pub index: usize,
+    pub row: usize,
+    pub col: usize,
+    pub count: i64,
+}
+
+/// Bucket size, inferred from the length of the first parseable bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Granularity {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Granularity {
+    fn of(range: &DateRange) -> Self {
+        match (range.before - range.after).num_days() {
+            1 => Granularity::Day,
+            7 => Granularity::Week,
+            365 | 366 => Granularity::Year,
+            _ => Granularity::Month,
+        }
+    }
+}
+
+/// Lay out Time aggregate rows on a calendar grid
+///
+/// Day buckets use weekday rows and week columns. Week and month buckets use
+/// one row per year; year buckets sit in a single row. Rows whose key cannot
+/// be parsed, or whose granularity differs from the first bucket, are skipped.
+pub fn layout_cells(aggregates: &[AggregateRow]) -> Vec<HeatmapCell> {
+    let ranges: Vec<(usize, DateRange, i64)> = aggregates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, agg)| DateRange::from_time_bucket(&agg.key).map(|r| (i, r, agg.count)))
+        .collect();
+
+    let Some(granularity) = ranges.first().map(|(_, r, _)| Granularity::of(r)) else {
+        return Vec::new();
+    };
+    let ranges: Vec<_> = ranges
+        .into_iter()
+        .filter(|(_, r, _)| Granularity::of(r) == granularity)
+        .collect();
+
+    let Some(first) = ranges.iter().map(|(_, r, _)| r.after).min() else {
+        return Vec::new();
+    };
+
+    ranges
+        .into_iter()
+        .map(|(index, range, count)| {
+            let date = range.after;
+            let (row, col) = match granularity {
+                Granularity::Day => {
+                    let first_monday = first
+                        - chrono::Duration::days(first.weekday().num_days_from_monday() as i64);
+                    let weeks = (date - first_monday).num_days() / 7;
+                    (
+                        date.weekday().num_days_from_monday() as usize,
+                        weeks as usize,
+                    )
+                }
+                Granularity::Week => {
+                    let week = date.iso_week();
+                    let row = week.year() - first.iso_week().year();
+                    (row as usize, week.week0() as usize)
+                }
+                Granularity::Month => (
+                    (date.year() - first.year()) as usize,
+                    date.month0() as usize,
+                ),
+                Granularity::Year => (0, (date.year() - first.year()) as usize),
+            };
+            HeatmapCell {
+                index,
+                row,
+                col,
+                count,
+            }
+        })
+        .collect()
+}
+
+/// Create a heatmap of Time aggregate rows
+///
+/// Returns None when no bucket key could be parsed, so callers can fall back
+/// to the list view.
+pub fn heatmap<'a>(
+    aggregates: &[AggregateRow],
+    selected_index: usize,
+) -> Option<Element<'a, Message>> {
+    let cells = layout_cells(aggregates);
+    if cells.is_empty() {
+        return None;
+    }
+
+    let rows = cells.iter().map(|c| c.row).max().unwrap_or(0) + 1;
+    let cols = cells.iter().map(|c| c.col).max().unwrap_or(0) + 1;
+    let max_count = cells.iter().map(|c| c.count).max().unwrap_or(0).max(1);
+
+    let width = cols as f32 * (CELL_SIZE + CELL_GAP);
+    let height = rows as f32 * (CELL_SIZE + CELL_GAP);
+
+    let program = Heatmap {
+        cells,
+        rows,
+        cols,
+        max_count,
+        selected_index,
+    };
+
+    Some(
+        Canvas::new(program)
+            .width(Length::Fixed(width))
+            .height(Length::Fixed(height))
+            .into(),
+    )
+}
+
+/// Canvas program drawing the heatmap grid
+struct Heatmap {
+    cells: Vec<HeatmapCell>,
+    rows: usize,
+    cols: usize,
+    max_count: i64,
+    selected_index: usize,
+}
+
+impl Heatmap {
+    /// Top-left corner of the cell at a grid position
+    fn origin(row: usize, col: usize) -> Point {
+        Point::new(
+            col as f32 * (CELL_SIZE + CELL_GAP),
+            row as f32 * (CELL_SIZE + CELL_GAP),
+        )
+    }
+
+    /// Find the cell under a position relative to the canvas
+    fn cell_at(&self, position: Point) -> Option<&HeatmapCell> {
+        let col = (position.x / (CELL_SIZE + CELL_GAP)) as usize;
+        let row = (position.y / (CELL_SIZE + CELL_GAP)) as usize;
+        self.cells.iter().find(|c| c.row == row && c.col == col)
+    }
+}
+
+impl canvas::Program<Message> for Heatmap {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Option<Message>) {
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+            if let Some(position) = cursor.position_in(bounds) {
+                if let Some(cell) = self.cell_at(position) {
+                    return (
+                        event::Status::Captured,
+                        Some(Message::DrillDownRow(cell.index)),
+                    );
+                }
+            }
+        }
+        (event::Status::Ignored, None)
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let cell_size = Size::new(CELL_SIZE, CELL_SIZE);
+
+        // Empty grid first so gaps in the data still read as a calendar
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let path = Path::rounded_rectangle(Self::origin(row, col), cell_size, 2.0.into());
+                frame.fill(&path, colors::bg_elevated());
+            }
+        }
+
+        let hovered = cursor
+            .position_in(bounds)
+            .and_then(|position| self.cell_at(position))
+            .map(|c| c.index);
+
+        for cell in &self.cells {
+            let path =
+                Path::rounded_rectangle(Self::origin(cell.row, cell.col), cell_size, 2.0.into());
+            if cell.count > 0 {
+                let intensity = cell.count as f32 / self.max_count as f32;
+                frame.fill(
+                    &path,
+                    colors::with_alpha(colors::accent_primary(), 0.2 + 0.8 * intensity),
+                );
+            }
+            if cell.index == self.selected_index || Some(cell.index) == hovered {
+                frame.stroke(
+                    &path,
+                    Stroke::default()
+                        .with_color(colors::focus_ring())
+                        .with_width(1.5),
+                );
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        match cursor.position_in(bounds) {
+            Some(position) if self.cell_at(position).is_some() => mouse::Interaction::Pointer,
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket(key: &str, count: i64) -> AggregateRow {
+        AggregateRow {
+            key: key.to_string(),
+            count,
+            total_size: 0,
+            attachment_size: 0,
+            attachment_count: 0,
+            total_unique: 0,
+        }
+    }
+
+    #[test]
+    fn test_layout_day_buckets() {
+        // 2024-01-01 is a Monday
+        let rows = vec![
+            bucket("2024-01-01", 3),
+            bucket("2024-01-03", 1),
+            bucket("2024-01-08", 5),
+        ];
+        let cells = layout_cells(&rows);
+
+        assert_eq!((cells[0].row, cells[0].col), (0, 0));
+        assert_eq!((cells[1].row, cells[1].col), (2, 0));
+        assert_eq!((cells[2].row, cells[2].col), (0, 1));
+    }
+
+    #[test]
+    fn test_layout_month_buckets() {
+        let rows = vec![bucket("2023-11", 2), bucket("2024-02", 4)];
+        let cells = layout_cells(&rows);
+
+        assert_eq!((cells[0].row, cells[0].col), (0, 10));
+        assert_eq!((cells[1].row, cells[1].col), (1, 1));
+    }
+
+    #[test]
+    fn test_layout_skips_unparseable_keys() {
+        let rows = vec![bucket("unknown", 9), bucket("2023-06", 1)];
+        let cells = layout_cells(&rows);
+
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].index, 1);
+    }
+}
@@ -0,0 +1,105 @@
+//! Keyboard hints widget
+//!
+//! Context-specific shortcut line shown under the current view. Key labels
+//! come from the keymap so hints always match the real bindings.
+
+use crate::api::types::ViewType;
+use crate::message::Message;
+use crate::model::{Action, Keymap, ViewLevel};
+use crate::theme::{components, spacing, typography};
+use iced::widget::{container, text};
+use iced::{Element, Length};
+
+/// Render the shortcut hints for a view
+pub fn keyboard_hints<'a>(view: &ViewLevel, keymap: &Keymap) -> Element<'a, Message> {
+    let line = hints_for(view)
+        .iter()
+        .map(|(actions, label)| {
+            let keys: Vec<&str> = actions.iter().map(|a| keymap.key(*a)).collect();
+            format!("{}: {}", keys.join("/"), label)
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
+
+    container(
+        text(line)
+            .size(typography::SIZE_2XS)
+            .font(typography::FONT_MONO)
+            .style(components::text_muted),
+    )
+    .padding([spacing::XS, spacing::LG])
+    .width(Length::Fill)
+    .into()
+}
+
+/// Actions worth hinting in each view, with their labels
+fn hints_for(view: &ViewLevel) -> Vec<(&'static [Action], &'static str)> {
+    match view {
+        ViewLevel::Dashboard => vec![
+            (&[Action::Search], "search"),
+            (&[Action::Sync], "sync"),
+            (&[Action::Accounts], "accounts"),
+            (&[Action::Settings], "settings"),
+            (&[Action::Help], "help"),
+        ],
+        ViewLevel::Aggregates { view_type } => {
+            let mut hints: Vec<(&'static [Action], &'static str)> = vec![
+                (&[Action::MoveDown, Action::MoveUp], "navigate"),
+                (&[Action::Open], "select"),
+                (&[Action::CycleView], "switch view"),
+                (&[Action::ToggleSortField], "sort field"),
+                (&[Action::ToggleSortDirection], "sort order"),
+                (&[Action::JumpTo], "jump to"),
+            ];
+            if *view_type == ViewType::Time {
+                hints.push((&[Action::ToggleTimeLayout], "heatmap/list"));
+            }
+            if matches!(view_type, ViewType::Senders | ViewType::Domains) {
+                hints.push((&[Action::CopyKey], "copy"));
+            }
+            hints.push((&[Action::Back], "back"));
+            hints
+        }
+        ViewLevel::Messages { .. } => vec![
+            (&[Action::MoveDown, Action::MoveUp], "navigate"),
+            (&[Action::Open], "open"),
+            (&[Action::ToggleSelection], "select"),
+            (&[Action::Delete], "delete"),
+            (&[Action::MarkAllRead], "mark all read"),
+            (&[Action::Label], "label"),
+            (&[Action::NextPage, Action::PreviousPage], "pages"),
+            (&[Action::JumpTo], "jump to"),
+            (&[Action::Back], "back"),
+        ],
+        ViewLevel::MessageDetail { .. } => vec![
+            (
+                &[Action::PreviousMessage, Action::NextMessage],
+                "prev/next message",
+            ),
+            (&[Action::Reply], "reply"),
+            (&[Action::ReplyAll], "reply all"),
+            (&[Action::Forward], "forward"),
+            (&[Action::ViewThread], "thread"),
+            (&[Action::Back], "back"),
+        ],
+        ViewLevel::Thread { .. } => vec![
+            (&[Action::MoveDown, Action::MoveUp], "navigate"),
+            (&[Action::Open], "toggle focused"),
+            (&[Action::ExpandAll], "expand all"),
+            (&[Action::CollapseAll], "collapse all"),
+            (&[Action::Back], "back"),
+        ],
+        ViewLevel::Search => vec![
+            (&[Action::Open], "open"),
+            (&[Action::ToggleSearchMode], "toggle mode"),
+            (&[Action::ToggleSelection], "select"),
+            (&[Action::SelectAll], "all"),
+            (&[Action::ClearSelection], "clear"),
+            (&[Action::Delete], "delete"),
+        ],
+        ViewLevel::Settings => vec![(&[Action::Back], "back (without saving)")],
+        ViewLevel::SubAggregates { .. } | ViewLevel::Sync | ViewLevel::Accounts => {
+            vec![(&[Action::Back], "back")]
+        }
+    }
+}
@@ -4,7 +4,7 @@
 
 use crate::message::Message;
 use crate::theme::{colors, components, icons, spacing, typography};
-use iced::widget::{center, column, text, Space};
+use iced::widget::{button, center, column, row, text, Space};
 use iced::Element;
 
 /// Render a loading indicator with animated dots
@@ -26,6 +26,35 @@ pub fn loading<'a>(message: &'a str) -> Element<'a, Message> {
     .into()
 }
 
+/// Frames of the activity spinner animation
+const SPINNER_FRAMES: &[&str] = &[
+    "\u{280B}", "\u{2819}", "\u{2839}", "\u{2838}", "\u{283C}", "\u{2834}", "\u{2826}", "\u{2827}",
+    "\u{2807}", "\u{280F}",
+];
+
+/// Render the small spinner shown while background work is running
+pub fn activity_spinner<'a>(frame: usize, operations: usize) -> Element<'a, Message> {
+    let label = if operations == 1 {
+        "Working".to_string()
+    } else {
+        format!("{} tasks", operations)
+    };
+
+    row![
+        text(SPINNER_FRAMES[frame % SPINNER_FRAMES.len()])
+            .size(typography::SIZE_SM)
+            .font(typography::FONT_MONO)
+            .style(components::text_accent),
+        text(label)
+            .size(typography::SIZE_2XS)
+            .font(typography::FONT_MONO)
+            .style(components::text_muted),
+    ]
+    .spacing(spacing::XS)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
 /// Render an error state with message
 pub fn error<'a>(error_message: &'a str) -> Element<'a, Message> {
     center(
@@ -51,7 +80,27 @@ pub fn error<'a>(error_message: &'a str) -> Element<'a, Message> {
 }
 
 /// Render an empty state (e.g., no messages, no search results)
-pub fn empty_state<'a>(icon: &'a str, title: &'a str, subtitle: &'a str) -> Element<'a, Message> {
+///
+/// `action` adds a primary button pointing at the next step, so the view
+/// doesn't dead-end.
+pub fn empty_state<'a>(
+    icon: &'a str,
+    title: &'a str,
+    subtitle: &'a str,
+    action: Option<(&'a str, Message)>,
+) -> Element<'a, Message> {
+    let action: Element<'a, Message> = match action {
+        Some((label, message)) => column![
+            Space::with_height(spacing::MD),
+            button(text(label).size(typography::SIZE_SM))
+                .padding([spacing::SM, spacing::LG])
+                .style(components::button_primary)
+                .on_press(message),
+        ]
+        .into(),
+        None => Space::with_height(0).into(),
+    };
+
     center(
         column![
             text(icon)
@@ -66,9 +115,11 @@ pub fn empty_state<'a>(icon: &'a str, title: &'a str, subtitle: &'a str) -> Elem
             text(subtitle)
                 .size(typography::SIZE_SM)
                 .style(components::text_muted),
+            action,
         ]
         .spacing(spacing::XS)
         .align_x(iced::Alignment::Center),
     )
+    .padding(spacing::XXL)
     .into()
 }
@@ -6,12 +6,26 @@ pub mod aggregate_row;
 pub mod avatar;
 pub mod badge;
 pub mod breadcrumb;
+pub mod confirm_modal;
+pub mod file_icon;
+pub mod focus_ring;
+pub mod heatmap;
+pub mod keyboard_hints;
 pub mod loading;
 pub mod stats_card;
+pub mod toast;
+pub mod tooltip;
 
 pub use aggregate_row::aggregate_row;
-pub use avatar::avatar;
+pub use avatar::{avatar, avatar_or_image};
 pub use badge::{badge, count_badge, unread_dot, attachment_indicator, BadgeStyle};
 pub use breadcrumb::breadcrumb;
-pub use loading::{empty_state, error, loading};
+pub use confirm_modal::{confirm_modal, confirm_modal_gated, confirm_modal_with};
+pub use file_icon::file_icon;
+pub use focus_ring::focus_ring;
+pub use heatmap::heatmap;
+pub use keyboard_hints::keyboard_hints;
+pub use loading::{activity_spinner, empty_state, error, loading};
 pub use stats_card::{format_bytes, format_number, stats_card};
+pub use toast::toast;
+pub use tooltip::{icon_button_with_tooltip, tooltip_label, with_tooltip, Position};
@@ -1,20 +1,23 @@
 //! Stats card widget
 //!
-//! Displays a single statistic with label and value.
+//! Displays a single statistic with label and value, and formats the
+//! counts and sizes shown in it and across the app.
 
 use crate::message::Message;
 use crate::theme::{colors, components, spacing, typography};
 use iced::widget::{column, container, text};
 use iced::{Background, Border, Element, Length, Theme};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 
 /// Render a stats card with label and value
 pub fn stats_card<'a>(label: &'a str, value: impl ToString) -> Element<'a, Message> {
     let card_style = |_theme: &Theme| container::Style {
-        background: Some(Background::Color(colors::BG_SURFACE)),
+        background: Some(Background::Color(colors::bg_surface())),
         border: Border {
             radius: spacing::RADIUS_MD.into(),
             width: 1.0,
-            color: colors::BORDER_SUBTLE,
+            color: colors::border_subtle(),
         },
         ..Default::default()
     };
@@ -38,32 +41,158 @@ pub fn stats_card<'a>(label: &'a str, value: impl ToString) -> Element<'a, Messa
     .into()
 }
 
-/// Format bytes as human-readable size
+/// Digit grouping and decimal separators for number display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Separators {
+    /// Between groups of three digits
+    pub group: char,
+    /// Before the fractional part
+    pub decimal: char,
+}
+
+impl Separators {
+    /// Separators used in a BCP 47 locale such as "de-DE" or "en_US"
+    pub fn for_locale(locale: &str) -> Self {
+        let mut parts = locale.split(['-', '_', '.']);
+        let language = parts.next().unwrap_or("").to_ascii_lowercase();
+        let region = parts.next().unwrap_or("").to_ascii_uppercase();
+
+        let (group, decimal) = match (language.as_str(), region.as_str()) {
+            (_, "CH" | "LI") => ('\'', '.'),
+            (
+                "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl"
+                | "sr" | "vi",
+                _,
+            ) => ('.', ','),
+            (
+                "fr" | "ru" | "pl" | "cs" | "sk" | "fi" | "sv" | "nb" | "nn" | "no" | "uk" | "hu"
+                | "bg" | "lt" | "lv" | "et",
+                _,
+            ) => ('\u{a0}', ','),
+            _ => (',', '.'),
+        };
+        Self { group, decimal }
+    }
+}
+
+/// Separators for the system locale, detected once
+fn separators() -> Separators {
+    static SEPARATORS: OnceLock<Separators> = OnceLock::new();
+    *SEPARATORS.get_or_init(|| {
+        sys_locale::get_locale()
+            .map(|locale| Separators::for_locale(&locale))
+            .unwrap_or(Separators::for_locale("en-US"))
+    })
+}
+
+/// Whether sizes use decimal units (KB = 1000) instead of binary (KiB = 1024)
+static DECIMAL_BYTE_UNITS: AtomicBool = AtomicBool::new(false);
+
+/// Choose decimal (KB) or binary (KiB) units for `format_bytes`
+pub fn set_decimal_byte_units(decimal: bool) {
+    DECIMAL_BYTE_UNITS.store(decimal, Ordering::Relaxed);
+}
+
+/// Format bytes as human-readable size in the system locale
 pub fn format_bytes(bytes: i64) -> String {
-    const KB: i64 = 1024;
-    const MB: i64 = KB * 1024;
-    const GB: i64 = MB * 1024;
-
-    if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
+    format_bytes_with(
+        bytes,
+        separators(),
+        DECIMAL_BYTE_UNITS.load(Ordering::Relaxed),
+    )
+}
+
+/// Format large numbers with the system locale's digit grouping
+pub fn format_number(n: i64) -> String {
+    format_number_with(n, separators())
+}
+
+/// Format bytes with the given separators and unit system
+fn format_bytes_with(bytes: i64, separators: Separators, decimal_units: bool) -> String {
+    let (base, units): (f64, [&str; 4]) = if decimal_units {
+        (1000.0, ["KB", "MB", "GB", "TB"])
     } else {
-        format!("{} B", bytes)
+        (1024.0, ["KiB", "MiB", "GiB", "TiB"])
+    };
+
+    if (bytes.unsigned_abs() as f64) < base {
+        return format!("{} B", bytes);
     }
+
+    let mut value = bytes as f64 / base;
+    let mut unit = units[0];
+    for next in &units[1..] {
+        if value.abs() < base {
+            break;
+        }
+        value /= base;
+        unit = next;
+    }
+
+    let formatted = format!("{:.1}", value).replace('.', &separators.decimal.to_string());
+    format!("{} {}", formatted, unit)
 }
 
-/// Format large numbers with commas
-pub fn format_number(n: i64) -> String {
-    let s = n.to_string();
+/// Format a number with the given digit grouping
+fn format_number_with(n: i64, separators: Separators) -> String {
+    let digits = n.unsigned_abs().to_string();
     let mut result = String::new();
-    for (i, c) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.insert(0, ',');
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            result.push(separators.group);
         }
-        result.insert(0, c);
+        result.push(c);
+    }
+    if n < 0 {
+        result.insert(0, '-');
     }
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const US: Separators = Separators {
+        group: ',',
+        decimal: '.',
+    };
+    const GERMAN: Separators = Separators {
+        group: '.',
+        decimal: ',',
+    };
+
+    #[test]
+    fn test_binary_byte_boundaries() {
+        assert_eq!(format_bytes_with(1023, US, false), "1023 B");
+        assert_eq!(format_bytes_with(1024, US, false), "1.0 KiB");
+        assert_eq!(format_bytes_with(1_000_000, US, false), "976.6 KiB");
+        assert_eq!(format_bytes_with(1024 * 1024, US, false), "1.0 MiB");
+    }
+
+    #[test]
+    fn test_decimal_byte_boundaries() {
+        assert_eq!(format_bytes_with(999, US, true), "999 B");
+        assert_eq!(format_bytes_with(1023, US, true), "1.0 KB");
+        assert_eq!(format_bytes_with(1024, US, true), "1.0 KB");
+        assert_eq!(format_bytes_with(1_000_000, US, true), "1.0 MB");
+        assert_eq!(format_bytes_with(1_000_000, GERMAN, true), "1,0 MB");
+    }
+
+    #[test]
+    fn test_number_grouping() {
+        assert_eq!(format_number_with(0, US), "0");
+        assert_eq!(format_number_with(1023, US), "1,023");
+        assert_eq!(format_number_with(1_000_000, US), "1,000,000");
+        assert_eq!(format_number_with(1_000_000, GERMAN), "1.000.000");
+        assert_eq!(format_number_with(-1024, US), "-1,024");
+    }
+
+    #[test]
+    fn test_separators_for_locale() {
+        assert_eq!(Separators::for_locale("en-US"), US);
+        assert_eq!(Separators::for_locale("de_DE.UTF-8"), GERMAN);
+        assert_eq!(Separators::for_locale("fr-FR").group, '\u{a0}');
+        assert_eq!(Separators::for_locale("de-CH").group, '\'');
+    }
+}
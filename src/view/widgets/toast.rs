@@ -0,0 +1,54 @@
+//! Toast widget
+//!
+//! Small confirmation bubble anchored to the bottom-right of the window.
+
+use crate::message::Message;
+use crate::model::Toast;
+use crate::theme::{components, icons, spacing, typography};
+use crate::view::widgets::{with_tooltip, Position};
+use iced::widget::{button, column, container, row, text, Space};
+use iced::{Element, Length};
+
+/// Distance from the bottom edge, clearing the status bar
+const BOTTOM_OFFSET: f32 = 40.0;
+
+/// Render a toast as a full-window overlay layer
+pub fn toast(toast: &Toast) -> Element<'_, Message> {
+    let (icon, icon_style): (&str, fn(&iced::Theme) -> text::Style) = if toast.is_error {
+        (icons::CROSS, components::text_error)
+    } else {
+        (icons::CHECK, components::text_success)
+    };
+
+    let dismiss = with_tooltip(
+        button(text(icons::CROSS).size(typography::SIZE_XS))
+            .padding([spacing::SPACE_1, spacing::XS])
+            .style(components::button_ghost)
+            .on_press(Message::DismissToast),
+        "Dismiss",
+        Position::Top,
+    );
+
+    let bubble = container(
+        row![
+            text(icon).size(typography::SIZE_SM).style(icon_style),
+            text(&toast.message)
+                .size(typography::SIZE_SM)
+                .style(components::text_primary),
+            dismiss,
+        ]
+        .spacing(spacing::SM)
+        .align_y(iced::Alignment::Center),
+    )
+    .padding([spacing::SM, spacing::MD])
+    .style(components::modal_dialog_style);
+
+    column![
+        Space::with_height(Length::Fill),
+        row![Space::with_width(Length::Fill), bubble].padding([0, spacing::LG]),
+        Space::with_height(BOTTOM_OFFSET),
+    ]
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
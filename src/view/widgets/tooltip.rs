@@ -0,0 +1,67 @@
+//! Tooltip helpers
+//!
+//! Hover labels for icon-only controls, naming the action and its
+//! keyboard shortcut.
+
+use crate::message::Message;
+use crate::theme::{components, spacing, typography};
+use iced::widget::{button, text, tooltip};
+use iced::Element;
+
+pub use iced::widget::tooltip::Position;
+
+/// Show `label` next to `content` while the pointer is over it
+pub fn with_tooltip<'a>(
+    content: impl Into<Element<'a, Message>>,
+    label: impl Into<String>,
+    position: Position,
+) -> Element<'a, Message> {
+    tooltip(
+        content,
+        text(label.into()).size(typography::SIZE_XS),
+        position,
+    )
+    .gap(spacing::SPACE_1)
+    .padding(spacing::XS)
+    .style(components::tooltip_style)
+    .into()
+}
+
+/// Icon-only ghost button with a tooltip below it
+///
+/// `label` should name the action and, where there is one, its shortcut,
+/// e.g. "Reply (r)".
+pub fn icon_button_with_tooltip<'a>(
+    icon: &'a str,
+    label: impl Into<String>,
+    message: Message,
+) -> Element<'a, Message> {
+    with_tooltip(
+        button(text(icon).size(typography::SIZE_SM))
+            .padding([spacing::XS, spacing::SM])
+            .style(components::button_ghost)
+            .on_press(message),
+        label,
+        Position::Bottom,
+    )
+}
+
+/// Tooltip text for an action with an optional shortcut key
+pub fn tooltip_label(action: &str, key: &str) -> String {
+    if key.is_empty() {
+        action.to_string()
+    } else {
+        format!("{} ({})", action, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tooltip_label() {
+        assert_eq!(tooltip_label("Reply", "r"), "Reply (r)");
+        assert_eq!(tooltip_label("Export as PDF", ""), "Export as PDF");
+    }
+}
@@ -5,30 +5,23 @@
 
 use crate::config::{DiscoveryResult, DiscoverySource, DiscoveryStep, DiscoveryStepStatus};
 use crate::message::Message;
-use crate::model::WizardStep;
+use crate::model::{AppState, WizardStep};
 use crate::theme::{colors, components, icons, spacing, typography};
-use iced::widget::{button, center, column, container, row, text, text_input, Space};
+use iced::widget::{button, center, column, container, row, scrollable, text, text_input, Space};
 use iced::{Element, Length};
 
 /// Render the wizard view based on current step
-pub fn wizard_view<'a>(
-    step: WizardStep,
-    _discovering: bool,
-    discovery_steps: &'a [DiscoveryStep],
-    discovery_result: Option<&'a DiscoveryResult>,
-    server_url: &'a str,
-    api_key: &'a str,
-) -> Element<'a, Message> {
-    let content = match step {
-        WizardStep::Discovering => discovering_view(discovery_steps),
+pub fn wizard_view(state: &AppState) -> Element<'_, Message> {
+    let content = match state.wizard_step {
+        WizardStep::Discovering => discovering_view(&state.discovery_steps),
         WizardStep::FoundServer => {
-            if let Some(result) = discovery_result {
+            if let Some(result) = &state.discovery_result {
                 found_server_view(result)
             } else {
-                discovering_view(discovery_steps)
+                discovering_view(&state.discovery_steps)
             }
         }
-        WizardStep::ManualEntry => manual_entry_view(server_url, api_key),
+        WizardStep::ManualEntry => manual_entry_view(state),
         WizardStep::Complete => {
             // Should not show wizard when complete
             column![text("Ready to connect...")].into()
@@ -40,7 +33,7 @@ pub fn wizard_view<'a>(
         .width(Length::Fill)
         .height(Length::Fill)
         .style(|_theme| container::Style {
-            background: Some(iced::Background::Color(colors::BG_DEEP)),
+            background: Some(iced::Background::Color(colors::bg_deep())),
             ..Default::default()
         })
         .into()
@@ -175,8 +168,12 @@ fn found_server_view(result: &DiscoveryResult) -> Element<'static, Message> {
             Space::with_height(spacing::XS),
             source_label,
             Space::with_height(spacing::XXL),
-            row![connect_button, Space::with_width(spacing::SM), manual_button]
-                .align_y(iced::Alignment::Center),
+            row![
+                connect_button,
+                Space::with_width(spacing::SM),
+                manual_button
+            ]
+            .align_y(iced::Alignment::Center),
         ]
         .align_x(iced::Alignment::Center),
     )
@@ -188,7 +185,11 @@ fn found_server_view(result: &DiscoveryResult) -> Element<'static, Message> {
 }
 
 /// Manual entry view - form for entering server details
-fn manual_entry_view<'a>(server_url: &'a str, api_key: &'a str) -> Element<'a, Message> {
+fn manual_entry_view(state: &AppState) -> Element<'_, Message> {
+    let server_url = &state.server_url;
+    let api_key = &state.api_key;
+    let diagnosing = state.discovering;
+
     let subtitle = text("Enter your msgvault server details")
         .size(typography::SIZE_MD)
         .style(components::text_secondary);
@@ -227,6 +228,45 @@ fn manual_entry_view<'a>(server_url: &'a str, api_key: &'a str) -> Element<'a, M
         .size(typography::SIZE_XS)
         .style(components::text_muted);
 
+    let rescan_button = button(text("Scan again").size(typography::SIZE_XS))
+        .padding([spacing::XS, spacing::SM])
+        .style(components::button_ghost)
+        .on_press_maybe((!diagnosing).then_some(Message::StartDiscovery));
+
+    let diagnose_button = button(
+        text(if diagnosing {
+            "Testing discovery sources\u{2026}"
+        } else {
+            "Test all discovery sources"
+        })
+        .size(typography::SIZE_XS),
+    )
+    .padding([spacing::XS, spacing::SM])
+    .style(components::button_ghost)
+    .on_press_maybe((!diagnosing).then_some(Message::RunDiscoveryDiagnostics));
+
+    // Search for a server on a custom localhost port
+    let port_input = text_input("Port, e.g. 8420", &state.wizard_port)
+        .on_input(Message::WizardPortChanged)
+        .on_submit(Message::DiscoverOnPort)
+        .padding(spacing::SM)
+        .width(Length::Fixed(140.0))
+        .style(components::text_input_style);
+    let port_button = button(text("Search port").size(typography::SIZE_XS))
+        .padding([spacing::XS, spacing::SM])
+        .style(components::button_secondary)
+        .on_press_maybe(
+            (!diagnosing && !state.wizard_port.trim().is_empty())
+                .then_some(Message::DiscoverOnPort),
+        );
+    let port_row = row![port_input, Space::with_width(spacing::SM), port_button]
+        .align_y(iced::Alignment::Center);
+
+    let diagnostics_el: Element<'_, Message> = match state.discovery_diagnostics.as_deref() {
+        Some(steps) => diagnostics_table(steps),
+        None => Space::with_height(0).into(),
+    };
+
     let card = container(
         column![
             logo_mark(),
@@ -244,6 +284,20 @@ fn manual_entry_view<'a>(server_url: &'a str, api_key: &'a str) -> Element<'a, M
             connect_button,
             Space::with_height(spacing::SM),
             hint,
+            Space::with_height(spacing::LG),
+            text("Running on another port?")
+                .size(typography::SIZE_XS)
+                .style(components::text_muted),
+            Space::with_height(spacing::XS),
+            port_row,
+            Space::with_height(spacing::SM),
+            row![
+                rescan_button,
+                Space::with_width(spacing::SM),
+                diagnose_button
+            ]
+            .align_y(iced::Alignment::Center),
+            diagnostics_el,
         ]
         .align_x(iced::Alignment::Center),
     )
@@ -254,13 +308,45 @@ fn manual_entry_view<'a>(server_url: &'a str, api_key: &'a str) -> Element<'a, M
     card.into()
 }
 
+/// Every discovery source with what it found or why it failed
+fn diagnostics_table<'a>(steps: &'a [DiscoveryStep]) -> Element<'a, Message> {
+    let found = steps
+        .iter()
+        .filter(|s| matches!(s.status, DiscoveryStepStatus::Found(_)))
+        .count();
+    let summary = match found {
+        0 => "No source found a running server".to_string(),
+        1 => "1 source found a running server".to_string(),
+        n => format!("{} sources found a running server", n),
+    };
+
+    // The closing "No server found" step only repeats the summary
+    let rows: Vec<Element<'a, Message>> = steps
+        .iter()
+        .filter(|s| s.name != "No server found")
+        .map(|s| step_row(&s.name, s.status.clone()))
+        .collect();
+
+    column![
+        Space::with_height(spacing::MD),
+        text(summary)
+            .size(typography::SIZE_SM)
+            .font(typography::FONT_MEDIUM)
+            .style(components::text_secondary),
+        Space::with_height(spacing::SM),
+        scrollable(column(rows).spacing(spacing::XS)).height(Length::Shrink),
+    ]
+    .width(Length::Fill)
+    .into()
+}
+
 /// Single discovery step row
 fn step_row(name: &str, status: DiscoveryStepStatus) -> Element<'static, Message> {
     let (icon, color) = match &status {
-        DiscoveryStepStatus::Checking => (icons::DOTS, colors::TEXT_MUTED),
-        DiscoveryStepStatus::Found(_) => (icons::CHECK, colors::ACCENT_SUCCESS),
-        DiscoveryStepStatus::NotFound => (icons::CROSS, colors::TEXT_MUTED),
-        DiscoveryStepStatus::Failed(_) => (icons::CROSS, colors::ACCENT_ERROR),
+        DiscoveryStepStatus::Checking => (icons::DOTS, colors::text_muted()),
+        DiscoveryStepStatus::Found(_) => (icons::CHECK, colors::accent_success()),
+        DiscoveryStepStatus::NotFound => (icons::CROSS, colors::text_muted()),
+        DiscoveryStepStatus::Failed(_) => (icons::CROSS, colors::accent_error()),
     };
 
     let icon_text = text(icon)